@@ -8,24 +8,67 @@ use std::rc::Rc;
 
 use gtk4::prelude::*;
 
+use crate::core::connection_uri::ConnectionUri;
 use crate::ui::window::MainWindow;
 
 /// Construit et lance l'application.
 pub fn run() -> glib::ExitCode {
     let app = libadwaita::Application::builder()
         .application_id("com.github.weedmanu.serial-ssh-term")
+        // Liens `sshterm://` passés en argument (ou ouverts depuis le bureau)
+        .flags(gio::ApplicationFlags::HANDLES_OPEN)
         .build();
 
+    // Option `--profile NOM` : profil de configuration à charger
+    app.add_main_option(
+        "profile",
+        glib::Char::from(b'p'),
+        glib::OptionFlags::NONE,
+        glib::OptionArg::String,
+        "Profil de configuration à utiliser",
+        Some("NOM"),
+    );
+    let profile: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    {
+        let profile = profile.clone();
+        app.connect_handle_local_options(move |_, options| {
+            if let Ok(Some(name)) = options.lookup::<String>("profile") {
+                *profile.borrow_mut() = Some(name);
+            }
+            -1 // Poursuivre le démarrage normal
+        });
+    }
+
     // Stocker la référence à la fenêtre pour éviter le drop prématuré
     let main_window: Rc<RefCell<Option<Rc<MainWindow>>>> = Rc::new(RefCell::new(None));
 
+    {
+        let mw = main_window.clone();
+        let profile = profile.clone();
+        app.connect_activate(move |app| {
+            let win = MainWindow::new(app, profile.borrow().as_deref());
+            *mw.borrow_mut() = Some(win);
+        });
+    }
+
+    // Une fenêtre pré-configurée par lien `sshterm://`
     let mw = main_window;
-    app.connect_activate(move |app| {
-        let win = MainWindow::new(app);
-        *mw.borrow_mut() = Some(win);
+    app.connect_open(move |app, files, _hint| {
+        for file in files {
+            let uri = file.uri();
+            let win = MainWindow::new(app, profile.borrow().as_deref());
+            match ConnectionUri::parse(&uri) {
+                Ok(connection) => win.apply_connection_uri(&connection),
+                Err(e) => {
+                    log::warn!("Lien ignoré ({uri}) : {e}");
+                    win.show_toast(&format!("⚠ Lien invalide : {e}"));
+                }
+            }
+            *mw.borrow_mut() = Some(win);
+        }
     });
 
     app.run()
 }
 
-use gtk4::glib;
+use gtk4::{gio, glib};
@@ -0,0 +1,170 @@
+// =============================================================================
+// Fichier : at_commands.rs
+// Rôle    : Référence des commandes AT (modems, modules cellulaires) et complétion
+// =============================================================================
+
+/// Commande AT de référence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtCommand {
+    pub command: &'static str,
+    pub description: &'static str,
+}
+
+const fn at(command: &'static str, description: &'static str) -> AtCommand {
+    AtCommand {
+        command,
+        description,
+    }
+}
+
+/// Commandes courantes (V.250, 27.007, 27.005), par ordre alphabétique.
+pub const AT_COMMANDS: &[AtCommand] = &[
+    at("AT", "Test de communication"),
+    at("AT&F", "Restaurer la configuration usine"),
+    at("AT&V", "Afficher la configuration courante"),
+    at("AT&W", "Enregistrer la configuration"),
+    at("AT+CCID", "Identifiant de la carte SIM (ICCID)"),
+    at("AT+CEREG?", "Enregistrement réseau EPS (LTE)"),
+    at(
+        "AT+CFUN=",
+        "Niveau de fonctionnement (0 = minimal, 1 = complet)",
+    ),
+    at("AT+CFUN?", "Niveau de fonctionnement courant"),
+    at("AT+CGATT?", "Attachement au service paquet"),
+    at("AT+CGDCONT=", "Définir un contexte PDP (APN)"),
+    at("AT+CGDCONT?", "Contextes PDP définis"),
+    at("AT+CGMI", "Fabricant"),
+    at("AT+CGMM", "Modèle"),
+    at("AT+CGMR", "Version du firmware"),
+    at("AT+CGPADDR", "Adresse IP des contextes PDP"),
+    at("AT+CGREG?", "Enregistrement réseau GPRS"),
+    at("AT+CGSN", "Numéro IMEI"),
+    at("AT+CIMI", "Identité de l'abonné (IMSI)"),
+    at("AT+CMEE=2", "Erreurs détaillées (texte)"),
+    at("AT+CMGF=1", "SMS en mode texte"),
+    at("AT+CMGL=\"ALL\"", "Lister tous les SMS"),
+    at("AT+CMGR=", "Lire un SMS"),
+    at("AT+CMGS=", "Envoyer un SMS"),
+    at("AT+COPS=?", "Rechercher les opérateurs"),
+    at("AT+COPS?", "Opérateur courant"),
+    at("AT+CPIN=", "Saisir le code PIN"),
+    at("AT+CPIN?", "État du code PIN"),
+    at("AT+CREG?", "Enregistrement réseau"),
+    at("AT+CSQ", "Qualité du signal (RSSI, BER)"),
+    at("AT+GCAP", "Capacités du modem"),
+    at("AT+IPR=", "Débit de la liaison série"),
+    at("AT+IPR?", "Débit courant de la liaison série"),
+    at("ATA", "Répondre à un appel"),
+    at("ATD", "Composer un numéro"),
+    at("ATE0", "Désactiver l'écho"),
+    at("ATE1", "Activer l'écho"),
+    at("ATH", "Raccrocher"),
+    at("ATI", "Informations d'identification"),
+    at("ATZ", "Réinitialiser le modem"),
+];
+
+/// Commandes commençant par `prefix` (insensible à la casse).
+pub fn completions(prefix: &str) -> Vec<AtCommand> {
+    let prefix = prefix.trim_start().to_uppercase();
+    AT_COMMANDS
+        .iter()
+        .filter(|c| c.command.starts_with(&prefix))
+        .copied()
+        .collect()
+}
+
+/// Plus long préfixe commun aux commandes proposées.
+pub fn common_prefix(candidates: &[AtCommand]) -> Option<&'static str> {
+    let first = candidates.first()?.command;
+    let len = candidates.iter().skip(1).fold(first.len(), |len, c| {
+        first
+            .bytes()
+            .zip(c.command.bytes())
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    Some(&first[..len])
+}
+
+/// Met en majuscules hors des chaînes entre guillemets (`AT+CMGS="+33…"`,
+/// texte de SMS, APN) qui doivent rester telles quelles.
+pub fn uppercase_command(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut quoted = false;
+    for c in text.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        if quoted {
+            out.push(c);
+        } else {
+            out.extend(c.to_uppercase());
+        }
+    }
+    out
+}
+
+/// Position (en caractères) du curseur `position` de `text` une fois le texte
+/// passé par `uppercase_command` : une lettre peut s'y allonger (ß → SS).
+pub fn uppercase_cursor(text: &str, position: usize) -> usize {
+    let before: String = text.chars().take(position).collect();
+    uppercase_command(&before).chars().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(found: &[AtCommand]) -> Vec<&'static str> {
+        found.iter().map(|c| c.command).collect()
+    }
+
+    #[test]
+    fn completions_ignore_case_and_leading_spaces() {
+        assert_eq!(
+            commands(&completions("  at+cpin")),
+            vec!["AT+CPIN=", "AT+CPIN?"]
+        );
+        assert_eq!(commands(&completions("ATZ")), vec!["ATZ"]);
+        assert_eq!(completions("").len(), AT_COMMANDS.len());
+        assert!(completions("AT+XYZ").is_empty());
+        assert!(completions("TA").is_empty());
+    }
+
+    #[test]
+    fn common_prefix_of_candidates() {
+        assert_eq!(common_prefix(&[]), None);
+        assert_eq!(common_prefix(&completions("at+cgm")), Some("AT+CGM"));
+        assert_eq!(common_prefix(&completions("at+cp")), Some("AT+CPIN"));
+        assert_eq!(common_prefix(&completions("ate")), Some("ATE"));
+        assert_eq!(common_prefix(&completions("atz")), Some("ATZ"));
+        assert_eq!(common_prefix(&completions("")), Some("AT"));
+    }
+
+    #[test]
+    fn uppercase_keeps_quoted_text() {
+        assert_eq!(
+            uppercase_command("at+cmgs=\"+33612345678\";text \"Bonjour à tous\""),
+            "AT+CMGS=\"+33612345678\";TEXT \"Bonjour à tous\""
+        );
+        assert_eq!(
+            uppercase_command("at+cgdcont=1,\"ip\",\"apn"),
+            "AT+CGDCONT=1,\"ip\",\"apn"
+        );
+        assert_eq!(uppercase_command("atéß"), "ATÉSS");
+    }
+
+    #[test]
+    fn cursor_follows_letters_that_grow() {
+        // `ß` devient `SS` : le curseur placé après lui avance d'un caractère.
+        assert_eq!(uppercase_cursor("aßc", 0), 0);
+        assert_eq!(uppercase_cursor("aßc", 1), 1);
+        assert_eq!(uppercase_cursor("aßc", 2), 3);
+        assert_eq!(uppercase_cursor("aßc", 3), 4);
+        // Entre guillemets, rien ne change.
+        assert_eq!(uppercase_cursor("at\"ßß\"ß", 5), 5);
+        assert_eq!(uppercase_cursor("at\"ßß\"ß", 7), 8);
+        assert_eq!(uppercase_cursor("ab", 10), 2);
+    }
+}
@@ -0,0 +1,193 @@
+// =============================================================================
+// Fichier : capture.rs
+// Rôle    : Capture brute des octets échangés (RX, et TX en option) dans un
+//           fichier binaire, pour l'analyse de protocole
+//
+// Format du fichier :
+//  - Sans marquage ni horodatage : octets RX bruts, tels que reçus. Les
+//    envois éventuels vont, bruts aussi, dans un fichier voisin `<nom>-tx<.ext>`.
+//  - Avec marquage de direction ou horodatage : en-tête `SSTCAP1\n` suivi d'un
+//    octet d'options (bit 0 = horodatage), puis une suite d'enregistrements :
+//      [direction : u8, 0x00 = RX, 0x01 = TX]
+//      [horodatage : u64 big-endian, µs depuis le début — si bit 0]
+//      [longueur : u32 big-endian]
+//      [données]
+// =============================================================================
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+
+/// Signature des fichiers de capture à enregistrements.
+pub const CAPTURE_MAGIC: &[u8] = b"SSTCAP1\n";
+
+/// Options de capture, choisies avant son démarrage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CaptureOptions {
+    /// Enregistre aussi les octets envoyés.
+    pub record_tx: bool,
+    /// Enregistrements marqués RX/TX dans un seul fichier.
+    pub tag_direction: bool,
+    /// Horodatage de chaque enregistrement (implique le format à enregistrements).
+    pub timestamps: bool,
+}
+
+impl CaptureOptions {
+    const fn records(self) -> bool {
+        self.tag_direction || self.timestamps
+    }
+}
+
+/// Sens d'un échange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Rx,
+    Tx,
+}
+
+/// Fichier de capture ouvert.
+pub struct RawCapture {
+    path: PathBuf,
+    options: CaptureOptions,
+    started: Instant,
+    main: BufWriter<File>,
+    /// Fichier des envois, en format brut uniquement.
+    tx: Option<BufWriter<File>>,
+}
+
+impl RawCapture {
+    /// Crée le fichier de capture (écrasé s'il existe).
+    pub fn create(path: &Path, options: CaptureOptions) -> Result<Self> {
+        let open = |path: &Path| {
+            File::create(path)
+                .map(BufWriter::new)
+                .with_context(|| format!("Impossible de créer {}", path.display()))
+        };
+        let mut main = open(path)?;
+        let tx = if options.record_tx && !options.records() {
+            Some(open(&tx_path(path))?)
+        } else {
+            None
+        };
+        if options.records() {
+            main.write_all(CAPTURE_MAGIC)?;
+            main.write_all(&[u8::from(options.timestamps)])?;
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            options,
+            started: Instant::now(),
+            main,
+            tx,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Ajoute un échange ; les envois sont ignorés sauf `record_tx`.
+    pub fn record(&mut self, direction: Direction, data: &[u8]) -> Result<()> {
+        if direction == Direction::Tx && !self.options.record_tx {
+            return Ok(());
+        }
+        if !self.options.records() {
+            let out = match (direction, self.tx.as_mut()) {
+                (Direction::Tx, Some(tx)) => tx,
+                _ => &mut self.main,
+            };
+            return out.write_all(data).context("Écriture de la capture");
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.push(match direction {
+            Direction::Rx => 0x00,
+            Direction::Tx => 0x01,
+        });
+        if self.options.timestamps {
+            let micros = u64::try_from(self.started.elapsed().as_micros()).unwrap_or(u64::MAX);
+            header.extend_from_slice(&micros.to_be_bytes());
+        }
+        let len = u32::try_from(data.len()).context("Enregistrement trop long")?;
+        header.extend_from_slice(&len.to_be_bytes());
+        self.main.write_all(&header)?;
+        self.main.write_all(data).context("Écriture de la capture")
+    }
+
+    /// Vide les tampons sur disque (fin de capture).
+    pub fn finish(mut self) -> Result<()> {
+        self.main.flush()?;
+        if let Some(tx) = self.tx.as_mut() {
+            tx.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Fichier des envois d'une capture brute : `capture.bin` → `capture-tx.bin`.
+pub fn tx_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map_or_else(|| "capture".into(), |s| s.to_string_lossy());
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-tx.{}", ext.to_string_lossy()),
+        None => format!("{stem}-tx"),
+    };
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Octets qui ne forment pas de texte UTF-8 valide.
+    const BINARY: &[u8] = b"\x00\xFF\xC3\r\n\xE2\x82";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("sst-capture-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn raw_capture_writes_bytes_verbatim() {
+        let path = temp_path("raw.bin");
+        let options = CaptureOptions {
+            record_tx: true,
+            ..CaptureOptions::default()
+        };
+        let mut capture = RawCapture::create(&path, options).unwrap();
+        capture.record(Direction::Rx, BINARY).unwrap();
+        capture.record(Direction::Tx, b"\xFE").unwrap();
+        capture.record(Direction::Rx, b"\xA9").unwrap();
+        capture.finish().unwrap();
+
+        let mut expected = BINARY.to_vec();
+        expected.push(0xA9);
+        assert_eq!(std::fs::read(&path).unwrap(), expected);
+        assert_eq!(std::fs::read(tx_path(&path)).unwrap(), b"\xFE");
+        std::fs::remove_file(tx_path(&path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn record_capture_keeps_payload_intact() {
+        let path = temp_path("records.bin");
+        let options = CaptureOptions {
+            tag_direction: true,
+            ..CaptureOptions::default()
+        };
+        let mut capture = RawCapture::create(&path, options).unwrap();
+        capture.record(Direction::Rx, BINARY).unwrap();
+        capture.finish().unwrap();
+
+        let mut expected = CAPTURE_MAGIC.to_vec();
+        expected.push(0);
+        expected.push(0x00);
+        expected.extend_from_slice(&7u32.to_be_bytes());
+        expected.extend_from_slice(BINARY);
+        assert_eq!(std::fs::read(&path).unwrap(), expected);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
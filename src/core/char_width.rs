@@ -0,0 +1,202 @@
+// =============================================================================
+// Fichier : char_width.rs
+// Rôle    : Largeur d'affichage des caractères en colonnes de terminal
+//           (largeur est-asiatique, caractères combinants)
+// =============================================================================
+
+/// Caractères sans largeur propre : combinants, jointures, sélecteurs de variante.
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302D),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0xE0100, 0xE01EF),
+];
+
+/// Caractères occupant deux colonnes (Unicode East Asian Width W et F).
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x3029),
+    (0x302E, 0x303E),
+    (0x3041, 0x3098),
+    (0x309B, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F900, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+fn in_table(table: &[(u32, u32)], c: u32) -> bool {
+    table
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                std::cmp::Ordering::Less
+            } else if start > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Nombre de colonnes occupées par `c` (0, 1 ou 2).
+///
+/// Les caractères de contrôle comptent pour 0 : ils ne sont jamais affichés
+/// tels quels dans la grille.
+pub fn char_width(c: char) -> usize {
+    let code = u32::from(c);
+    if code < 0x20 || (0x7F..0xA0).contains(&code) || in_table(ZERO_WIDTH, code) {
+        0
+    } else if in_table(WIDE, code) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Nombre de colonnes occupées par `text` sur une ligne de terminal.
+pub fn str_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Position de la colonne `column` dans `line` : index (en caractères) du
+/// caractère affiché à cette colonne et nombre d'espaces à ajouter quand la
+/// ligne est plus courte.
+///
+/// Une colonne au milieu d'un caractère large désigne ce caractère ; les
+/// caractères sans largeur restent attachés à celui qui les précède.
+pub fn column_to_index(line: &str, column: usize) -> (usize, usize) {
+    let mut width = 0;
+    let mut count = 0;
+    for (index, c) in line.chars().enumerate() {
+        let w = char_width(c);
+        if w > 0 && width + w > column {
+            return (index, 0);
+        }
+        width += w;
+        count = index + 1;
+    }
+    (count, column.saturating_sub(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_characters_take_two_columns() {
+        for c in ['漢', 'か', 'カ', '한', '（', '😀', '🚀', '⌚'] {
+            assert_eq!(char_width(c), 2, "{c:?}");
+        }
+        for c in ['a', 'é', 'ß', 'Ж', 'ｶ', '→', '€'] {
+            assert_eq!(char_width(c), 1, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn zero_width_and_control_characters() {
+        for c in [
+            '\u{301}',  // accent aigu combinant
+            '\u{20DD}', // cercle englobant
+            '\u{200B}', // espace sans chasse
+            '\u{200D}', // jointure sans chasse
+            '\u{FE0F}', // sélecteur de variante emoji
+            '\u{FEFF}', '\u{3099}', // dakuten combinant
+            '\0', '\t', '\x1b', '\u{7F}', '\u{85}',
+        ] {
+            assert_eq!(char_width(c), 0, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn string_width_sums_columns() {
+        assert_eq!(str_width(""), 0);
+        assert_eq!(str_width("abc"), 3);
+        assert_eq!(str_width("漢字ab"), 6);
+        // `e` + accent combinant : une seule colonne.
+        assert_eq!(str_width("e\u{301}"), 1);
+        assert_eq!(str_width("か\u{3099}"), 2);
+        assert_eq!(str_width("👍\u{FE0F}"), 2);
+    }
+
+    #[test]
+    fn columns_map_to_characters_by_display_width() {
+        assert_eq!(column_to_index("abc", 0), (0, 0));
+        assert_eq!(column_to_index("abc", 2), (2, 0));
+        // 漢 occupe les colonnes 0-1, 字 les colonnes 2-3.
+        assert_eq!(column_to_index("漢字ab", 2), (1, 0));
+        assert_eq!(column_to_index("漢字ab", 4), (2, 0));
+        assert_eq!(column_to_index("漢字ab", 3), (1, 0));
+        // Le combinant suit sa base : la colonne 1 désigne le `x`.
+        assert_eq!(column_to_index("e\u{301}x", 1), (2, 0));
+    }
+
+    #[test]
+    fn columns_past_the_line_end_need_padding() {
+        assert_eq!(column_to_index("", 3), (0, 3));
+        assert_eq!(column_to_index("ab", 2), (2, 0));
+        assert_eq!(column_to_index("漢", 5), (1, 3));
+        assert_eq!(column_to_index("a\u{301}", 4), (2, 3));
+    }
+}
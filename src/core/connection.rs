@@ -7,9 +7,14 @@
 //   - Le pont UI↔core se fait dans window.rs via async_channel.
 // =============================================================================
 
-use anyhow::Result;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
 use async_trait::async_trait;
 
+use super::capture::{CaptureOptions, Direction, RawCapture};
+use super::sftp::SftpHandle;
+
 /// Type de connexion supporté.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionType {
@@ -46,6 +51,82 @@ impl std::fmt::Display for ConnectionType {
     }
 }
 
+/// Instantané de l'état d'une connexion, obtenu via `ConnectionCommand::QueryInfo`.
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    pub conn_type: ConnectionType,
+    pub state: ConnectionState,
+    pub description: String,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// Détails propres au type de connexion (libellé, valeur).
+    pub details: Vec<(String, String)>,
+}
+
+/// Latences requête/réponse mesurées sur une session : délai entre un envoi
+/// et le premier octet reçu ensuite.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub min: Duration,
+    pub max: Duration,
+    pub total: Duration,
+}
+
+impl LatencyStats {
+    /// Ajoute une mesure.
+    pub fn record(&mut self, latency: Duration) {
+        self.min = if self.count == 0 {
+            latency
+        } else {
+            self.min.min(latency)
+        };
+        self.max = self.max.max(latency);
+        self.total += latency;
+        self.count += 1;
+    }
+
+    /// Latence moyenne (`None` sans mesure).
+    pub fn average(&self) -> Option<Duration> {
+        let count = u32::try_from(self.count).ok()?;
+        self.total.checked_div(count)
+    }
+
+    /// Durée en millisecondes, au dixième.
+    pub fn format_ms(latency: Duration) -> String {
+        format!("{:.1} ms", latency.as_secs_f64() * 1000.0)
+    }
+}
+
+impl std::fmt::Display for LatencyStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(average) = self.average() else {
+            return write!(f, "aucune mesure");
+        };
+        write!(
+            f,
+            "min {} / moy {} / max {} ({} mesure{})",
+            Self::format_ms(self.min),
+            Self::format_ms(average),
+            Self::format_ms(self.max),
+            self.count,
+            if self.count > 1 { "s" } else { "" }
+        )
+    }
+}
+
+/// Réponse de l'utilisateur à une demande de vérification de clé d'hôte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostKeyDecision {
+    /// Connexion refusée.
+    Reject,
+    /// Connexion acceptée sans enregistrer la clé (mémorisée jusqu'à la
+    /// fermeture de l'application).
+    AcceptOnce,
+    /// Connexion acceptée et clé enregistrée dans `known_hosts`.
+    AcceptAndSave,
+}
+
 /// Événements envoyés par la connexion vers l'UI.
 ///
 /// SOLID : ce type n'a aucune dépendance vers GTK/glib.
@@ -58,15 +139,50 @@ pub enum ConnectionEvent {
     },
     /// Données reçues du périphérique distant.
     DataReceived(Vec<u8>),
+    /// Sortie d'erreur distante (SSH : données étendues de type stderr).
+    StderrReceived(Vec<u8>),
+    /// La lecture a été suspendue faute de place dans le canal d'événements
+    /// (l'UI n'affiche pas assez vite). Aucune donnée n'est perdue : elle
+    /// reprend dès que l'UI a consommé. Compteurs cumulés depuis la connexion.
+    ReadStalled { count: u64, total_ms: u64 },
+    /// Un `SendData` a été écrit (nombre d'octets).
+    ///
+    /// Émis dans le même flux que `DataReceived` : l'UI y affiche l'écho TX
+    /// pour respecter l'ordre réel des échanges.
+    DataSent(usize),
+    /// Message informatif (nouvel essai d'ouverture…), la connexion continue.
+    Notice(String),
     /// Connexion fermée proprement.
     Disconnected,
     /// Erreur non-récupérable (affichée dans le terminal).
     Error(String),
+    /// Échec d'une commande ponctuelle — la connexion reste active.
+    CommandFailed(String),
+    /// Latence mesurée (envoi → premier octet reçu) et cumul de la session,
+    /// si la mesure est activée par `ConnectionCommand::SetLatencyTracking`.
+    Latency { last: Duration, stats: LatencyStats },
+    /// Bilan de fin de session, émis juste avant `Disconnected` / `Error`.
+    SessionStats {
+        bytes_sent: u64,
+        bytes_received: u64,
+        /// Latences mesurées (`None` si aucune mesure).
+        latency: Option<LatencyStats>,
+    },
+    /// Tampons série vidés suite à `ConnectionCommand::FlushBuffers`.
+    BuffersFlushed { input: bool, output: bool },
+    /// Signal de contrôle appliqué (break, reset…) ; libellé affichable.
+    ControlSignalSent(String),
+    /// Lecture suspendue (`true`) ou reprise suite à `ConnectionCommand::PauseReading`.
+    ReadingPaused(bool),
+    /// Capture brute démarrée (chemin du fichier) ou arrêtée (`None`).
+    RawCapture(Option<std::path::PathBuf>),
+    /// État des lignes de contrôle série, à l'ouverture puis à chaque changement.
+    ControlLines { dtr: bool, rts: bool },
     /// Vérification de clé d'hôte SSH requise.
     ///
     /// `is_key_changed = true` indique une clé DIFFÉRENTE de celle en
     /// `known_hosts` → risque potentiel MITM. L'UI doit avertir fortement.
-    /// L'UI envoie `true` (accepter) ou `false` (refuser) via `decision_tx`.
+    /// L'UI envoie sa décision via `decision_tx` (canal fermé = refus).
     HostKeyUnknown {
         host: String,
         key_type: String,
@@ -74,14 +190,73 @@ pub enum ConnectionEvent {
         /// `true` = clé connue MAIS différente (possible MITM).
         /// `false` = hôte inconnu (première connexion).
         is_key_changed: bool,
-        decision_tx: tokio::sync::oneshot::Sender<bool>,
+        decision_tx: tokio::sync::oneshot::Sender<HostKeyDecision>,
+    },
+    /// Questions d'une authentification SSH keyboard-interactive (OTP, 2FA…).
+    ///
+    /// Chaque question est accompagnée de son drapeau d'écho (`false` = saisie
+    /// masquée). L'UI renvoie une réponse par question, dans l'ordre, via
+    /// `response_tx` (canal fermé = authentification abandonnée).
+    AuthPrompt {
+        prompts: Vec<(String, bool)>,
+        response_tx: tokio::sync::oneshot::Sender<Vec<String>>,
+    },
+    /// La clé privée `key_path` est chiffrée : passphrase absente ou refusée.
+    ///
+    /// L'UI renvoie la passphrase saisie via `response_tx` (canal fermé =
+    /// connexion abandonnée).
+    PassphraseRequired {
+        key_path: String,
+        response_tx: tokio::sync::oneshot::Sender<String>,
     },
+    /// La clé `key_path` s'est chargée avec la passphrase saisie après
+    /// `PassphraseRequired` : elle peut être mémorisée.
+    PassphraseAccepted { key_path: String },
 }
 
 /// Commandes envoyées par l'UI vers la connexion.
 #[derive(Debug)]
 pub enum ConnectionCommand {
     SendData(Vec<u8>),
+    /// Vide les tampons d'entrée et/ou de sortie (données en attente perdues).
+    FlushBuffers {
+        input: bool,
+        output: bool,
+    },
+    /// Maintient une condition de break pendant `duration_ms`.
+    SendBreak {
+        duration_ms: u64,
+    },
+    /// Active DTR/RTS pendant `duration_ms` puis les relâche (reset de la carte).
+    PulseReset {
+        duration_ms: u64,
+    },
+    /// Active (`true`) ou relâche la ligne DTR.
+    SetDtr(bool),
+    /// Active (`true`) ou relâche la ligne RTS.
+    SetRts(bool),
+    /// Suspend (`true`) ou reprend la lecture : l'acteur cesse d'appeler
+    /// `read()`, les données restent dans le périphérique ou le tampon du
+    /// système (contrairement à la pause d'affichage).
+    PauseReading(bool),
+    /// Démarre la capture brute des échanges dans `path`.
+    StartCapture {
+        path: std::path::PathBuf,
+        options: CaptureOptions,
+    },
+    /// Arrête la capture brute en cours.
+    StopCapture,
+    /// Active ou désactive la mesure de latence requête/réponse.
+    SetLatencyTracking(bool),
+    /// Demande un instantané de l'état courant, renvoyé sur le canal fourni.
+    QueryInfo(tokio::sync::oneshot::Sender<ConnectionInfo>),
+    /// Nouvelle taille du terminal affiché, en caractères.
+    Resize {
+        cols: u32,
+        rows: u32,
+    },
+    /// Ouvre une session SFTP sur la connexion (SSH uniquement).
+    OpenSftp(tokio::sync::oneshot::Sender<Result<SftpHandle>>),
     Disconnect,
 }
 
@@ -104,13 +279,85 @@ pub trait Connection: Send {
     /// Ferme proprement la connexion.
     async fn disconnect(&mut self) -> Result<()>;
 
-    /// Envoie des données brutes.
+    /// Envoie des données brutes en totalité (pas d'écriture partielle
+    /// silencieuse) et renvoie le nombre d'octets effectivement écrits.
     async fn send(&mut self, data: &[u8]) -> Result<usize>;
 
-    /// Lit les données disponibles (non-bloquant).
-    /// Retourne les octets lus, ou un vecteur vide si rien n'est disponible.
+    /// Vide les tampons d'entrée et/ou de sortie du périphérique.
+    ///
+    /// Implémentation par défaut : non supporté (SSH n'a pas de tampon matériel).
+    async fn flush_buffers(&mut self, _input: bool, _output: bool) -> Result<()> {
+        bail!("Vidage des tampons non supporté pour ce type de connexion")
+    }
+
+    /// Maintient une condition de break sur la ligne pendant `duration`.
+    ///
+    /// Implémentation par défaut : non supporté.
+    async fn send_break(&mut self, _duration: Duration) -> Result<()> {
+        bail!("Break non supporté pour ce type de connexion")
+    }
+
+    /// Active DTR et RTS pendant `duration` puis les relâche.
+    ///
+    /// Implémentation par défaut : non supporté.
+    async fn pulse_reset(&mut self, _duration: Duration) -> Result<()> {
+        bail!("Reset DTR/RTS non supporté pour ce type de connexion")
+    }
+
+    /// Pilote la ligne DTR.
+    ///
+    /// Implémentation par défaut : no-op (SSH n'a pas de lignes de contrôle).
+    async fn set_dtr(&mut self, _level: bool) -> Result<()> {
+        Ok(())
+    }
+
+    /// Pilote la ligne RTS.
+    ///
+    /// Implémentation par défaut : no-op (SSH n'a pas de lignes de contrôle).
+    async fn set_rts(&mut self, _level: bool) -> Result<()> {
+        Ok(())
+    }
+
+    /// État courant `(DTR, RTS)`, `None` sans lignes de contrôle.
+    fn control_lines(&self) -> Option<(bool, bool)> {
+        None
+    }
+
+    /// Signale au périphérique la suspension de la lecture (contrôle de flux).
+    ///
+    /// Implémentation par défaut : rien (la lecture est simplement suspendue).
+    async fn set_reading_paused(&mut self, _paused: bool) -> Result<()> {
+        Ok(())
+    }
+
+    /// Attend les prochaines données disponibles.
+    ///
+    /// Doit être annulable (utilisé dans un `select!`) : un futur abandonné
+    /// ne doit pas perdre de données. Retourne un vecteur vide pour les
+    /// messages sans contenu (contrôle, fin de flux).
     async fn read(&mut self) -> Result<Vec<u8>>;
 
+    /// Adapte la taille du terminal distant (`cols` × `rows` caractères).
+    ///
+    /// Implémentation par défaut : no-op (une liaison série n'a pas de PTY).
+    async fn resize(&mut self, _cols: u32, _rows: u32) -> Result<()> {
+        Ok(())
+    }
+
+    /// Ouvre une session SFTP sur un second canal de la même connexion.
+    ///
+    /// Implémentation par défaut : non supporté (série).
+    async fn open_sftp(&mut self) -> Result<SftpHandle> {
+        bail!("SFTP disponible uniquement pour les connexions SSH")
+    }
+
+    /// Retire la sortie d'erreur reçue par le dernier `read()`.
+    ///
+    /// Implémentation par défaut : aucune (flux unique).
+    fn take_stderr(&mut self) -> Vec<u8> {
+        Vec::new()
+    }
+
     /// Retourne l'état courant de la connexion.
     fn state(&self) -> ConnectionState;
 
@@ -125,6 +372,79 @@ pub trait Connection: Send {
 
     /// Retourne le nombre d'octets reçus depuis la connexion.
     fn bytes_received(&self) -> u64;
+
+    /// Détails propres au type de connexion (paramètres, méthode d'auth…).
+    fn details(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Construit un instantané complet de la connexion.
+    fn info(&self) -> ConnectionInfo {
+        ConnectionInfo {
+            conn_type: self.connection_type(),
+            state: self.state(),
+            description: self.description(),
+            bytes_sent: self.bytes_sent(),
+            bytes_received: self.bytes_received(),
+            details: self.details(),
+        }
+    }
+}
+
+/// Ajoute un échange à la capture brute ; une erreur d'écriture l'arrête.
+async fn record_capture(
+    capture: &mut Option<RawCapture>,
+    direction: Direction,
+    data: &[u8],
+    event_tx: &async_channel::Sender<ConnectionEvent>,
+) {
+    let Some(active) = capture.as_mut() else {
+        return;
+    };
+    if let Err(e) = active.record(direction, data) {
+        *capture = None;
+        let _ = event_tx
+            .send(ConnectionEvent::CommandFailed(format!(
+                "Capture brute interrompue : {e:#}"
+            )))
+            .await;
+        let _ = event_tx.send(ConnectionEvent::RawCapture(None)).await;
+    }
+}
+
+/// Termine la capture en cours, s'il y en a une.
+fn stop_capture(capture: &mut Option<RawCapture>) {
+    if let Some(active) = capture.take() {
+        let path = active.path().display().to_string();
+        match active.finish() {
+            Ok(()) => log::info!("Capture brute enregistrée dans {path}"),
+            Err(e) => log::warn!("Fin de la capture brute {path} : {e:#}"),
+        }
+    }
+}
+
+/// Événement `ControlLines` pour les connexions qui ont des lignes de contrôle.
+fn control_lines_event(connection: &dyn Connection) -> Option<ConnectionEvent> {
+    connection
+        .control_lines()
+        .map(|(dtr, rts)| ConnectionEvent::ControlLines { dtr, rts })
+}
+
+/// Issue d'un `SetDtr`/`SetRts` : l'échec éventuel, puis un seul
+/// `ControlLines`, aussi en cas d'échec pour que l'UI revienne à l'état réel.
+async fn report_line_change(
+    result: Result<()>,
+    lines: Option<ConnectionEvent>,
+    event_tx: &async_channel::Sender<ConnectionEvent>,
+) {
+    if let Err(e) = result {
+        let _ = event_tx
+            .send(ConnectionEvent::CommandFailed(e.to_string()))
+            .await;
+    }
+    if let Some(event) = lines {
+        let _ = event_tx.send(event).await;
+    }
 }
 
 /// Lance une tâche asynchrone pour gérer la connexion.
@@ -134,15 +454,20 @@ pub trait Connection: Send {
 /// - Sortie (core → UI) : `async_channel::Receiver<ConnectionEvent>`
 ///
 /// Le core ne dépend d'aucun toolkit UI. Le pont vers `GLib` est dans window.rs.
+///
+/// `command_capacity` et `event_capacity` bornent les deux canaux (minimum 1).
 pub fn spawn_connection_actor(
     mut connection: Box<dyn Connection>,
+    command_capacity: usize,
+    event_capacity: usize,
 ) -> (
     tokio::sync::mpsc::Sender<ConnectionCommand>,
     async_channel::Receiver<ConnectionEvent>,
 ) {
-    let (cmd_tx, mut cmd_rx) = tokio::sync::mpsc::channel::<ConnectionCommand>(32);
-    // bounded(128) : backpressure si l'UI consomme trop lentement
-    let (event_tx, event_rx) = async_channel::bounded::<ConnectionEvent>(128);
+    let (cmd_tx, mut cmd_rx) =
+        tokio::sync::mpsc::channel::<ConnectionCommand>(command_capacity.max(1));
+    // Canal borné : backpressure si l'UI consomme trop lentement
+    let (event_tx, event_rx) = async_channel::bounded::<ConnectionEvent>(event_capacity.max(1));
 
     // Injecter le canal avant connect() — utilisé par SshManager pour la
     // vérification interactive des clés d'hôte (SOLID : core sans dépendance GTK).
@@ -161,6 +486,9 @@ pub fn spawn_connection_actor(
                         description: connection.description(),
                     })
                     .await;
+                if let Some(event) = control_lines_event(connection.as_ref()) {
+                    let _ = event_tx.send(event).await;
+                }
             }
             Err(e) => {
                 let _ = event_tx.send(ConnectionEvent::Error(e.to_string())).await;
@@ -169,7 +497,17 @@ pub fn spawn_connection_actor(
         }
 
         // ── Phase 2 : Boucle I/O ──────────────────────────────────────────────
-        loop {
+        // La boucle se termine avec l'événement final à transmettre à l'UI
+        // (`None` si l'UI ne consomme plus).
+        let mut stall_count: u64 = 0;
+        let mut stall_total_ms: u64 = 0;
+        let mut reading_paused = false;
+        let mut latency_tracking = false;
+        // Instant du dernier envoi encore sans réponse.
+        let mut latency_pending: Option<Instant> = None;
+        let mut latency = LatencyStats::default();
+        let mut capture: Option<RawCapture> = None;
+        let final_event = loop {
             tokio::select! {
                 biased; // prioritise les commandes UI sur la lecture
 
@@ -177,58 +515,387 @@ pub fn spawn_connection_actor(
                 cmd = cmd_rx.recv() => {
                     match cmd {
                         Some(ConnectionCommand::SendData(data)) => {
-                            if let Err(e) = connection.send(&data).await {
+                            let sent_at = Instant::now();
+                            let written = match connection.send(&data).await {
+                                Ok(written) => written,
+                                Err(e) => {
+                                    let _ = connection.disconnect().await;
+                                    break Some(ConnectionEvent::Error(e.to_string()));
+                                }
+                            };
+                            record_capture(&mut capture, Direction::Tx, &data, &event_tx).await;
+                            // Le dernier envoi fait référence : une commande restée
+                            // sans réponse ne fausse pas la mesure suivante.
+                            if latency_tracking {
+                                latency_pending = Some(sent_at);
+                            }
+                            if event_tx.send(ConnectionEvent::DataSent(written)).await.is_err() {
                                 let _ = connection.disconnect().await;
-                                let _ = event_tx.send(ConnectionEvent::Error(e.to_string())).await;
-                                break;
+                                break None;
+                            }
+                        }
+                        Some(ConnectionCommand::FlushBuffers { input, output }) => {
+                            let event = match connection.flush_buffers(input, output).await {
+                                Ok(()) => ConnectionEvent::BuffersFlushed { input, output },
+                                Err(e) => ConnectionEvent::CommandFailed(e.to_string()),
+                            };
+                            let _ = event_tx.send(event).await;
+                        }
+                        Some(ConnectionCommand::SendBreak { duration_ms }) => {
+                            let duration = Duration::from_millis(duration_ms);
+                            let event = match connection.send_break(duration).await {
+                                Ok(()) => ConnectionEvent::ControlSignalSent(format!(
+                                    "Break envoyé ({duration_ms} ms)"
+                                )),
+                                Err(e) => ConnectionEvent::CommandFailed(e.to_string()),
+                            };
+                            let _ = event_tx.send(event).await;
+                        }
+                        Some(ConnectionCommand::PulseReset { duration_ms }) => {
+                            let duration = Duration::from_millis(duration_ms);
+                            let event = match connection.pulse_reset(duration).await {
+                                Ok(()) => ConnectionEvent::ControlSignalSent(format!(
+                                    "Reset DTR/RTS envoyé ({duration_ms} ms)"
+                                )),
+                                Err(e) => ConnectionEvent::CommandFailed(e.to_string()),
+                            };
+                            let _ = event_tx.send(event).await;
+                            if let Some(event) = control_lines_event(connection.as_ref()) {
+                                let _ = event_tx.send(event).await;
+                            }
+                        }
+                        Some(ConnectionCommand::SetDtr(level)) => {
+                            let result = connection.set_dtr(level).await;
+                            let lines = control_lines_event(connection.as_ref());
+                            report_line_change(result, lines, &event_tx).await;
+                        }
+                        Some(ConnectionCommand::SetRts(level)) => {
+                            let result = connection.set_rts(level).await;
+                            let lines = control_lines_event(connection.as_ref());
+                            report_line_change(result, lines, &event_tx).await;
+                        }
+                        Some(ConnectionCommand::PauseReading(paused)) => {
+                            let event = match connection.set_reading_paused(paused).await {
+                                Ok(()) => {
+                                    reading_paused = paused;
+                                    ConnectionEvent::ReadingPaused(paused)
+                                }
+                                Err(e) => ConnectionEvent::CommandFailed(e.to_string()),
+                            };
+                            let _ = event_tx.send(event).await;
+                            // Contrôle de flux matériel : RTS a pu changer.
+                            if let Some(event) = control_lines_event(connection.as_ref()) {
+                                let _ = event_tx.send(event).await;
+                            }
+                        }
+                        Some(ConnectionCommand::StartCapture { path, options }) => {
+                            stop_capture(&mut capture);
+                            let event = match RawCapture::create(&path, options) {
+                                Ok(started) => {
+                                    capture = Some(started);
+                                    ConnectionEvent::RawCapture(Some(path))
+                                }
+                                Err(e) => ConnectionEvent::CommandFailed(format!("{e:#}")),
+                            };
+                            let _ = event_tx.send(event).await;
+                        }
+                        Some(ConnectionCommand::StopCapture) => {
+                            stop_capture(&mut capture);
+                            let _ = event_tx.send(ConnectionEvent::RawCapture(None)).await;
+                        }
+                        Some(ConnectionCommand::SetLatencyTracking(enabled)) => {
+                            latency_tracking = enabled;
+                            latency_pending = None;
+                        }
+                        Some(ConnectionCommand::QueryInfo(reply_tx)) => {
+                            // L'UI a pu abandonner la requête entre-temps.
+                            let _ = reply_tx.send(connection.info());
+                        }
+                        Some(ConnectionCommand::Resize { cols, rows }) => {
+                            // Émis à chaque redimensionnement : pas d'erreur affichée.
+                            if let Err(e) = connection.resize(cols, rows).await {
+                                log::warn!("Redimensionnement du terminal distant : {e:#}");
                             }
                         }
+                        Some(ConnectionCommand::OpenSftp(reply_tx)) => {
+                            let _ = reply_tx.send(connection.open_sftp().await);
+                        }
                         Some(ConnectionCommand::Disconnect) | None => {
                             // Déconnexion propre demandée ou channel fermé
                             let _ = connection.disconnect().await;
-                            let _ = event_tx.send(ConnectionEvent::Disconnected).await;
-                            break;
+                            break Some(ConnectionEvent::Disconnected);
                         }
                     }
                 }
 
-                // Lecture depuis la connexion
-                read_result = connection.read() => {
+                // Lecture depuis la connexion (sauf si suspendue)
+                read_result = connection.read(), if !reading_paused => {
                     match read_result {
                         Ok(data) if !data.is_empty() => {
+                            let last = latency_pending.take().map(|sent_at| sent_at.elapsed());
+                            record_capture(&mut capture, Direction::Rx, &data, &event_tx).await;
+                            let stalled_at = event_tx.is_full().then(Instant::now);
                             if event_tx.send(ConnectionEvent::DataReceived(data)).await.is_err() {
                                 // L'UI ne consomme plus → on arrête
                                 let _ = connection.disconnect().await;
-                                break;
+                                break None;
+                            }
+                            if let Some(last) = last {
+                                latency.record(last);
+                                log::info!("Latence : {}", LatencyStats::format_ms(last));
+                                let event = ConnectionEvent::Latency { last, stats: latency };
+                                if event_tx.send(event).await.is_err() {
+                                    let _ = connection.disconnect().await;
+                                    break None;
+                                }
+                            }
+                            if let Some(stalled_at) = stalled_at {
+                                stall_count += 1;
+                                stall_total_ms += u64::try_from(stalled_at.elapsed().as_millis())
+                                    .unwrap_or(u64::MAX);
+                                let event = ConnectionEvent::ReadStalled {
+                                    count: stall_count,
+                                    total_ms: stall_total_ms,
+                                };
+                                if event_tx.send(event).await.is_err() {
+                                    let _ = connection.disconnect().await;
+                                    break None;
+                                }
                             }
                         }
                         Ok(_) => {
+                            let stderr = connection.take_stderr();
+                            if !stderr.is_empty() {
+                                if event_tx.send(ConnectionEvent::StderrReceived(stderr)).await.is_err() {
+                                    let _ = connection.disconnect().await;
+                                    break None;
+                                }
+                                continue;
+                            }
                             // Pas de données ; vérifier déconnexion spontanée
                             let s = connection.state();
                             if s == ConnectionState::Disconnected || s == ConnectionState::Error {
                                 // Fermer proprement (ex: SSH envoie un message de fin)
                                 let _ = connection.disconnect().await;
-                                let _ = event_tx.send(ConnectionEvent::Disconnected).await;
-                                break;
+                                break Some(ConnectionEvent::Disconnected);
                             }
                         }
                         Err(e) => {
                             let _ = connection.disconnect().await;
-                            let _ = event_tx.send(ConnectionEvent::Error(e.to_string())).await;
-                            break;
+                            break Some(ConnectionEvent::Error(e.to_string()));
                         }
                     }
                 }
             }
-        }
+        };
 
+        stop_capture(&mut capture);
         log::info!(
             "Connexion terminée — envoyés: {} octets, reçus: {} octets",
             connection.bytes_sent(),
             connection.bytes_received()
         );
+        if latency.count > 0 {
+            log::info!("Latence de la session : {latency}");
+        }
+
+        // Bilan de session puis événement final (l'UI arrête son pompage dessus).
+        if let Some(event) = final_event {
+            let _ = event_tx
+                .send(ConnectionEvent::SessionStats {
+                    bytes_sent: connection.bytes_sent(),
+                    bytes_received: connection.bytes_received(),
+                    latency: (latency.count > 0).then_some(latency),
+                })
+                .await;
+            let _ = event_tx.send(event).await;
+        }
         log::debug!("Acteur de connexion arrêté proprement.");
     });
 
     (cmd_tx, event_rx)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn latency_stats_track_min_average_and_max() {
+        let mut stats = LatencyStats::default();
+        assert_eq!(stats.average(), None);
+        assert_eq!(stats.to_string(), "aucune mesure");
+
+        stats.record(Duration::from_millis(12));
+        assert_eq!(
+            stats.to_string(),
+            "min 12.0 ms / moy 12.0 ms / max 12.0 ms (1 mesure)"
+        );
+
+        stats.record(Duration::from_micros(4_250));
+        stats.record(Duration::from_millis(30));
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.min, Duration::from_micros(4_250));
+        assert_eq!(stats.max, Duration::from_millis(30));
+        assert_eq!(stats.average(), Some(Duration::from_nanos(15_416_666)));
+        assert_eq!(
+            stats.to_string(),
+            "min 4.2 ms / moy 15.4 ms / max 30.0 ms (3 mesures)"
+        );
+    }
+
+    #[test]
+    fn latency_stats_keep_a_zero_minimum() {
+        let mut stats = LatencyStats::default();
+        stats.record(Duration::from_millis(5));
+        stats.record(Duration::ZERO);
+        stats.record(Duration::from_millis(1));
+        assert_eq!(stats.min, Duration::ZERO);
+        assert_eq!(stats.average(), Some(Duration::from_millis(2)));
+        assert_eq!(
+            LatencyStats::format_ms(Duration::from_micros(1_550)),
+            "1.6 ms"
+        );
+    }
+
+    /// Échange vu par la connexion, dans l'ordre où il a eu lieu.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Exchange {
+        Sent(usize),
+        Received(Vec<u8>),
+    }
+
+    /// Périphérique simulé qui répond `<données>!` à chaque envoi et note
+    /// l'ordre réel des écritures et des lectures.
+    struct EchoDevice {
+        journal: Arc<Mutex<Vec<Exchange>>>,
+        replies_tx: mpsc::UnboundedSender<Vec<u8>>,
+        replies_rx: mpsc::UnboundedReceiver<Vec<u8>>,
+        state: ConnectionState,
+    }
+
+    #[async_trait]
+    impl Connection for EchoDevice {
+        async fn connect(&mut self) -> Result<()> {
+            self.state = ConnectionState::Connected;
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            self.state = ConnectionState::Disconnected;
+            Ok(())
+        }
+
+        async fn send(&mut self, data: &[u8]) -> Result<usize> {
+            self.journal
+                .lock()
+                .unwrap()
+                .push(Exchange::Sent(data.len()));
+            let mut reply = data.to_vec();
+            reply.push(b'!');
+            self.replies_tx.send(reply)?;
+            Ok(data.len())
+        }
+
+        async fn read(&mut self) -> Result<Vec<u8>> {
+            // `recv` est annulable sans perte ; la réponse est notée au
+            // moment où elle est rendue, donc dans l'ordre réel.
+            let data = self.replies_rx.recv().await.unwrap_or_default();
+            self.journal
+                .lock()
+                .unwrap()
+                .push(Exchange::Received(data.clone()));
+            Ok(data)
+        }
+
+        fn state(&self) -> ConnectionState {
+            self.state
+        }
+
+        fn connection_type(&self) -> ConnectionType {
+            ConnectionType::Serial
+        }
+
+        fn description(&self) -> String {
+            "écho simulé".to_string()
+        }
+
+        fn bytes_sent(&self) -> u64 {
+            0
+        }
+
+        fn bytes_received(&self) -> u64 {
+            0
+        }
+    }
+
+    #[tokio::test]
+    async fn sent_and_received_events_follow_exchange_order() {
+        let journal = Arc::new(Mutex::new(Vec::new()));
+        let (replies_tx, replies_rx) = mpsc::unbounded_channel();
+        let device = EchoDevice {
+            journal: journal.clone(),
+            replies_tx,
+            replies_rx,
+            state: ConnectionState::Disconnected,
+        };
+        let (cmd_tx, event_rx) = spawn_connection_actor(Box::new(device), 16, 4);
+
+        // Envois groupés puis espacés : l'acteur entrelace écritures et
+        // lectures différemment selon les cas. Tailles distinctes pour
+        // reconnaître chaque `DataSent`.
+        let commands: [&[u8]; 6] = [b"a", b"bb", b"ccc", b"dddd", b"eeeee", b"ffffff"];
+        for (i, data) in commands.iter().enumerate() {
+            cmd_tx
+                .send(ConnectionCommand::SendData(data.to_vec()))
+                .await
+                .unwrap();
+            if i % 2 == 1 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+
+        let mut events = Vec::new();
+        let mut received = 0;
+        while received < commands.len() {
+            match event_rx.recv().await.unwrap() {
+                ConnectionEvent::DataSent(n) => events.push(Exchange::Sent(n)),
+                ConnectionEvent::DataReceived(data) => {
+                    received += 1;
+                    events.push(Exchange::Received(data));
+                }
+                _ => {}
+            }
+        }
+        cmd_tx.send(ConnectionCommand::Disconnect).await.unwrap();
+        while !matches!(
+            event_rx.recv().await,
+            Ok(ConnectionEvent::Disconnected) | Err(_)
+        ) {}
+
+        assert_eq!(events, *journal.lock().unwrap());
+        // Chaque réponse suit son envoi, et les réponses gardent l'ordre des envois.
+        let replies: Vec<Vec<u8>> = events
+            .iter()
+            .filter_map(|e| match e {
+                Exchange::Received(data) => Some(data.clone()),
+                Exchange::Sent(_) => None,
+            })
+            .collect();
+        let expected: Vec<Vec<u8>> = commands.iter().map(|c| [*c, b"!"].concat()).collect();
+        assert_eq!(replies, expected);
+        for (i, command) in commands.iter().enumerate() {
+            let sent = events
+                .iter()
+                .position(|e| *e == Exchange::Sent(command.len()));
+            let reply = events
+                .iter()
+                .position(|e| *e == Exchange::Received(expected[i].clone()));
+            assert!(sent < reply, "réponse {i} avant son envoi : {events:?}");
+        }
+    }
+}
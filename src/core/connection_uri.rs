@@ -0,0 +1,273 @@
+// =============================================================================
+// Fichier : connection_uri.rs
+// Rôle    : Lien partageable `sshterm://` décrivant une connexion (sans secret)
+//
+//   sshterm://ssh/utilisateur@hôte:22?key=~/.ssh/id_ed25519
+//   sshterm://serial/%2Fdev%2FttyUSB0?baud=115200&data=8&parity=None&stop=1&flow=None
+// =============================================================================
+
+use anyhow::{bail, Context, Result};
+
+/// Schéma des liens, déclaré dans le fichier .desktop.
+pub const URI_SCHEME: &str = "sshterm";
+
+/// Paramètres de connexion transportés par un lien.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionUri {
+    Serial {
+        port: String,
+        baudrate: u32,
+        data_bits: u8,
+        parity: String,
+        stop_bits: u8,
+        flow_control: String,
+    },
+    Ssh {
+        host: String,
+        port: u16,
+        username: String,
+        /// Chemin de la clé privée (vide = mot de passe).
+        key_path: String,
+    },
+}
+
+impl ConnectionUri {
+    /// Construit le lien. Mots de passe et passphrases ne sont jamais inclus.
+    pub fn to_uri(&self) -> String {
+        match self {
+            Self::Serial {
+                port,
+                baudrate,
+                data_bits,
+                parity,
+                stop_bits,
+                flow_control,
+            } => format!(
+                "{URI_SCHEME}://serial/{}?baud={baudrate}&data={data_bits}&parity={}&stop={stop_bits}&flow={}",
+                percent_encode(port),
+                percent_encode(parity),
+                percent_encode(flow_control)
+            ),
+            Self::Ssh {
+                host,
+                port,
+                username,
+                key_path,
+            } => {
+                let mut uri = format!(
+                    "{URI_SCHEME}://ssh/{}@{}:{port}",
+                    percent_encode(username),
+                    percent_encode(host)
+                );
+                if !key_path.is_empty() {
+                    uri.push_str(&format!("?key={}", percent_encode(key_path)));
+                }
+                uri
+            }
+        }
+    }
+
+    /// Parse un lien `sshterm://`. Les paramètres absents prennent les
+    /// valeurs usuelles (8N1, 115200 bauds, port SSH 22).
+    pub fn parse(uri: &str) -> Result<Self> {
+        let rest = uri
+            .trim()
+            .strip_prefix(URI_SCHEME)
+            .and_then(|r| r.strip_prefix("://"))
+            .with_context(|| format!("Lien {URI_SCHEME}:// attendu"))?;
+        let (kind, rest) = rest.split_once('/').context("Type de connexion manquant")?;
+        let (target, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let params: Vec<(String, String)> = query
+            .split('&')
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let (k, v) = p.split_once('=').unwrap_or((p, ""));
+                Ok((percent_decode(k)?, percent_decode(v)?))
+            })
+            .collect::<Result<_>>()?;
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str())
+        };
+
+        match kind {
+            "serial" => {
+                let port = percent_decode(target)?;
+                if port.is_empty() {
+                    bail!("Port série manquant");
+                }
+                Ok(Self::Serial {
+                    port,
+                    baudrate: parse_number(param("baud"), 115_200, "baud")?,
+                    data_bits: parse_number(param("data"), 8, "data")?,
+                    parity: param("parity").unwrap_or("None").to_string(),
+                    stop_bits: parse_number(param("stop"), 1, "stop")?,
+                    flow_control: param("flow").unwrap_or("None").to_string(),
+                })
+            }
+            "ssh" => {
+                let (user, address) = target.split_once('@').context("Utilisateur manquant")?;
+                let (host, port) = match address.rsplit_once(':') {
+                    Some((host, port)) => (host, Some(port)),
+                    None => (address, None),
+                };
+                let host = percent_decode(host)?;
+                if host.is_empty() {
+                    bail!("Hôte SSH manquant");
+                }
+                Ok(Self::Ssh {
+                    host,
+                    port: parse_number(port, 22, "port")?,
+                    username: percent_decode(user)?,
+                    key_path: param("key").unwrap_or_default().to_string(),
+                })
+            }
+            other => bail!("Type de connexion inconnu : {other}"),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<&str>, default: T, name: &str) -> Result<T> {
+    match value {
+        None | Some("") => Ok(default),
+        Some(v) => v
+            .parse()
+            .ok()
+            .with_context(|| format!("Valeur invalide pour {name} : {v}")),
+    }
+}
+
+/// Encode tout sauf les caractères non réservés (RFC 3986).
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            out.push(char::from(b));
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+fn percent_decode(value: &str) -> Result<String> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3).context("Échappement % incomplet")?;
+            // `from_str_radix` accepterait un signe (`%+1`).
+            if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                bail!("Échappement % invalide : %{hex}");
+            }
+            out.push(u8::from_str_radix(hex, 16).context("Échappement % invalide")?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).context("Lien non UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serial_uri_round_trip() {
+        let serial = ConnectionUri::Serial {
+            port: "/dev/ttyUSB0".to_string(),
+            baudrate: 921_600,
+            data_bits: 7,
+            parity: "Even".to_string(),
+            stop_bits: 2,
+            flow_control: "Hardware".to_string(),
+        };
+        let uri = serial.to_uri();
+        assert_eq!(
+            uri,
+            "sshterm://serial/%2Fdev%2FttyUSB0?baud=921600&data=7&parity=Even&stop=2&flow=Hardware"
+        );
+        assert_eq!(ConnectionUri::parse(&uri).unwrap(), serial);
+
+        // Paramètres absents : 8N1 à 115200 bauds.
+        assert_eq!(
+            ConnectionUri::parse(" sshterm://serial/COM3 ").unwrap(),
+            ConnectionUri::Serial {
+                port: "COM3".to_string(),
+                baudrate: 115_200,
+                data_bits: 8,
+                parity: "None".to_string(),
+                stop_bits: 1,
+                flow_control: "None".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn ssh_uri_round_trip_with_reserved_characters() {
+        let ssh = ConnectionUri::Ssh {
+            host: "2001:db8::1".to_string(),
+            port: 2222,
+            username: "jean.dupont@corp:x y/é".to_string(),
+            key_path: "~/.ssh/clé #1?&=%.pem".to_string(),
+        };
+        let uri = ssh.to_uri();
+        assert!(uri.starts_with(
+            "sshterm://ssh/jean.dupont%40corp%3Ax%20y%2F%C3%A9@2001%3Adb8%3A%3A1:2222?key="
+        ));
+        assert_eq!(ConnectionUri::parse(&uri).unwrap(), ssh);
+
+        let password = ConnectionUri::Ssh {
+            host: "example.org".to_string(),
+            port: 22,
+            username: "root".to_string(),
+            key_path: String::new(),
+        };
+        assert_eq!(password.to_uri(), "sshterm://ssh/root@example.org:22");
+        assert_eq!(
+            ConnectionUri::parse("sshterm://ssh/root@example.org").unwrap(),
+            password
+        );
+    }
+
+    #[test]
+    fn percent_encoding_round_trip() {
+        let reserved = ":/?#[]@!$&'()*+,;= %é\u{1F600}\0";
+        let encoded = percent_encode(reserved);
+        assert!(encoded
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'%'));
+        assert_eq!(percent_decode(&encoded).unwrap(), reserved);
+        assert_eq!(percent_encode("A-z.0_9~"), "A-z.0_9~");
+        assert_eq!(percent_decode("a%2fb%2Fc").unwrap(), "a/b/c");
+    }
+
+    #[test]
+    fn malformed_uris_are_rejected() {
+        for uri in [
+            "http://serial/COM3",
+            "sshterm:serial/COM3",
+            "sshterm://serial",
+            "sshterm://serial/?baud=9600",
+            "sshterm://serial/COM3?baud=rapide",
+            "sshterm://serial/COM3?data=300",
+            "sshterm://ssh/example.org",
+            "sshterm://ssh/root@:22",
+            "sshterm://ssh/root@host:99999",
+            "sshterm://telnet/host",
+            "sshterm://serial/COM%",
+            "sshterm://serial/COM%4",
+            "sshterm://serial/COM%G1",
+            "sshterm://serial/COM%+1",
+            "sshterm://serial/COM%FF",
+            "sshterm://serial/COM3?key=%Zz",
+        ] {
+            assert!(ConnectionUri::parse(uri).is_err(), "{uri}");
+        }
+    }
+}
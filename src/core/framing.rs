@@ -0,0 +1,663 @@
+// =============================================================================
+// Fichier : framing.rs
+// Rôle    : Décodage de trames (SLIP, COBS, préfixe de longueur, délimiteurs
+//           personnalisés) du flux reçu
+//
+// Le décodeur est alimenté morceau par morceau : une trame peut arriver en
+// plusieurs lectures, ou plusieurs trames dans une seule.
+// =============================================================================
+
+/// Taille maximale d'une trame : au-delà, le tampon est abandonné.
+const MAX_FRAME_LEN: usize = 64 * 1024;
+
+/// Octets spéciaux SLIP (RFC 1055).
+const SLIP_END: u8 = 0xC0;
+const SLIP_ESC: u8 = 0xDB;
+const SLIP_ESC_END: u8 = 0xDC;
+const SLIP_ESC_ESC: u8 = 0xDD;
+
+/// Mode de décodage de trame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramingMode {
+    /// Flux affiché tel quel (terminal).
+    None,
+    /// SLIP : trames terminées par `0xC0`, échappement `0xDB`.
+    Slip,
+    /// COBS : trames terminées par `0x00`.
+    Cobs,
+    /// Longueur sur 2 octets (big-endian) suivie des données.
+    LengthPrefixed,
+    /// Marqueurs de début et de fin choisis par l'utilisateur (ex. STX/ETX).
+    Delimited,
+}
+
+impl FramingMode {
+    /// Parse depuis la valeur persistée.
+    pub fn from_str_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "slip" => Self::Slip,
+            "cobs" => Self::Cobs,
+            "length" | "length-prefixed" => Self::LengthPrefixed,
+            "delimited" => Self::Delimited,
+            _ => Self::None,
+        }
+    }
+
+    /// Identifiant persisté dans les paramètres.
+    pub const fn id(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Slip => "slip",
+            Self::Cobs => "cobs",
+            Self::LengthPrefixed => "length",
+            Self::Delimited => "delimited",
+        }
+    }
+
+    /// Nom d'affichage.
+    pub const fn display_name(&self) -> &str {
+        match self {
+            Self::None => "Aucun",
+            Self::Slip => "SLIP",
+            Self::Cobs => "COBS",
+            Self::LengthPrefixed => "Longueur (2 octets BE)",
+            Self::Delimited => "Délimiteurs début/fin",
+        }
+    }
+
+    /// Liste de tous les modes.
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::None,
+            Self::Slip,
+            Self::Cobs,
+            Self::LengthPrefixed,
+            Self::Delimited,
+        ]
+    }
+}
+
+/// Décodeur de trames à état.
+#[derive(Debug)]
+pub struct FrameDecoder {
+    mode: FramingMode,
+    buffer: Vec<u8>,
+    /// SLIP : octet d'échappement reçu, en attente du suivant.
+    escape: bool,
+    /// Délimiteurs : marqueur de début (vide = trames séparées par la fin).
+    start: Vec<u8>,
+    /// Délimiteurs : marqueur de fin.
+    end: Vec<u8>,
+    /// Délimiteurs : début reçu, trame en cours.
+    in_frame: bool,
+    /// Trame trop longue déjà signalée : ignorée jusqu'à sa fin, pour ne pas
+    /// en livrer la suite comme une trame.
+    discarding: bool,
+}
+
+impl FrameDecoder {
+    pub const fn new(mode: FramingMode) -> Self {
+        Self {
+            mode,
+            buffer: Vec::new(),
+            escape: false,
+            start: Vec::new(),
+            end: Vec::new(),
+            in_frame: false,
+            discarding: false,
+        }
+    }
+
+    pub const fn mode(&self) -> FramingMode {
+        self.mode
+    }
+
+    /// Change de mode et abandonne la trame en cours.
+    pub fn set_mode(&mut self, mode: FramingMode) {
+        self.mode = mode;
+        self.reset();
+    }
+
+    /// Marqueurs du mode `Delimited` ; abandonne la trame en cours.
+    ///
+    /// Sans marqueur de fin, aucune trame n'est produite.
+    pub fn set_delimiters(&mut self, start: Vec<u8>, end: Vec<u8>) {
+        self.start = start;
+        self.end = end;
+        self.reset();
+    }
+
+    /// Abandonne la trame en cours (nouvelle connexion).
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.escape = false;
+        self.in_frame = false;
+        self.discarding = false;
+    }
+
+    /// Ajoute des octets reçus et retourne les trames complètes, dans l'ordre.
+    ///
+    /// Une trame invalide est signalée par `Err` ; le décodage reprend à la
+    /// trame suivante. En mode `None`, rien n'est retourné.
+    pub fn push(&mut self, data: &[u8]) -> Vec<Result<Vec<u8>, String>> {
+        let mut frames = Vec::new();
+        for &byte in data {
+            match self.mode {
+                FramingMode::None => return Vec::new(),
+                FramingMode::Slip => self.push_slip(byte, &mut frames),
+                FramingMode::Cobs => {
+                    if byte == 0 {
+                        if std::mem::take(&mut self.discarding) {
+                            self.buffer.clear();
+                        } else if !self.buffer.is_empty() {
+                            frames.push(cobs_decode(&self.buffer));
+                            self.buffer.clear();
+                        }
+                    } else {
+                        self.push_byte(byte, &mut frames);
+                    }
+                }
+                FramingMode::Delimited => self.push_delimited(byte, &mut frames),
+                FramingMode::LengthPrefixed => {
+                    // La longueur sur 2 octets borne déjà la trame : la limite
+                    // `MAX_FRAME_LEN` couperait une trame de 64 Kio en plein
+                    // milieu et ferait lire ses données comme un en-tête.
+                    self.buffer.push(byte);
+                    if self.buffer.len() >= 2 {
+                        let len = usize::from(u16::from_be_bytes([self.buffer[0], self.buffer[1]]));
+                        if self.buffer.len() == len + 2 {
+                            frames.push(Ok(self.buffer[2..].to_vec()));
+                            self.buffer.clear();
+                        }
+                    }
+                }
+            }
+        }
+        frames
+    }
+
+    fn push_slip(&mut self, byte: u8, frames: &mut Vec<Result<Vec<u8>, String>>) {
+        if std::mem::take(&mut self.escape) && !self.discarding {
+            match byte {
+                SLIP_ESC_END => self.push_byte(SLIP_END, frames),
+                SLIP_ESC_ESC => self.push_byte(SLIP_ESC, frames),
+                other => {
+                    frames.push(Err(format!(
+                        "SLIP : échappement invalide 0x{other:02X}, trame ignorée"
+                    )));
+                    self.buffer.clear();
+                }
+            }
+            return;
+        }
+        match byte {
+            SLIP_END if std::mem::take(&mut self.discarding) => self.buffer.clear(),
+            // Trames vides (END de synchronisation) ignorées.
+            SLIP_END if !self.buffer.is_empty() => {
+                frames.push(Ok(std::mem::take(&mut self.buffer)));
+            }
+            SLIP_END => {}
+            SLIP_ESC => self.escape = true,
+            other => self.push_byte(other, frames),
+        }
+    }
+
+    fn push_delimited(&mut self, byte: u8, frames: &mut Vec<Result<Vec<u8>, String>>) {
+        if self.end.is_empty() {
+            return;
+        }
+        if !self.start.is_empty() && !self.in_frame {
+            // Hors trame : seule la fin du tampon peut encore former le début.
+            self.buffer.push(byte);
+            if self.buffer.ends_with(&self.start) {
+                self.buffer.clear();
+                self.in_frame = true;
+            } else if self.buffer.len() >= self.start.len() {
+                let excess = self.buffer.len() + 1 - self.start.len();
+                self.buffer.drain(..excess);
+            }
+            return;
+        }
+
+        self.push_byte(byte, frames);
+        if self.buffer.ends_with(&self.end) {
+            self.buffer.truncate(self.buffer.len() - self.end.len());
+            self.in_frame = false;
+            let frame = std::mem::take(&mut self.buffer);
+            // Sans début, des fins consécutives ne font pas de trames vides.
+            if !std::mem::take(&mut self.discarding)
+                && (!frame.is_empty() || !self.start.is_empty())
+            {
+                frames.push(Ok(frame));
+            }
+        } else if !self.start.is_empty() && self.buffer.ends_with(&self.start) {
+            if !std::mem::take(&mut self.discarding) {
+                frames.push(Err(
+                    "Délimiteur de début reçu avant la fin : trame tronquée ignorée".to_string(),
+                ));
+            }
+            self.buffer.clear();
+        }
+    }
+
+    fn push_byte(&mut self, byte: u8, frames: &mut Vec<Result<Vec<u8>, String>>) {
+        self.buffer.push(byte);
+        if self.discarding {
+            // Seuls les derniers octets servent encore : reconnaître les
+            // marqueurs du mode `Delimited`.
+            let keep = self.start.len().max(self.end.len());
+            let excess = self.buffer.len().saturating_sub(keep);
+            self.buffer.drain(..excess);
+        } else if self.buffer.len() > MAX_FRAME_LEN {
+            frames.push(Err(format!(
+                "Trame de plus de {MAX_FRAME_LEN} octets abandonnée"
+            )));
+            self.buffer.clear();
+            self.escape = false;
+            self.discarding = true;
+        }
+    }
+}
+
+/// Décode une trame COBS (sans le `0x00` final).
+fn cobs_decode(encoded: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        let code = usize::from(encoded[i]);
+        if code == 0 {
+            return Err("COBS : octet de code nul".to_string());
+        }
+        let end = i + code;
+        if end > encoded.len() {
+            return Err(format!(
+                "COBS : trame tronquée ({} octets attendus)",
+                end - encoded.len()
+            ));
+        }
+        decoded.extend_from_slice(&encoded[i + 1..end]);
+        // Un code < 0xFF implique un zéro, sauf en fin de trame.
+        if code < 0xFF && end < encoded.len() {
+            decoded.push(0);
+        }
+        i = end;
+    }
+    Ok(decoded)
+}
+
+/// Octets en hexadécimal séparés par des espaces (`48 65 0D 0A`).
+pub fn hex_bytes(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse une saisie hexadécimale (`48 65 0D`, `48650d`, `0x48,0x65`).
+///
+/// Espaces, virgules, `:` et préfixes `0x` sont ignorés ; un nombre impair
+/// de chiffres (ou un `0x` seul) est refusé plutôt que complété.
+pub fn parse_hex_bytes(text: &str) -> Result<Vec<u8>, String> {
+    let mut digits = String::with_capacity(text.len());
+    for token in text.split(|c: char| c.is_whitespace() || c == ',' || c == ':') {
+        let digits_only = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"));
+        if digits_only == Some("") {
+            return Err(format!("Préfixe sans chiffres : {token}"));
+        }
+        let token = digits_only.unwrap_or(token);
+        if let Some(c) = token.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(format!("Caractère non hexadécimal : {c:?}"));
+        }
+        if token.len() % 2 != 0 {
+            return Err(format!("Nombre impair de chiffres : {token}"));
+        }
+        digits.push_str(token);
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// Texte échappé : ASCII imprimable tel quel, `\r` `\n` `\t`, sinon `\xNN`.
+pub fn escape_bytes(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len());
+    for &b in data {
+        match b {
+            b'\r' => out.push_str("\\r"),
+            b'\n' => out.push_str("\\n"),
+            b'\t' => out.push_str("\\t"),
+            b'\\' => out.push_str("\\\\"),
+            b' '..=b'~' => out.push(char::from(b)),
+            _ => out.push_str(&format!("\\x{b:02X}")),
+        }
+    }
+    out
+}
+
+/// Inverse de `escape_bytes` : `\xNN`, `\r`, `\n`, `\t`, `\\` ; le reste
+/// est pris tel quel (UTF-8).
+pub fn unescape_bytes(text: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('r') => out.push(b'\r'),
+            Some('n') => out.push(b'\n'),
+            Some('t') => out.push(b'\t'),
+            Some('\\') => out.push(b'\\'),
+            Some('x') => {
+                let digits: String = chars.by_ref().take(2).collect();
+                // `from_str_radix` accepterait un signe (`\x+1`).
+                let byte = Some(&digits)
+                    .filter(|d| d.len() == 2 && d.chars().all(|c| c.is_ascii_hexdigit()))
+                    .and_then(|d| u8::from_str_radix(d, 16).ok())
+                    .ok_or_else(|| format!("Séquence \\x invalide : \\x{digits}"))?;
+                out.push(byte);
+            }
+            Some(other) => return Err(format!("Échappement inconnu : \\{other}")),
+            None => return Err("Barre oblique inverse finale".to_string()),
+        }
+    }
+    Ok(out)
+}
+
+/// Vidage hexadécimal : 16 octets par ligne, offset, hex et ASCII.
+pub fn hex_dump(data: &[u8]) -> String {
+    hex_dump_rows(data, 0, 4)
+}
+
+/// Vidage hexadécimal d'un morceau de flux commençant à l'octet `offset`
+/// (offset sur 8 chiffres). Les lignes restent alignées sur 16 octets : un
+/// morceau commencé en milieu de ligne laisse vides les colonnes des octets
+/// déjà affichés.
+pub fn hex_dump_at(data: &[u8], offset: u64) -> String {
+    hex_dump_rows(data, offset, 8)
+}
+
+fn hex_dump_rows(data: &[u8], offset: u64, offset_width: usize) -> String {
+    let mut out = String::new();
+    let mut skip = usize::try_from(offset % 16).unwrap_or(0);
+    let mut row_offset = offset - offset % 16;
+    let mut rest = data;
+    while !rest.is_empty() {
+        let (chunk, tail) = rest.split_at((16 - skip).min(rest.len()));
+        let mut hex = vec!["  ".to_string(); skip];
+        hex.extend(chunk.iter().map(|b| format!("{b:02X}")));
+        let ascii: String = " ".repeat(skip)
+            + &chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        char::from(b)
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>();
+        out.push_str(&format!(
+            "{row_offset:0offset_width$X}  {:<47}  |{ascii}|\n",
+            hex.join(" ")
+        ));
+        row_offset += 16;
+        skip = 0;
+        rest = tail;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_dump_shows_invalid_utf8_as_dots() {
+        assert_eq!(
+            hex_dump(b"ok\xFF\xC3\xA9\x00"),
+            format!("0000  {:<47}  |ok....|\n", "6F 6B FF C3 A9 00")
+        );
+        assert_eq!(
+            hex_dump_at(b"\xE2\x82", 0x10),
+            format!("00000010  {:<47}  |..|\n", "E2 82")
+        );
+    }
+
+    #[test]
+    fn hex_dump_keeps_rows_aligned_across_reads() {
+        // Lecture commencée en colonne 14 : 2 octets sur la ligne 0x10, la
+        // suite sur une ligne alignée en 0x20.
+        let dump = hex_dump_at(b"ABCDE", 0x1E);
+        let rows: Vec<&str> = dump.lines().collect();
+        assert_eq!(
+            rows,
+            vec![
+                format!(
+                    "00000010  {:<47}  |              AB|",
+                    format!("{}41 42", "   ".repeat(14))
+                ),
+                format!("00000020  {:<47}  |CDE|", "43 44 45"),
+            ]
+        );
+        // Colonnes identiques à celles d'une ligne complète lue d'un coup.
+        let full = hex_dump_at(b"0123456789abcdAB", 0x10);
+        assert_eq!(rows[0].find("41 42"), full.find("41 42"));
+        assert_eq!(rows[0].find('|'), full.find('|'));
+
+        assert_eq!(
+            hex_dump_at(&[0x20; 20], 0x100).lines().count(),
+            2,
+            "un morceau aligné de 20 octets tient sur deux lignes"
+        );
+    }
+
+    #[test]
+    fn parse_hex_bytes_separators() {
+        let expected = Ok(vec![0x48, 0x65, 0x0D, 0x0A]);
+        for text in [
+            "48 65 0D 0A",
+            "48650d0a",
+            "0x48,0x65,0X0d,0x0a",
+            "48:65:0D:0A",
+            " 48\t65\n0d  0A ",
+            "4865 0D0A",
+        ] {
+            assert_eq!(parse_hex_bytes(text), expected, "{text:?}");
+        }
+        assert_eq!(parse_hex_bytes(""), Ok(Vec::new()));
+        assert_eq!(parse_hex_bytes(" , "), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn parse_hex_bytes_rejects_odd_and_invalid_digits() {
+        for text in [
+            "4", "486", "48 6", "0x4", "4 8", "0x", "48 G0", "+1", "0x-1", "48é",
+        ] {
+            assert!(parse_hex_bytes(text).is_err(), "{text:?}");
+        }
+        assert_eq!(
+            parse_hex_bytes("48 6"),
+            Err("Nombre impair de chiffres : 6".to_string())
+        );
+        assert_eq!(
+            parse_hex_bytes("4Z"),
+            Err("Caractère non hexadécimal : 'Z'".to_string())
+        );
+    }
+
+    #[test]
+    fn unescape_bytes_sequences_and_errors() {
+        assert_eq!(
+            unescape_bytes(r"AT\r\n\t\\\x00\xfFé"),
+            Ok(b"AT\r\n\t\\\x00\xFF\xC3\xA9".to_vec())
+        );
+        assert_eq!(unescape_bytes(""), Ok(Vec::new()));
+        assert!(unescape_bytes(r"\x4").is_err());
+        assert!(unescape_bytes(r"\x4G").is_err());
+        assert!(unescape_bytes(r"\x+1").is_err());
+        assert!(unescape_bytes(r"\q").is_err());
+        assert!(unescape_bytes(r"fin\").is_err());
+    }
+
+    #[test]
+    fn escape_then_unescape_round_trips_every_byte() {
+        let all: Vec<u8> = (0..=u8::MAX).collect();
+        let escaped = escape_bytes(&all);
+        assert!(escaped.is_ascii());
+        assert_eq!(unescape_bytes(&escaped), Ok(all));
+        assert_eq!(escape_bytes(b"a\\b\r\n"), r"a\\b\r\n");
+    }
+
+    /// Alimente le décodeur morceau par morceau et concatène les résultats.
+    fn decode_chunks(decoder: &mut FrameDecoder, chunks: &[&[u8]]) -> Vec<Result<Vec<u8>, String>> {
+        chunks
+            .iter()
+            .flat_map(|chunk| decoder.push(chunk))
+            .collect()
+    }
+
+    #[test]
+    fn slip_frames_split_across_pushes() {
+        let mut decoder = FrameDecoder::new(FramingMode::Slip);
+        let frames = decode_chunks(&mut decoder, &[b"\xC0ab", b"c\xC0\xC0d", b"", b"e\xC0"]);
+        assert_eq!(frames, vec![Ok(b"abc".to_vec()), Ok(b"de".to_vec())]);
+    }
+
+    #[test]
+    fn slip_escape_sequences() {
+        let mut decoder = FrameDecoder::new(FramingMode::Slip);
+        // Échappement coupé entre deux lectures.
+        let frames = decode_chunks(&mut decoder, &[b"1\xDB", b"\xDC2\xDB\xDD3\xC0"]);
+        assert_eq!(frames, vec![Ok(b"1\xC02\xDB3".to_vec())]);
+
+        // Échappement invalide : trame abandonnée, la suivante est intacte.
+        let frames = decoder.push(b"x\xDBy\xC0ok\xC0");
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].is_err());
+        assert_eq!(frames[1], Ok(b"ok".to_vec()));
+    }
+
+    #[test]
+    fn cobs_frames_and_encoded_zeros() {
+        let mut decoder = FrameDecoder::new(FramingMode::Cobs);
+        // 11 22 00 33 → 03 11 22 02 33 ; 00 → 01 01.
+        let frames = decode_chunks(
+            &mut decoder,
+            &[b"\x03\x11", b"\x22\x02\x33\x00\x01", b"\x01\x00"],
+        );
+        assert_eq!(
+            frames,
+            vec![Ok(vec![0x11, 0x22, 0x00, 0x33]), Ok(vec![0x00])]
+        );
+
+        // Bloc de 254 octets non nuls (code 0xFF) : pas de zéro implicite.
+        let mut encoded = vec![0xFF];
+        encoded.extend(1..=254u8);
+        encoded.extend_from_slice(&[0x02, 0x07, 0x00]);
+        let mut expected: Vec<u8> = (1..=254u8).collect();
+        expected.push(0x07);
+        assert_eq!(decoder.push(&encoded), vec![Ok(expected)]);
+    }
+
+    #[test]
+    fn cobs_decode_errors() {
+        assert!(cobs_decode(&[0x00]).is_err());
+        assert!(cobs_decode(&[0x02, 0x11, 0x00, 0x01]).is_err());
+        assert_eq!(
+            cobs_decode(&[0x05, 0x11, 0x22]),
+            Err("COBS : trame tronquée (2 octets attendus)".to_string())
+        );
+        // Une trame invalide n'empêche pas la suivante.
+        let mut decoder = FrameDecoder::new(FramingMode::Cobs);
+        let frames = decoder.push(b"\x04\x11\x00\x02\x42\x00");
+        assert!(frames[0].is_err());
+        assert_eq!(frames[1], Ok(vec![0x42]));
+    }
+
+    #[test]
+    fn length_prefixed_frames_split_across_pushes() {
+        let mut decoder = FrameDecoder::new(FramingMode::LengthPrefixed);
+        let frames = decode_chunks(&mut decoder, &[b"\x00", b"\x03ab", b"c\x00\x00\x00\x01z"]);
+        assert_eq!(
+            frames,
+            vec![Ok(b"abc".to_vec()), Ok(Vec::new()), Ok(b"z".to_vec())]
+        );
+    }
+
+    #[test]
+    fn length_prefixed_maximum_length_keeps_sync() {
+        let mut decoder = FrameDecoder::new(FramingMode::LengthPrefixed);
+        let payload: Vec<u8> = (0..usize::from(u16::MAX))
+            .map(|i| u8::try_from(i % 7).unwrap())
+            .collect();
+        let mut stream = u16::MAX.to_be_bytes().to_vec();
+        stream.extend_from_slice(&payload);
+        stream.extend_from_slice(b"\x00\x02ok");
+
+        let frames = decode_chunks(&mut decoder, &[&stream[..40_000], &stream[40_000..]]);
+        assert_eq!(frames, vec![Ok(payload), Ok(b"ok".to_vec())]);
+    }
+
+    #[test]
+    fn oversize_frame_is_dropped_until_its_end() {
+        let oversize = vec![0x55; MAX_FRAME_LEN + 10];
+
+        let mut decoder = FrameDecoder::new(FramingMode::Slip);
+        let mut stream = oversize.clone();
+        stream.extend_from_slice(b"\xC0next\xC0");
+        let frames = decoder.push(&stream);
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].is_err());
+        assert_eq!(frames[1], Ok(b"next".to_vec()));
+
+        let mut decoder = FrameDecoder::new(FramingMode::Cobs);
+        let mut stream = oversize.clone();
+        stream.extend_from_slice(b"\x00\x02\x42\x00");
+        let frames = decoder.push(&stream);
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].is_err());
+        assert_eq!(frames[1], Ok(vec![0x42]));
+
+        let mut decoder = FrameDecoder::new(FramingMode::Delimited);
+        decoder.set_delimiters(b"<<".to_vec(), b">>".to_vec());
+        let mut stream = b"<<".to_vec();
+        stream.extend_from_slice(&oversize);
+        stream.extend_from_slice(b">><<ok>>");
+        let frames = decoder.push(&stream);
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].is_err());
+        assert_eq!(frames[1], Ok(b"ok".to_vec()));
+    }
+
+    #[test]
+    fn delimited_frames() {
+        let mut decoder = FrameDecoder::new(FramingMode::Delimited);
+        decoder.set_delimiters(vec![0x02], vec![0x03]);
+        // Bruit avant le début, marqueurs et données répartis sur plusieurs lectures.
+        let frames = decode_chunks(&mut decoder, &[b"xx\x02he", b"llo\x03yy\x02", b"\x03"]);
+        assert_eq!(frames, vec![Ok(b"hello".to_vec()), Ok(Vec::new())]);
+
+        // Début reçu avant la fin : trame tronquée signalée, la suivante gardée.
+        let frames = decoder.push(b"\x02ab\x02cd\x03");
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].is_err());
+        assert_eq!(frames[1], Ok(b"cd".to_vec()));
+
+        // Sans début : trames séparées par la fin (marqueur sur 2 octets coupé).
+        decoder.set_delimiters(Vec::new(), b"\r\n".to_vec());
+        let frames = decode_chunks(&mut decoder, &[b"a\r", b"\n\r\nb\r\n"]);
+        assert_eq!(frames, vec![Ok(b"a".to_vec()), Ok(b"b".to_vec())]);
+    }
+
+    #[test]
+    fn no_framing_returns_nothing() {
+        let mut decoder = FrameDecoder::new(FramingMode::None);
+        assert!(decoder.push(b"\xC0abc\x00").is_empty());
+    }
+}
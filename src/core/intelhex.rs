@@ -0,0 +1,321 @@
+// =============================================================================
+// Fichier : intelhex.rs
+// Rôle    : Lecture et validation d'un fichier Intel HEX avant envoi série
+//
+// Chaque enregistrement est vérifié (syntaxe, longueur, somme de contrôle)
+// avant tout envoi : un bootloader recevant une ligne corrompue peut laisser
+// la cible dans un état incohérent.
+// =============================================================================
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Type d'enregistrement Intel HEX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordType {
+    Data,
+    EndOfFile,
+    ExtendedSegmentAddress,
+    StartSegmentAddress,
+    ExtendedLinearAddress,
+    StartLinearAddress,
+}
+
+impl RecordType {
+    const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0x00 => Some(Self::Data),
+            0x01 => Some(Self::EndOfFile),
+            0x02 => Some(Self::ExtendedSegmentAddress),
+            0x03 => Some(Self::StartSegmentAddress),
+            0x04 => Some(Self::ExtendedLinearAddress),
+            0x05 => Some(Self::StartLinearAddress),
+            _ => None,
+        }
+    }
+}
+
+/// Enregistrement validé.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexRecord {
+    /// Numéro de ligne dans le fichier (à partir de 1).
+    pub line: usize,
+    pub record_type: RecordType,
+    /// Adresse 16 bits du champ d'adresse.
+    pub address: u16,
+    pub data: Vec<u8>,
+    /// Ligne telle qu'envoyée (`:` compris, sans fin de ligne).
+    pub text: String,
+}
+
+/// Erreur de validation d'une ligne.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Fichier Intel HEX entièrement validé.
+#[derive(Debug, Clone, Default)]
+pub struct IntelHexFile {
+    pub records: Vec<HexRecord>,
+    /// Plage d'adresses absolues couverte par les données `[début, fin]`.
+    pub address_range: Option<(u32, u32)>,
+    /// Nombre total d'octets de données.
+    pub data_len: usize,
+}
+
+/// Lit et valide un fichier Intel HEX.
+///
+/// Retourne `Ok(Err(erreurs))` si des enregistrements sont invalides : le
+/// fichier ne doit alors pas être envoyé.
+pub fn load_intel_hex(path: &Path) -> Result<Result<IntelHexFile, Vec<HexError>>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Impossible de lire {}", path.display()))?;
+    Ok(parse_intel_hex(&content))
+}
+
+/// Valide le contenu d'un fichier Intel HEX (lignes vides ignorées).
+///
+/// Toutes les erreurs sont collectées, une par ligne fautive.
+pub fn parse_intel_hex(content: &str) -> Result<IntelHexFile, Vec<HexError>> {
+    let mut file = IntelHexFile::default();
+    let mut errors = Vec::new();
+    // Base d'adresse courante (enregistrements 02 et 04).
+    let mut base: u32 = 0;
+    let mut end_seen = false;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = idx + 1;
+        let text = raw.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if end_seen {
+            errors.push(HexError {
+                line,
+                message: "Données après l'enregistrement de fin".to_string(),
+            });
+            continue;
+        }
+
+        let record = match parse_record(line, text) {
+            Ok(record) => record,
+            Err(message) => {
+                errors.push(HexError { line, message });
+                continue;
+            }
+        };
+
+        match record.record_type {
+            RecordType::Data if !record.data.is_empty() => {
+                let start = base.wrapping_add(u32::from(record.address));
+                let len = u32::try_from(record.data.len()).unwrap_or(u32::MAX);
+                let end = start.wrapping_add(len - 1);
+                file.address_range = Some(match file.address_range {
+                    Some((lo, hi)) => (lo.min(start), hi.max(end)),
+                    None => (start, end),
+                });
+                file.data_len += record.data.len();
+            }
+            RecordType::ExtendedSegmentAddress | RecordType::ExtendedLinearAddress => {
+                if record.data.len() == 2 {
+                    let value = u32::from(u16::from_be_bytes([record.data[0], record.data[1]]));
+                    base = if record.record_type == RecordType::ExtendedSegmentAddress {
+                        value << 4
+                    } else {
+                        value << 16
+                    };
+                } else {
+                    errors.push(HexError {
+                        line,
+                        message: "Adresse étendue : 2 octets attendus".to_string(),
+                    });
+                    continue;
+                }
+            }
+            RecordType::EndOfFile => end_seen = true,
+            _ => {}
+        }
+        file.records.push(record);
+    }
+
+    if !end_seen && errors.is_empty() {
+        errors.push(HexError {
+            line: content.lines().count(),
+            message: "Enregistrement de fin (:00000001FF) manquant".to_string(),
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(file)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parse une ligne `:LLAAAATT[DD…]CC`.
+fn parse_record(line: usize, text: &str) -> Result<HexRecord, String> {
+    let hex = text
+        .strip_prefix(':')
+        .ok_or_else(|| "La ligne doit commencer par ':'".to_string())?;
+    if hex.len() % 2 != 0 || hex.len() < 10 {
+        return Err(format!("Longueur invalide ({} caractères)", text.len()));
+    }
+
+    // Lecture octet par octet : un caractère non ASCII ne doit pas couper
+    // une tranche au milieu d'un caractère UTF-8.
+    let bytes = hex
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = char::from(pair[0]).to_digit(16)?;
+            let low = char::from(pair[1]).to_digit(16)?;
+            u8::try_from(high << 4 | low).ok()
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| "Caractère non hexadécimal".to_string())?;
+
+    let count = usize::from(bytes[0]);
+    if bytes.len() != count + 5 {
+        return Err(format!(
+            "Nombre d'octets annoncé ({count}) différent du contenu ({})",
+            bytes.len() - 5
+        ));
+    }
+
+    let sum = bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+    if sum != 0 {
+        let expected = bytes[..bytes.len() - 1]
+            .iter()
+            .fold(0u8, |acc, b| acc.wrapping_add(*b))
+            .wrapping_neg();
+        return Err(format!(
+            "Somme de contrôle invalide : 0x{:02X} au lieu de 0x{expected:02X}",
+            bytes[bytes.len() - 1]
+        ));
+    }
+
+    let record_type = RecordType::from_code(bytes[3])
+        .ok_or_else(|| format!("Type d'enregistrement inconnu 0x{:02X}", bytes[3]))?;
+
+    Ok(HexRecord {
+        line,
+        record_type,
+        address: u16::from_be_bytes([bytes[1], bytes[2]]),
+        data: bytes[4..bytes.len() - 1].to_vec(),
+        text: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EOF: &str = ":00000001FF";
+
+    /// Construit une ligne avec sa somme de contrôle.
+    fn record(kind: u8, address: u16, data: &[u8]) -> String {
+        let [hi, lo] = address.to_be_bytes();
+        let mut bytes = vec![u8::try_from(data.len()).unwrap(), hi, lo, kind];
+        bytes.extend_from_slice(data);
+        let sum = bytes.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+        bytes.push(sum.wrapping_neg());
+        let hex: String = bytes.iter().map(|b| format!("{b:02X}")).collect();
+        format!(":{hex}")
+    }
+
+    fn parse_errors(content: &str) -> Vec<HexError> {
+        parse_intel_hex(content).expect_err("fichier invalide accepté")
+    }
+
+    #[test]
+    fn valid_file_range_and_length() {
+        // Exemple classique : 16 octets à 0x0100, puis 3 à 0x0110.
+        let content = format!(
+            ":10010000214601360121470136007EFE09D2190140\n{}\n\n{EOF}\n",
+            record(0x00, 0x0110, &[1, 2, 3])
+        );
+        let file = parse_intel_hex(&content).unwrap();
+        assert_eq!(file.records.len(), 3);
+        assert_eq!(file.records[0].record_type, RecordType::Data);
+        assert_eq!(file.records[0].address, 0x0100);
+        assert_eq!(file.records[2].record_type, RecordType::EndOfFile);
+        assert_eq!(file.records[2].line, 4);
+        assert_eq!(file.address_range, Some((0x0100, 0x0112)));
+        assert_eq!(file.data_len, 19);
+    }
+
+    #[test]
+    fn bad_checksum() {
+        let errors = parse_errors(&format!(":0300300002337A1F\n{EOF}"));
+        assert_eq!(
+            errors,
+            vec![HexError {
+                line: 1,
+                message: "Somme de contrôle invalide : 0x1F au lieu de 0x1E".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_end_of_file_record() {
+        let errors = parse_errors(&record(0x00, 0, &[0xAA]));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("manquant"), "{errors:?}");
+
+        let errors = parse_errors(&format!("{EOF}\n{}", record(0x00, 0, &[0xAA])));
+        assert_eq!(errors[0].line, 2);
+        assert!(errors[0].message.contains("après"), "{errors:?}");
+    }
+
+    #[test]
+    fn extended_linear_address() {
+        let content = [
+            record(0x04, 0, &[0x08, 0x00]),
+            record(0x00, 0x0010, &[0; 4]),
+            record(0x04, 0, &[0x08, 0x01]),
+            record(0x00, 0x0000, &[0; 2]),
+            EOF.to_string(),
+        ]
+        .join("\n");
+        let file = parse_intel_hex(&content).unwrap();
+        assert_eq!(file.address_range, Some((0x0800_0010, 0x0801_0001)));
+        assert_eq!(file.data_len, 6);
+    }
+
+    #[test]
+    fn extended_segment_address() {
+        let content = [
+            record(0x02, 0, &[0x10, 0x00]),
+            record(0x00, 0x0004, &[0; 8]),
+            EOF.to_string(),
+        ]
+        .join("\n");
+        let file = parse_intel_hex(&content).unwrap();
+        assert_eq!(file.address_range, Some((0x0001_0004, 0x0001_000B)));
+
+        let errors = parse_errors(&format!("{}\n{EOF}", record(0x04, 0, &[0x08])));
+        assert_eq!(errors[0].message, "Adresse étendue : 2 octets attendus");
+    }
+
+    #[test]
+    fn every_invalid_line_is_reported() {
+        let content = format!(
+            "10010000\n:0G000000\n:0100000001\n{}\n:00000007F9\n{EOF}",
+            record(0x00, 0, &[1])
+        );
+        let lines: Vec<usize> = parse_errors(&content).iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn non_ascii_and_signed_digits_are_rejected() {
+        for line in [":aé0000000", ":é00000001FF", ":+0000001FF"] {
+            let errors = parse_errors(&format!("{line}\n{EOF}"));
+            assert_eq!(errors.len(), 1, "{line}");
+            assert_eq!(errors[0].message, "Caractère non hexadécimal", "{line}");
+        }
+    }
+}
@@ -0,0 +1,335 @@
+// =============================================================================
+// Fichier : keymap.rs
+// Rôle    : Traduction des touches du clavier en séquences d'octets de
+//           terminal (xterm), pour le mode clavier direct
+// =============================================================================
+
+/// Touche indépendante du toolkit graphique (l'UI convertit ses événements).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalKey {
+    /// Caractère imprimable, majuscule déjà appliquée.
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Right,
+    Left,
+    Home,
+    End,
+    Insert,
+    Delete,
+    PageUp,
+    PageDown,
+    /// Touche de fonction F1 à F12.
+    Function(u8),
+}
+
+/// Modificateurs actifs au moment de l'appui.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyModifiers {
+    pub shift: bool,
+    pub alt: bool,
+    pub ctrl: bool,
+}
+
+impl KeyModifiers {
+    /// Paramètre de modificateur xterm (`1 + shift + 2·alt + 4·ctrl`),
+    /// `None` sans modificateur.
+    fn xterm_param(self) -> Option<u8> {
+        let param = 1 + u8::from(self.shift) + 2 * u8::from(self.alt) + 4 * u8::from(self.ctrl);
+        (param > 1).then_some(param)
+    }
+}
+
+/// Octet envoyé par la touche Retour arrière.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackspaceMode {
+    /// DEL (0x7F), attendu par Linux, readline et la plupart des shells.
+    #[default]
+    Del,
+    /// BS (0x08, Ctrl+H), pour certains équipements et anciens systèmes.
+    Bs,
+}
+
+impl BackspaceMode {
+    /// Parse depuis la valeur persistée.
+    pub fn from_str_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "bs" => Self::Bs,
+            _ => Self::Del,
+        }
+    }
+
+    /// Identifiant persisté dans les paramètres.
+    pub const fn id(&self) -> &str {
+        match self {
+            Self::Del => "del",
+            Self::Bs => "bs",
+        }
+    }
+
+    /// Nom d'affichage.
+    pub const fn display_name(&self) -> &str {
+        match self {
+            Self::Del => "DEL (0x7F)",
+            Self::Bs => "BS (0x08)",
+        }
+    }
+
+    /// Liste de tous les modes.
+    pub const fn all() -> &'static [Self] {
+        &[Self::Del, Self::Bs]
+    }
+}
+
+const ESC: u8 = 0x1B;
+
+/// Séquence envoyée pour `key`, `None` si la touche n'a pas d'équivalent.
+///
+/// Suit les conventions xterm (TERM=xterm-256color demandé par la session SSH) :
+/// curseur en mode normal (`ESC [ A`), F1–F4 en SS3, modificateurs sous la
+/// forme `ESC [ 1 ; m X`. Alt préfixe les touches simples par ESC.
+pub fn key_to_bytes(
+    key: TerminalKey,
+    mods: KeyModifiers,
+    backspace: BackspaceMode,
+) -> Option<Vec<u8>> {
+    let simple = match key {
+        TerminalKey::Char(c) => char_bytes(c, mods.ctrl)?,
+        TerminalKey::Enter => vec![b'\r'],
+        TerminalKey::Tab if mods.shift => return Some(b"\x1b[Z".to_vec()),
+        TerminalKey::Tab => vec![b'\t'],
+        TerminalKey::Backspace => match (backspace, mods.ctrl) {
+            // Ctrl inverse le choix, comme dans xterm.
+            (BackspaceMode::Del, false) | (BackspaceMode::Bs, true) => vec![0x7F],
+            (BackspaceMode::Del, true) | (BackspaceMode::Bs, false) => vec![0x08],
+        },
+        TerminalKey::Escape => vec![ESC],
+        TerminalKey::Up => return Some(cursor_key(b'A', mods)),
+        TerminalKey::Down => return Some(cursor_key(b'B', mods)),
+        TerminalKey::Right => return Some(cursor_key(b'C', mods)),
+        TerminalKey::Left => return Some(cursor_key(b'D', mods)),
+        TerminalKey::Home => return Some(cursor_key(b'H', mods)),
+        TerminalKey::End => return Some(cursor_key(b'F', mods)),
+        TerminalKey::Insert => return Some(tilde_key(2, mods)),
+        TerminalKey::Delete => return Some(tilde_key(3, mods)),
+        TerminalKey::PageUp => return Some(tilde_key(5, mods)),
+        TerminalKey::PageDown => return Some(tilde_key(6, mods)),
+        TerminalKey::Function(n) => return function_key(n, mods),
+    };
+    if mods.alt {
+        let mut bytes = Vec::with_capacity(simple.len() + 1);
+        bytes.push(ESC);
+        bytes.extend(simple);
+        Some(bytes)
+    } else {
+        Some(simple)
+    }
+}
+
+/// Caractère imprimable, éventuellement combiné avec Ctrl.
+fn char_bytes(c: char, ctrl: bool) -> Option<Vec<u8>> {
+    if !ctrl {
+        let mut buf = [0; 4];
+        return Some(c.encode_utf8(&mut buf).as_bytes().to_vec());
+    }
+    let byte = match c {
+        'a'..='z' => c as u8 - b'a' + 1,
+        'A'..='Z' => c as u8 - b'A' + 1,
+        '@' | ' ' | '2' => 0x00,
+        '[' | '3' => ESC,
+        '\\' | '4' => 0x1C,
+        ']' | '5' => 0x1D,
+        '^' | '6' => 0x1E,
+        '_' | '-' | '7' => 0x1F,
+        '?' | '8' => 0x7F,
+        _ => return None,
+    };
+    Some(vec![byte])
+}
+
+/// Flèches, Début et Fin : `ESC [ X` ou `ESC [ 1 ; m X`.
+fn cursor_key(code: u8, mods: KeyModifiers) -> Vec<u8> {
+    match mods.xterm_param() {
+        Some(param) => format!("\x1b[1;{param}{}", code as char).into_bytes(),
+        None => vec![ESC, b'[', code],
+    }
+}
+
+/// Touches d'édition : `ESC [ n ~` ou `ESC [ n ; m ~`.
+fn tilde_key(number: u8, mods: KeyModifiers) -> Vec<u8> {
+    match mods.xterm_param() {
+        Some(param) => format!("\x1b[{number};{param}~"),
+        None => format!("\x1b[{number}~"),
+    }
+    .into_bytes()
+}
+
+/// F1–F4 en SS3 (`ESC O P`…), F5–F12 en `ESC [ n ~`.
+fn function_key(n: u8, mods: KeyModifiers) -> Option<Vec<u8>> {
+    let number = match n {
+        1..=4 => {
+            let code = (b'P' + n - 1) as char;
+            return Some(match mods.xterm_param() {
+                Some(param) => format!("\x1b[1;{param}{code}").into_bytes(),
+                None => format!("\x1bO{code}").into_bytes(),
+            });
+        }
+        5 => 15,
+        6 => 17,
+        7 => 18,
+        8 => 19,
+        9 => 20,
+        10 => 21,
+        11 => 23,
+        12 => 24,
+        _ => return None,
+    };
+    Some(tilde_key(number, mods))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: KeyModifiers = KeyModifiers {
+        shift: false,
+        alt: false,
+        ctrl: false,
+    };
+    const SHIFT: KeyModifiers = KeyModifiers {
+        shift: true,
+        ..NONE
+    };
+    const ALT: KeyModifiers = KeyModifiers { alt: true, ..NONE };
+    const CTRL: KeyModifiers = KeyModifiers { ctrl: true, ..NONE };
+    const CTRL_SHIFT: KeyModifiers = KeyModifiers {
+        shift: true,
+        ctrl: true,
+        ..NONE
+    };
+    const ALL: KeyModifiers = KeyModifiers {
+        shift: true,
+        alt: true,
+        ctrl: true,
+    };
+
+    #[test]
+    fn key_table() {
+        use TerminalKey::*;
+
+        let table: &[(TerminalKey, KeyModifiers, &[u8])] = &[
+            // Flèches, Début/Fin : mode curseur normal, paramètre xterm sinon.
+            (Up, NONE, b"\x1b[A"),
+            (Down, NONE, b"\x1b[B"),
+            (Right, NONE, b"\x1b[C"),
+            (Left, NONE, b"\x1b[D"),
+            (Home, NONE, b"\x1b[H"),
+            (End, NONE, b"\x1b[F"),
+            (Up, SHIFT, b"\x1b[1;2A"),
+            (Left, ALT, b"\x1b[1;3D"),
+            (Right, CTRL, b"\x1b[1;5C"),
+            (End, CTRL_SHIFT, b"\x1b[1;6F"),
+            (Home, ALL, b"\x1b[1;8H"),
+            // Touches d'édition.
+            (Insert, NONE, b"\x1b[2~"),
+            (Delete, NONE, b"\x1b[3~"),
+            (PageUp, NONE, b"\x1b[5~"),
+            (PageDown, NONE, b"\x1b[6~"),
+            (Delete, CTRL, b"\x1b[3;5~"),
+            (PageUp, SHIFT, b"\x1b[5;2~"),
+            // F1–F4 en SS3, F5–F12 numérotées avec les trous de xterm.
+            (Function(1), NONE, b"\x1bOP"),
+            (Function(2), NONE, b"\x1bOQ"),
+            (Function(3), NONE, b"\x1bOR"),
+            (Function(4), NONE, b"\x1bOS"),
+            (Function(5), NONE, b"\x1b[15~"),
+            (Function(6), NONE, b"\x1b[17~"),
+            (Function(7), NONE, b"\x1b[18~"),
+            (Function(8), NONE, b"\x1b[19~"),
+            (Function(9), NONE, b"\x1b[20~"),
+            (Function(10), NONE, b"\x1b[21~"),
+            (Function(11), NONE, b"\x1b[23~"),
+            (Function(12), NONE, b"\x1b[24~"),
+            (Function(1), SHIFT, b"\x1b[1;2P"),
+            (Function(4), CTRL, b"\x1b[1;5S"),
+            (Function(5), CTRL, b"\x1b[15;5~"),
+            (Function(12), ALT, b"\x1b[24;3~"),
+            // Tabulation, Entrée, Échap ; Shift+Tab = CBT.
+            (Tab, NONE, b"\t"),
+            (Tab, SHIFT, b"\x1b[Z"),
+            (Enter, NONE, b"\r"),
+            (Escape, NONE, b"\x1b"),
+            // Caractères, Ctrl et préfixe Alt.
+            (Char('a'), NONE, b"a"),
+            (Char('é'), NONE, "é".as_bytes()),
+            (Char('c'), CTRL, b"\x03"),
+            (Char('C'), CTRL_SHIFT, b"\x03"),
+            (Char('@'), CTRL, b"\x00"),
+            (Char('['), CTRL, b"\x1b"),
+            (Char('?'), CTRL, b"\x7f"),
+            (Char('x'), ALT, b"\x1bx"),
+            (
+                Char('b'),
+                KeyModifiers {
+                    alt: true,
+                    ctrl: true,
+                    ..NONE
+                },
+                b"\x1b\x02",
+            ),
+            (Enter, ALT, b"\x1b\r"),
+        ];
+        for &(key, mods, expected) in table {
+            assert_eq!(
+                key_to_bytes(key, mods, BackspaceMode::Del).as_deref(),
+                Some(expected),
+                "{key:?} {mods:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn backspace_modes_and_ctrl_inversion() {
+        let cases = [
+            (BackspaceMode::Del, NONE, 0x7F),
+            (BackspaceMode::Del, CTRL, 0x08),
+            (BackspaceMode::Bs, NONE, 0x08),
+            (BackspaceMode::Bs, CTRL, 0x7F),
+        ];
+        for (mode, mods, byte) in cases {
+            assert_eq!(
+                key_to_bytes(TerminalKey::Backspace, mods, mode),
+                Some(vec![byte]),
+                "{mode:?} {mods:?}"
+            );
+        }
+        assert_eq!(
+            key_to_bytes(TerminalKey::Backspace, ALT, BackspaceMode::Del),
+            Some(vec![ESC, 0x7F])
+        );
+    }
+
+    #[test]
+    fn keys_without_sequence() {
+        for key in [TerminalKey::Function(0), TerminalKey::Function(13)] {
+            assert_eq!(key_to_bytes(key, NONE, BackspaceMode::Del), None);
+        }
+        assert_eq!(
+            key_to_bytes(TerminalKey::Char('é'), CTRL, BackspaceMode::Del),
+            None
+        );
+    }
+
+    #[test]
+    fn backspace_mode_names_round_trip() {
+        for &mode in BackspaceMode::all() {
+            assert_eq!(BackspaceMode::from_str_name(mode.id()), mode);
+        }
+        assert_eq!(BackspaceMode::from_str_name("BS"), BackspaceMode::Bs);
+        assert_eq!(BackspaceMode::from_str_name("?"), BackspaceMode::Del);
+    }
+}
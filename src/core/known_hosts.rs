@@ -0,0 +1,245 @@
+// =============================================================================
+// Fichier : known_hosts.rs
+// Rôle    : Lecture et édition de ~/.ssh/known_hosts (hôtes SSH connus)
+//
+// Le fichier est partagé avec OpenSSH : seules les lignes supprimées par
+// l'utilisateur sont retirées, le reste est réécrit à l'identique.
+// =============================================================================
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use russh::keys::{parse_public_key_base64, HashAlg};
+
+/// Entrée d'hôte connu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownHostEntry {
+    /// Numéro de ligne dans le fichier (à partir de 1).
+    pub line: usize,
+    /// Hôtes concernés (`hôte`, `[hôte]:port`…) ; vide si le nom est haché.
+    pub hosts: String,
+    /// Nom d'hôte haché (`|1|…`) : illisible sans connaître l'hôte.
+    pub hashed: bool,
+    /// Marqueur éventuel (`@cert-authority`, `@revoked`).
+    pub marker: Option<String>,
+    pub key_type: String,
+    /// Empreinte `SHA256:…` (vide si la clé n'a pas pu être décodée).
+    pub fingerprint: String,
+    /// Ligne brute, pour vérifier qu'elle n'a pas changé avant suppression.
+    raw: String,
+}
+
+/// Chemin de `~/.ssh/known_hosts` (celui utilisé à l'enregistrement des clés).
+pub fn known_hosts_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ssh").join("known_hosts"))
+}
+
+/// Liste les entrées de `~/.ssh/known_hosts` (fichier absent → liste vide).
+pub fn list_known_hosts() -> Result<Vec<KnownHostEntry>> {
+    let Some(path) = known_hosts_path() else {
+        return Ok(Vec::new());
+    };
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Impossible de lire {}", path.display()))?;
+    Ok(parse_known_hosts(&content))
+}
+
+/// Parse le contenu d'un fichier `known_hosts` (commentaires et lignes vides ignorés).
+pub fn parse_known_hosts(content: &str) -> Vec<KnownHostEntry> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| parse_line(idx + 1, line))
+        .collect()
+}
+
+/// Parse une ligne `[@marqueur] hôtes type clé [commentaire]`.
+fn parse_line(number: usize, line: &str) -> Option<KnownHostEntry> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    let mut fields = trimmed.split_whitespace();
+    let mut first = fields.next()?;
+    let marker = if first.starts_with('@') {
+        let marker = first.to_string();
+        first = fields.next()?;
+        Some(marker)
+    } else {
+        None
+    };
+    let key_type = fields.next()?.to_string();
+    let key_data = fields.next()?;
+
+    let hashed = first.starts_with("|1|");
+    let fingerprint = parse_public_key_base64(key_data)
+        .map(|key| key.fingerprint(HashAlg::Sha256).to_string())
+        .unwrap_or_default();
+
+    Some(KnownHostEntry {
+        line: number,
+        hosts: if hashed {
+            String::new()
+        } else {
+            first.to_string()
+        },
+        hashed,
+        marker,
+        key_type,
+        fingerprint,
+        raw: line.to_string(),
+    })
+}
+
+/// Supprime `entry` de `~/.ssh/known_hosts`.
+///
+/// Échoue si la ligne a changé depuis la lecture (fichier modifié entre-temps).
+pub fn remove_known_host(entry: &KnownHostEntry) -> Result<()> {
+    let path = known_hosts_path().context("Dossier personnel introuvable")?;
+    remove_entry_from(&path, entry)?;
+    log::info!(
+        "known_hosts : ligne {} supprimée ({} {})",
+        entry.line,
+        entry.hosts,
+        entry.key_type
+    );
+    Ok(())
+}
+
+/// Retire la ligne de `entry` du fichier `path`.
+///
+/// Le fichier est remplacé d'un coup (fichier temporaire du même dossier puis
+/// renommage) : OpenSSH ne voit jamais une version tronquée.
+fn remove_entry_from(path: &Path, entry: &KnownHostEntry) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Impossible de lire {}", path.display()))?;
+    let Some(updated) = without_entry(&content, entry) else {
+        bail!(
+            "{} a été modifié entre-temps, rechargez la liste",
+            path.display()
+        );
+    };
+
+    let file_name = path
+        .file_name()
+        .context("Chemin known_hosts invalide")?
+        .to_string_lossy();
+    let temp = path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+    let written = fs::write(&temp, updated)
+        .and_then(|()| fs::set_permissions(&temp, fs::metadata(path)?.permissions()))
+        .and_then(|()| fs::rename(&temp, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(e).with_context(|| format!("Impossible d'écrire {}", path.display()));
+    }
+    Ok(())
+}
+
+/// Contenu sans la ligne de `entry` (fins de ligne des autres lignes
+/// conservées, `\r\n` compris) ; `None` si cette ligne a changé.
+fn without_entry(content: &str, entry: &KnownHostEntry) -> Option<String> {
+    let idx = entry.line.checked_sub(1)?;
+    let mut lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let line = lines.get(idx)?;
+    if line
+        .strip_suffix('\n')
+        .map_or(*line, |l| l.strip_suffix('\r').unwrap_or(l))
+        != entry.raw
+    {
+        return None;
+    }
+    lines.remove(idx);
+    Some(lines.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "# hôtes\r\n\
+                           alpha ssh-ed25519 AAAA\r\n\
+                           @revoked beta ssh-rsa BBBB commentaire\r\n\
+                           |1|c2FsdA==|aGFzaA== ecdsa-sha2-nistp256 CCCC";
+
+    #[test]
+    fn entries_keep_their_line_numbers() {
+        let entries = parse_known_hosts(CONTENT);
+        assert_eq!(entries.len(), 3);
+        assert_eq!((entries[0].line, entries[0].hosts.as_str()), (2, "alpha"));
+        assert_eq!(entries[1].marker.as_deref(), Some("@revoked"));
+        assert_eq!(entries[1].key_type, "ssh-rsa");
+        assert!(entries[2].hashed && entries[2].hosts.is_empty());
+    }
+
+    #[test]
+    fn removal_keeps_other_lines_and_crlf() {
+        let entries = parse_known_hosts(CONTENT);
+        assert_eq!(
+            without_entry(CONTENT, &entries[0]).as_deref(),
+            Some(
+                "# hôtes\r\n\
+                 @revoked beta ssh-rsa BBBB commentaire\r\n\
+                 |1|c2FsdA==|aGFzaA== ecdsa-sha2-nistp256 CCCC"
+            )
+        );
+        // Dernière ligne, sans fin de ligne.
+        assert_eq!(
+            without_entry(CONTENT, &entries[2]).as_deref(),
+            Some(
+                "# hôtes\r\n\
+                 alpha ssh-ed25519 AAAA\r\n\
+                 @revoked beta ssh-rsa BBBB commentaire\r\n"
+            )
+        );
+        let lf = "a ssh-ed25519 AAAA\nb ssh-ed25519 BBBB\n";
+        let entries = parse_known_hosts(lf);
+        assert_eq!(
+            without_entry(lf, &entries[1]).as_deref(),
+            Some("a ssh-ed25519 AAAA\n")
+        );
+    }
+
+    #[test]
+    fn changed_line_is_not_removed() {
+        let entries = parse_known_hosts(CONTENT);
+        let shifted = format!("nouveau ssh-ed25519 DDDD\n{CONTENT}");
+        assert_eq!(without_entry(&shifted, &entries[0]), None);
+        assert_eq!(without_entry("", &entries[0]), None);
+        let first = KnownHostEntry {
+            line: 0,
+            ..entries[0].clone()
+        };
+        assert_eq!(without_entry(CONTENT, &first), None);
+    }
+
+    #[test]
+    fn file_is_replaced_in_place() {
+        let dir = std::env::temp_dir().join(format!("sst-known-hosts-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("known_hosts");
+        fs::write(&path, CONTENT).unwrap();
+
+        let entries = parse_known_hosts(CONTENT);
+        remove_entry_from(&path, &entries[1]).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# hôtes\r\n\
+             alpha ssh-ed25519 AAAA\r\n\
+             |1|c2FsdA==|aGFzaA== ecdsa-sha2-nistp256 CCCC"
+        );
+        // Ligne déjà supprimée : refus, fichier intact.
+        assert!(remove_entry_from(&path, &entries[1]).is_err());
+        assert_eq!(
+            fs::read_dir(&dir).unwrap().count(),
+            1,
+            "pas de fichier temporaire restant"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,385 @@
+// =============================================================================
+// Fichier : line_assembler.rs
+// Rôle    : Découpage du flux reçu en lignes selon un délimiteur configurable
+//
+// Toutes les fonctions « par ligne » (coloration, horodatage, détection…)
+// s'appuient sur ce découpage : le texte sorti du parseur d'échappement est
+// normalisé pour que chaque délimiteur devienne un `\n` unique.
+// =============================================================================
+
+/// Octet(s) marquant la fin d'une ligne reçue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineDelimiter {
+    /// `\n` (Unix, la plupart des firmwares).
+    Lf,
+    /// `\r` seul (un `\n` qui suit immédiatement est absorbé).
+    Cr,
+    /// `\r\n` uniquement.
+    CrLf,
+    /// `\r`, `\n` ou `\r\n`.
+    Any,
+    /// Octet personnalisé (ex: `0x03` ETX, `;`).
+    Byte(u8),
+}
+
+impl LineDelimiter {
+    /// Parse depuis la valeur persistée : `LF`, `CR`, `CRLF`, `ANY` ou `0xNN`.
+    pub fn from_str_name(s: &str) -> Self {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                return Self::Byte(byte);
+            }
+        }
+        match s.to_uppercase().as_str() {
+            "CR" => Self::Cr,
+            "CRLF" => Self::CrLf,
+            "ANY" | "AUTO" => Self::Any,
+            _ => Self::Lf,
+        }
+    }
+
+    /// Valeur persistée dans les paramètres.
+    pub fn id(&self) -> String {
+        match self {
+            Self::Lf => "LF".to_string(),
+            Self::Cr => "CR".to_string(),
+            Self::CrLf => "CRLF".to_string(),
+            Self::Any => "ANY".to_string(),
+            Self::Byte(byte) => format!("0x{byte:02X}"),
+        }
+    }
+}
+
+/// Assemble les lignes du flux reçu, caractère par caractère.
+///
+/// Il reçoit ce que le parseur d'échappement a laissé passer (texte
+/// imprimable et contrôles C0) : un délimiteur présent dans une séquence
+/// (`;` de `\x1b[1;31m`) n'est jamais vu. Un délimiteur à cheval sur deux
+/// morceaux (`\r` puis `\n`) est géré.
+#[derive(Debug)]
+pub struct LineAssembler {
+    delimiter: LineDelimiter,
+    /// Dernier caractère traité = `\r` (début possible d'un `\r\n`).
+    after_cr: bool,
+}
+
+impl LineAssembler {
+    pub const fn new(delimiter: LineDelimiter) -> Self {
+        Self {
+            delimiter,
+            after_cr: false,
+        }
+    }
+
+    /// Change le délimiteur et oublie l'état en cours.
+    pub fn set_delimiter(&mut self, delimiter: LineDelimiter) {
+        self.delimiter = delimiter;
+        self.clear();
+    }
+
+    /// Oublie l'état en cours (nouvelle session, terminal effacé).
+    pub fn clear(&mut self) {
+        self.after_cr = false;
+    }
+
+    /// Normalise `c` : un délimiteur devient un `\n` unique.
+    ///
+    /// Retourne les caractères à afficher, dans l'ordre : aucun (LF d'un
+    /// `\r\n` déjà compté, `\r` mis en attente), un, ou deux quand un `\r`
+    /// en attente s'avère isolé. Un délimiteur `Byte` est comparé au
+    /// caractère de même code (ASCII en pratique).
+    pub fn push(&mut self, c: char) -> [Option<char>; 2] {
+        let was_cr = std::mem::take(&mut self.after_cr);
+        match (self.delimiter, c) {
+            (LineDelimiter::Cr | LineDelimiter::Any, '\r') => {
+                self.after_cr = true;
+                [Some('\n'), None]
+            }
+            // LF d'un `\r\n` : la ligne est déjà terminée.
+            (LineDelimiter::Cr | LineDelimiter::Any, '\n') if was_cr => [None, None],
+            (LineDelimiter::CrLf, '\r') => {
+                // En attente du LF éventuel.
+                self.after_cr = true;
+                [was_cr.then_some('\r'), None]
+            }
+            (LineDelimiter::CrLf, '\n') if was_cr => [Some('\n'), None],
+            (LineDelimiter::Lf | LineDelimiter::Any, '\n') => [Some('\n'), None],
+            (LineDelimiter::Byte(delimiter), _) if c == char::from(delimiter) => [Some('\n'), None],
+            // `\r` isolé (mode CRLF) : simple retour chariot.
+            _ => [
+                (was_cr && self.delimiter == LineDelimiter::CrLf).then_some('\r'),
+                Some(c),
+            ],
+        }
+    }
+
+    /// Rend le `\r` mis en attente en mode CRLF, avant une séquence
+    /// d'échappement qui doit s'appliquer après lui.
+    ///
+    /// Dans les autres modes, le `\r` a déjà été rendu : l'état est gardé
+    /// pour absorber le LF qui suivrait la séquence.
+    pub fn take_pending(&mut self) -> Option<char> {
+        (self.delimiter == LineDelimiter::CrLf && std::mem::take(&mut self.after_cr))
+            .then_some('\r')
+    }
+}
+
+/// Nombre d'octets examinés au plus pour détecter la fin de ligne.
+const DETECT_SAMPLE_BYTES: usize = 512;
+
+/// Fins de ligne nécessaires pour conclure avant la fin de l'échantillon.
+const DETECT_MIN_LINES: u32 = 4;
+
+/// Détecte la fin de ligne utilisée par un périphérique à partir des
+/// premiers octets reçus.
+#[derive(Debug, Default)]
+pub struct LineEndingDetector {
+    sampled: usize,
+    lf: u32,
+    cr: u32,
+    crlf: u32,
+    /// Dernier octet examiné = `\r` (un `\n` peut suivre au morceau suivant).
+    after_cr: bool,
+}
+
+impl LineEndingDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Examine un morceau reçu ; retourne la fin de ligne majoritaire dès
+    /// qu'elle est établie (`Lf`, `Cr` ou `CrLf`), sinon `None`.
+    ///
+    /// Une fois l'échantillon épuisé sans aucune fin de ligne, la détection
+    /// abandonne et retourne toujours `None`.
+    pub fn push(&mut self, data: &[u8]) -> Option<LineDelimiter> {
+        for &byte in data {
+            if self.sampled >= DETECT_SAMPLE_BYTES {
+                break;
+            }
+            self.sampled += 1;
+            match byte {
+                b'\n' if std::mem::take(&mut self.after_cr) => self.crlf += 1,
+                b'\n' => self.lf += 1,
+                b'\r' => {
+                    if std::mem::replace(&mut self.after_cr, true) {
+                        self.cr += 1;
+                    }
+                }
+                _ => {
+                    if std::mem::take(&mut self.after_cr) {
+                        self.cr += 1;
+                    }
+                }
+            }
+        }
+
+        let total = self.lf + self.cr + self.crlf;
+        if total >= DETECT_MIN_LINES || (self.sampled >= DETECT_SAMPLE_BYTES && total > 0) {
+            Some(if self.crlf >= self.lf && self.crlf >= self.cr {
+                LineDelimiter::CrLf
+            } else if self.lf >= self.cr {
+                LineDelimiter::Lf
+            } else {
+                LineDelimiter::Cr
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Indique si l'échantillon est épuisé.
+    pub const fn is_exhausted(&self) -> bool {
+        self.sampled >= DETECT_SAMPLE_BYTES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Texte normalisé par `assembler`, comme le verrait le terminal.
+    fn normalize(assembler: &mut LineAssembler, text: &str) -> String {
+        text.chars()
+            .flat_map(|c| assembler.push(c))
+            .flatten()
+            .collect()
+    }
+
+    fn lines(delimiter: LineDelimiter, text: &str) -> String {
+        normalize(&mut LineAssembler::new(delimiter), text)
+    }
+
+    /// Performer minimal : le texte passe par l'assembleur après le parseur,
+    /// chaque séquence CSI est notée `<CSI params action>`.
+    struct Collect {
+        lines: LineAssembler,
+        out: String,
+    }
+
+    impl vte::Perform for Collect {
+        fn print(&mut self, c: char) {
+            self.out.extend(self.lines.push(c).into_iter().flatten());
+        }
+
+        fn execute(&mut self, byte: u8) {
+            self.print(char::from(byte));
+        }
+
+        fn csi_dispatch(&mut self, params: &vte::Params, _: &[u8], _: bool, action: char) {
+            self.out.extend(self.lines.take_pending());
+            let params: Vec<String> = params.iter().map(|p| format!("{p:?}")).collect();
+            self.out
+                .push_str(&format!("<CSI {} {action}>", params.join(",")));
+        }
+    }
+
+    fn parsed(delimiter: LineDelimiter, chunks: &[&[u8]]) -> String {
+        let mut parser = vte::Parser::new();
+        let mut collect = Collect {
+            lines: LineAssembler::new(delimiter),
+            out: String::new(),
+        };
+        for chunk in chunks {
+            parser.advance(&mut collect, chunk);
+        }
+        collect.out
+    }
+
+    #[test]
+    fn delimiter_names_round_trip() {
+        for delimiter in [
+            LineDelimiter::Lf,
+            LineDelimiter::Cr,
+            LineDelimiter::CrLf,
+            LineDelimiter::Any,
+            LineDelimiter::Byte(b';'),
+            LineDelimiter::Byte(0x03),
+        ] {
+            assert_eq!(LineDelimiter::from_str_name(&delimiter.id()), delimiter);
+        }
+        assert_eq!(LineDelimiter::from_str_name(" auto "), LineDelimiter::Any);
+        assert_eq!(LineDelimiter::from_str_name("0xZZ"), LineDelimiter::Lf);
+    }
+
+    #[test]
+    fn standard_delimiters() {
+        assert_eq!(lines(LineDelimiter::Lf, "a\nb\rc\r\n"), "a\nb\rc\r\n");
+        assert_eq!(lines(LineDelimiter::Cr, "a\rb\r\nc\n"), "a\nb\nc\n");
+        assert_eq!(
+            lines(LineDelimiter::CrLf, "a\r\nb\rc\nd\r\r\n"),
+            "a\nb\rc\nd\r\n"
+        );
+        assert_eq!(lines(LineDelimiter::Any, "a\rb\nc\r\nd"), "a\nb\nc\nd");
+    }
+
+    #[test]
+    fn crlf_split_across_chunks() {
+        for delimiter in [LineDelimiter::Cr, LineDelimiter::CrLf, LineDelimiter::Any] {
+            let mut assembler = LineAssembler::new(delimiter);
+            let first = normalize(&mut assembler, "ok\r");
+            let second = normalize(&mut assembler, "\nnext");
+            assert_eq!(first + &second, "ok\nnext", "{delimiter:?}");
+        }
+        // `clear` oublie le `\r` en attente.
+        let mut assembler = LineAssembler::new(LineDelimiter::CrLf);
+        normalize(&mut assembler, "ok\r");
+        assembler.clear();
+        assert_eq!(normalize(&mut assembler, "\n"), "\n");
+        assert_eq!(assembler.take_pending(), None);
+    }
+
+    #[test]
+    fn custom_byte_delimiter() {
+        assert_eq!(lines(LineDelimiter::Byte(b';'), "a;b\nc;"), "a\nb\nc\n");
+        assert_eq!(
+            lines(LineDelimiter::Byte(0x03), "trame\x03suite"),
+            "trame\nsuite"
+        );
+    }
+
+    #[test]
+    fn escape_sequences_are_not_split() {
+        let delimiter = LineDelimiter::Byte(b';');
+        assert_eq!(
+            parsed(delimiter, &[b"\x1b[1;31mrouge;vert\x1b[0m;"]),
+            "<CSI [1],[31] m>rouge\nvert<CSI [0] m>\n"
+        );
+        // Séquence coupée entre deux lectures.
+        assert_eq!(
+            parsed(delimiter, &[b"a\x1b[1;", b"31mb;"]),
+            "a<CSI [1],[31] m>b\n"
+        );
+    }
+
+    #[test]
+    fn pending_cr_precedes_escape_sequence() {
+        assert_eq!(
+            parsed(LineDelimiter::CrLf, &[b"abc\r\x1b[K\r\n"]),
+            "abc\r<CSI [0] K>\n"
+        );
+        // Hors CRLF, le `\r` est déjà rendu et le LF qui suit reste absorbé.
+        assert_eq!(
+            parsed(LineDelimiter::Any, &[b"abc\r\x1b[K\n"]),
+            "abc\n<CSI [0] K>"
+        );
+    }
+
+    /// Passe les morceaux au détecteur ; résultat après le dernier.
+    fn detect(chunks: &[&[u8]]) -> Option<LineDelimiter> {
+        let mut detector = LineEndingDetector::new();
+        chunks
+            .iter()
+            .map(|chunk| detector.push(chunk))
+            .last()
+            .flatten()
+    }
+
+    #[test]
+    fn detects_each_line_ending() {
+        assert_eq!(detect(&[b"1\n2\n3\n4\n"]), Some(LineDelimiter::Lf));
+        assert_eq!(
+            detect(&[b"1\r\n2\r\n3\r\n4\r\n"]),
+            Some(LineDelimiter::CrLf)
+        );
+        // Un `\r` n'est compté qu'au caractère suivant (ce peut être un `\n`).
+        assert_eq!(detect(&[b"1\r2\r3\r4\r"]), None);
+        assert_eq!(detect(&[b"1\r2\r3\r4\r5"]), Some(LineDelimiter::Cr));
+        assert_eq!(detect(&[b"1\r\r\r\r5"]), Some(LineDelimiter::Cr));
+        assert_eq!(detect(&[b"1\n2\n3"]), None);
+    }
+
+    #[test]
+    fn mixed_endings_use_the_majority() {
+        assert_eq!(detect(&[b"a\r\nb\nc\nd\n"]), Some(LineDelimiter::Lf));
+        assert_eq!(detect(&[b"a\rb\rc\r\nd\re"]), Some(LineDelimiter::Cr));
+        // Égalité : CRLF, puis LF.
+        assert_eq!(detect(&[b"a\r\nb\nc\r\nd\n"]), Some(LineDelimiter::CrLf));
+        assert_eq!(detect(&[b"a\rb\nc\rd\ne"]), Some(LineDelimiter::Lf));
+    }
+
+    #[test]
+    fn crlf_split_across_reads() {
+        let mut detector = LineEndingDetector::new();
+        for chunk in [&b"ok\r"[..], b"\nok\r", b"\nok\r", b"\nok\r"] {
+            assert_eq!(detector.push(chunk), None);
+        }
+        assert_eq!(detector.push(b"\n"), Some(LineDelimiter::CrLf));
+    }
+
+    #[test]
+    fn sample_is_limited() {
+        let filler = [b'x'; DETECT_SAMPLE_BYTES];
+        let mut detector = LineEndingDetector::new();
+        assert_eq!(detector.push(&filler), None);
+        assert!(detector.is_exhausted());
+        // Au-delà de l'échantillon, plus rien n'est examiné.
+        assert_eq!(detector.push(b"\n\n\n\n"), None);
+
+        // Une seule fin de ligne suffit une fois l'échantillon épuisé.
+        let mut detector = LineEndingDetector::new();
+        assert_eq!(detector.push(b"ligne\r\n"), None);
+        assert_eq!(detector.push(&filler), Some(LineDelimiter::CrLf));
+    }
+}
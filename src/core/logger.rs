@@ -4,8 +4,9 @@
 // =============================================================================
 
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use chrono::Local;
+use chrono::{DateTime, Local};
 use env_logger::Builder;
 use log::LevelFilter;
 
@@ -23,3 +24,228 @@ pub fn init_logger(level: LevelFilter) {
         })
         .init();
 }
+
+// =============================================================================
+// Journaux de session (sauvegarde du terminal)
+// =============================================================================
+
+/// Convertit des octets reçus en texte pour l'affichage et les journaux.
+///
+/// Les séquences UTF-8 valides sont conservées ; chaque octet invalide
+/// devient un marqueur visible `\xNN` (au lieu de `�`, qui masque la valeur).
+pub fn decode_lossy(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len());
+    let incomplete = decode_complete(data, &mut out);
+    // Séquence tronquée en fin de tampon : marquée comme le reste.
+    push_markers(&mut out, &data[data.len() - incomplete..]);
+    out
+}
+
+/// Décodage d'un flux reçu par morceaux, avec les marqueurs de
+/// `decode_lossy` : une séquence UTF-8 coupée entre deux lectures est
+/// complétée au morceau suivant au lieu d'être marquée invalide.
+#[derive(Debug, Default)]
+pub struct StreamDecoder {
+    /// Début de séquence en attente de ses octets de continuation.
+    pending: Vec<u8>,
+}
+
+impl StreamDecoder {
+    pub fn decode(&mut self, data: &[u8]) -> String {
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(data);
+        let mut out = String::with_capacity(bytes.len());
+        let incomplete = decode_complete(&bytes, &mut out);
+        self.pending = bytes.split_off(bytes.len() - incomplete);
+        out
+    }
+
+    /// Oublie une séquence en attente (terminal vidé, nouvelle connexion).
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// Ajoute à `out` le texte de `data`, octets invalides marqués, sauf une
+/// éventuelle séquence incomplète finale dont la longueur est retournée.
+fn decode_complete(data: &[u8], out: &mut String) -> usize {
+    let mut rest = data;
+    while !rest.is_empty() {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                out.push_str(valid);
+                break;
+            }
+            Err(e) => {
+                let (valid, invalid) = rest.split_at(e.valid_up_to());
+                // Sûr : `valid_up_to` délimite un préfixe UTF-8 valide.
+                out.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                let Some(bad_len) = e.error_len() else {
+                    return invalid.len();
+                };
+                push_markers(out, &invalid[..bad_len]);
+                rest = &invalid[bad_len..];
+            }
+        }
+    }
+    0
+}
+
+fn push_markers(out: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        out.push_str(&format!("\\x{byte:02X}"));
+    }
+}
+
+/// Préfixe chaque ligne de l'horodatage courant si `timestamp` est vrai.
+pub fn format_session_log(content: &str, timestamp: bool) -> String {
+    if !timestamp {
+        return content.to_string();
+    }
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S");
+    content
+        .lines()
+        .map(|line| format!("[{now}] {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Construit un nom de fichier de session à partir d'un modèle.
+///
+/// Variables : `{host}`, `{type}`, `{date}` (`AAAAMMJJ_HHMMSS`). Chaque suite
+/// de caractères invalides dans un nom de fichier devient un `_` ; l'extension
+/// `.txt` est ajoutée.
+pub fn render_log_filename(
+    template: &str,
+    host: &str,
+    conn_type: &str,
+    date: DateTime<Local>,
+) -> String {
+    let template = if template.trim().is_empty() {
+        "{host}_{date}"
+    } else {
+        template
+    };
+    let name = template
+        .replace("{host}", host)
+        .replace("{type}", conn_type)
+        .replace("{date}", &date.format("%Y%m%d_%H%M%S").to_string());
+    let mut sanitized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_alphanumeric() || matches!(c, '-' | '.' | '@') {
+            sanitized.push(c);
+        } else if !sanitized.ends_with('_') {
+            sanitized.push('_');
+        }
+    }
+    format!("{}.txt", sanitized.trim_matches('_'))
+}
+
+/// Écrit `content` dans un nouveau fichier `path`, sans jamais écraser un
+/// journal existant : si le nom est pris (deux sessions dans la même
+/// seconde), un suffixe `-1`, `-2`… est ajouté avant l'extension.
+///
+/// Retourne le chemin effectivement écrit.
+pub fn write_new_file(path: &Path, content: &[u8]) -> std::io::Result<PathBuf> {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut candidate = path.to_path_buf();
+    for suffix in 1.. {
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(mut file) => {
+                file.write_all(content)?;
+                return Ok(candidate);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                candidate.set_file_name(format!("{stem}-{suffix}{extension}"));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("la boucle ne se termine que par un retour")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lone_invalid_byte_is_marked() {
+        assert_eq!(decode_lossy(b"\xFF"), "\\xFF");
+        assert_eq!(decode_lossy(b"a\xFFb"), "a\\xFFb");
+        assert_eq!(decode_lossy(b"\x80\x80"), "\\x80\\x80");
+    }
+
+    #[test]
+    fn truncated_sequence_is_marked() {
+        // « € » (E2 82 AC) coupé avant son dernier octet.
+        assert_eq!(decode_lossy(b"ab\xE2\x82"), "ab\\xE2\\x82");
+        // Coupé puis suivi d'ASCII : seul le préfixe est invalide.
+        assert_eq!(decode_lossy(b"\xE2\x82x"), "\\xE2\\x82x");
+    }
+
+    #[test]
+    fn mixed_valid_and_invalid_input() {
+        assert_eq!(decode_lossy("été\u{1F600}".as_bytes()), "été\u{1F600}");
+        assert_eq!(
+            decode_lossy(b"\xC3\xA9t\xFF\xC3\xA9\r\n\xFE"),
+            "ét\\xFFé\r\n\\xFE"
+        );
+        // Demi-substitut UTF-16 encodé : chaque octet est rejeté.
+        assert_eq!(decode_lossy(b"\xED\xA0\x80"), "\\xED\\xA0\\x80");
+    }
+
+    #[test]
+    fn stream_decoder_joins_split_sequences() {
+        let mut decoder = StreamDecoder::default();
+        assert_eq!(decoder.decode(b"caf\xC3"), "caf");
+        assert_eq!(decoder.decode(b"\xA9 \xE2"), "é ");
+        assert_eq!(decoder.decode(b"\x82"), "");
+        assert_eq!(decoder.decode(b"\xAC"), "€");
+        // Séquence interrompue par un octet ASCII : marquée, l'ASCII conservé.
+        assert_eq!(decoder.decode(b"\xC3"), "");
+        assert_eq!(decoder.decode(b"A\xFF"), "\\xC3A\\xFF");
+        assert_eq!(decoder.decode(b"\xF0\x9F"), "");
+        decoder.clear();
+        assert_eq!(decoder.decode(b"\x98\x80"), "\\x98\\x80");
+    }
+
+    #[test]
+    fn session_log_keeps_markers() {
+        let text = decode_lossy(b"ok\n\xFF\xFE\nfin \xE2\x82");
+        assert_eq!(format_session_log(&text, false), text);
+        let stamped = format_session_log(&text, true);
+        let lines: Vec<&str> = stamped.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with("] \\xFF\\xFE"));
+        assert!(lines[2].ends_with("] fin \\xE2\\x82"));
+    }
+
+    #[test]
+    fn new_file_is_written_verbatim_without_overwriting() {
+        let dir = std::env::temp_dir().join(format!("sst-logger-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.txt");
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(dir.join("session-1.txt"));
+
+        let raw = b"\x00\xFF\xC3\n";
+        assert_eq!(write_new_file(&path, raw).unwrap(), path);
+        let second = write_new_file(&path, b"autre").unwrap();
+        assert_eq!(second, dir.join("session-1.txt"));
+        assert_eq!(std::fs::read(&path).unwrap(), raw);
+        assert_eq!(std::fs::read(&second).unwrap(), b"autre");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
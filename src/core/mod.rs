@@ -1,6 +1,19 @@
+pub mod at_commands;
+pub mod capture;
+pub mod char_width;
 pub mod connection;
+pub mod connection_uri;
+pub mod framing;
+pub mod intelhex;
+pub mod keymap;
+pub mod known_hosts;
+pub mod line_assembler;
 pub mod logger;
+pub mod reconnect;
 pub mod secrets;
 pub mod serial_manager;
 pub mod settings;
+pub mod sftp;
+pub mod sgr;
+pub mod ssh_config;
 pub mod ssh_manager;
@@ -0,0 +1,112 @@
+// =============================================================================
+// Fichier : reconnect.rs
+// Rôle    : Calcul du délai entre deux tentatives de reconnexion automatique
+// =============================================================================
+
+/// Stratégie d'espacement des tentatives de reconnexion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffStrategy {
+    /// Délai constant entre chaque tentative.
+    Fixed,
+    /// Délai doublé à chaque échec, plafonné.
+    Exponential,
+}
+
+impl BackoffStrategy {
+    /// Parse depuis la valeur persistée (`"fixed"` | `"exponential"`).
+    pub fn from_str_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "fixed" | "fixe" => Self::Fixed,
+            _ => Self::Exponential,
+        }
+    }
+
+    /// Identifiant persisté dans les paramètres.
+    pub const fn id(&self) -> &str {
+        match self {
+            Self::Fixed => "fixed",
+            Self::Exponential => "exponential",
+        }
+    }
+}
+
+/// Délai (en secondes) avant la tentative numéro `attempt` (à partir de 1).
+///
+/// `Fixed` retourne toujours `base_secs` ; `Exponential` retourne
+/// `base_secs × 2^(attempt-1)`. Le résultat vaut au moins 1 et est plafonné
+/// à `max_secs` (ou à `base_secs`, si le plafond lui est inférieur).
+pub fn backoff_delay(
+    strategy: BackoffStrategy,
+    base_secs: u32,
+    max_secs: u32,
+    attempt: u32,
+) -> u32 {
+    let base = base_secs.max(1);
+    let delay = match strategy {
+        BackoffStrategy::Fixed => base,
+        BackoffStrategy::Exponential => {
+            let exponent = attempt.saturating_sub(1).min(31);
+            base.saturating_mul(1 << exponent)
+        }
+    };
+    delay.min(max_secs.max(base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_delay_ignores_attempts() {
+        for attempt in [0, 1, 2, 50, u32::MAX] {
+            assert_eq!(backoff_delay(BackoffStrategy::Fixed, 5, 60, attempt), 5);
+        }
+        assert_eq!(backoff_delay(BackoffStrategy::Fixed, 0, 60, 3), 1);
+    }
+
+    #[test]
+    fn exponential_delay_doubles_up_to_the_cap() {
+        let delays: Vec<u32> = (1..=8)
+            .map(|attempt| backoff_delay(BackoffStrategy::Exponential, 2, 60, attempt))
+            .collect();
+        assert_eq!(delays, vec![2, 4, 8, 16, 32, 60, 60, 60]);
+        // Tentative 0 traitée comme la première.
+        assert_eq!(backoff_delay(BackoffStrategy::Exponential, 2, 60, 0), 2);
+        // Plafond inférieur à la base : la base l'emporte.
+        assert_eq!(backoff_delay(BackoffStrategy::Exponential, 10, 3, 4), 10);
+        assert_eq!(backoff_delay(BackoffStrategy::Exponential, 0, 0, 1), 1);
+    }
+
+    #[test]
+    fn exponential_delay_never_overflows() {
+        for attempt in [32, 33, 1000, u32::MAX] {
+            assert_eq!(
+                backoff_delay(BackoffStrategy::Exponential, 3, u32::MAX, attempt),
+                u32::MAX
+            );
+            assert_eq!(
+                backoff_delay(BackoffStrategy::Exponential, 1, 300, attempt),
+                300
+            );
+        }
+        assert_eq!(
+            backoff_delay(BackoffStrategy::Exponential, u32::MAX, u32::MAX, 2),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn strategy_names_round_trip() {
+        for strategy in [BackoffStrategy::Fixed, BackoffStrategy::Exponential] {
+            assert_eq!(BackoffStrategy::from_str_name(strategy.id()), strategy);
+        }
+        assert_eq!(
+            BackoffStrategy::from_str_name("Fixe"),
+            BackoffStrategy::Fixed
+        );
+        assert_eq!(
+            BackoffStrategy::from_str_name("inconnu"),
+            BackoffStrategy::Exponential
+        );
+    }
+}
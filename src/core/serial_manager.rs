@@ -7,11 +7,27 @@ use std::time::Duration;
 
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
-use serialport::{available_ports, DataBits, FlowControl, Parity, StopBits};
+use serialport::{
+    available_ports, ClearBuffer, DataBits, FlowControl, Parity, SerialPort, StopBits,
+};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio_serial::{SerialPortBuilderExt, SerialStream};
 
-use super::connection::{Connection, ConnectionState, ConnectionType};
+use super::connection::{Connection, ConnectionEvent, ConnectionState, ConnectionType};
+
+/// État `(DTR, RTS)` laissé par le système à l'ouverture, aucune valeur
+/// n'étant imposée (cela réinitialiserait certaines cartes) : Unix active
+/// les deux lignes ; sous Windows, `serialport` désactive DTR et RTS garde
+/// sa valeur par défaut, active.
+const LINES_ON_OPEN: (bool, bool) = if cfg!(windows) {
+    (false, true)
+} else {
+    (true, true)
+};
+
+/// Caractères de contrôle de flux logiciel.
+const XON: u8 = 0x11;
+const XOFF: u8 = 0x13;
 
 // =============================================================================
 // Information sur un port série
@@ -23,6 +39,67 @@ pub struct SerialPortInfo {
     pub device: String,
     pub manufacturer: String,
     pub description: String,
+    /// Identité de l'adaptateur, pour les ports USB.
+    pub usb: Option<UsbIdentity>,
+}
+
+/// Identité d'un adaptateur USB : contrairement au chemin (`ttyUSB0` →
+/// `ttyUSB1`), elle ne change pas d'un branchement à l'autre.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbIdentity {
+    pub vid: u16,
+    pub pid: u16,
+    pub serial_number: Option<String>,
+}
+
+impl UsbIdentity {
+    fn matches(&self, other: &Self) -> bool {
+        self.vid == other.vid && self.pid == other.pid && self.serial_number == other.serial_number
+    }
+}
+
+impl std::fmt::Display for UsbIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vid, self.pid)?;
+        if let Some(serial) = &self.serial_number {
+            write!(f, " ({serial})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Chemin actuel de l'adaptateur `identity`, après un éventuel rebranchement.
+///
+/// `previous` est retenu s'il correspond toujours. Sans numéro de série,
+/// plusieurs adaptateurs identiques sont indiscernables : `None`. Avec numéro
+/// de série, plusieurs chemins pour un même adaptateur (`cu.*`/`tty.*` sous
+/// macOS) sont départagés par leur ressemblance avec `previous`.
+pub fn find_port_by_identity(
+    ports: &[SerialPortInfo],
+    identity: &UsbIdentity,
+    previous: &str,
+) -> Option<String> {
+    let matches: Vec<&str> = ports
+        .iter()
+        .filter(|p| p.usb.as_ref().is_some_and(|usb| usb.matches(identity)))
+        .map(|p| p.device.as_str())
+        .collect();
+    if matches.contains(&previous) {
+        return Some(previous.to_string());
+    }
+    if matches.len() > 1 && identity.serial_number.is_none() {
+        return None;
+    }
+    matches
+        .into_iter()
+        .max_by_key(|device| {
+            device
+                .bytes()
+                .zip(previous.bytes())
+                .take_while(|(a, b)| a == b)
+                .count()
+        })
+        .map(str::to_string)
 }
 
 /// Liste les ports série disponibles sur le système.
@@ -31,17 +108,23 @@ pub fn list_serial_ports() -> Vec<SerialPortInfo> {
         Ok(ports) => ports
             .into_iter()
             .map(|p| {
-                let (manufacturer, description) = match &p.port_type {
+                let (manufacturer, description, usb) = match &p.port_type {
                     serialport::SerialPortType::UsbPort(info) => (
                         info.manufacturer.clone().unwrap_or_default(),
                         info.product.clone().unwrap_or_default(),
+                        Some(UsbIdentity {
+                            vid: info.vid,
+                            pid: info.pid,
+                            serial_number: info.serial_number.clone(),
+                        }),
                     ),
-                    _ => (String::new(), String::new()),
+                    _ => (String::new(), String::new(), None),
                 };
                 SerialPortInfo {
                     device: p.port_name,
                     manufacturer,
                     description,
+                    usb,
                 }
             })
             .collect(),
@@ -66,6 +149,9 @@ pub struct SerialConfig {
     pub stop_bits: StopBits,
     pub flow_control: FlowControl,
     pub timeout: Duration,
+    /// Nouvelles tentatives d'ouverture si le port est momentanément occupé.
+    pub open_retries: u32,
+    pub open_retry_delay: Duration,
 }
 
 impl Default for SerialConfig {
@@ -78,6 +164,8 @@ impl Default for SerialConfig {
             stop_bits: StopBits::One,
             flow_control: FlowControl::None,
             timeout: Duration::from_millis(10),
+            open_retries: 3,
+            open_retry_delay: Duration::from_millis(500),
         }
     }
 }
@@ -117,6 +205,7 @@ impl SerialConfig {
                 _ => FlowControl::None,
             },
             timeout: Duration::from_millis(timeout_ms),
+            ..Self::default()
         }
     }
 }
@@ -128,6 +217,10 @@ pub struct SerialManager {
     state: ConnectionState,
     bytes_sent: u64,
     bytes_received: u64,
+    /// Canal vers l'UI, pour signaler les tentatives d'ouverture.
+    event_tx: Option<async_channel::Sender<ConnectionEvent>>,
+    /// État des lignes `(DTR, RTS)` depuis l'ouverture du port.
+    control_lines: (bool, bool),
 }
 
 impl SerialManager {
@@ -139,12 +232,69 @@ impl SerialManager {
             state: ConnectionState::Disconnected,
             bytes_sent: 0,
             bytes_received: 0,
+            event_tx: None,
+            control_lines: LINES_ON_OPEN,
+        }
+    }
+
+    /// Ouvre le port, en réessayant tant que l'erreur est passagère
+    /// (port occupé juste après sa fermeture par un autre processus).
+    async fn open_with_retry(&self) -> Result<SerialStream> {
+        let mut attempt = 0;
+        loop {
+            let result = tokio_serial::new(&self.config.port, self.config.baudrate)
+                .data_bits(self.config.data_bits)
+                .parity(self.config.parity)
+                .stop_bits(self.config.stop_bits)
+                .flow_control(self.config.flow_control)
+                .timeout(self.config.timeout)
+                .open_native_async();
+            match result {
+                Ok(port) => return Ok(port),
+                Err(e) if attempt < self.config.open_retries && is_transient_open_error(&e) => {
+                    attempt += 1;
+                    let message = format!(
+                        "Port {} indisponible ({e}) — nouvel essai {attempt}/{} dans {} ms",
+                        self.config.port,
+                        self.config.open_retries,
+                        self.config.open_retry_delay.as_millis()
+                    );
+                    log::warn!("{message}");
+                    if let Some(tx) = &self.event_tx {
+                        let _ = tx.send(ConnectionEvent::Notice(message)).await;
+                    }
+                    tokio::time::sleep(self.config.open_retry_delay).await;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!("Impossible d'ouvrir le port {}", self.config.port)
+                    })
+                }
+            }
         }
     }
 }
 
+/// Erreur d'ouverture susceptible de disparaître d'elle-même (port occupé,
+/// appel interrompu). Port absent ou droits insuffisants : échec immédiat.
+const fn is_transient_open_error(e: &serialport::Error) -> bool {
+    matches!(
+        e.kind,
+        serialport::ErrorKind::NoDevice
+            | serialport::ErrorKind::Io(
+                std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::TimedOut
+            )
+    )
+}
+
 #[async_trait]
 impl Connection for SerialManager {
+    fn init_event_sender(&mut self, tx: async_channel::Sender<ConnectionEvent>) {
+        self.event_tx = Some(tx);
+    }
+
     async fn connect(&mut self) -> Result<()> {
         if self.state == ConnectionState::Connected {
             bail!("Déjà connecté à {}", self.config.port);
@@ -157,17 +307,17 @@ impl Connection for SerialManager {
             self.config.baudrate
         );
 
-        let port = tokio_serial::new(&self.config.port, self.config.baudrate)
-            .data_bits(self.config.data_bits)
-            .parity(self.config.parity)
-            .stop_bits(self.config.stop_bits)
-            .flow_control(self.config.flow_control)
-            .timeout(self.config.timeout)
-            .open_native_async()
-            .with_context(|| format!("Impossible d'ouvrir le port {}", self.config.port))?;
+        let port = match self.open_with_retry().await {
+            Ok(port) => port,
+            Err(e) => {
+                self.state = ConnectionState::Error;
+                return Err(e);
+            }
+        };
 
         self.port = Some(port);
         self.state = ConnectionState::Connected;
+        self.control_lines = LINES_ON_OPEN;
         self.bytes_sent = 0;
         self.bytes_received = 0;
         log::info!("Connecté à {} @ {}", self.config.port, self.config.baudrate);
@@ -194,10 +344,120 @@ impl Connection for SerialManager {
     async fn send(&mut self, data: &[u8]) -> Result<usize> {
         let port = self.port.as_mut().context("Port série non connecté")?;
 
-        let written = port.write(data).await.context("Erreur d'écriture série")?;
+        // `write` peut n'écrire qu'une partie du bloc (tampon du pilote plein) :
+        // `write_all` boucle jusqu'à ce que tout soit transmis.
+        port.write_all(data)
+            .await
+            .context("Erreur d'écriture série")?;
         port.flush().await.context("Erreur de flush série")?;
-        self.bytes_sent += written as u64;
-        Ok(written)
+        self.bytes_sent += data.len() as u64;
+        Ok(data.len())
+    }
+
+    async fn flush_buffers(&mut self, input: bool, output: bool) -> Result<()> {
+        let port = self.port.as_ref().context("Port série non connecté")?;
+
+        let target = match (input, output) {
+            (true, true) => ClearBuffer::All,
+            (true, false) => ClearBuffer::Input,
+            (false, true) => ClearBuffer::Output,
+            (false, false) => return Ok(()),
+        };
+        port.clear(target)
+            .context("Impossible de vider les tampons série")?;
+        log::info!(
+            "Tampons série vidés sur {} (entrée: {input}, sortie: {output})",
+            self.config.port
+        );
+        Ok(())
+    }
+
+    async fn send_break(&mut self, duration: Duration) -> Result<()> {
+        let port = self.port.as_ref().context("Port série non connecté")?;
+
+        port.set_break()
+            .context("Impossible d'activer le break série")?;
+        tokio::time::sleep(duration).await;
+        port.clear_break()
+            .context("Impossible de relâcher le break série")?;
+        log::info!("Break envoyé sur {} ({duration:?})", self.config.port);
+        Ok(())
+    }
+
+    async fn pulse_reset(&mut self, duration: Duration) -> Result<()> {
+        let port = self.port.as_mut().context("Port série non connecté")?;
+
+        port.write_data_terminal_ready(true)
+            .context("Impossible de piloter DTR")?;
+        port.write_request_to_send(true)
+            .context("Impossible de piloter RTS")?;
+        self.control_lines = (true, true);
+        tokio::time::sleep(duration).await;
+        port.write_data_terminal_ready(false)
+            .context("Impossible de piloter DTR")?;
+        port.write_request_to_send(false)
+            .context("Impossible de piloter RTS")?;
+        self.control_lines = (false, false);
+        log::info!(
+            "Reset DTR/RTS envoyé sur {} ({duration:?})",
+            self.config.port
+        );
+        Ok(())
+    }
+
+    async fn set_dtr(&mut self, level: bool) -> Result<()> {
+        let port = self.port.as_mut().context("Port série non connecté")?;
+        port.write_data_terminal_ready(level)
+            .context("Impossible de piloter DTR")?;
+        self.control_lines.0 = level;
+        log::info!(
+            "DTR {} sur {}",
+            if level { "activé" } else { "relâché" },
+            self.config.port
+        );
+        Ok(())
+    }
+
+    async fn set_rts(&mut self, level: bool) -> Result<()> {
+        let port = self.port.as_mut().context("Port série non connecté")?;
+        port.write_request_to_send(level)
+            .context("Impossible de piloter RTS")?;
+        self.control_lines.1 = level;
+        log::info!(
+            "RTS {} sur {}",
+            if level { "activé" } else { "relâché" },
+            self.config.port
+        );
+        Ok(())
+    }
+
+    fn control_lines(&self) -> Option<(bool, bool)> {
+        self.port.is_some().then_some(self.control_lines)
+    }
+
+    async fn set_reading_paused(&mut self, paused: bool) -> Result<()> {
+        let port = self.port.as_mut().context("Port série non connecté")?;
+
+        match self.config.flow_control {
+            FlowControl::Software => {
+                let byte = if paused { XOFF } else { XON };
+                port.write_all(&[byte])
+                    .await
+                    .context("Impossible d'envoyer XON/XOFF")?;
+            }
+            FlowControl::Hardware => {
+                port.write_request_to_send(!paused)
+                    .context("Impossible de piloter RTS")?;
+                self.control_lines.1 = !paused;
+            }
+            FlowControl::None => {}
+        }
+        log::info!(
+            "Lecture {} sur {}",
+            if paused { "suspendue" } else { "reprise" },
+            self.config.port
+        );
+        Ok(())
     }
 
     async fn read(&mut self) -> Result<Vec<u8>> {
@@ -237,6 +497,29 @@ impl Connection for SerialManager {
         format!("{} @ {}", self.config.port, self.config.baudrate)
     }
 
+    fn details(&self) -> Vec<(String, String)> {
+        vec![
+            ("Port".to_string(), self.config.port.clone()),
+            (
+                "Débit".to_string(),
+                format!("{} bauds", self.config.baudrate),
+            ),
+            (
+                "Bits de données".to_string(),
+                self.config.data_bits.to_string(),
+            ),
+            ("Parité".to_string(), self.config.parity.to_string()),
+            (
+                "Bits de stop".to_string(),
+                self.config.stop_bits.to_string(),
+            ),
+            (
+                "Contrôle de flux".to_string(),
+                self.config.flow_control.to_string(),
+            ),
+        ]
+    }
+
     fn bytes_sent(&self) -> u64 {
         self.bytes_sent
     }
@@ -245,3 +528,93 @@ impl Connection for SerialManager {
         self.bytes_received
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(vid: u16, pid: u16, serial: Option<&str>) -> UsbIdentity {
+        UsbIdentity {
+            vid,
+            pid,
+            serial_number: serial.map(str::to_string),
+        }
+    }
+
+    fn port(device: &str, usb: Option<UsbIdentity>) -> SerialPortInfo {
+        SerialPortInfo {
+            device: device.to_string(),
+            manufacturer: String::new(),
+            description: String::new(),
+            usb,
+        }
+    }
+
+    #[test]
+    fn port_found_by_vid_pid_and_serial() {
+        let ftdi = identity(0x0403, 0x6001, Some("A50285BI"));
+        let ports = [
+            port("/dev/ttyS0", None),
+            port(
+                "/dev/ttyUSB0",
+                Some(identity(0x0403, 0x6001, Some("AB0JQ1X2"))),
+            ),
+            port(
+                "/dev/ttyUSB1",
+                Some(identity(0x10c4, 0xea60, Some("A50285BI"))),
+            ),
+            port("/dev/ttyUSB2", Some(ftdi.clone())),
+        ];
+        // Rebranché sous un autre nom.
+        assert_eq!(
+            find_port_by_identity(&ports, &ftdi, "/dev/ttyUSB0"),
+            Some("/dev/ttyUSB2".to_string())
+        );
+        // Adaptateur absent, ou numéro de série différent.
+        assert_eq!(
+            find_port_by_identity(&ports[..3], &ftdi, "/dev/ttyUSB2"),
+            None
+        );
+        assert_eq!(
+            find_port_by_identity(&ports, &identity(0x0403, 0x6001, None), "/dev/ttyUSB2"),
+            None
+        );
+    }
+
+    #[test]
+    fn previous_path_is_kept_while_it_matches() {
+        let ch340 = identity(0x1a86, 0x7523, None);
+        let ports = [
+            port("/dev/ttyUSB0", Some(ch340.clone())),
+            port("/dev/ttyUSB1", Some(ch340.clone())),
+        ];
+        assert_eq!(
+            find_port_by_identity(&ports, &ch340, "/dev/ttyUSB1"),
+            Some("/dev/ttyUSB1".to_string())
+        );
+        // Sans numéro de série, deux adaptateurs identiques sont indiscernables.
+        assert_eq!(find_port_by_identity(&ports, &ch340, "/dev/ttyUSB3"), None);
+        // Un seul candidat : retenu même sans numéro de série.
+        assert_eq!(
+            find_port_by_identity(&ports[1..], &ch340, "/dev/ttyUSB3"),
+            Some("/dev/ttyUSB1".to_string())
+        );
+    }
+
+    #[test]
+    fn several_paths_for_one_adapter_use_the_closest_name() {
+        let board = identity(0x2341, 0x0043, Some("7523230"));
+        let ports = [
+            port("/dev/cu.usbmodem14201", Some(board.clone())),
+            port("/dev/tty.usbmodem14201", Some(board.clone())),
+        ];
+        assert_eq!(
+            find_port_by_identity(&ports, &board, "/dev/tty.usbmodem14101"),
+            Some("/dev/tty.usbmodem14201".to_string())
+        );
+        assert_eq!(
+            find_port_by_identity(&ports, &board, "/dev/cu.usbmodem14101"),
+            Some("/dev/cu.usbmodem14201".to_string())
+        );
+    }
+}
@@ -4,11 +4,14 @@
 // =============================================================================
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 
+use super::connection::ConnectionType;
+use super::ssh_manager::PortForward;
+
 // =============================================================================
 // Structures de configuration
 // =============================================================================
@@ -22,6 +25,8 @@ pub struct AppSettings {
     pub ssh_favorites: Vec<SshFavorite>,
     pub ui: UiSettings,
     pub log: LogSettings,
+    pub reconnect: ReconnectSettings,
+    pub channels: ChannelSettings,
 }
 
 /// Favori SSH enregistrable pour réutilisation rapide.
@@ -34,6 +39,14 @@ pub struct SshFavorite {
     pub username: String,
     pub auth_method: String,
     pub key_path: String,
+    /// Dossier distant proposé par défaut pour les transferts de fichiers.
+    pub default_remote_dir: String,
+    /// Dossier local proposé par défaut dans les dialogues de fichiers.
+    pub default_local_dir: String,
+    /// Note libre (ex: "routeur salle serveur, login via OTP").
+    pub notes: String,
+    /// Commandes envoyées automatiquement après la connexion.
+    pub on_connect_commands: Vec<String>,
 }
 
 /// Paramètres de connexion série.
@@ -47,6 +60,38 @@ pub struct SerialSettings {
     pub stop_bits: u8,
     pub flow_control: String,
     pub timeout_ms: u64,
+    /// Nouvelles tentatives d'ouverture si le port est momentanément occupé.
+    pub open_retries: u32,
+    pub open_retry_delay_ms: u64,
+    /// Propose de rouvrir le port quand un paramètre change en cours de connexion.
+    pub reconfigure_on_change: bool,
+    /// Mode lecture seule par défaut (aucun envoi possible).
+    pub read_only: bool,
+    /// Reconnexion automatique après une coupure inattendue.
+    pub auto_reconnect: bool,
+    /// Reconnexion : retrouve l'adaptateur USB par VID:PID/n° de série
+    /// s'il revient sur un autre chemin après rebranchement.
+    #[serde(default = "default_true")]
+    pub reconnect_by_identity: bool,
+    /// Envoie un break juste après l'ouverture du port.
+    pub break_on_connect: bool,
+    /// Durée du break (bouton « Break » et break à la connexion), en ms.
+    pub break_ms: u64,
+    /// Pulse DTR/RTS juste après l'ouverture du port (reset de la carte).
+    pub reset_on_connect: bool,
+    /// Capture du démarrage : à la connexion, fige l'affichage pendant
+    /// `boot_capture_secs` pour ne pas perdre les premiers messages.
+    pub boot_capture: bool,
+    pub boot_capture_secs: u32,
+    /// Pause entre deux enregistrements lors de l'envoi d'un fichier Intel HEX.
+    pub hex_line_delay_ms: u32,
+    /// Fin de ligne des envois série (vide = `ui.line_ending`).
+    pub line_ending: String,
+    /// Saisie interprétée en hexadécimal (`48 65 0D`) par défaut.
+    pub send_hex: bool,
+    /// Écho local des envois dans le terminal.
+    #[serde(default = "default_true")]
+    pub local_echo: bool,
 }
 
 /// Paramètres de connexion SSH.
@@ -56,24 +101,134 @@ pub struct SshSettings {
     pub host: String,
     pub port: u16,
     pub username: String,
-    pub auth_method: String, // "password" | "key"
+    pub auth_method: String, // "password" | "key" | "agent" | "interactive"
     pub key_path: String,
     #[serde(default = "default_true")]
     pub remember_secrets: bool,
+    /// Vide le champ mot de passe/passphrase N secondes après la connexion
+    /// s'il a été rempli entre-temps (0 = jamais).
+    pub password_clear_secs: u32,
+    /// Délai maximal d'établissement de la connexion (secondes).
+    pub connect_timeout_secs: u64,
+    /// Redirections de ports locales ouvertes à chaque connexion SSH.
+    pub port_forwards: Vec<PortForward>,
+    /// Nom du dernier favori SSH connecté avec succès.
+    pub last_favorite: String,
+    /// Mode lecture seule par défaut (aucun envoi possible).
+    pub read_only: bool,
+    /// Reconnexion automatique après une coupure inattendue.
+    pub auto_reconnect: bool,
+    /// Fin de ligne des envois SSH (vide = `ui.line_ending`).
+    pub line_ending: String,
+    /// Saisie interprétée en hexadécimal par défaut.
+    pub send_hex: bool,
+    /// Écho local des envois dans le terminal.
+    #[serde(default = "default_true")]
+    pub local_echo: bool,
 }
 
 /// Paramètres d'interface utilisateur.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UiSettings {
-    pub theme: String, // "light" | "dark" | "hacker"
+    pub theme: String, // "auto" | "light" | "dark" | "hacker"
     pub font_family: String,
     pub font_size: u32,
     pub window_width: i32,
     pub window_height: i32,
     pub show_line_numbers: bool,
     pub max_scrollback_lines: u32,
-    pub line_ending: String, // "LF" | "CR" | "CRLF"
+    /// Fin de ligne par défaut, si aucune n'est mémorisée pour le type de connexion.
+    pub line_ending: String, // "LF" | "CR" | "CRLF" | "None"
+    /// Fin de ligne des données reçues : "LF" | "CR" | "CRLF" | "ANY" | "0xNN".
+    pub line_delimiter: String,
+    /// Décodage de trame des données reçues : "none" | "slip" | "cobs" | "length".
+    pub frame_decoding: String,
+    /// Marqueurs du décodage "delimited", notation échappée (`\x02`, `\r\n`).
+    /// Début vide : les trames sont séparées par le marqueur de fin.
+    pub frame_start: String,
+    pub frame_end: String,
+    /// Affiche les octets reçus en vidage hexadécimal (offset, hex, ASCII).
+    pub hex_view: bool,
+    /// Convertit les tabulations reçues en espaces (taquets tous les `tab_width`).
+    pub expand_tabs: bool,
+    pub tab_width: u32,
+    /// Fige les statistiques de l'en-tête quand la vue est remontée dans l'historique.
+    pub freeze_stats_when_scrolled: bool,
+    /// Mesure la latence entre chaque envoi et le premier octet reçu ensuite.
+    pub measure_latency: bool,
+    /// Détecte la fin de ligne du périphérique à la connexion et l'adopte pour les envois.
+    pub detect_line_ending: bool,
+    /// Mode AT : saisie en majuscules, fin de ligne CR, complétion des commandes.
+    pub at_mode: bool,
+    /// Clavier direct : les touches frappées dans le terminal sont envoyées
+    /// immédiatement (flèches, Ctrl+C…), pour les shells et éditeurs distants.
+    pub direct_keyboard: bool,
+    /// Octet de la touche Retour arrière en clavier direct : "del" | "bs".
+    pub backspace_key: String,
+    /// Octets NUL reçus : "strip" | "caret" | "pass".
+    pub nul_handling: String,
+    /// Saut de page reçu : "ignore" | "literal" | "clear".
+    pub form_feed: String,
+    /// Curseur du terminal : "none" | "block" | "underline" | "beam".
+    pub cursor_style: String,
+    pub cursor_blink: bool,
+    /// Horodate l'écho local des données envoyées (TX).
+    pub show_tx_timestamps: bool,
+    /// Horodate chaque ligne reçue (RX) dans le terminal.
+    pub show_rx_timestamps: bool,
+    /// Affiche les octets de contrôle reçus en notation caret.
+    pub show_control_chars: bool,
+    /// Masque le panneau de connexion pour agrandir le terminal.
+    pub compact_mode: bool,
+    /// Demande confirmation avant de fermer la fenêtre pendant une connexion.
+    pub confirm_quit_connected: bool,
+    /// Notification bureau quand une connexion s'établit (fenêtre inactive).
+    pub notify_on_connect: bool,
+    /// Notification bureau quand une connexion se termine (fenêtre inactive).
+    pub notify_on_disconnect: bool,
+    /// Affiche les octets exacts (texte échappé + hex) avant chaque envoi saisi.
+    pub confirm_send: bool,
+    /// Au-delà de ce nombre de lignes, "Coller et envoyer" demande confirmation.
+    pub paste_confirm_lines: u32,
+    /// Envoi de fichier : taille des morceaux (octets) et pause entre eux (ms),
+    /// pour ne pas saturer les petits microcontrôleurs.
+    pub file_chunk_size: u32,
+    pub file_chunk_delay_ms: u32,
+    /// Active la coloration des lignes reçues selon `highlight_rules`.
+    pub highlight_enabled: bool,
+    /// Règles de coloration (la première qui correspond l'emporte).
+    pub highlight_rules: Vec<HighlightRule>,
+    /// Macros du menu Macros, dans l'ordre d'affichage.
+    pub macros: Vec<SendMacro>,
+}
+
+/// Macro : commande envoyée (fin de ligne sélectionnée ajoutée) depuis le
+/// menu Macros ou par son raccourci.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SendMacro {
+    pub name: String,
+    pub payload: String,
+    /// Raccourci au format GTK (`F1`, `<Ctrl>1`…) ; vide = aucun.
+    pub accel: String,
+}
+
+/// Règle de coloration : toute ligne reçue correspondant à `pattern`
+/// (expression régulière) est affichée dans la couleur `color`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightRule {
+    pub pattern: String,
+    pub color: String, // "#RRGGBB"
+}
+
+impl HighlightRule {
+    fn new(pattern: &str, color: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            color: color.to_string(),
+        }
+    }
 }
 
 /// Paramètres de logging.
@@ -86,6 +241,43 @@ pub struct LogSettings {
     pub log_directory: String,
     #[serde(default = "default_true")]
     pub timestamp_saved_lines: bool,
+    /// Enregistre automatiquement le terminal à chaque déconnexion.
+    pub auto_save_on_disconnect: bool,
+    /// Dossier des sauvegardes automatiques (vide → `log_directory`).
+    pub auto_save_directory: String,
+    /// Modèle de nom de fichier : `{host}`, `{type}`, `{date}`.
+    pub auto_save_template: String,
+    /// Capture brute : enregistre aussi les octets envoyés.
+    #[serde(default = "default_true")]
+    pub raw_capture_tx: bool,
+    /// Capture brute : enregistrements marqués RX/TX dans un seul fichier.
+    #[serde(default = "default_true")]
+    pub raw_capture_tagged: bool,
+    /// Capture brute : horodatage de chaque enregistrement (µs).
+    #[serde(default = "default_true")]
+    pub raw_capture_timestamps: bool,
+}
+
+/// Paramètres de la reconnexion automatique.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReconnectSettings {
+    pub strategy: String, // "fixed" | "exponential"
+    pub delay_secs: u32,
+    /// Plafond du délai en mode exponentiel.
+    pub max_delay_secs: u32,
+    /// Durée de connexion au-delà de laquelle le délai repart de zéro.
+    pub stable_after_secs: u32,
+}
+
+/// Capacité des canaux entre l'UI et la tâche de connexion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelSettings {
+    /// Commandes en attente (UI → connexion) avant la file d'envoi de l'UI.
+    pub command_capacity: usize,
+    /// Événements en attente (connexion → UI) avant que la lecture ne soit suspendue.
+    pub event_capacity: usize,
 }
 
 const fn default_true() -> bool {
@@ -106,6 +298,21 @@ impl Default for SerialSettings {
             stop_bits: 1,
             flow_control: "None".to_string(),
             timeout_ms: 1000,
+            open_retries: 3,
+            open_retry_delay_ms: 500,
+            reconfigure_on_change: true,
+            read_only: false,
+            auto_reconnect: false,
+            reconnect_by_identity: true,
+            break_on_connect: false,
+            break_ms: 250,
+            reset_on_connect: false,
+            boot_capture: false,
+            boot_capture_secs: 10,
+            hex_line_delay_ms: 20,
+            line_ending: String::new(),
+            send_hex: false,
+            local_echo: true,
         }
     }
 }
@@ -119,6 +326,15 @@ impl Default for SshSettings {
             auth_method: "password".to_string(),
             key_path: String::new(),
             remember_secrets: true,
+            password_clear_secs: 30,
+            connect_timeout_secs: 10,
+            port_forwards: Vec::new(),
+            last_favorite: String::new(),
+            read_only: false,
+            auto_reconnect: false,
+            line_ending: String::new(),
+            send_hex: false,
+            local_echo: true,
         }
     }
 }
@@ -132,6 +348,10 @@ impl Default for SshFavorite {
             username: String::new(),
             auth_method: "password".to_string(),
             key_path: String::new(),
+            default_remote_dir: String::new(),
+            default_local_dir: String::new(),
+            notes: String::new(),
+            on_connect_commands: Vec::new(),
         }
     }
 }
@@ -147,6 +367,61 @@ impl Default for UiSettings {
             show_line_numbers: false,
             max_scrollback_lines: 10000,
             line_ending: "LF".to_string(),
+            line_delimiter: "LF".to_string(),
+            frame_decoding: "none".to_string(),
+            frame_start: "\\x02".to_string(),
+            frame_end: "\\x03".to_string(),
+            hex_view: false,
+            expand_tabs: false,
+            tab_width: 8,
+            freeze_stats_when_scrolled: false,
+            measure_latency: false,
+            detect_line_ending: false,
+            at_mode: false,
+            direct_keyboard: false,
+            backspace_key: "del".to_string(),
+            nul_handling: "caret".to_string(),
+            form_feed: "ignore".to_string(),
+            cursor_style: "block".to_string(),
+            cursor_blink: true,
+            show_tx_timestamps: false,
+            show_rx_timestamps: false,
+            show_control_chars: false,
+            compact_mode: false,
+            confirm_quit_connected: false,
+            notify_on_connect: false,
+            notify_on_disconnect: false,
+            confirm_send: false,
+            paste_confirm_lines: 5,
+            file_chunk_size: 4096,
+            file_chunk_delay_ms: 0,
+            highlight_enabled: false,
+            highlight_rules: vec![
+                HighlightRule::new("ERROR|ERR", "#FF5555"),
+                HighlightRule::new("WARN", "#F1C40F"),
+                HighlightRule::new(r"\bOK\b", "#50FA7B"),
+            ],
+            macros: Vec::new(),
+        }
+    }
+}
+
+impl Default for ReconnectSettings {
+    fn default() -> Self {
+        Self {
+            strategy: "exponential".to_string(),
+            delay_secs: 1,
+            max_delay_secs: 30,
+            stable_after_secs: 10,
+        }
+    }
+}
+
+impl Default for ChannelSettings {
+    fn default() -> Self {
+        Self {
+            command_capacity: 32,
+            event_capacity: 128,
         }
     }
 }
@@ -159,6 +434,12 @@ impl Default for LogSettings {
             log_to_file: false,
             log_directory: "logs".to_string(),
             timestamp_saved_lines: true,
+            auto_save_on_disconnect: false,
+            auto_save_directory: String::new(),
+            auto_save_template: "{host}_{date}".to_string(),
+            raw_capture_tx: true,
+            raw_capture_tagged: true,
+            raw_capture_timestamps: true,
         }
     }
 }
@@ -167,30 +448,120 @@ impl Default for LogSettings {
 // Gestionnaire de configuration
 // =============================================================================
 
+/// Nom du profil par défaut (`settings.json` à la racine du dossier de configuration).
+pub const DEFAULT_PROFILE: &str = "default";
+
 /// Gestionnaire de configuration avec chargement/sauvegarde JSON.
+///
+/// Chaque profil est un jeu de paramètres indépendant : le profil par défaut
+/// vit dans `settings.json`, les autres dans `profiles/<nom>/settings.json`.
 #[derive(Debug, Clone)]
 pub struct SettingsManager {
     settings: AppSettings,
     config_path: PathBuf,
+    profile: String,
 }
 
 impl SettingsManager {
-    /// Crée un nouveau gestionnaire en chargeant depuis le chemin par défaut.
-    pub fn new() -> Self {
-        let config_path = Self::default_config_path();
+    /// Crée un gestionnaire pour le profil `name`.
+    ///
+    /// Un nom invalide retombe sur le profil par défaut ; un profil inexistant
+    /// démarre avec les paramètres par défaut et sera créé à la sauvegarde.
+    pub fn with_profile(name: &str) -> Self {
+        let profile = if Self::is_valid_profile_name(name) {
+            name.to_string()
+        } else {
+            log::warn!("Nom de profil invalide « {name} », profil par défaut utilisé");
+            DEFAULT_PROFILE.to_string()
+        };
+        let config_path = Self::profile_config_path(&profile);
         let settings = Self::load_from_path(&config_path).unwrap_or_default();
         Self {
             settings,
             config_path,
+            profile,
         }
     }
 
-    /// Chemin par défaut du fichier de configuration.
-    fn default_config_path() -> PathBuf {
+    /// Dossier racine de la configuration.
+    fn config_root() -> PathBuf {
         dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("serial-ssh-term")
-            .join("settings.json")
+    }
+
+    /// Chemin du fichier de configuration d'un profil.
+    fn profile_config_path(name: &str) -> PathBuf {
+        if name == DEFAULT_PROFILE {
+            Self::config_root().join("settings.json")
+        } else {
+            Self::config_root()
+                .join("profiles")
+                .join(name)
+                .join("settings.json")
+        }
+    }
+
+    /// Un nom de profil n'accepte que lettres, chiffres, `-` et `_`.
+    pub fn is_valid_profile_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= 64
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Liste les profils existants, le profil par défaut en tête.
+    pub fn list_profiles() -> Vec<String> {
+        let mut profiles: Vec<String> = fs::read_dir(Self::config_root().join("profiles"))
+            .map(|entries| {
+                entries
+                    .filter_map(std::result::Result::ok)
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .filter(|name| name != DEFAULT_PROFILE && Self::is_valid_profile_name(name))
+                    .collect()
+            })
+            .unwrap_or_default();
+        profiles.sort();
+        profiles.insert(0, DEFAULT_PROFILE.to_string());
+        profiles
+    }
+
+    /// Nom du profil actif.
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Recharge les paramètres depuis le profil `name`.
+    pub fn switch_profile(&mut self, name: &str) -> Result<()> {
+        if !Self::is_valid_profile_name(name) {
+            bail!("Nom de profil invalide : {name}");
+        }
+        let config_path = Self::profile_config_path(name);
+        self.settings = if config_path.exists() {
+            Self::load_from_path(&config_path)?
+        } else {
+            AppSettings::default()
+        };
+        self.config_path = config_path;
+        self.profile = name.to_string();
+        log::info!("Profil de configuration actif : {name}");
+        Ok(())
+    }
+
+    /// Crée le profil `name` à partir des paramètres courants et l'active.
+    pub fn create_profile(&mut self, name: &str) -> Result<()> {
+        if !Self::is_valid_profile_name(name) {
+            bail!("Nom de profil invalide : {name}");
+        }
+        let config_path = Self::profile_config_path(name);
+        if name == DEFAULT_PROFILE || config_path.exists() {
+            bail!("Le profil « {name} » existe déjà");
+        }
+        self.config_path = config_path;
+        self.profile = name.to_string();
+        self.save()
     }
 
     /// Charge la configuration depuis un fichier JSON.
@@ -220,6 +591,42 @@ impl SettingsManager {
         Ok(())
     }
 
+    /// Dossier contenant le fichier de configuration du profil.
+    pub fn config_directory(&self) -> PathBuf {
+        self.config_path
+            .parent()
+            .map_or_else(Self::config_root, Path::to_path_buf)
+    }
+
+    /// Résout un chemin relatif dans le dossier de configuration.
+    fn resolve_directory(&self, dir: &str) -> PathBuf {
+        let dir = PathBuf::from(dir);
+        if dir.is_absolute() {
+            dir
+        } else {
+            self.config_directory().join(dir)
+        }
+    }
+
+    /// Dossier des journaux (`log.log_directory`).
+    ///
+    /// Un chemin relatif est résolu dans le dossier de configuration.
+    pub fn log_directory(&self) -> PathBuf {
+        self.resolve_directory(&self.settings.log.log_directory)
+    }
+
+    /// Dossier des sauvegardes automatiques de session.
+    ///
+    /// Un chemin relatif est résolu dans le dossier de configuration.
+    pub fn auto_save_directory(&self) -> PathBuf {
+        let log = &self.settings.log;
+        if log.auto_save_directory.trim().is_empty() {
+            self.log_directory()
+        } else {
+            self.resolve_directory(&log.auto_save_directory)
+        }
+    }
+
     /// Accès en lecture aux paramètres.
     pub const fn settings(&self) -> &AppSettings {
         &self.settings
@@ -242,9 +649,95 @@ impl SettingsManager {
         self.settings.ui.window_height = height;
     }
 
-    /// Met à jour la terminaison de ligne.
-    pub fn set_line_ending(&mut self, ending: &str) {
-        self.settings.ui.line_ending = ending.to_string();
+    /// Retrouve le favori SSH correspondant à un couple hôte/port/utilisateur.
+    pub fn find_ssh_favorite_mut(
+        &mut self,
+        host: &str,
+        port: u16,
+        username: &str,
+    ) -> Option<&mut SshFavorite> {
+        self.settings
+            .ssh_favorites
+            .iter_mut()
+            .find(|f| f.host == host && f.port == port && f.username == username)
+    }
+
+    /// Mémorise le dernier dossier local utilisé pour un favori SSH et sauvegarde.
+    pub fn set_favorite_local_dir(&mut self, host: &str, port: u16, username: &str, dir: &str) {
+        if let Some(favorite) = self.find_ssh_favorite_mut(host, port, username) {
+            if favorite.default_local_dir != dir {
+                favorite.default_local_dir = dir.to_string();
+                if let Err(e) = self.save() {
+                    log::warn!("Impossible de sauvegarder le dossier local du favori : {e}");
+                }
+            }
+        }
+    }
+
+    /// Mémorise le dernier dossier distant utilisé pour un favori SSH et sauvegarde.
+    pub fn set_favorite_remote_dir(&mut self, host: &str, port: u16, username: &str, dir: &str) {
+        if let Some(favorite) = self.find_ssh_favorite_mut(host, port, username) {
+            if favorite.default_remote_dir != dir {
+                favorite.default_remote_dir = dir.to_string();
+                if let Err(e) = self.save() {
+                    log::warn!("Impossible de sauvegarder le dossier distant du favori : {e}");
+                }
+            }
+        }
+    }
+
+    /// Mode d'envoi hexadécimal par défaut d'un type de connexion.
+    pub const fn send_hex_for(&self, conn_type: ConnectionType) -> bool {
+        match conn_type {
+            ConnectionType::Serial => self.settings.serial.send_hex,
+            ConnectionType::Ssh => self.settings.ssh.send_hex,
+        }
+    }
+
+    /// Mémorise le mode d'envoi d'un type de connexion et sauvegarde.
+    pub fn set_send_hex(&mut self, conn_type: ConnectionType, hex: bool) {
+        let current = match conn_type {
+            ConnectionType::Serial => &mut self.settings.serial.send_hex,
+            ConnectionType::Ssh => &mut self.settings.ssh.send_hex,
+        };
+        if *current == hex {
+            return;
+        }
+        *current = hex;
+        let _ = self.save();
+    }
+
+    /// Écho local des envois pour un type de connexion.
+    pub const fn local_echo_for(&self, conn_type: ConnectionType) -> bool {
+        match conn_type {
+            ConnectionType::Serial => self.settings.serial.local_echo,
+            ConnectionType::Ssh => self.settings.ssh.local_echo,
+        }
+    }
+
+    /// Terminaison de ligne des envois pour un type de connexion.
+    pub fn line_ending_for(&self, conn_type: ConnectionType) -> &str {
+        let ending = match conn_type {
+            ConnectionType::Serial => &self.settings.serial.line_ending,
+            ConnectionType::Ssh => &self.settings.ssh.line_ending,
+        };
+        if ending.is_empty() {
+            &self.settings.ui.line_ending
+        } else {
+            ending
+        }
+    }
+
+    /// Mémorise la terminaison de ligne d'un type de connexion et sauvegarde.
+    pub fn set_line_ending(&mut self, conn_type: ConnectionType, ending: &str) {
+        let current = match conn_type {
+            ConnectionType::Serial => &mut self.settings.serial.line_ending,
+            ConnectionType::Ssh => &mut self.settings.ssh.line_ending,
+        };
+        if current == ending {
+            return;
+        }
+        *current = ending.to_string();
         let _ = self.save();
     }
 }
@@ -0,0 +1,1055 @@
+// =============================================================================
+// Fichier : sftp.rs
+// Rôle    : Client SFTP (protocole version 3) sur un canal déjà ouvert
+//
+// Architecture :
+//  - Indépendant de l'UI et du transport : fonctionne sur tout flux
+//    `AsyncRead + AsyncWrite` (en pratique un canal russh du sous-système
+//    "sftp", ouvert sur la session SSH authentifiée).
+//  - Même modèle que `spawn_connection_actor` : commandes via
+//    `tokio::sync::mpsc`, événements via `async_channel`.
+//  - Une requête à la fois : les transferts se suivent, la navigation attend
+//    la fin du transfert en cours.
+//  - Une erreur de fichier (droits, absent…) est signalée sans fermer la
+//    session ; seul un flux inutilisable (canal fermé, paquet invalide ou
+//    réponse désynchronisée) la termine.
+// =============================================================================
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Version du protocole négociée (draft-ietf-secsh-filexfer-02).
+const SFTP_VERSION: u32 = 3;
+
+/// Taille des lectures/écritures d'un transfert (limite usuelle des serveurs).
+const TRANSFER_CHUNK: u32 = 32 * 1024;
+
+/// Taille maximale acceptée pour un paquet reçu.
+const MAX_PACKET_LEN: usize = 256 * 1024;
+
+/// Intervalle minimal entre deux événements de progression.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// Types de paquets
+const FXP_INIT: u8 = 1;
+const FXP_VERSION: u8 = 2;
+const FXP_OPEN: u8 = 3;
+const FXP_CLOSE: u8 = 4;
+const FXP_READ: u8 = 5;
+const FXP_WRITE: u8 = 6;
+const FXP_OPENDIR: u8 = 11;
+const FXP_READDIR: u8 = 12;
+const FXP_REALPATH: u8 = 16;
+const FXP_STAT: u8 = 17;
+const FXP_STATUS: u8 = 101;
+const FXP_HANDLE: u8 = 102;
+const FXP_DATA: u8 = 103;
+const FXP_NAME: u8 = 104;
+const FXP_ATTRS: u8 = 105;
+
+// Drapeaux d'ouverture
+const FXF_READ: u32 = 0x01;
+const FXF_WRITE: u32 = 0x02;
+const FXF_CREAT: u32 = 0x08;
+const FXF_TRUNC: u32 = 0x10;
+
+// Attributs présents
+const ATTR_SIZE: u32 = 0x01;
+const ATTR_UIDGID: u32 = 0x02;
+const ATTR_PERMISSIONS: u32 = 0x04;
+const ATTR_ACMODTIME: u32 = 0x08;
+const ATTR_EXTENDED: u32 = 0x8000_0000;
+
+// Codes de statut
+const FX_OK: u32 = 0;
+const FX_EOF: u32 = 1;
+
+/// Masque du type de fichier dans les permissions POSIX.
+const S_IFMT: u32 = 0o170_000;
+const S_IFDIR: u32 = 0o040_000;
+
+/// Entrée d'un dossier distant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SftpEntry {
+    pub name: String,
+    pub is_dir: bool,
+    /// Taille en octets, si le serveur la fournit.
+    pub size: Option<u64>,
+    /// Dernière modification (secondes Unix).
+    pub modified: Option<u32>,
+}
+
+/// Commande envoyée par l'UI à la session SFTP.
+#[derive(Debug)]
+pub enum SftpCommand {
+    /// Liste un dossier distant (chemin absolu ou relatif au dossier personnel).
+    ListDir(String),
+    /// Télécharge le fichier distant `remote` vers `local` (écrasé).
+    Download { remote: String, local: PathBuf },
+    /// Envoie le fichier `local` vers le chemin distant `remote` (écrasé).
+    Upload { local: PathBuf, remote: String },
+    /// Ferme le canal SFTP (la session SSH reste ouverte).
+    Close,
+}
+
+/// Sens d'un transfert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferDirection {
+    Download,
+    Upload,
+}
+
+/// Événement renvoyé à l'UI.
+#[derive(Debug)]
+pub enum SftpEvent {
+    /// Session initialisée ; `home` est le dossier de départ du serveur.
+    Ready { home: String },
+    /// Contenu d'un dossier (chemin canonique, dossiers en tête).
+    Listing {
+        path: String,
+        entries: Vec<SftpEntry>,
+    },
+    /// Progression d'un transfert (`total` inconnu si le serveur ne donne pas la taille).
+    Progress {
+        name: String,
+        direction: TransferDirection,
+        done: u64,
+        total: Option<u64>,
+    },
+    /// Transfert terminé.
+    TransferDone {
+        name: String,
+        direction: TransferDirection,
+        bytes: u64,
+    },
+    /// Échec d'une commande ; la session reste utilisable.
+    Error(String),
+    /// Session terminée (fermeture demandée, canal coupé ou erreur de protocole).
+    Closed(Option<String>),
+}
+
+/// Canaux d'une session SFTP lancée par `spawn_sftp_session`.
+#[derive(Debug)]
+pub struct SftpHandle {
+    pub commands: tokio::sync::mpsc::Sender<SftpCommand>,
+    pub events: async_channel::Receiver<SftpEvent>,
+}
+
+/// Lance la session SFTP sur `stream` dans une tâche tokio.
+///
+/// Le premier événement est `Ready` (ou `Closed` si l'initialisation échoue).
+pub fn spawn_sftp_session<S>(stream: S) -> SftpHandle
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (cmd_tx, mut cmd_rx) = tokio::sync::mpsc::channel::<SftpCommand>(16);
+    let (event_tx, event_rx) = async_channel::bounded::<SftpEvent>(64);
+
+    tokio::spawn(async move {
+        let mut client = SftpClient { stream, next_id: 0 };
+        let home = match client.init().await {
+            Ok(home) => home,
+            Err(e) => {
+                let _ = event_tx.send(SftpEvent::Closed(Some(e.to_string()))).await;
+                return;
+            }
+        };
+        let _ = event_tx.send(SftpEvent::Ready { home }).await;
+
+        let reason = loop {
+            let Some(command) = cmd_rx.recv().await else {
+                break None;
+            };
+            let result = match command {
+                SftpCommand::ListDir(path) => client.list_dir(&path, &event_tx).await,
+                SftpCommand::Download { remote, local } => {
+                    client.download(&remote, &local, &event_tx).await
+                }
+                SftpCommand::Upload { local, remote } => {
+                    client.upload(&local, &remote, &event_tx).await
+                }
+                SftpCommand::Close => break None,
+            };
+            match result {
+                Ok(()) => {}
+                Err(e) if e.is::<ChannelError>() => break Some(format!("{e:#}")),
+                Err(e) => {
+                    let _ = event_tx.send(SftpEvent::Error(format!("{e:#}"))).await;
+                }
+            }
+        };
+
+        let _ = client.stream.shutdown().await;
+        if let Some(reason) = &reason {
+            log::warn!("Session SFTP interrompue : {reason}");
+        }
+        let _ = event_tx.send(SftpEvent::Closed(reason)).await;
+    });
+
+    SftpHandle {
+        commands: cmd_tx,
+        events: event_rx,
+    }
+}
+
+/// Chemin distant d'un élément de `dir`.
+pub fn join_remote(dir: &str, name: &str) -> String {
+    if dir.ends_with('/') {
+        format!("{dir}{name}")
+    } else {
+        format!("{dir}/{name}")
+    }
+}
+
+/// Dossier parent d'un chemin distant (`/` reste `/`).
+pub fn parent_remote(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(0) | None if path.starts_with('/') => "/".to_string(),
+        Some(index) => trimmed[..index].to_string(),
+        None => ".".to_string(),
+    }
+}
+
+/// Réponse STATUS du serveur signalant un échec (fichier absent, droits…).
+#[derive(Debug)]
+struct StatusError {
+    code: u32,
+    message: String,
+}
+
+impl std::fmt::Display for StatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self.code {
+            2 => "fichier introuvable",
+            3 => "permission refusée",
+            4 => "échec",
+            8 => "opération non supportée",
+            _ => "erreur",
+        };
+        if self.message.is_empty() {
+            write!(f, "{label} (code {})", self.code)
+        } else {
+            write!(f, "{label} : {}", self.message)
+        }
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// Flux SFTP inutilisable : la session ne peut pas continuer.
+#[derive(Debug)]
+struct ChannelError(String);
+
+impl std::fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+/// Paquet reçu : type et contenu après l'identifiant de requête.
+struct Packet {
+    kind: u8,
+    body: Vec<u8>,
+}
+
+/// Client SFTP séquentiel : une requête en vol à la fois.
+struct SftpClient<S> {
+    stream: S,
+    next_id: u32,
+}
+
+impl<S> SftpClient<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Négocie la version puis résout le dossier de départ.
+    async fn init(&mut self) -> Result<String> {
+        let mut payload = vec![FXP_INIT];
+        put_u32(&mut payload, SFTP_VERSION);
+        self.write_packet(&payload).await?;
+        let reply = self.read_packet().await?;
+        if reply.first() != Some(&FXP_VERSION) {
+            bail!("Réponse SFTP inattendue à l'initialisation");
+        }
+        let version = Reader::new(&reply[1..]).u32()?;
+        if version < SFTP_VERSION {
+            bail!("Version SFTP {version} non supportée");
+        }
+        self.realpath(".").await
+    }
+
+    /// Liste `path` (READDIR jusqu'à EOF) et envoie le résultat trié.
+    async fn list_dir(
+        &mut self,
+        path: &str,
+        events: &async_channel::Sender<SftpEvent>,
+    ) -> Result<()> {
+        let path = self.realpath(path).await?;
+        let handle = self.open_handle(FXP_OPENDIR, &path, None).await?;
+        let mut entries = Vec::new();
+        let listed = loop {
+            let mut body = Vec::new();
+            put_bytes(&mut body, &handle);
+            let reply = match self.request(FXP_READDIR, &body).await {
+                Ok(reply) => reply,
+                Err(e) => break Err(e),
+            };
+            match reply.kind {
+                FXP_NAME => match parse_names(&reply.body) {
+                    Ok(names) => entries.extend(
+                        names
+                            .into_iter()
+                            .filter(|entry| entry.name != "." && entry.name != ".."),
+                    ),
+                    Err(e) => break Err(e),
+                },
+                FXP_STATUS => match status_result(&reply.body) {
+                    Ok(FX_EOF) => break Ok(()),
+                    Ok(_) => break Err(anyhow::anyhow!("Réponse READDIR inattendue")),
+                    Err(e) => break Err(e),
+                },
+                _ => break Err(anyhow::anyhow!("Réponse READDIR inattendue")),
+            }
+        };
+        self.close_handle(&handle).await?;
+        listed.with_context(|| format!("Lecture de {path}"))?;
+
+        entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        let _ = events.send(SftpEvent::Listing { path, entries }).await;
+        Ok(())
+    }
+
+    /// Copie le fichier distant dans `local`, par blocs de `TRANSFER_CHUNK`.
+    async fn download(
+        &mut self,
+        remote: &str,
+        local: &std::path::Path,
+        events: &async_channel::Sender<SftpEvent>,
+    ) -> Result<()> {
+        let name = file_name(remote);
+        let total = self.stat(remote).await?.size;
+        // Le fichier local n'est créé (ou tronqué) qu'une fois la lecture
+        // distante acceptée : un refus du serveur laisse un fichier existant intact.
+        let handle = self.open_handle(FXP_OPEN, remote, Some(FXF_READ)).await?;
+        let mut file = match tokio::fs::File::create(local).await {
+            Ok(file) => file,
+            Err(e) => {
+                self.close_handle(&handle).await?;
+                return Err(anyhow::Error::from(e)
+                    .context(format!("Impossible de créer {}", local.display())));
+            }
+        };
+
+        let mut progress = Progress::new(name.clone(), TransferDirection::Download, total);
+        let copied = loop {
+            let mut body = Vec::new();
+            put_bytes(&mut body, &handle);
+            put_u64(&mut body, progress.done);
+            put_u32(&mut body, TRANSFER_CHUNK);
+            let reply = match self.request(FXP_READ, &body).await {
+                Ok(reply) => reply,
+                Err(e) => break Err(e),
+            };
+            match reply.kind {
+                FXP_DATA => {
+                    let data = match Reader::new(&reply.body).bytes() {
+                        Ok(data) => data,
+                        Err(e) => break Err(e),
+                    };
+                    if let Err(e) = file.write_all(data).await {
+                        break Err(anyhow::Error::from(e)
+                            .context(format!("Écriture de {}", local.display())));
+                    }
+                    progress.advance(data.len() as u64, events).await;
+                }
+                FXP_STATUS => match status_result(&reply.body) {
+                    Ok(FX_EOF) => break Ok(()),
+                    Ok(_) => break Err(anyhow::anyhow!("Réponse READ inattendue")),
+                    Err(e) => break Err(e),
+                },
+                _ => break Err(anyhow::anyhow!("Réponse READ inattendue")),
+            }
+        };
+        self.close_handle(&handle).await?;
+        copied.with_context(|| format!("Téléchargement de {remote}"))?;
+        file.flush().await?;
+
+        progress.finish(events).await;
+        Ok(())
+    }
+
+    /// Envoie `local` vers le chemin distant `remote` (créé ou tronqué).
+    async fn upload(
+        &mut self,
+        local: &std::path::Path,
+        remote: &str,
+        events: &async_channel::Sender<SftpEvent>,
+    ) -> Result<()> {
+        let name = file_name(remote);
+        let mut file = tokio::fs::File::open(local)
+            .await
+            .with_context(|| format!("Impossible de lire {}", local.display()))?;
+        let total = file.metadata().await.ok().map(|m| m.len());
+        let handle = self
+            .open_handle(FXP_OPEN, remote, Some(FXF_WRITE | FXF_CREAT | FXF_TRUNC))
+            .await?;
+
+        let mut progress = Progress::new(name, TransferDirection::Upload, total);
+        let mut buffer = vec![0; TRANSFER_CHUNK as usize];
+        let copied = loop {
+            let n = match file.read(&mut buffer).await {
+                Ok(0) => break Ok(()),
+                Ok(n) => n,
+                Err(e) => {
+                    break Err(
+                        anyhow::Error::from(e).context(format!("Lecture de {}", local.display()))
+                    )
+                }
+            };
+            let mut body = Vec::new();
+            put_bytes(&mut body, &handle);
+            put_u64(&mut body, progress.done);
+            put_bytes(&mut body, &buffer[..n]);
+            if let Err(e) = self.expect_ok(FXP_WRITE, &body).await {
+                break Err(e);
+            }
+            progress.advance(n as u64, events).await;
+        };
+        self.close_handle(&handle).await?;
+        copied.with_context(|| format!("Envoi vers {remote}"))?;
+
+        progress.finish(events).await;
+        Ok(())
+    }
+
+    /// Chemin canonique côté serveur.
+    async fn realpath(&mut self, path: &str) -> Result<String> {
+        let mut body = Vec::new();
+        put_bytes(&mut body, path.as_bytes());
+        let reply = self.request(FXP_REALPATH, &body).await?;
+        match reply.kind {
+            FXP_NAME => parse_names(&reply.body)?
+                .into_iter()
+                .next()
+                .map(|entry| entry.name)
+                .context("Réponse REALPATH vide"),
+            FXP_STATUS => {
+                status_result(&reply.body).with_context(|| format!("Chemin {path}"))?;
+                bail!("Réponse REALPATH inattendue")
+            }
+            _ => bail!("Réponse REALPATH inattendue"),
+        }
+    }
+
+    /// Attributs d'un fichier distant.
+    async fn stat(&mut self, path: &str) -> Result<Attributes> {
+        let mut body = Vec::new();
+        put_bytes(&mut body, path.as_bytes());
+        let reply = self.request(FXP_STAT, &body).await?;
+        match reply.kind {
+            FXP_ATTRS => Reader::new(&reply.body).attrs(),
+            FXP_STATUS => {
+                status_result(&reply.body).with_context(|| format!("Fichier {path}"))?;
+                bail!("Réponse STAT inattendue")
+            }
+            _ => bail!("Réponse STAT inattendue"),
+        }
+    }
+
+    /// OPEN (avec `flags`) ou OPENDIR (`flags` absent) : retourne le handle.
+    async fn open_handle(&mut self, kind: u8, path: &str, flags: Option<u32>) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        put_bytes(&mut body, path.as_bytes());
+        if let Some(flags) = flags {
+            put_u32(&mut body, flags);
+            // Attributs vides : permissions par défaut du serveur.
+            put_u32(&mut body, 0);
+        }
+        let reply = self.request(kind, &body).await?;
+        match reply.kind {
+            FXP_HANDLE => Ok(Reader::new(&reply.body).bytes()?.to_vec()),
+            FXP_STATUS => {
+                status_result(&reply.body).with_context(|| format!("Ouverture de {path}"))?;
+                bail!("Réponse OPEN inattendue")
+            }
+            _ => bail!("Réponse OPEN inattendue"),
+        }
+    }
+
+    async fn close_handle(&mut self, handle: &[u8]) -> Result<()> {
+        let mut body = Vec::new();
+        put_bytes(&mut body, handle);
+        self.expect_ok(FXP_CLOSE, &body).await
+    }
+
+    /// Requête dont la seule réponse attendue est un STATUS OK.
+    async fn expect_ok(&mut self, kind: u8, body: &[u8]) -> Result<()> {
+        let reply = self.request(kind, body).await?;
+        if reply.kind != FXP_STATUS {
+            bail!("Réponse SFTP inattendue (type {})", reply.kind);
+        }
+        status_result(&reply.body)?;
+        Ok(())
+    }
+
+    /// Envoie une requête et attend la réponse portant le même identifiant.
+    async fn request(&mut self, kind: u8, body: &[u8]) -> Result<Packet> {
+        self.next_id = self.next_id.wrapping_add(1);
+        let id = self.next_id;
+        let mut payload = Vec::with_capacity(body.len() + 5);
+        payload.push(kind);
+        put_u32(&mut payload, id);
+        payload.extend_from_slice(body);
+        self.write_packet(&payload).await?;
+
+        let reply = self.read_packet().await?;
+        let mut reader = Reader::new(&reply);
+        let kind = reader.u8()?;
+        let reply_id = reader.u32()?;
+        if reply_id != id {
+            return Err(ChannelError(format!(
+                "Réponse SFTP désynchronisée (requête {id}, réponse {reply_id})"
+            ))
+            .into());
+        }
+        Ok(Packet {
+            kind,
+            body: reader.rest().to_vec(),
+        })
+    }
+
+    async fn write_packet(&mut self, payload: &[u8]) -> Result<()> {
+        let len = u32::try_from(payload.len()).context("Paquet SFTP trop long")?;
+        let mut packet = Vec::with_capacity(payload.len() + 4);
+        put_u32(&mut packet, len);
+        packet.extend_from_slice(payload);
+        let closed = |e: std::io::Error| ChannelError(format!("Canal SFTP fermé : {e}"));
+        self.stream.write_all(&packet).await.map_err(closed)?;
+        self.stream.flush().await.map_err(closed)?;
+        Ok(())
+    }
+
+    async fn read_packet(&mut self) -> Result<Vec<u8>> {
+        let closed = |e: std::io::Error| ChannelError(format!("Canal SFTP fermé : {e}"));
+        let len = self.stream.read_u32().await.map_err(closed)? as usize;
+        if len == 0 || len > MAX_PACKET_LEN {
+            return Err(ChannelError(format!("Paquet SFTP invalide ({len} octets)")).into());
+        }
+        let mut packet = vec![0; len];
+        self.stream.read_exact(&mut packet).await.map_err(closed)?;
+        Ok(packet)
+    }
+}
+
+/// Suivi d'un transfert, avec événements de progression espacés.
+struct Progress {
+    name: String,
+    direction: TransferDirection,
+    total: Option<u64>,
+    done: u64,
+    last_event: Instant,
+}
+
+impl Progress {
+    fn new(name: String, direction: TransferDirection, total: Option<u64>) -> Self {
+        Self {
+            name,
+            direction,
+            total,
+            done: 0,
+            last_event: Instant::now(),
+        }
+    }
+
+    async fn advance(&mut self, bytes: u64, events: &async_channel::Sender<SftpEvent>) {
+        self.done += bytes;
+        if self.last_event.elapsed() >= PROGRESS_INTERVAL {
+            self.last_event = Instant::now();
+            let _ = events
+                .send(SftpEvent::Progress {
+                    name: self.name.clone(),
+                    direction: self.direction,
+                    done: self.done,
+                    total: self.total,
+                })
+                .await;
+        }
+    }
+
+    async fn finish(self, events: &async_channel::Sender<SftpEvent>) {
+        let _ = events
+            .send(SftpEvent::TransferDone {
+                name: self.name,
+                direction: self.direction,
+                bytes: self.done,
+            })
+            .await;
+    }
+}
+
+/// Attributs utiles d'un fichier distant.
+#[derive(Debug, Default)]
+struct Attributes {
+    size: Option<u64>,
+    permissions: Option<u32>,
+    modified: Option<u32>,
+}
+
+/// Lecture des types SFTP (big-endian, chaînes préfixées par leur longueur).
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.data.len() < n {
+            bail!("Paquet SFTP tronqué");
+        }
+        let (head, tail) = self.data.split_at(n);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok((u64::from(self.u32()?) << 32) | u64::from(self.u32()?))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        Ok(String::from_utf8_lossy(self.bytes()?).into_owned())
+    }
+
+    const fn rest(&self) -> &'a [u8] {
+        self.data
+    }
+
+    fn attrs(&mut self) -> Result<Attributes> {
+        let flags = self.u32()?;
+        let mut attrs = Attributes::default();
+        if flags & ATTR_SIZE != 0 {
+            attrs.size = Some(self.u64()?);
+        }
+        if flags & ATTR_UIDGID != 0 {
+            self.take(8)?;
+        }
+        if flags & ATTR_PERMISSIONS != 0 {
+            attrs.permissions = Some(self.u32()?);
+        }
+        if flags & ATTR_ACMODTIME != 0 {
+            self.u32()?;
+            attrs.modified = Some(self.u32()?);
+        }
+        if flags & ATTR_EXTENDED != 0 {
+            for _ in 0..self.u32()? {
+                self.bytes()?;
+                self.bytes()?;
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+/// Entrées d'une réponse NAME.
+fn parse_names(body: &[u8]) -> Result<Vec<SftpEntry>> {
+    let mut reader = Reader::new(body);
+    let count = reader.u32()?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let name = reader.string()?;
+        let long_name = reader.string()?;
+        let attrs = reader.attrs()?;
+        let is_dir = attrs.permissions.map_or_else(
+            // Sans permissions, se fier au format `ls -l` de longname.
+            || long_name.starts_with('d'),
+            |mode| mode & S_IFMT == S_IFDIR,
+        );
+        entries.push(SftpEntry {
+            name,
+            is_dir,
+            size: attrs.size,
+            modified: attrs.modified,
+        });
+    }
+    Ok(entries)
+}
+
+/// Code d'une réponse STATUS : `Ok` pour OK et EOF, `StatusError` sinon.
+fn status_result(body: &[u8]) -> Result<u32> {
+    let mut reader = Reader::new(body);
+    let code = reader.u32()?;
+    if code == FX_OK || code == FX_EOF {
+        return Ok(code);
+    }
+    let message = reader.string().unwrap_or_default();
+    Err(StatusError { code, message }.into())
+}
+
+/// Dernier composant d'un chemin distant.
+fn file_name(path: &str) -> String {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .to_string()
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_bytes(buf: &mut Vec<u8>, data: &[u8]) {
+    // Les données passées ici sont bornées par TRANSFER_CHUNK ou un chemin.
+    #[allow(clippy::cast_possible_truncation)]
+    put_u32(buf, data.len() as u32);
+    buf.extend_from_slice(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Attributs complets tels qu'envoyés par OpenSSH (taille, uid/gid,
+    /// permissions, dates), plus une extension.
+    fn full_attrs(size: u64, mode: u32, mtime: u32) -> Vec<u8> {
+        let mut attrs = Vec::new();
+        put_u32(
+            &mut attrs,
+            ATTR_SIZE | ATTR_UIDGID | ATTR_PERMISSIONS | ATTR_ACMODTIME | ATTR_EXTENDED,
+        );
+        put_u64(&mut attrs, size);
+        put_u32(&mut attrs, 1000);
+        put_u32(&mut attrs, 1000);
+        put_u32(&mut attrs, mode);
+        put_u32(&mut attrs, mtime - 60);
+        put_u32(&mut attrs, mtime);
+        put_u32(&mut attrs, 1);
+        put_bytes(&mut attrs, b"acl@example.com");
+        put_bytes(&mut attrs, b"");
+        attrs
+    }
+
+    fn name_entry(body: &mut Vec<u8>, name: &str, long_name: &str, attrs: &[u8]) {
+        put_bytes(body, name.as_bytes());
+        put_bytes(body, long_name.as_bytes());
+        body.extend_from_slice(attrs);
+    }
+
+    fn status_body(code: u32, message: &str) -> Vec<u8> {
+        let mut body = Vec::new();
+        put_u32(&mut body, code);
+        put_bytes(&mut body, message.as_bytes());
+        put_bytes(&mut body, b"en");
+        body
+    }
+
+    #[test]
+    fn attrs_with_every_field() {
+        let mut packet = full_attrs(1234, 0o100_644, 1_700_000_000);
+        packet.push(0xAA);
+        let mut reader = Reader::new(&packet);
+        let attrs = reader.attrs().unwrap();
+        assert_eq!(attrs.size, Some(1234));
+        assert_eq!(attrs.permissions, Some(0o100_644));
+        assert_eq!(attrs.modified, Some(1_700_000_000));
+        assert_eq!(reader.rest(), [0xAA]);
+
+        // Aucun attribut.
+        let attrs = Reader::new(&[0, 0, 0, 0]).attrs().unwrap();
+        assert_eq!(
+            (attrs.size, attrs.permissions, attrs.modified),
+            (None, None, None)
+        );
+    }
+
+    #[test]
+    fn names_from_a_directory_listing() {
+        let mut body = Vec::new();
+        put_u32(&mut body, 3);
+        name_entry(
+            &mut body,
+            "docs",
+            "drwxr-xr-x    2 user  user      4096 Jan  1 00:00 docs",
+            &full_attrs(4096, 0o040_755, 1_700_000_000),
+        );
+        name_entry(
+            &mut body,
+            "notes.txt",
+            "-rw-r--r--    1 user  user        12 Jan  1 00:00 notes.txt",
+            &full_attrs(12, 0o100_644, 1_700_000_100),
+        );
+        // Sans permissions : type déduit de longname.
+        name_entry(&mut body, "lien", "drwxr-xr-x ... lien", &[0, 0, 0, 0]);
+
+        let entries = parse_names(&body).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                SftpEntry {
+                    name: "docs".to_string(),
+                    is_dir: true,
+                    size: Some(4096),
+                    modified: Some(1_700_000_000),
+                },
+                SftpEntry {
+                    name: "notes.txt".to_string(),
+                    is_dir: false,
+                    size: Some(12),
+                    modified: Some(1_700_000_100),
+                },
+                SftpEntry {
+                    name: "lien".to_string(),
+                    is_dir: true,
+                    size: None,
+                    modified: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn status_codes() {
+        assert_eq!(
+            status_result(&status_body(FX_OK, "Success")).unwrap(),
+            FX_OK
+        );
+        assert_eq!(
+            status_result(&status_body(FX_EOF, "End of file")).unwrap(),
+            FX_EOF
+        );
+        let error = status_result(&status_body(2, "No such file")).unwrap_err();
+        assert_eq!(error.to_string(), "fichier introuvable : No such file");
+        // Message absent (serveur minimal) : le code suffit.
+        let error = status_result(&3u32.to_be_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "permission refusée (code 3)");
+    }
+
+    #[test]
+    fn truncated_and_oversize_input() {
+        // Deux entrées annoncées, une seule présente.
+        let mut body = Vec::new();
+        put_u32(&mut body, 2);
+        name_entry(&mut body, "a", "a", &[0, 0, 0, 0]);
+        assert!(parse_names(&body).is_err());
+
+        // Nombre d'entrées et longueur de chaîne démesurés.
+        let mut body = Vec::new();
+        put_u32(&mut body, u32::MAX);
+        put_u32(&mut body, u32::MAX);
+        body.extend_from_slice(b"abc");
+        assert!(parse_names(&body).is_err());
+
+        // Taille annoncée mais coupée ; extensions démesurées.
+        let attrs = full_attrs(1, 0o100_644, 1_700_000_000);
+        assert!(Reader::new(&attrs[..8]).attrs().is_err());
+        let mut attrs = Vec::new();
+        put_u32(&mut attrs, ATTR_EXTENDED);
+        put_u32(&mut attrs, u32::MAX);
+        assert!(Reader::new(&attrs).attrs().is_err());
+
+        assert!(status_result(&[]).is_err());
+        assert!(Reader::new(&[0, 0, 0]).u32().is_err());
+    }
+
+    #[test]
+    fn remote_paths() {
+        let cases = [
+            ("/", "/"),
+            ("//", "/"),
+            ("/home", "/"),
+            ("/home/user", "/home"),
+            ("/home/user/", "/home"),
+            ("docs/a", "docs"),
+            ("docs", "."),
+            ("", "."),
+        ];
+        for (path, parent) in cases {
+            assert_eq!(parent_remote(path), parent, "{path:?}");
+        }
+        assert_eq!(join_remote("/", "etc"), "/etc");
+        assert_eq!(join_remote("/home", "user"), "/home/user");
+        assert_eq!(file_name("/var/log/syslog"), "syslog");
+        assert_eq!(file_name("/var/log/"), "log");
+    }
+
+    /// Serveur simulé sur un flux en mémoire : `respond` reçoit le type et le
+    /// corps de chaque requête et rend le type et le corps de la réponse.
+    fn fake_server(respond: impl Fn(u8, &[u8]) -> (u8, Vec<u8>) + Send + 'static) -> SftpHandle {
+        let (client, mut server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            while let Ok(len) = server.read_u32().await {
+                let mut packet = vec![0; len as usize];
+                if server.read_exact(&mut packet).await.is_err() {
+                    break;
+                }
+                let mut reply = Vec::new();
+                if packet[0] == FXP_INIT {
+                    reply.push(FXP_VERSION);
+                    put_u32(&mut reply, SFTP_VERSION);
+                } else {
+                    let (kind, body) = respond(packet[0], &packet[5..]);
+                    reply.push(kind);
+                    reply.extend_from_slice(&packet[1..5]);
+                    reply.extend_from_slice(&body);
+                }
+                let mut out = Vec::new();
+                put_bytes(&mut out, &reply);
+                if server.write_all(&out).await.is_err() {
+                    break;
+                }
+            }
+        });
+        spawn_sftp_session(client)
+    }
+
+    /// Réponses communes : dossier de départ et taille du fichier.
+    fn respond_common(kind: u8) -> Option<(u8, Vec<u8>)> {
+        match kind {
+            FXP_REALPATH => {
+                let mut body = Vec::new();
+                put_u32(&mut body, 1);
+                name_entry(&mut body, "/home/test", "", &[0, 0, 0, 0]);
+                Some((FXP_NAME, body))
+            }
+            FXP_STAT => {
+                let mut body = Vec::new();
+                put_u32(&mut body, ATTR_SIZE);
+                put_u64(&mut body, 5);
+                Some((FXP_ATTRS, body))
+            }
+            FXP_CLOSE => Some((FXP_STATUS, status_body(FX_OK, ""))),
+            _ => None,
+        }
+    }
+
+    async fn next_event(handle: &SftpHandle) -> SftpEvent {
+        tokio::time::timeout(Duration::from_secs(5), handle.events.recv())
+            .await
+            .expect("aucun événement SFTP")
+            .expect("session SFTP terminée")
+    }
+
+    fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("sst-sftp-{}-{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn refused_download_keeps_local_file() {
+        let handle = fake_server(|kind, _| {
+            respond_common(kind).unwrap_or((FXP_STATUS, status_body(3, "Permission denied")))
+        });
+        assert!(
+            matches!(next_event(&handle).await, SftpEvent::Ready { home } if home == "/home/test")
+        );
+
+        let local = temp_file("refused", b"ancien contenu");
+        handle
+            .commands
+            .send(SftpCommand::Download {
+                remote: "/root/secret".to_string(),
+                local: local.clone(),
+            })
+            .await
+            .unwrap();
+        match next_event(&handle).await {
+            SftpEvent::Error(message) => {
+                assert!(message.contains("permission refusée"), "{message}")
+            }
+            other => panic!("événement inattendu : {other:?}"),
+        }
+        assert_eq!(std::fs::read(&local).unwrap(), b"ancien contenu");
+        std::fs::remove_file(&local).unwrap();
+    }
+
+    #[tokio::test]
+    async fn download_writes_remote_content() {
+        let handle = fake_server(|kind, body| {
+            if let Some(reply) = respond_common(kind) {
+                return reply;
+            }
+            match kind {
+                FXP_OPEN => {
+                    let mut reply = Vec::new();
+                    put_bytes(&mut reply, b"h1");
+                    (FXP_HANDLE, reply)
+                }
+                FXP_READ => {
+                    let mut reader = Reader::new(body);
+                    reader.bytes().unwrap();
+                    if reader.u64().unwrap() == 0 {
+                        let mut reply = Vec::new();
+                        put_bytes(&mut reply, b"hello");
+                        (FXP_DATA, reply)
+                    } else {
+                        (FXP_STATUS, status_body(FX_EOF, ""))
+                    }
+                }
+                _ => (FXP_STATUS, status_body(8, "")),
+            }
+        });
+        assert!(matches!(next_event(&handle).await, SftpEvent::Ready { .. }));
+
+        let local = temp_file("download", b"ancien contenu plus long");
+        handle
+            .commands
+            .send(SftpCommand::Download {
+                remote: "/home/test/hello.txt".to_string(),
+                local: local.clone(),
+            })
+            .await
+            .unwrap();
+        loop {
+            match next_event(&handle).await {
+                SftpEvent::Progress { .. } => {}
+                SftpEvent::TransferDone { name, bytes, .. } => {
+                    assert_eq!((name.as_str(), bytes), ("hello.txt", 5));
+                    break;
+                }
+                other => panic!("événement inattendu : {other:?}"),
+            }
+        }
+        assert_eq!(std::fs::read(&local).unwrap(), b"hello");
+        std::fs::remove_file(&local).unwrap();
+    }
+
+    #[tokio::test]
+    async fn oversize_packet_closes_session() {
+        let handle = fake_server(|_, _| (FXP_NAME, vec![0; MAX_PACKET_LEN]));
+        match next_event(&handle).await {
+            SftpEvent::Closed(Some(reason)) => assert!(reason.contains("invalide"), "{reason}"),
+            other => panic!("événement inattendu : {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+// =============================================================================
+// Fichier : sgr.rs
+// Rôle    : Décodage des paramètres SGR (`CSI … m`) : attributs et couleurs
+// =============================================================================
+
+/// Couleur SGR : palette indexée (16 couleurs de base, `38;5;n` pour les
+/// 256) ou 24 bits (`38;2;r;g;b`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsiColor {
+    Indexed(u8),
+    Rgb([u8; 3]),
+}
+
+impl AnsiColor {
+    /// Composantes d'une couleur hors des 16 de base, qui ont leurs tags
+    /// prédéfinis : cube 6×6×6 (16-231) puis rampe de gris (232-255) xterm.
+    pub fn extended_rgb(self) -> Option<[u8; 3]> {
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match self {
+            Self::Indexed(0..=15) => None,
+            Self::Indexed(n @ 16..=231) => {
+                let i = usize::from(n - 16);
+                Some([LEVELS[i / 36], LEVELS[(i / 6) % 6], LEVELS[i % 6]])
+            }
+            Self::Indexed(n) => {
+                let level = 8 + (n - 232) * 10;
+                Some([level; 3])
+            }
+            Self::Rgb(rgb) => Some(rgb),
+        }
+    }
+}
+
+/// Effet d'un paramètre SGR sur le style courant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SgrAttribute {
+    /// `0` (ou séquence sans paramètre) : retour au style par défaut.
+    Reset,
+    Bold(bool),
+    Italic(bool),
+    Underline(bool),
+    /// Couleur de premier plan ; `None` = couleur par défaut (`39`).
+    Foreground(Option<AnsiColor>),
+    /// Couleur de fond ; `None` = couleur par défaut (`49`).
+    Background(Option<AnsiColor>),
+}
+
+/// Décode les paramètres d'une séquence SGR, chacun avec ses sous-paramètres
+/// (séparés par `:`), tels que les fournit `vte::Params::iter`.
+///
+/// Les paramètres inconnus, la couleur de soulignement (`58`) et les
+/// couleurs étendues invalides ou tronquées sont ignorés.
+pub fn parse_sgr<'a>(params: impl IntoIterator<Item = &'a [u16]>) -> Vec<SgrAttribute> {
+    let mut attributes = Vec::new();
+    let mut has_params = false;
+    let mut params = params.into_iter();
+    while let Some(param) = params.next() {
+        has_params = true;
+        let p = param.first().copied().unwrap_or(0);
+        let attribute = match p {
+            0 => SgrAttribute::Reset,
+            1 => SgrAttribute::Bold(true),
+            3 => SgrAttribute::Italic(true),
+            4 => SgrAttribute::Underline(true),
+            22 => SgrAttribute::Bold(false),
+            23 => SgrAttribute::Italic(false),
+            24 => SgrAttribute::Underline(false),
+            30..=37 => SgrAttribute::Foreground(Some(base_color(p - 30))),
+            39 => SgrAttribute::Foreground(None),
+            40..=47 => SgrAttribute::Background(Some(base_color(p - 40))),
+            49 => SgrAttribute::Background(None),
+            90..=97 => SgrAttribute::Foreground(Some(base_color(p - 90 + 8))),
+            100..=107 => SgrAttribute::Background(Some(base_color(p - 100 + 8))),
+            // Couleurs étendues : sous-paramètres `38:2:r:g:b` (éventuellement
+            // avec espace colorimétrique `38:2::r:g:b`) ou paramètres suivants
+            // `38;2;r;g;b`.
+            38 | 48 | 58 => {
+                let color = if param.len() > 1 {
+                    let sub = match &param[1..] {
+                        [2, _, r, g, b] => vec![2, *r, *g, *b],
+                        sub => sub.to_vec(),
+                    };
+                    parse_extended_color(&mut sub.into_iter())
+                } else {
+                    parse_extended_color(
+                        &mut params
+                            .by_ref()
+                            .map(|next| next.first().copied().unwrap_or(0)),
+                    )
+                };
+                match (p, color) {
+                    (38, Some(color)) => SgrAttribute::Foreground(Some(color)),
+                    (48, Some(color)) => SgrAttribute::Background(Some(color)),
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        attributes.push(attribute);
+    }
+    if !has_params {
+        attributes.push(SgrAttribute::Reset);
+    }
+    attributes
+}
+
+/// Couleur de base `index` (0-15, garanti par les plages de `parse_sgr`).
+fn base_color(index: u16) -> AnsiColor {
+    AnsiColor::Indexed(u8::try_from(index).unwrap_or(0))
+}
+
+/// Lit la couleur étendue qui suit `38`/`48`/`58` : `5;n` ou `2;r;g;b`.
+///
+/// Les valeurs sont consommées même si elles sont invalides, pour que la
+/// suite de la séquence ne soit pas interprétée comme d'autres attributs.
+fn parse_extended_color(values: &mut dyn Iterator<Item = u16>) -> Option<AnsiColor> {
+    match values.next()? {
+        5 => u8::try_from(values.next()?).ok().map(AnsiColor::Indexed),
+        2 => {
+            let mut rgb = [0u8; 3];
+            let mut valid = true;
+            for component in &mut rgb {
+                let value = values.next()?;
+                valid &= u8::try_from(value).map(|v| *component = v).is_ok();
+            }
+            valid.then_some(AnsiColor::Rgb(rgb))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Paramètres séparés par `;`, sans sous-paramètres.
+    fn semicolons(values: &[u16]) -> Vec<SgrAttribute> {
+        parse_sgr(values.chunks(1))
+    }
+
+    #[test]
+    fn cube_and_grayscale_indices() {
+        assert_eq!(AnsiColor::Indexed(7).extended_rgb(), None);
+        assert_eq!(AnsiColor::Indexed(16).extended_rgb(), Some([0, 0, 0]));
+        assert_eq!(AnsiColor::Indexed(196).extended_rgb(), Some([255, 0, 0]));
+        assert_eq!(
+            AnsiColor::Indexed(110).extended_rgb(),
+            Some([135, 175, 215])
+        );
+        assert_eq!(
+            AnsiColor::Indexed(231).extended_rgb(),
+            Some([255, 255, 255])
+        );
+        assert_eq!(AnsiColor::Indexed(232).extended_rgb(), Some([8, 8, 8]));
+        assert_eq!(
+            AnsiColor::Indexed(255).extended_rgb(),
+            Some([238, 238, 238])
+        );
+        assert_eq!(AnsiColor::Rgb([1, 2, 3]).extended_rgb(), Some([1, 2, 3]));
+    }
+
+    #[test]
+    fn indexed_256_semicolon_form() {
+        assert_eq!(
+            semicolons(&[38, 5, 196, 48, 5, 232]),
+            vec![
+                SgrAttribute::Foreground(Some(AnsiColor::Indexed(196))),
+                SgrAttribute::Background(Some(AnsiColor::Indexed(232))),
+            ]
+        );
+    }
+
+    #[test]
+    fn truecolor_semicolon_form() {
+        assert_eq!(
+            semicolons(&[1, 38, 2, 10, 20, 30, 4]),
+            vec![
+                SgrAttribute::Bold(true),
+                SgrAttribute::Foreground(Some(AnsiColor::Rgb([10, 20, 30]))),
+                SgrAttribute::Underline(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn colon_forms() {
+        let fg = SgrAttribute::Foreground(Some(AnsiColor::Rgb([10, 20, 30])));
+        assert_eq!(parse_sgr([&[38, 2, 10, 20, 30][..]]), vec![fg]);
+        // Avec espace colorimétrique (vide ou non).
+        assert_eq!(parse_sgr([&[38, 2, 0, 10, 20, 30][..]]), vec![fg]);
+        assert_eq!(
+            parse_sgr([&[48, 5, 42][..], &[1][..]]),
+            vec![
+                SgrAttribute::Background(Some(AnsiColor::Indexed(42))),
+                SgrAttribute::Bold(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated_extended_colors() {
+        assert_eq!(semicolons(&[38]), vec![]);
+        assert_eq!(semicolons(&[38, 5]), vec![]);
+        assert_eq!(semicolons(&[48, 2, 10, 20]), vec![]);
+        assert_eq!(
+            parse_sgr([&[38, 2, 10][..], &[1][..]]),
+            vec![SgrAttribute::Bold(true)]
+        );
+    }
+
+    #[test]
+    fn invalid_components_are_consumed() {
+        // 300 ne tient pas dans u8 : couleur ignorée, mais pas relue comme attribut.
+        assert_eq!(
+            semicolons(&[38, 2, 300, 1, 4, 3]),
+            vec![SgrAttribute::Italic(true)]
+        );
+        assert_eq!(semicolons(&[38, 5, 256, 1]), vec![SgrAttribute::Bold(true)]);
+        assert_eq!(semicolons(&[38, 9, 1]), vec![SgrAttribute::Bold(true)]);
+    }
+
+    #[test]
+    fn underline_color_is_ignored() {
+        assert_eq!(
+            semicolons(&[58, 2, 1, 2, 3, 3]),
+            vec![SgrAttribute::Italic(true)]
+        );
+        assert_eq!(parse_sgr([&[58, 5, 9][..]]), vec![]);
+    }
+
+    #[test]
+    fn base_colors_and_resets() {
+        assert_eq!(semicolons(&[]), vec![SgrAttribute::Reset]);
+        assert_eq!(
+            semicolons(&[0, 31, 102, 39, 49, 22]),
+            vec![
+                SgrAttribute::Reset,
+                SgrAttribute::Foreground(Some(AnsiColor::Indexed(1))),
+                SgrAttribute::Background(Some(AnsiColor::Indexed(10))),
+                SgrAttribute::Foreground(None),
+                SgrAttribute::Background(None),
+                SgrAttribute::Bold(false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,506 @@
+// =============================================================================
+// Fichier : ssh_config.rs
+// Rôle    : Lecture des hôtes d'un ~/.ssh/config OpenSSH ou d'une liste CSV
+//
+// Seules les directives utiles aux favoris et aux alias sont interprétées :
+//   Host, HostName, Port, User, IdentityFile, ProxyJump.
+// Les motifs génériques (`*`, `?`, `!`) ne donnent pas d'entrée mais leurs
+// directives s'appliquent aux alias qu'ils désignent ; les blocs `Match`
+// sont ignorés.
+// =============================================================================
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::settings::SshFavorite;
+
+/// Entrée `Host` d'un fichier de configuration OpenSSH.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SshConfigHost {
+    pub alias: String,
+    pub host_name: Option<String>,
+    pub port: Option<u16>,
+    pub user: Option<String>,
+    pub identity_file: Option<String>,
+    /// Rebond `[utilisateur@]hôte[:port]` (`ProxyJump none` → absent).
+    pub proxy_jump: Option<String>,
+}
+
+impl SshConfigHost {
+    /// Hôte réel à joindre (`HostName`, sinon l'alias lui-même).
+    pub fn resolved_host(&self) -> &str {
+        self.host_name.as_deref().unwrap_or(&self.alias)
+    }
+
+    /// Clé privée déclarée, `~` développé.
+    pub fn resolved_identity_file(&self) -> Option<String> {
+        self.identity_file.as_deref().map(expand_tilde)
+    }
+
+    /// Convertit l'entrée en favori SSH (`HostName` absent → alias utilisé).
+    pub fn to_favorite(&self) -> SshFavorite {
+        let key_path = self.resolved_identity_file().unwrap_or_default();
+
+        SshFavorite {
+            name: self.alias.clone(),
+            host: self.host_name.clone().unwrap_or_else(|| self.alias.clone()),
+            port: self.port.unwrap_or(22),
+            username: self.user.clone().unwrap_or_default(),
+            auth_method: if key_path.is_empty() {
+                "password".to_string()
+            } else {
+                "key".to_string()
+            },
+            key_path,
+            ..SshFavorite::default()
+        }
+    }
+
+    /// Applique une directive ; comme OpenSSH, la première valeur rencontrée
+    /// l'emporte.
+    fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            "hostname" if self.host_name.is_none() => self.host_name = Some(value.to_string()),
+            "port" if self.port.is_none() => self.port = value.parse().ok(),
+            "user" if self.user.is_none() => self.user = Some(value.to_string()),
+            "identityfile" if self.identity_file.is_none() => {
+                self.identity_file = Some(value.to_string());
+            }
+            "proxyjump" if self.proxy_jump.is_none() => self.proxy_jump = Some(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// Chemin du fichier de configuration OpenSSH de l'utilisateur.
+pub fn user_ssh_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ssh").join("config"))
+}
+
+/// Découpe une ligne de configuration en (mot-clé, valeur).
+///
+/// Accepte `Clé Valeur` et `Clé=Valeur`, guillemets autour de la valeur retirés.
+fn split_directive(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let split_at = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let (key, rest) = line.split_at(split_at);
+    let value = rest
+        .trim_start_matches(|c: char| c.is_whitespace() || c == '=')
+        .trim()
+        .trim_matches('"');
+    if value.is_empty() {
+        return None;
+    }
+
+    Some((key.to_ascii_lowercase(), value.to_string()))
+}
+
+/// Indique si un motif `Host` est générique (non importable tel quel).
+fn is_pattern(alias: &str) -> bool {
+    alias.contains(['*', '?', '!'])
+}
+
+/// Motif OpenSSH : `*` couvre toute suite de caractères, `?` un seul.
+fn matches_pattern(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| matches_pattern(rest, &name[skip..])),
+        Some((&p, rest)) => name
+            .split_first()
+            .is_some_and(|(&c, tail)| (p == '?' || p == c) && matches_pattern(rest, tail)),
+    }
+}
+
+/// Indique si une ligne `Host` s'applique à `alias` : un motif désigne
+/// l'alias et aucun motif nié (`!motif`) ne l'exclut.
+fn host_matches(patterns: &[String], alias: &str) -> bool {
+    let alias: Vec<char> = alias.chars().collect();
+    let mut matched = false;
+    for pattern in patterns {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern.as_str()),
+        };
+        if matches_pattern(&pattern.chars().collect::<Vec<_>>(), &alias) {
+            if negated {
+                return false;
+            }
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// Bloc `Host` (ou `Match`, jamais appliqué) et ses directives.
+struct Block {
+    patterns: Option<Vec<String>>,
+    directives: Vec<(String, String)>,
+}
+
+/// Parse le contenu d'un fichier de configuration OpenSSH.
+///
+/// Chaque alias non générique des lignes `Host` donne une seule entrée, même
+/// s'il apparaît dans plusieurs blocs. Elle reçoit, dans l'ordre du fichier,
+/// les directives de tous les blocs qui la désignent (`Host *` compris) et
+/// celles placées avant le premier `Host`.
+pub fn parse_ssh_config(content: &str) -> Vec<SshConfigHost> {
+    // Les directives en tête de fichier valent pour tous les hôtes.
+    let mut blocks = vec![Block {
+        patterns: Some(vec!["*".to_string()]),
+        directives: Vec::new(),
+    }];
+    let mut aliases: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let Some((key, value)) = split_directive(line) else {
+            continue;
+        };
+
+        match key.as_str() {
+            "host" => {
+                let patterns: Vec<String> = value.split_whitespace().map(str::to_string).collect();
+                for alias in patterns.iter().filter(|a| !is_pattern(a)) {
+                    if !aliases.contains(alias) {
+                        aliases.push(alias.clone());
+                    }
+                }
+                blocks.push(Block {
+                    patterns: Some(patterns),
+                    directives: Vec::new(),
+                });
+            }
+            "match" => blocks.push(Block {
+                patterns: None,
+                directives: Vec::new(),
+            }),
+            _ => {
+                if let Some(block) = blocks.last_mut() {
+                    block.directives.push((key, value));
+                }
+            }
+        }
+    }
+
+    aliases
+        .into_iter()
+        .map(|alias| {
+            let mut host = SshConfigHost {
+                alias,
+                ..SshConfigHost::default()
+            };
+            for block in &blocks {
+                let applies = block
+                    .patterns
+                    .as_ref()
+                    .is_some_and(|patterns| host_matches(patterns, &host.alias));
+                if applies {
+                    for (key, value) in &block.directives {
+                        host.apply(key, value);
+                    }
+                }
+            }
+            host
+        })
+        .collect()
+}
+
+/// Lit le `~/.ssh/config` de l'utilisateur ; fichier absent ou illisible →
+/// liste vide (signalé dans le journal).
+pub fn load_user_hosts() -> Vec<SshConfigHost> {
+    let Some(path) = user_ssh_config_path().filter(|p| p.exists()) else {
+        return Vec::new();
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => parse_ssh_config(&content),
+        Err(e) => {
+            log::warn!("Impossible de lire {} : {e}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// Entrée dont l'alias correspond exactement à `alias`.
+pub fn find_host<'a>(hosts: &'a [SshConfigHost], alias: &str) -> Option<&'a SshConfigHost> {
+    hosts.iter().find(|host| host.alias == alias)
+}
+
+/// Découpe un rebond `ProxyJump` `[utilisateur@]hôte[:port]`.
+///
+/// `none`, les valeurs vides et un port invalide donnent `None` ; seul le
+/// premier rebond d'une liste séparée par des virgules est retenu.
+pub fn parse_jump_host(spec: &str) -> Option<(Option<String>, String, Option<u16>)> {
+    let first = spec.split(',').next()?.trim();
+    if first.is_empty() || first.eq_ignore_ascii_case("none") {
+        return None;
+    }
+    let (user, rest) = match first.rsplit_once('@') {
+        Some((user, rest)) => ((!user.is_empty()).then(|| user.to_string()), rest),
+        None => (None, first),
+    };
+    let (host, port) = if let Some(bracketed) = rest.strip_prefix('[') {
+        // IPv6 : `[adresse]:port`
+        let (host, after) = bracketed.split_once(']')?;
+        let port = match after {
+            "" => None,
+            _ => Some(after.strip_prefix(':')?.parse().ok()?),
+        };
+        (host, port)
+    } else {
+        match rest.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') => (host, Some(port.parse().ok()?)),
+            // IPv6 nue : pas de port possible.
+            _ => (rest, None),
+        }
+    };
+    (!host.is_empty()).then(|| (user, host.to_string(), port))
+}
+
+/// Parse une liste d'hôtes au format CSV.
+///
+/// Colonnes : `nom,hôte,port,utilisateur,clé` (seul l'hôte est obligatoire).
+/// Les lignes vides, les commentaires `#` et une éventuelle ligne d'en-tête
+/// commençant par `name`/`nom` sont ignorés.
+pub fn parse_hosts_csv(content: &str) -> Vec<SshFavorite> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |i: usize| fields.get(i).copied().unwrap_or_default();
+
+            let name = field(0);
+            if name.eq_ignore_ascii_case("name") || name.eq_ignore_ascii_case("nom") {
+                return None;
+            }
+            let host = field(1);
+            if host.is_empty() {
+                return None;
+            }
+            let port = field(2).parse().unwrap_or(22);
+            let username = field(3).to_string();
+            let key_path = expand_tilde(field(4));
+
+            Some(SshFavorite {
+                name: match (name.is_empty(), username.is_empty()) {
+                    (false, _) => name.to_string(),
+                    (true, true) => host.to_string(),
+                    (true, false) => format!("{username}@{host}:{port}"),
+                },
+                host: host.to_string(),
+                port,
+                username,
+                auth_method: if key_path.is_empty() {
+                    "password".to_string()
+                } else {
+                    "key".to_string()
+                },
+                key_path,
+                ..SshFavorite::default()
+            })
+        })
+        .collect()
+}
+
+/// Charge des favoris depuis un fichier OpenSSH config ou CSV (selon l'extension).
+pub fn load_favorites_from_file(path: &Path) -> Result<Vec<SshFavorite>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Impossible de lire {}", path.display()))?;
+
+    let is_csv = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+
+    Ok(if is_csv {
+        parse_hosts_csv(&content)
+    } else {
+        parse_ssh_config(&content)
+            .iter()
+            .map(SshConfigHost::to_favorite)
+            .collect()
+    })
+}
+
+/// Remplace un `~` initial par le dossier personnel de l'utilisateur.
+fn expand_tilde(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jump(
+        user: Option<&str>,
+        host: &str,
+        port: Option<u16>,
+    ) -> Option<(Option<String>, String, Option<u16>)> {
+        Some((user.map(str::to_string), host.to_string(), port))
+    }
+
+    fn host(alias: &str) -> SshConfigHost {
+        SshConfigHost {
+            alias: alias.to_string(),
+            ..SshConfigHost::default()
+        }
+    }
+
+    #[test]
+    fn config_hosts_and_directive_syntax() {
+        let hosts = parse_ssh_config(
+            "# commentaire\n\
+             Host web db\n\
+             \tHostName=\"10.0.0.5\"\n\
+             \tport 2222\n\
+             \tUSER deploy\n\
+             \tIdentityFile ~/.ssh/id_web\n\
+             \tProxyJump ops@bastion\n\
+             Host nas\n\
+             \tPort invalide\n",
+        );
+        let web = SshConfigHost {
+            alias: "web".to_string(),
+            host_name: Some("10.0.0.5".to_string()),
+            port: Some(2222),
+            user: Some("deploy".to_string()),
+            identity_file: Some("~/.ssh/id_web".to_string()),
+            proxy_jump: Some("ops@bastion".to_string()),
+        };
+        let db = SshConfigHost {
+            alias: "db".to_string(),
+            ..web.clone()
+        };
+        assert_eq!(hosts, vec![web, db, host("nas")]);
+        assert_eq!(hosts[2].resolved_host(), "nas");
+    }
+
+    #[test]
+    fn host_star_defaults_fill_unset_values() {
+        let hosts = parse_ssh_config(
+            "User global\n\
+             Host web\n\
+             Port 2222\n\
+             Host *\n\
+             Port 22\n\
+             IdentityFile ~/.ssh/id_default\n\
+             User ignored\n",
+        );
+        assert_eq!(
+            hosts,
+            vec![SshConfigHost {
+                port: Some(2222),
+                user: Some("global".to_string()),
+                identity_file: Some("~/.ssh/id_default".to_string()),
+                ..host("web")
+            }]
+        );
+    }
+
+    #[test]
+    fn patterns_negation_and_match_blocks() {
+        let hosts = parse_ssh_config(
+            "Host prod-? !prod-b\n\
+             User admin\n\
+             Match host prod-a\n\
+             Port 2200\n\
+             Host prod-a prod-b prod-ab\n\
+             HostName %h.example.org\n",
+        );
+        let host_name = Some("%h.example.org".to_string());
+        assert_eq!(
+            hosts,
+            vec![
+                SshConfigHost {
+                    user: Some("admin".to_string()),
+                    host_name: host_name.clone(),
+                    ..host("prod-a")
+                },
+                SshConfigHost {
+                    host_name: host_name.clone(),
+                    ..host("prod-b")
+                },
+                SshConfigHost {
+                    host_name,
+                    ..host("prod-ab")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_aliases_give_one_entry() {
+        let hosts = parse_ssh_config(
+            "Host web\n\
+             HostName first\n\
+             Host web web\n\
+             HostName second\n\
+             User deploy\n",
+        );
+        assert_eq!(
+            hosts,
+            vec![SshConfigHost {
+                host_name: Some("first".to_string()),
+                user: Some("deploy".to_string()),
+                ..host("web")
+            }]
+        );
+    }
+
+    #[test]
+    fn jump_host_forms() {
+        assert_eq!(parse_jump_host("bastion"), jump(None, "bastion", None));
+        assert_eq!(
+            parse_jump_host(" admin@bastion:2222 "),
+            jump(Some("admin"), "bastion", Some(2222))
+        );
+        assert_eq!(
+            parse_jump_host("a@b@bastion"),
+            jump(Some("a@b"), "bastion", None)
+        );
+        assert_eq!(parse_jump_host("@bastion"), jump(None, "bastion", None));
+        assert_eq!(
+            parse_jump_host("[2001:db8::1]:2200"),
+            jump(None, "2001:db8::1", Some(2200))
+        );
+        assert_eq!(parse_jump_host("ops@[::1]"), jump(Some("ops"), "::1", None));
+        assert_eq!(
+            parse_jump_host("2001:db8::1"),
+            jump(None, "2001:db8::1", None)
+        );
+        // Seul le premier rebond d'une chaîne est retenu.
+        assert_eq!(
+            parse_jump_host("first:23,second"),
+            jump(None, "first", Some(23))
+        );
+    }
+
+    #[test]
+    fn invalid_jump_hosts() {
+        for spec in [
+            "",
+            " ",
+            "none",
+            "NONE",
+            ",second",
+            "user@",
+            "host:",
+            "host:ssh",
+            "host:70000",
+            "[::1",
+            "[::1]2200",
+            "[::1]:x",
+            "[]:22",
+        ] {
+            assert_eq!(parse_jump_host(spec), None, "{spec:?}");
+        }
+    }
+}
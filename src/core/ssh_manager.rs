@@ -11,8 +11,12 @@
 //      2. Si connue → accepte silencieusement.
 //      3. Si clé changée → alerte MITM envoyée à l'UI.
 //      4. Si inconnue → demande confirmation à l'UI.
-//      5. Si acceptée → enregistre dans ~/.ssh/known_hosts.
+//      5. Si acceptée → enregistre dans ~/.ssh/known_hosts, ou seulement en
+//         mémoire pour la session (« Accepter temporairement »).
 //  - Ouvre une session PTY (xterm-256color) + shell interactif.
+//  - Redirections de ports locales : une tâche tokio par redirection écoute
+//    sur 127.0.0.1 et relaie chaque client via un canal `direct-tcpip`.
+//    Les tâches sont annulées à la déconnexion.
 //
 // Sécurité :
 //  - Aucun `unwrap()` ni `expect()`.
@@ -20,18 +24,31 @@
 //  - Connexion refusée si l'utilisateur rejette la clé.
 // =============================================================================
 
+use std::collections::VecDeque;
+use std::fmt;
 use std::future::Future;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
-use russh::client;
+use russh::client::{self, KeyboardInteractiveAuthResponse};
 use russh::keys::known_hosts::{check_known_hosts, learn_known_hosts};
 use russh::keys::{self, HashAlg, PrivateKeyWithHashAlg};
 use russh::{ChannelMsg, Pty};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::{JoinHandle, JoinSet};
 
-use super::connection::{Connection, ConnectionEvent, ConnectionState, ConnectionType};
+use super::connection::{
+    Connection, ConnectionEvent, ConnectionState, ConnectionType, HostKeyDecision,
+};
+use super::sftp::{self, SftpHandle};
+use super::ssh_config;
+
+/// Code des données étendues de type stderr (RFC 4254 §5.2).
+const SSH_EXTENDED_DATA_STDERR: u32 = 1;
 
 // =============================================================================
 // Configuration SSH
@@ -45,7 +62,38 @@ pub struct SshConfig {
     pub username: String,
     pub auth_method: SshAuthMethod,
     /// Délai de connexion TCP (défaut : 10 s).
+    /// Le délai d'inactivité de la session en est dérivé (× 3).
     pub connect_timeout_secs: u64,
+    /// Redirections de ports locales ouvertes après l'authentification.
+    pub port_forwards: Vec<PortForward>,
+    /// Taille initiale du PTY `(colonnes, lignes)`, ajustée ensuite par
+    /// `Connection::resize`.
+    pub terminal_size: (u32, u32),
+    /// Commande unique exécutée (`exec`) à la place du PTY + shell ; la
+    /// session se termine avec elle.
+    pub command: Option<String>,
+    /// Rebond `[utilisateur@]hôte[:port]` traversé pour joindre l'hôte
+    /// (`ProxyJump` de `~/.ssh/config`), authentifié comme la cible.
+    pub proxy_jump: Option<String>,
+}
+
+/// Redirection locale : `127.0.0.1:local_port` → `remote_host:remote_port`,
+/// l'hôte distant étant résolu par le serveur SSH (équivalent de `ssh -L`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortForward {
+    pub local_port: u16,
+    pub remote_host: String,
+    pub remote_port: u16,
+}
+
+impl fmt::Display for PortForward {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "localhost:{} → {}:{}",
+            self.local_port, self.remote_host, self.remote_port
+        )
+    }
 }
 
 /// Méthode d'authentification SSH.
@@ -56,6 +104,17 @@ pub enum SshAuthMethod {
         private_key_path: String,
         passphrase: Option<String>,
     },
+    /// Clé privée collée par l'utilisateur (OpenSSH/PEM), décodée en mémoire
+    /// sans jamais être écrite sur disque.
+    KeyData {
+        private_key: String,
+        passphrase: Option<String>,
+    },
+    /// Clés détenues par l'agent SSH (`SSH_AUTH_SOCK`), essayées tour à tour.
+    Agent,
+    /// Questions posées par le serveur (mot de passe, code OTP…), soumises à
+    /// l'UI par `ConnectionEvent::AuthPrompt`.
+    KeyboardInteractive,
 }
 
 impl Default for SshConfig {
@@ -66,6 +125,10 @@ impl Default for SshConfig {
             username: String::new(),
             auth_method: SshAuthMethod::Password(String::new()),
             connect_timeout_secs: 10,
+            port_forwards: Vec::new(),
+            terminal_size: (220, 50),
+            command: None,
+            proxy_jump: None,
         }
     }
 }
@@ -102,73 +165,375 @@ impl client::Handler for SshClientHandler {
             let fingerprint = key.fingerprint(HashAlg::Sha256).to_string();
             let key_type = key.algorithm().to_string();
 
-            match check_known_hosts(&host, port, &key) {
+            let is_key_changed = match check_known_hosts(&host, port, &key) {
                 Ok(true) => {
                     log::info!("SSH: clé connue pour {host}:{port} ({key_type}) — approuvée");
-                    Ok(true)
+                    return Ok(true);
                 }
-
                 Err(keys::Error::KeyChanged { line }) => {
                     // Clé CHANGÉE → risque MITM.
                     log::warn!(
                         "SSH: AVERTISSEMENT MITM — clé différente ligne {line} \
                          pour {host}:{port} ! fingerprint: {fingerprint}"
                     );
-                    let (decision_tx, decision_rx) = tokio::sync::oneshot::channel::<bool>();
-                    let _ = event_tx
-                        .send(ConnectionEvent::HostKeyUnknown {
-                            host: host.clone(),
-                            key_type,
-                            fingerprint,
-                            is_key_changed: true,
-                            decision_tx,
-                        })
-                        .await;
-                    let accepted = tokio::time::timeout(Duration::from_secs(300), decision_rx)
-                        .await
-                        .ok()
-                        .and_then(std::result::Result::ok)
-                        .unwrap_or(false);
-                    if accepted {
-                        if let Err(e) = learn_known_hosts(&host, port, &key) {
-                            log::warn!("SSH: impossible d'enregistrer la clé : {e}");
-                        }
-                    }
-                    Ok(accepted)
+                    true
                 }
-
                 Ok(false) | Err(_) => {
                     // Hôte inconnu — première connexion.
                     log::info!("SSH: hôte inconnu {host}:{port} — demande confirmation");
-                    let (decision_tx, decision_rx) = tokio::sync::oneshot::channel::<bool>();
+                    false
+                }
+            };
+
+            // Déjà acceptée « temporairement » pendant cette exécution.
+            if is_session_trusted(&host, port, &fingerprint) {
+                log::info!("SSH: clé de {host}:{port} acceptée pour la session — approuvée");
+                return Ok(true);
+            }
+
+            let (decision_tx, decision_rx) = tokio::sync::oneshot::channel::<HostKeyDecision>();
+            let _ = event_tx
+                .send(ConnectionEvent::HostKeyUnknown {
+                    host: host.clone(),
+                    key_type,
+                    fingerprint: fingerprint.clone(),
+                    is_key_changed,
+                    decision_tx,
+                })
+                .await;
+            let decision = tokio::time::timeout(Duration::from_secs(300), decision_rx)
+                .await
+                .ok()
+                .and_then(std::result::Result::ok)
+                .unwrap_or(HostKeyDecision::Reject);
+
+            match decision {
+                HostKeyDecision::Reject => Ok(false),
+                HostKeyDecision::AcceptOnce => {
+                    trust_for_session(&host, port, &fingerprint);
+                    log::info!("SSH: clé de {host}:{port} acceptée sans enregistrement");
+                    Ok(true)
+                }
+                HostKeyDecision::AcceptAndSave => {
+                    if let Err(e) = learn_known_hosts(&host, port, &key) {
+                        log::warn!("SSH: impossible d'enregistrer la clé dans known_hosts : {e}");
+                    } else {
+                        log::info!("SSH: clé de {host}:{port} ajoutée à ~/.ssh/known_hosts");
+                    }
+                    Ok(true)
+                }
+            }
+        }
+    }
+}
+
+/// Clés d'hôte acceptées temporairement (hôte, port, empreinte), jamais
+/// écrites sur disque : oubliées à la fermeture de l'application.
+static SESSION_TRUSTED_KEYS: Mutex<Vec<(String, u16, String)>> = Mutex::new(Vec::new());
+
+fn is_session_trusted(host: &str, port: u16, fingerprint: &str) -> bool {
+    SESSION_TRUSTED_KEYS.lock().is_ok_and(|keys| {
+        keys.iter()
+            .any(|(h, p, f)| h == host && *p == port && f == fingerprint)
+    })
+}
+
+fn trust_for_session(host: &str, port: u16, fingerprint: &str) {
+    if let Ok(mut keys) = SESSION_TRUSTED_KEYS.lock() {
+        keys.push((host.to_string(), port, fingerprint.to_string()));
+    }
+}
+
+/// Authentifie la session avec une clé privée déjà chargée (signature SHA-256).
+async fn authenticate_with_key(
+    handle: &mut client::Handle<SshClientHandler>,
+    username: &str,
+    key: keys::PrivateKey,
+) -> Result<client::AuthResult> {
+    let key_with_alg = PrivateKeyWithHashAlg::new(Arc::new(key), Some(HashAlg::Sha256));
+    handle
+        .authenticate_publickey(username, key_with_alg)
+        .await
+        .context("Erreur lors de l'authentification par clé publique")
+}
+
+/// Authentifie `username` sur la session `handle` selon `method`.
+async fn authenticate(
+    handle: &mut client::Handle<SshClientHandler>,
+    username: &str,
+    method: &SshAuthMethod,
+    event_tx: &async_channel::Sender<ConnectionEvent>,
+) -> Result<client::AuthResult> {
+    Ok(match method {
+        SshAuthMethod::Password(password) => handle
+            .authenticate_password(username, password)
+            .await
+            .context("Erreur lors de l'authentification par mot de passe")?,
+
+        SshAuthMethod::KeyFile {
+            private_key_path,
+            passphrase,
+        } => {
+            let key =
+                load_key_with_prompt(private_key_path, passphrase.as_deref(), event_tx).await?;
+            authenticate_with_key(handle, username, key).await?
+        }
+
+        SshAuthMethod::KeyData {
+            private_key,
+            passphrase,
+        } => {
+            let key = keys::decode_secret_key(private_key.trim(), passphrase.as_deref())
+                .context("Clé privée collée invalide (ou passphrase incorrecte)")?;
+            authenticate_with_key(handle, username, key).await?
+        }
+
+        SshAuthMethod::Agent => authenticate_with_agent(handle, username).await?,
+
+        SshAuthMethod::KeyboardInteractive => {
+            authenticate_keyboard_interactive(handle, username, event_tx).await?
+        }
+    })
+}
+
+/// Authentification sur un rebond : la clé de l'hôte final (sa passphrase
+/// reste locale) ou l'agent SSH. Un mot de passe ou des réponses
+/// interactives destinés à l'hôte final ne sont jamais présentés au rebond.
+fn jump_auth_method(method: &SshAuthMethod) -> SshAuthMethod {
+    match method {
+        SshAuthMethod::KeyFile { .. } | SshAuthMethod::KeyData { .. } | SshAuthMethod::Agent => {
+            method.clone()
+        }
+        SshAuthMethod::Password(_) | SshAuthMethod::KeyboardInteractive => SshAuthMethod::Agent,
+    }
+}
+
+/// Nombre de passphrases refusées avant d'abandonner la connexion.
+const MAX_PASSPHRASE_ATTEMPTS: u32 = 3;
+
+/// Charge la clé privée `path` ; si elle est chiffrée et que la passphrase
+/// manque ou est refusée, la demande à l'UI (`ConnectionEvent::PassphraseRequired`).
+async fn load_key_with_prompt(
+    path: &str,
+    passphrase: Option<&str>,
+    event_tx: &async_channel::Sender<ConnectionEvent>,
+) -> Result<keys::PrivateKey> {
+    let mut passphrase = passphrase.map(str::to_string);
+    let mut attempts = 0;
+    loop {
+        match keys::load_secret_key(path, passphrase.as_deref()) {
+            Ok(key) => {
+                if attempts > 0 {
                     let _ = event_tx
-                        .send(ConnectionEvent::HostKeyUnknown {
-                            host: host.clone(),
-                            key_type,
-                            fingerprint,
-                            is_key_changed: false,
-                            decision_tx,
+                        .send(ConnectionEvent::PassphraseAccepted {
+                            key_path: path.to_string(),
                         })
                         .await;
-                    let accepted = tokio::time::timeout(Duration::from_secs(300), decision_rx)
-                        .await
-                        .ok()
-                        .and_then(std::result::Result::ok)
-                        .unwrap_or(false);
-                    if accepted {
-                        if let Err(e) = learn_known_hosts(&host, port, &key) {
-                            log::warn!(
-                                "SSH: impossible d'enregistrer la clé dans known_hosts : {e}"
-                            );
-                        } else {
-                            log::info!("SSH: clé de {host}:{port} ajoutée à ~/.ssh/known_hosts");
-                        }
-                    }
-                    Ok(accepted)
                 }
+                return Ok(key);
+            }
+            Err(
+                keys::Error::KeyIsEncrypted
+                | keys::Error::SshKey(keys::ssh_key::Error::Crypto)
+                | keys::Error::Unpad(_),
+            ) => {}
+            Err(e) => return Err(e).context("Impossible de charger la clé privée SSH"),
+        }
+        if attempts == MAX_PASSPHRASE_ATTEMPTS {
+            bail!("Passphrase incorrecte pour la clé {path}");
+        }
+        if passphrase.is_some() {
+            log::info!("Passphrase refusée pour la clé {path}");
+        }
+        attempts += 1;
+
+        let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+        let _ = event_tx
+            .send(ConnectionEvent::PassphraseRequired {
+                key_path: path.to_string(),
+                response_tx,
+            })
+            .await;
+        match tokio::time::timeout(Duration::from_secs(300), response_rx).await {
+            Ok(Ok(answer)) => passphrase = Some(answer),
+            Ok(Err(_)) => bail!("Saisie de la passphrase annulée"),
+            Err(_) => bail!("Saisie de la passphrase : délai de réponse dépassé"),
+        }
+    }
+}
+
+/// Authentifie la session avec les identités de l'agent SSH, essayées dans
+/// l'ordre où l'agent les propose ; la signature reste dans l'agent.
+#[cfg(unix)]
+async fn authenticate_with_agent(
+    handle: &mut client::Handle<SshClientHandler>,
+    username: &str,
+) -> Result<client::AuthResult> {
+    let mut agent = match keys::agent::client::AgentClient::connect_env().await {
+        Ok(agent) => agent,
+        Err(keys::Error::EnvVar(_)) => {
+            bail!("Agent SSH introuvable : SSH_AUTH_SOCK n'est pas défini")
+        }
+        Err(e) => return Err(e).context("Impossible de joindre l'agent SSH (SSH_AUTH_SOCK)"),
+    };
+    let identities = agent
+        .request_identities()
+        .await
+        .context("Impossible de lister les clés de l'agent SSH")?;
+    if identities.is_empty() {
+        bail!("L'agent SSH ne propose aucune clé (ssh-add pour en ajouter)");
+    }
+
+    let count = identities.len();
+    for key in identities {
+        let comment = key.comment().to_string();
+        let hash_alg =
+            matches!(key.algorithm(), keys::Algorithm::Rsa { .. }).then_some(HashAlg::Sha256);
+        match handle
+            .authenticate_publickey_with(username, key, hash_alg, &mut agent)
+            .await
+        {
+            Ok(result) if result.success() => {
+                log::info!("Authentifié par l'agent SSH (clé {comment})");
+                return Ok(result);
             }
+            Ok(_) => log::info!("Clé de l'agent refusée par le serveur : {comment}"),
+            Err(e) => log::warn!("Signature par l'agent SSH impossible ({comment}) : {e}"),
         }
     }
+    bail!("Aucune des {count} clé(s) de l'agent SSH n'a été acceptée par le serveur")
+}
+
+/// L'agent n'est joignable que par socket Unix (`SSH_AUTH_SOCK`).
+#[cfg(not(unix))]
+async fn authenticate_with_agent(
+    _handle: &mut client::Handle<SshClientHandler>,
+    _username: &str,
+) -> Result<client::AuthResult> {
+    bail!("Authentification par agent SSH non supportée sur cette plateforme")
+}
+
+/// Authentifie la session en keyboard-interactive : chaque série de questions
+/// du serveur est soumise à l'UI, jusqu'au succès ou au refus du serveur.
+async fn authenticate_keyboard_interactive(
+    handle: &mut client::Handle<SshClientHandler>,
+    username: &str,
+    event_tx: &async_channel::Sender<ConnectionEvent>,
+) -> Result<client::AuthResult> {
+    let mut response = handle
+        .authenticate_keyboard_interactive_start(username, None)
+        .await
+        .context("Erreur lors de l'authentification keyboard-interactive")?;
+    loop {
+        let (instructions, prompts) = match response {
+            KeyboardInteractiveAuthResponse::Success => return Ok(client::AuthResult::Success),
+            KeyboardInteractiveAuthResponse::Failure {
+                remaining_methods,
+                partial_success,
+            } => {
+                return Ok(client::AuthResult::Failure {
+                    remaining_methods,
+                    partial_success,
+                })
+            }
+            KeyboardInteractiveAuthResponse::InfoRequest {
+                instructions,
+                prompts,
+                ..
+            } => (instructions, prompts),
+        };
+
+        if !instructions.trim().is_empty() {
+            let _ = event_tx
+                .send(ConnectionEvent::Notice(instructions.trim().to_string()))
+                .await;
+        }
+        // Une requête sans question attend simplement une réponse vide.
+        let answers = if prompts.is_empty() {
+            Vec::new()
+        } else {
+            let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+            let _ = event_tx
+                .send(ConnectionEvent::AuthPrompt {
+                    prompts: prompts.into_iter().map(|p| (p.prompt, p.echo)).collect(),
+                    response_tx,
+                })
+                .await;
+            match tokio::time::timeout(Duration::from_secs(300), response_rx).await {
+                Ok(Ok(answers)) => answers,
+                Ok(Err(_)) => bail!("Authentification keyboard-interactive annulée"),
+                Err(_) => bail!("Authentification keyboard-interactive : délai de réponse dépassé"),
+            }
+        };
+        response = handle
+            .authenticate_keyboard_interactive_respond(answers)
+            .await
+            .context("Erreur lors de l'authentification keyboard-interactive")?;
+    }
+}
+
+/// Accepte les clients d'une redirection locale et relaie chacun dans son
+/// propre canal `direct-tcpip`.
+///
+/// Les relais vivent dans un `JoinSet` : annuler cette tâche les annule tous.
+async fn run_port_forward(
+    handle: Arc<client::Handle<SshClientHandler>>,
+    listener: TcpListener,
+    forward: PortForward,
+) {
+    let mut relays = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    relays.spawn(relay_forward_client(
+                        handle.clone(),
+                        stream,
+                        peer,
+                        forward.clone(),
+                    ));
+                }
+                Err(e) => {
+                    log::warn!("Redirection {forward} : accept impossible : {e}");
+                    return;
+                }
+            },
+            // Récolte les relais terminés pour ne pas les accumuler.
+            Some(_) = relays.join_next(), if !relays.is_empty() => {}
+        }
+    }
+}
+
+/// Relaie un client local dans un canal `direct-tcpip` jusqu'à la fermeture
+/// d'un des deux côtés.
+async fn relay_forward_client(
+    handle: Arc<client::Handle<SshClientHandler>>,
+    mut stream: TcpStream,
+    peer: std::net::SocketAddr,
+    forward: PortForward,
+) {
+    let channel = match handle
+        .channel_open_direct_tcpip(
+            forward.remote_host.as_str(),
+            u32::from(forward.remote_port),
+            peer.ip().to_string(),
+            u32::from(peer.port()),
+        )
+        .await
+    {
+        Ok(channel) => channel,
+        Err(e) => {
+            log::warn!("Redirection {forward} : ouverture du canal refusée : {e}");
+            return;
+        }
+    };
+    let mut remote = channel.into_stream();
+    match tokio::io::copy_bidirectional(&mut stream, &mut remote).await {
+        Ok((up, down)) => {
+            log::info!("Redirection {forward} : client {peer} terminé ({up} ↑ / {down} ↓ octets)");
+        }
+        Err(e) => log::info!("Redirection {forward} : client {peer} coupé : {e}"),
+    }
 }
 
 // =============================================================================
@@ -178,15 +543,24 @@ impl client::Handler for SshClientHandler {
 /// Gestionnaire de connexion SSH implémentant le trait `Connection`.
 pub struct SshManager {
     config: SshConfig,
-    /// Handle russh (connexion TCP + protocole SSH).
-    handle: Option<client::Handle<SshClientHandler>>,
+    /// Handle russh (connexion TCP + protocole SSH), partagé avec les
+    /// tâches de redirection de ports.
+    handle: Option<Arc<client::Handle<SshClientHandler>>>,
     /// Canal de session SSH avec PTY + shell.
     channel: Option<russh::Channel<client::Msg>>,
     state: ConnectionState,
     bytes_sent: u64,
     bytes_received: u64,
+    /// Sortie d'erreur du dernier `read()`, remise via `take_stderr()`.
+    stderr: Vec<u8>,
+    /// Messages du canal reçus pendant un `send()`, rendus par `read()`.
+    pending_msgs: VecDeque<ChannelMsg>,
     /// Canal d'événements injecté par `spawn_connection_actor` avant `connect()`.
     event_tx: Option<async_channel::Sender<ConnectionEvent>>,
+    /// Tâches d'écoute des redirections actives (annulées à la déconnexion).
+    forward_tasks: Vec<(PortForward, JoinHandle<()>)>,
+    /// Session sur le rebond `ProxyJump`, qui porte le tunnel vers l'hôte.
+    jump_handle: Option<client::Handle<SshClientHandler>>,
 }
 
 impl SshManager {
@@ -199,8 +573,109 @@ impl SshManager {
             state: ConnectionState::Disconnected,
             bytes_sent: 0,
             bytes_received: 0,
+            stderr: Vec::new(),
+            pending_msgs: VecDeque::new(),
             event_tx: None,
+            forward_tasks: Vec::new(),
+            jump_handle: None,
+        }
+    }
+
+    /// Ouvre et authentifie la session sur le rebond `jump` (voir
+    /// `jump_auth_method`).
+    async fn open_jump_session(
+        &self,
+        jump: &str,
+        ssh_config: Arc<client::Config>,
+        event_tx: &async_channel::Sender<ConnectionEvent>,
+    ) -> Result<client::Handle<SshClientHandler>> {
+        let (user, host, port) = ssh_config::parse_jump_host(jump)
+            .with_context(|| format!("Rebond SSH invalide : {jump}"))?;
+        let user = user.unwrap_or_else(|| self.config.username.clone());
+        let port = port.unwrap_or(22);
+        log::info!("Connexion SSH au rebond {user}@{host}:{port}...");
+
+        let handler = SshClientHandler {
+            event_tx: event_tx.clone(),
+            host: host.clone(),
+            port,
+        };
+        let mut handle = tokio::time::timeout(
+            Duration::from_secs(self.config.connect_timeout_secs + 2),
+            client::connect(ssh_config, (host.as_str(), port), handler),
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("Timeout de connexion au rebond {host}:{port}"))?
+        .with_context(|| format!("Impossible de joindre le rebond SSH {host}:{port}"))?;
+
+        let method = jump_auth_method(&self.config.auth_method);
+        let auth_result = authenticate(&mut handle, &user, &method, event_tx)
+            .await
+            .with_context(|| format!("Rebond {host}:{port}"))?;
+        if !auth_result.success() {
+            let _ = handle
+                .disconnect(russh::Disconnect::ByApplication, "", "en")
+                .await;
+            bail!(
+                "Authentification SSH échouée sur le rebond {user}@{host}:{port} \
+                 (clé de la connexion ou agent SSH uniquement)"
+            );
         }
+        Ok(handle)
+    }
+
+    /// Transmet un message informatif à l'UI (`ConnectionEvent::Notice`).
+    async fn notify(&self, message: String) {
+        log::info!("{message}");
+        if let Some(event_tx) = &self.event_tx {
+            let _ = event_tx.send(ConnectionEvent::Notice(message)).await;
+        }
+    }
+
+    /// Ouvre les redirections configurées. Un port local indisponible est
+    /// signalé à l'UI sans interrompre la session.
+    async fn start_port_forwards(
+        &mut self,
+        handle: &Arc<client::Handle<SshClientHandler>>,
+        event_tx: &async_channel::Sender<ConnectionEvent>,
+    ) {
+        for forward in &self.config.port_forwards {
+            match TcpListener::bind(("127.0.0.1", forward.local_port)).await {
+                Ok(listener) => {
+                    log::info!("Redirection SSH ouverte : {forward}");
+                    let task =
+                        tokio::spawn(run_port_forward(handle.clone(), listener, forward.clone()));
+                    self.forward_tasks.push((forward.clone(), task));
+                    let _ = event_tx
+                        .send(ConnectionEvent::Notice(format!(
+                            "Redirection ouverte : {forward}"
+                        )))
+                        .await;
+                }
+                Err(e) => {
+                    log::warn!("Redirection {forward} impossible : {e}");
+                    let _ = event_tx
+                        .send(ConnectionEvent::Notice(format!(
+                            "Redirection {forward} impossible : {e}"
+                        )))
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Ferme les écoutes et tous les relais en cours.
+    fn stop_port_forwards(&mut self) {
+        for (forward, task) in self.forward_tasks.drain(..) {
+            task.abort();
+            log::info!("Redirection SSH fermée : {forward}");
+        }
+    }
+}
+
+impl Drop for SshManager {
+    fn drop(&mut self) {
+        self.stop_port_forwards();
     }
 }
 
@@ -232,14 +707,46 @@ impl Connection for SshManager {
         });
 
         let handler = SshClientHandler {
-            event_tx,
+            event_tx: event_tx.clone(),
             host: self.config.host.clone(),
             port: self.config.port,
         };
 
+        // Rebond éventuel : la session vers l'hôte passe dans un canal direct-tcpip.
+        let jump_handle = match &self.config.proxy_jump {
+            Some(jump) => match self
+                .open_jump_session(jump, ssh_config.clone(), &event_tx)
+                .await
+            {
+                Ok(jump_handle) => Some(jump_handle),
+                Err(e) => {
+                    self.state = ConnectionState::Disconnected;
+                    return Err(e);
+                }
+            },
+            None => None,
+        };
+        let connecting = async {
+            match &jump_handle {
+                Some(jump_handle) => {
+                    let tunnel = jump_handle
+                        .channel_open_direct_tcpip(
+                            self.config.host.as_str(),
+                            u32::from(self.config.port),
+                            "127.0.0.1",
+                            0,
+                        )
+                        .await
+                        .context("Le rebond refuse d'ouvrir le tunnel vers l'hôte")?;
+                    client::connect_stream(ssh_config, tunnel.into_stream(), handler).await
+                }
+                None => client::connect(ssh_config, addr.as_str(), handler).await,
+            }
+        };
+
         let mut handle = match tokio::time::timeout(
             Duration::from_secs(self.config.connect_timeout_secs + 2),
-            client::connect(ssh_config, addr.as_str(), handler),
+            connecting,
         )
         .await
         {
@@ -255,25 +762,13 @@ impl Connection for SshManager {
         };
 
         // Authentification
-        let auth_result = match &self.config.auth_method {
-            SshAuthMethod::Password(password) => handle
-                .authenticate_password(&self.config.username, password)
-                .await
-                .context("Erreur lors de l'authentification par mot de passe")?,
-
-            SshAuthMethod::KeyFile {
-                private_key_path,
-                passphrase,
-            } => {
-                let key = keys::load_secret_key(private_key_path, passphrase.as_deref())
-                    .context("Impossible de charger la clé privée SSH")?;
-                let key_with_alg = PrivateKeyWithHashAlg::new(Arc::new(key), Some(HashAlg::Sha256));
-                handle
-                    .authenticate_publickey(&self.config.username, key_with_alg)
-                    .await
-                    .context("Erreur lors de l'authentification par clé publique")?
-            }
-        };
+        let auth_result = authenticate(
+            &mut handle,
+            &self.config.username,
+            &self.config.auth_method,
+            &event_tx,
+        )
+        .await?;
 
         if !auth_result.success() {
             self.state = ConnectionState::Disconnected;
@@ -288,7 +783,6 @@ impl Connection for SshManager {
             );
         }
 
-        // Session interactive avec PTY xterm-256color + shell
         let channel = match handle.channel_open_session().await {
             Ok(c) => c,
             Err(e) => {
@@ -300,47 +794,74 @@ impl Connection for SshManager {
             }
         };
 
-        if let Err(e) = channel
-            .request_pty(
-                true,
-                "xterm-256color",
-                220,
-                50,
-                0,
-                0,
-                &[(Pty::ECHO, 1), (Pty::ICANON, 1)],
-            )
-            .await
-        {
-            self.state = ConnectionState::Disconnected;
-            let _ = channel.close().await;
-            let _ = handle
-                .disconnect(russh::Disconnect::ByApplication, "", "en")
-                .await;
-            return Err(e).context("Impossible d'obtenir un PTY SSH");
-        }
+        if let Some(command) = &self.config.command {
+            // Commande unique : ni PTY ni shell, stdout et stderr restent séparés.
+            if let Err(e) = channel.exec(true, command.as_str()).await {
+                self.state = ConnectionState::Disconnected;
+                let _ = channel.close().await;
+                let _ = handle
+                    .disconnect(russh::Disconnect::ByApplication, "", "en")
+                    .await;
+                return Err(e).context("Impossible d'exécuter la commande SSH");
+            }
+        } else {
+            // Session interactive avec PTY xterm-256color + shell
+            let (cols, rows) = self.config.terminal_size;
+            if let Err(e) = channel
+                .request_pty(
+                    true,
+                    "xterm-256color",
+                    cols,
+                    rows,
+                    0,
+                    0,
+                    &[(Pty::ECHO, 1), (Pty::ICANON, 1)],
+                )
+                .await
+            {
+                self.state = ConnectionState::Disconnected;
+                let _ = channel.close().await;
+                let _ = handle
+                    .disconnect(russh::Disconnect::ByApplication, "", "en")
+                    .await;
+                return Err(e).context("Impossible d'obtenir un PTY SSH");
+            }
 
-        if let Err(e) = channel.request_shell(true).await {
-            self.state = ConnectionState::Disconnected;
-            let _ = channel.close().await;
-            let _ = handle
-                .disconnect(russh::Disconnect::ByApplication, "", "en")
-                .await;
-            return Err(e).context("Impossible de démarrer le shell SSH");
+            if let Err(e) = channel.request_shell(true).await {
+                self.state = ConnectionState::Disconnected;
+                let _ = channel.close().await;
+                let _ = handle
+                    .disconnect(russh::Disconnect::ByApplication, "", "en")
+                    .await;
+                return Err(e).context("Impossible de démarrer le shell SSH");
+            }
         }
 
+        let handle = Arc::new(handle);
+        self.start_port_forwards(&handle, &event_tx).await;
+
+        self.jump_handle = jump_handle;
         self.handle = Some(handle);
         self.channel = Some(channel);
+        self.pending_msgs.clear();
         self.state = ConnectionState::Connected;
         self.bytes_sent = 0;
         self.bytes_received = 0;
 
-        log::info!(
-            "Connecté SSH à {}@{}:{} (PTY xterm-256color + shell)",
-            self.config.username,
-            self.config.host,
-            self.config.port
-        );
+        match &self.config.command {
+            Some(command) => log::info!(
+                "Connecté SSH à {}@{}:{} (commande : {command})",
+                self.config.username,
+                self.config.host,
+                self.config.port
+            ),
+            None => log::info!(
+                "Connecté SSH à {}@{}:{} (PTY xterm-256color + shell)",
+                self.config.username,
+                self.config.host,
+                self.config.port
+            ),
+        }
         Ok(())
     }
 
@@ -355,15 +876,23 @@ impl Connection for SshManager {
             self.config.port
         );
 
+        self.stop_port_forwards();
+
         if let Some(channel) = self.channel.take() {
             let _ = channel.close().await;
         }
+        self.pending_msgs.clear();
 
         if let Some(handle) = self.handle.take() {
             let _ = handle
                 .disconnect(russh::Disconnect::ByApplication, "", "en")
                 .await;
         }
+        if let Some(jump_handle) = self.jump_handle.take() {
+            let _ = jump_handle
+                .disconnect(russh::Disconnect::ByApplication, "", "en")
+                .await;
+        }
 
         self.state = ConnectionState::Disconnected;
         log::info!(
@@ -374,46 +903,149 @@ impl Connection for SshManager {
         Ok(())
     }
 
+    /// Écrit paquet par paquet (découpage à la fenêtre et à la taille de
+    /// paquet fait par russh), `bytes_sent` progressant au fil de l'envoi.
+    ///
+    /// Pendant l'attente de la fenêtre, les messages entrants sont mis de côté
+    /// pour `read()` : sans cela, l'écho distant remplit la file du canal,
+    /// la session russh se bloque et ne traite plus les `WINDOW_ADJUST`.
     async fn send(&mut self, data: &[u8]) -> Result<usize> {
         let channel = self.channel.as_mut().context("Canal SSH non disponible")?;
-        channel.data(data).await.context("Erreur d'écriture SSH")?;
-        self.bytes_sent += data.len() as u64;
-        Ok(data.len())
+        let writer = channel.make_writer();
+        tokio::pin!(writer);
+        let mut written = 0;
+        while written < data.len() {
+            let write = writer.write(&data[written..]);
+            tokio::pin!(write);
+            let n = loop {
+                tokio::select! {
+                    result = &mut write => break result.context("Erreur d'écriture SSH")?,
+                    msg = channel.wait() => match msg {
+                        Some(msg) => self.pending_msgs.push_back(msg),
+                        None => bail!("Canal SSH fermé pendant l'envoi"),
+                    },
+                }
+            };
+            if n == 0 {
+                bail!("Canal SSH fermé pendant l'envoi");
+            }
+            written += n;
+            self.bytes_sent += n as u64;
+        }
+        Ok(written)
     }
 
+    /// Attend le prochain message du canal, sans polling.
+    ///
+    /// `Channel::wait()` est annulable sans perte : quand `select!` privilégie
+    /// une commande UI, le futur est abandonné puis recréé au tour suivant.
     async fn read(&mut self) -> Result<Vec<u8>> {
         let channel = self.channel.as_mut().context("Canal SSH non disponible")?;
 
-        match tokio::time::timeout(Duration::from_millis(10), channel.wait()).await {
-            Ok(Some(ChannelMsg::Data { data })) => {
+        let msg = match self.pending_msgs.pop_front() {
+            Some(msg) => Some(msg),
+            None => channel.wait().await,
+        };
+        match msg {
+            Some(ChannelMsg::Data { data }) => {
                 let len = data.len();
                 self.bytes_received += len as u64;
                 Ok(data.to_vec())
             }
-            Ok(Some(ChannelMsg::ExtendedData { data, .. })) => {
-                // stderr du serveur — on l'affiche également
+            Some(ChannelMsg::ExtendedData { data, ext }) => {
                 let len = data.len();
                 self.bytes_received += len as u64;
+                if ext == SSH_EXTENDED_DATA_STDERR {
+                    self.stderr.extend_from_slice(&data);
+                    return Ok(Vec::new());
+                }
+                // Seul stderr est défini par la RFC 4254 : affiché avec le flux normal.
+                log::warn!("Données étendues SSH de type inattendu {ext} ({len} octets)");
                 Ok(data.to_vec())
             }
-            Ok(Some(ChannelMsg::Eof | ChannelMsg::Close)) => {
+            Some(ChannelMsg::ExitStatus { exit_status }) => {
+                self.notify(format!("Commande terminée (code de sortie {exit_status})"))
+                    .await;
+                Ok(Vec::new())
+            }
+            Some(ChannelMsg::ExitSignal { signal_name, .. }) => {
+                self.notify(format!(
+                    "Commande interrompue par le signal {signal_name:?}"
+                ))
+                .await;
+                Ok(Vec::new())
+            }
+            // Commande unique : le statut de sortie suit la fin du flux, la
+            // session s'arrête à la fermeture du canal.
+            Some(ChannelMsg::Eof) if self.config.command.is_some() => Ok(Vec::new()),
+            Some(ChannelMsg::Eof | ChannelMsg::Close) => {
                 self.state = ConnectionState::Disconnected;
                 log::info!("Canal SSH fermé par le serveur distant");
                 Ok(Vec::new())
             }
-            Ok(Some(ChannelMsg::Success | _)) => {
+            Some(ChannelMsg::Success | _) => {
                 // Messages de contrôle ignorés
                 Ok(Vec::new())
             }
-            Ok(None) => {
+            None => {
                 self.state = ConnectionState::Disconnected;
                 Ok(Vec::new())
             }
-            Err(_) => {
-                // Timeout normal — pas de données disponibles
-                Ok(Vec::new())
+        }
+    }
+
+    /// Ouvre un canal de session sur le handle authentifié et y démarre le
+    /// sous-système "sftp" : pas de seconde connexion ni d'authentification.
+    async fn open_sftp(&mut self) -> Result<SftpHandle> {
+        let handle = self.handle.as_ref().context("Session SSH non disponible")?;
+        let mut channel = handle
+            .channel_open_session()
+            .await
+            .context("Impossible d'ouvrir un canal SFTP")?;
+        channel
+            .request_subsystem(true, "sftp")
+            .await
+            .context("Impossible de demander le sous-système SFTP")?;
+        loop {
+            match channel.wait().await {
+                Some(ChannelMsg::Success) => break,
+                Some(ChannelMsg::Failure) => {
+                    let _ = channel.close().await;
+                    bail!("Le serveur refuse le sous-système SFTP");
+                }
+                Some(ChannelMsg::Eof | ChannelMsg::Close) | None => {
+                    bail!("Canal SFTP fermé par le serveur");
+                }
+                Some(_) => {}
             }
         }
+        log::info!(
+            "Session SFTP ouverte sur {}@{}:{}",
+            self.config.username,
+            self.config.host,
+            self.config.port
+        );
+        Ok(sftp::spawn_sftp_session(channel.into_stream()))
+    }
+
+    /// Transmet la nouvelle taille au serveur (`window-change`) : les
+    /// programmes plein écran (vim, htop…) reçoivent SIGWINCH.
+    async fn resize(&mut self, cols: u32, rows: u32) -> Result<()> {
+        let Some(channel) = self
+            .channel
+            .as_ref()
+            .filter(|_| self.config.command.is_none())
+        else {
+            return Ok(());
+        };
+        channel
+            .window_change(cols, rows, 0, 0)
+            .await
+            .context("Impossible de redimensionner le PTY SSH")
+    }
+
+    fn take_stderr(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.stderr)
     }
 
     fn state(&self) -> ConnectionState {
@@ -425,10 +1057,73 @@ impl Connection for SshManager {
     }
 
     fn description(&self) -> String {
-        format!(
+        let target = format!(
             "{}@{}:{}",
             self.config.username, self.config.host, self.config.port
-        )
+        );
+        match &self.config.command {
+            Some(command) => format!("{target} $ {command}"),
+            None => target,
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let auth = match &self.config.auth_method {
+            SshAuthMethod::Password(_) => "Mot de passe".to_string(),
+            SshAuthMethod::KeyFile {
+                private_key_path, ..
+            } => format!("Clé {private_key_path}"),
+            SshAuthMethod::KeyData { .. } => "Clé collée".to_string(),
+            SshAuthMethod::Agent => "Agent SSH".to_string(),
+            SshAuthMethod::KeyboardInteractive => "Keyboard-interactive".to_string(),
+        };
+        vec![
+            (
+                "Hôte".to_string(),
+                format!("{}:{}", self.config.host, self.config.port),
+            ),
+            ("Utilisateur".to_string(), self.config.username.clone()),
+            ("Authentification".to_string(), auth),
+            (
+                "Délai de connexion".to_string(),
+                format!("{} s", self.config.connect_timeout_secs),
+            ),
+            (
+                "Redirections".to_string(),
+                if self.forward_tasks.is_empty() {
+                    "aucune".to_string()
+                } else {
+                    self.forward_tasks
+                        .iter()
+                        .map(|(forward, _)| forward.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+            ),
+            (
+                "Rebond".to_string(),
+                self.config
+                    .proxy_jump
+                    .clone()
+                    .unwrap_or_else(|| "aucun".to_string()),
+            ),
+            (
+                "Commande".to_string(),
+                self.config
+                    .command
+                    .clone()
+                    .unwrap_or_else(|| "shell interactif".to_string()),
+            ),
+            (
+                "Canal shell".to_string(),
+                if self.channel.is_some() {
+                    "ouvert"
+                } else {
+                    "fermé"
+                }
+                .to_string(),
+            ),
+        ]
     }
 
     fn bytes_sent(&self) -> u64 {
@@ -439,3 +1134,212 @@ impl Connection for SshManager {
         self.bytes_received
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use russh::keys::ssh_key::rand_core::OsRng;
+    use russh::server::{self, Auth, Msg, Session};
+    use russh::{Channel, ChannelId, CryptoVec};
+
+    use super::*;
+
+    /// Serveur d'écho minimal : accepte tout mot de passe et renvoie chaque
+    /// paquet reçu, comme un PTY en écho local.
+    struct EchoServer;
+
+    impl server::Handler for EchoServer {
+        type Error = russh::Error;
+
+        async fn auth_password(
+            &mut self,
+            _user: &str,
+            _password: &str,
+        ) -> Result<Auth, Self::Error> {
+            Ok(Auth::Accept)
+        }
+
+        async fn channel_open_session(
+            &mut self,
+            _channel: Channel<Msg>,
+            _session: &mut Session,
+        ) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+
+        async fn exec_request(
+            &mut self,
+            channel: ChannelId,
+            _data: &[u8],
+            session: &mut Session,
+        ) -> Result<(), Self::Error> {
+            session.channel_success(channel)
+        }
+
+        /// Redirection vers la seule cible connue du serveur de test ; le
+        /// canal accepté est alors servi par l'écho de `data`.
+        async fn channel_open_direct_tcpip(
+            &mut self,
+            _channel: Channel<Msg>,
+            host_to_connect: &str,
+            port_to_connect: u32,
+            _originator_address: &str,
+            _originator_port: u32,
+            _session: &mut Session,
+        ) -> Result<bool, Self::Error> {
+            Ok((host_to_connect, port_to_connect) == ("db.internal", 5432))
+        }
+
+        async fn data(
+            &mut self,
+            channel: ChannelId,
+            data: &[u8],
+            session: &mut Session,
+        ) -> Result<(), Self::Error> {
+            session.data(channel, CryptoVec::from_slice(data))
+        }
+    }
+
+    /// Connecte un `SshManager` (commande unique, sans PTY) à un serveur
+    /// d'écho local.
+    async fn connect_to_echo_server(port_forwards: Vec<PortForward>) -> SshManager {
+        let config = Arc::new(server::Config {
+            keys: vec![keys::PrivateKey::random(&mut OsRng, keys::Algorithm::Ed25519).unwrap()],
+            ..Default::default()
+        });
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let session = server::run_stream(config, socket, EchoServer)
+                .await
+                .unwrap();
+            let _ = session.await;
+        });
+
+        // Clé d'hôte du serveur de test acceptée pour la session uniquement.
+        let (event_tx, event_rx) = async_channel::unbounded();
+        tokio::spawn(async move {
+            while let Ok(event) = event_rx.recv().await {
+                if let ConnectionEvent::HostKeyUnknown { decision_tx, .. } = event {
+                    let _ = decision_tx.send(HostKeyDecision::AcceptOnce);
+                }
+            }
+        });
+
+        let mut manager = SshManager::new(SshConfig {
+            port,
+            username: "test".to_string(),
+            command: Some("cat".to_string()),
+            port_forwards,
+            ..SshConfig::default()
+        });
+        manager.init_event_sender(event_tx);
+        manager.connect().await.unwrap();
+        manager
+    }
+
+    #[tokio::test]
+    async fn large_send_with_unread_echo_completes() {
+        let mut manager = connect_to_echo_server(Vec::new()).await;
+
+        // 16 Mo sans lire l'écho : bien au-delà de la fenêtre du canal et des
+        // messages que russh garde en file avant de bloquer la session.
+        let payload: Vec<u8> = (0..16 * 1024 * 1024)
+            .map(|i| u8::try_from(i % 251).unwrap())
+            .collect();
+        let sent = tokio::time::timeout(Duration::from_secs(30), manager.send(&payload))
+            .await
+            .expect("envoi bloqué en attente de la fenêtre SSH")
+            .unwrap();
+        assert_eq!(sent, payload.len());
+        assert_eq!(manager.bytes_sent, payload.len() as u64);
+
+        let mut echoed = Vec::with_capacity(payload.len());
+        while echoed.len() < payload.len() {
+            assert_eq!(manager.state, ConnectionState::Connected);
+            let data = tokio::time::timeout(Duration::from_secs(30), manager.read())
+                .await
+                .expect("écho incomplet")
+                .unwrap();
+            echoed.extend_from_slice(&data);
+        }
+        assert_eq!(echoed, payload);
+
+        manager.disconnect().await.unwrap();
+    }
+
+    /// Port local libre au moment de l'appel.
+    async fn free_local_port() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    #[test]
+    fn jump_host_never_receives_target_secrets() {
+        let jump = jump_auth_method(&SshAuthMethod::Password("secret".to_string()));
+        assert!(matches!(jump, SshAuthMethod::Agent));
+        let jump = jump_auth_method(&SshAuthMethod::KeyboardInteractive);
+        assert!(matches!(jump, SshAuthMethod::Agent));
+        let key = SshAuthMethod::KeyFile {
+            private_key_path: "/home/test/.ssh/id_ed25519".to_string(),
+            passphrase: None,
+        };
+        assert!(matches!(
+            jump_auth_method(&key),
+            SshAuthMethod::KeyFile { private_key_path, .. } if private_key_path.ends_with("id_ed25519")
+        ));
+    }
+
+    #[tokio::test]
+    async fn port_forward_round_trip() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let allowed = PortForward {
+            local_port: free_local_port().await,
+            remote_host: "db.internal".to_string(),
+            remote_port: 5432,
+        };
+        let refused = PortForward {
+            local_port: free_local_port().await,
+            remote_host: "ailleurs".to_string(),
+            remote_port: 80,
+        };
+        let mut manager = connect_to_echo_server(vec![allowed.clone(), refused.clone()]).await;
+        assert_eq!(manager.forward_tasks.len(), 2);
+
+        // Deux clients simultanés, chacun avec son canal `direct-tcpip`.
+        let mut first = TcpStream::connect(("127.0.0.1", allowed.local_port))
+            .await
+            .unwrap();
+        let mut second = TcpStream::connect(("127.0.0.1", allowed.local_port))
+            .await
+            .unwrap();
+        for (client, message) in [(&mut first, &b"SELECT 1;"[..]), (&mut second, b"\\q")] {
+            client.write_all(message).await.unwrap();
+            let mut echoed = vec![0; message.len()];
+            tokio::time::timeout(Duration::from_secs(5), client.read_exact(&mut echoed))
+                .await
+                .expect("pas de réponse par la redirection")
+                .unwrap();
+            assert_eq!(echoed, message);
+        }
+
+        // Canal refusé par le serveur : le client local est fermé.
+        let mut client = TcpStream::connect(("127.0.0.1", refused.local_port))
+            .await
+            .unwrap();
+        let mut buf = [0; 1];
+        let read = tokio::time::timeout(Duration::from_secs(5), client.read(&mut buf))
+            .await
+            .expect("client d'une redirection refusée laissé ouvert");
+        assert!(matches!(read, Ok(0) | Err(_)));
+
+        // La déconnexion libère le port local.
+        manager.disconnect().await.unwrap();
+        assert!(manager.forward_tasks.is_empty());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        TcpListener::bind(("127.0.0.1", allowed.local_port))
+            .await
+            .expect("port local toujours occupé après la déconnexion");
+    }
+}
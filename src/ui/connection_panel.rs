@@ -3,19 +3,45 @@
 // Rôle    : Panneau de connexion avec onglets Série / SSH
 // =============================================================================
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::accessible::{Property, Relation};
 use gtk4::prelude::*;
 use gtk4::{
-    Box as GtkBox, Button, CheckButton, DropDown, Entry, Label, Notebook, Orientation,
-    PasswordEntry, SpinButton, StringList,
+    gio, Accessible, Box as GtkBox, Button, CheckButton, DropDown, Entry, Label, ListBox,
+    MenuButton, Notebook, Orientation, PasswordEntry, Popover, ScrolledWindow, SpinButton,
+    StringList, TextView, ToggleButton,
 };
 
 use crate::core::serial_manager::list_serial_ports;
 use crate::core::settings::SshFavorite;
+use crate::core::ssh_manager::PortForward;
+
+// =============================================================================
+// Accessibilité
+// =============================================================================
+
+/// Nom lu par les lecteurs d'écran (boutons à icône seule, surtout).
+fn set_accessible_label(widget: &impl IsA<Accessible>, label: &str) {
+    widget.update_property(&[Property::Label(label)]);
+}
+
+/// Associe un champ au label affiché à côté de lui.
+fn set_labelled_by(widget: &impl IsA<Accessible>, label: &Label) {
+    widget.update_relation(&[Relation::LabelledBy(&[label.upcast_ref()])]);
+}
 
 // =============================================================================
 // Panneau de connexion série
 // =============================================================================
 
+/// Entrée du menu des vitesses qui révèle la saisie libre.
+const CUSTOM_BAUD_LABEL: &str = "Autre…";
+
+/// Plage acceptée pour une vitesse saisie librement (bauds).
+const CUSTOM_BAUD_RANGE: std::ops::RangeInclusive<u32> = 1..=4_000_000;
+
 /// Information interne d'un port pour retrouver le nom device à partir de l'index.
 struct PortEntry {
     device: String,
@@ -26,13 +52,27 @@ pub struct SerialPanel {
     pub container: GtkBox,
     pub port_dropdown: DropDown,
     pub baud_dropdown: DropDown,
+    /// Vitesse libre, visible quand « Autre… » est choisi.
+    pub baud_entry: Entry,
     pub databits_dropdown: DropDown,
     pub parity_dropdown: DropDown,
     pub stopbits_dropdown: DropDown,
     pub flowcontrol_dropdown: DropDown,
     pub refresh_button: Button,
+    /// Lignes de contrôle, actives seulement port ouvert.
+    pub dtr_button: ToggleButton,
+    pub rts_button: ToggleButton,
+    /// Envoi d'un break de `break_spin` millisecondes.
+    pub break_button: Button,
+    pub break_spin: SpinButton,
     port_model: StringList,
     port_entries: std::cell::RefCell<Vec<PortEntry>>,
+    /// Vrai pendant l'affichage de l'état réel des lignes (pas une action).
+    syncing_lines: std::cell::Cell<bool>,
+    /// Lignes de contrôle disponibles (port ouvert).
+    lines_available: std::cell::Cell<bool>,
+    /// Lecture seule : aucun signal n'est envoyé au périphérique.
+    read_only: std::cell::Cell<bool>,
 }
 
 impl SerialPanel {
@@ -64,12 +104,46 @@ impl SerialPanel {
         // Vitesse
         let baud_label = Label::new(Some("Vitesse :"));
         let baud_model = StringList::new(&[
-            "9600", "19200", "38400", "57600", "115200", "230400", "460800", "921600",
+            "9600",
+            "19200",
+            "38400",
+            "57600",
+            "115200",
+            "230400",
+            "460800",
+            "921600",
+            CUSTOM_BAUD_LABEL,
         ]);
         let baud_dropdown = DropDown::builder()
             .model(&baud_model)
             .selected(4) // 115200
             .build();
+        let baud_entry = Entry::builder()
+            .placeholder_text("ex. 250000")
+            .input_purpose(gtk4::InputPurpose::Digits)
+            .width_chars(8)
+            .max_width_chars(8)
+            .tooltip_text("Vitesse en bauds (1 à 4 000 000)")
+            .visible(false)
+            .build();
+        {
+            let entry = baud_entry.clone();
+            baud_dropdown.connect_selected_notify(move |dropdown| {
+                let custom = Self::dropdown_text(dropdown).as_deref() == Some(CUSTOM_BAUD_LABEL);
+                entry.set_visible(custom);
+                if custom {
+                    entry.grab_focus();
+                }
+            });
+        }
+        // Validation en direct de la vitesse saisie.
+        baud_entry.connect_changed(|entry| {
+            if parse_custom_baudrate(&entry.text()).is_ok() {
+                entry.remove_css_class("error");
+            } else {
+                entry.add_css_class("error");
+            }
+        });
 
         // Bits de données
         let databits_model = StringList::new(&["5", "6", "7", "8"]);
@@ -96,6 +170,62 @@ impl SerialPanel {
             .selected(0)
             .build();
 
+        // Vidage des tampons (actions win.flush-serial)
+        let flush_menu = gio::Menu::new();
+        flush_menu.append(Some("Vider l'entrée (RX)"), Some("win.flush-serial::input"));
+        flush_menu.append(
+            Some("Vider la sortie (TX)"),
+            Some("win.flush-serial::output"),
+        );
+        flush_menu.append(Some("Vider les deux"), Some("win.flush-serial::all"));
+        let flush_button = MenuButton::builder()
+            .icon_name("edit-clear-symbolic")
+            .menu_model(&flush_menu)
+            .tooltip_text("Vider les tampons série")
+            .build();
+
+        // Signaux envoyés à l'ouverture du port (options du profil série)
+        let on_connect_menu = gio::Menu::new();
+        on_connect_menu.append(
+            Some("Reset DTR/RTS à la connexion"),
+            Some("win.serial-reset-on-connect"),
+        );
+        on_connect_menu.append(
+            Some("Break à la connexion"),
+            Some("win.serial-break-on-connect"),
+        );
+        on_connect_menu.append(
+            Some("Capturer le démarrage"),
+            Some("win.serial-boot-capture"),
+        );
+        let on_connect_button = MenuButton::builder()
+            .icon_name("system-reboot-symbolic")
+            .menu_model(&on_connect_menu)
+            .tooltip_text("Signaux envoyés à l'ouverture du port")
+            .build();
+
+        // Reconnexion automatique : même état que l'option du menu
+        let auto_reconnect_check = CheckButton::builder()
+            .label("Reconnexion auto")
+            .action_name("win.auto-reconnect-serial")
+            .tooltip_text("Rouvrir le port après une coupure inattendue (adaptateur débranché…)")
+            .build();
+
+        // Taille du terminal pour les consoles (getty, htop…)
+        let size_button = Button::builder()
+            .icon_name("view-fullscreen-symbolic")
+            .action_name("win.notify-terminal-size")
+            .tooltip_text("Notifier la taille du terminal (stty rows/cols)")
+            .build();
+
+        set_labelled_by(&port_dropdown, &port_label);
+        set_labelled_by(&baud_dropdown, &baud_label);
+        set_accessible_label(&baud_entry, "Vitesse personnalisée en bauds");
+        set_accessible_label(&refresh_button, "Rafraîchir les ports");
+        set_accessible_label(&flush_button, "Vider les tampons série");
+        set_accessible_label(&on_connect_button, "Signaux à l'ouverture du port");
+        set_accessible_label(&size_button, "Notifier la taille du terminal");
+
         // Layout
         container.append(&port_label);
         container.append(&port_dropdown);
@@ -106,6 +236,7 @@ impl SerialPanel {
 
         container.append(&baud_label);
         container.append(&baud_dropdown);
+        container.append(&baud_entry);
 
         // Paramètres avancés
         let advanced_box = GtkBox::builder()
@@ -116,32 +247,89 @@ impl SerialPanel {
         let db_label = Label::new(Some("Bits:"));
         advanced_box.append(&db_label);
         advanced_box.append(&databits_dropdown);
+        set_labelled_by(&databits_dropdown, &db_label);
 
         let p_label = Label::new(Some("Parité:"));
         advanced_box.append(&p_label);
         advanced_box.append(&parity_dropdown);
+        set_labelled_by(&parity_dropdown, &p_label);
 
         let sb_label = Label::new(Some("Stop:"));
         advanced_box.append(&sb_label);
         advanced_box.append(&stopbits_dropdown);
+        set_labelled_by(&stopbits_dropdown, &sb_label);
 
         let fc_label = Label::new(Some("Flux:"));
         advanced_box.append(&fc_label);
         advanced_box.append(&flowcontrol_dropdown);
+        set_labelled_by(&flowcontrol_dropdown, &fc_label);
 
         container.append(&advanced_box);
 
+        let sep2 = gtk4::Separator::new(Orientation::Vertical);
+        container.append(&sep2);
+        container.append(&flush_button);
+        container.append(&on_connect_button);
+        container.append(&auto_reconnect_check);
+        container.append(&size_button);
+
+        // Lignes de contrôle DTR/RTS (reset, mode bootloader…)
+        let dtr_button = ToggleButton::builder()
+            .label("DTR")
+            .sensitive(false)
+            .tooltip_text("Data Terminal Ready : enfoncé = ligne active")
+            .build();
+        let rts_button = ToggleButton::builder()
+            .label("RTS")
+            .sensitive(false)
+            .tooltip_text("Request To Send : enfoncé = ligne active")
+            .build();
+        set_accessible_label(&dtr_button, "Ligne DTR");
+        set_accessible_label(&rts_button, "Ligne RTS");
+        let lines_box = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .build();
+        lines_box.add_css_class("linked");
+        lines_box.append(&dtr_button);
+        lines_box.append(&rts_button);
+        container.append(&lines_box);
+
+        // Break (délimitation de trame, reset de certains périphériques)
+        let break_button = Button::builder()
+            .label("Break")
+            .tooltip_text("Maintenir une condition de break sur la ligne")
+            .build();
+        let break_spin = SpinButton::with_range(10.0, 5000.0, 10.0);
+        break_spin.set_value(250.0);
+        break_spin.set_tooltip_text(Some("Durée du break (ms)"));
+        set_accessible_label(&break_spin, "Durée du break en millisecondes");
+        let break_box = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .build();
+        break_box.add_css_class("linked");
+        break_box.append(&break_button);
+        break_box.append(&break_spin);
+        container.append(&break_box);
+
         let panel = Self {
             container,
             port_dropdown,
             baud_dropdown,
+            baud_entry,
             databits_dropdown,
             parity_dropdown,
             stopbits_dropdown,
             flowcontrol_dropdown,
             refresh_button,
+            dtr_button,
+            rts_button,
+            break_button,
+            break_spin,
             port_model,
             port_entries: std::cell::RefCell::new(Vec::new()),
+            syncing_lines: std::cell::Cell::new(false),
+            lines_available: std::cell::Cell::new(false),
+            read_only: std::cell::Cell::new(false),
         };
 
         panel.refresh_ports();
@@ -185,6 +373,51 @@ impl SerialPanel {
         log::info!("Ports série rafraîchis : {} trouvé(s)", ports.len());
     }
 
+    /// Durée du break choisie (ms).
+    pub fn break_ms(&self) -> u64 {
+        u64::try_from(self.break_spin.value_as_int()).unwrap_or(250)
+    }
+
+    /// Affiche l'état réel des lignes DTR/RTS ; `None` (port fermé ou
+    /// connexion SSH) désactive les boutons.
+    pub fn set_control_lines(&self, lines: Option<(bool, bool)>) {
+        let (dtr, rts) = lines.unwrap_or((false, false));
+        self.syncing_lines.set(true);
+        self.dtr_button.set_active(dtr);
+        self.rts_button.set_active(rts);
+        self.syncing_lines.set(false);
+        self.lines_available.set(lines.is_some());
+        self.refresh_signal_buttons();
+    }
+
+    /// Désactive les boutons qui agissent sur le périphérique (DTR, RTS,
+    /// break) en lecture seule.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.set(read_only);
+        self.refresh_signal_buttons();
+    }
+
+    fn refresh_signal_buttons(&self) {
+        let read_only = self.read_only.get();
+        let lines = self.lines_available.get() && !read_only;
+        self.dtr_button.set_sensitive(lines);
+        self.rts_button.set_sensitive(lines);
+        self.break_button.set_sensitive(!read_only);
+    }
+
+    /// Vrai si le basculement en cours vient de `set_control_lines`.
+    pub fn is_syncing_lines(&self) -> bool {
+        self.syncing_lines.get()
+    }
+
+    /// Indique si au moins un port série a été détecté.
+    pub fn has_ports(&self) -> bool {
+        self.port_entries
+            .borrow()
+            .iter()
+            .any(|entry| !entry.device.is_empty())
+    }
+
     /// Retourne le port sélectionné (nom device).
     pub fn selected_port(&self) -> Option<String> {
         let idx = self.port_dropdown.selected() as usize;
@@ -208,9 +441,11 @@ impl SerialPanel {
     }
 
     /// Positionne un `DropDown` `StringList` sur une valeur textuelle donnée.
-    fn set_dropdown_by_text(dropdown: &DropDown, value: &str) {
+    ///
+    /// Retourne `false` si la valeur ne figure pas dans la liste.
+    fn set_dropdown_by_text(dropdown: &DropDown, value: &str) -> bool {
         let Some(model) = dropdown.model() else {
-            return;
+            return false;
         };
 
         for idx in 0..model.n_items() {
@@ -222,16 +457,35 @@ impl SerialPanel {
             };
             if string_obj.string() == value {
                 dropdown.set_selected(idx);
-                return;
+                return true;
             }
         }
+        false
     }
 
-    /// Retourne le baudrate sélectionné.
+    /// Retourne le baudrate sélectionné, ou une erreur si la vitesse saisie
+    /// librement est invalide.
+    pub fn checked_baudrate(&self) -> Result<u32, String> {
+        match Self::dropdown_text(&self.baud_dropdown).as_deref() {
+            Some(CUSTOM_BAUD_LABEL) => parse_custom_baudrate(&self.baud_entry.text()),
+            text => Ok(text.and_then(|s| s.parse().ok()).unwrap_or(115_200)),
+        }
+    }
+
+    /// Retourne le baudrate sélectionné (115200 si la saisie libre est invalide).
     pub fn selected_baudrate(&self) -> u32 {
-        Self::dropdown_text(&self.baud_dropdown)
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(115_200)
+        self.checked_baudrate().unwrap_or(115_200)
+    }
+
+    /// Sélectionne une vitesse : préréglage s'il existe, sinon « Autre… »
+    /// avec la valeur dans la saisie libre.
+    fn select_baudrate(&self, baudrate: u32) {
+        let text = baudrate.to_string();
+        if Self::set_dropdown_by_text(&self.baud_dropdown, &text) {
+            return;
+        }
+        self.baud_entry.set_text(&text);
+        Self::set_dropdown_by_text(&self.baud_dropdown, CUSTOM_BAUD_LABEL);
     }
 
     /// Retourne les data bits sélectionnés.
@@ -283,7 +537,7 @@ impl SerialPanel {
         stop_bits: u8,
         flow_control: &str,
     ) {
-        Self::set_dropdown_by_text(&self.baud_dropdown, &baudrate.to_string());
+        self.select_baudrate(baudrate);
         Self::set_dropdown_by_text(&self.databits_dropdown, &data_bits.to_string());
         Self::set_dropdown_by_text(&self.parity_dropdown, parity);
         Self::set_dropdown_by_text(&self.stopbits_dropdown, &stop_bits.to_string());
@@ -291,6 +545,22 @@ impl SerialPanel {
     }
 }
 
+/// Valide une vitesse saisie librement.
+fn parse_custom_baudrate(text: &str) -> Result<u32, String> {
+    text.trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|baud| CUSTOM_BAUD_RANGE.contains(baud))
+        .ok_or_else(|| {
+            format!(
+                "Vitesse invalide « {} » : entier de {} à {} attendu",
+                text.trim(),
+                CUSTOM_BAUD_RANGE.start(),
+                CUSTOM_BAUD_RANGE.end()
+            )
+        })
+}
+
 // =============================================================================
 // Panneau de connexion SSH
 // =============================================================================
@@ -300,14 +570,32 @@ pub struct SshPanel {
     pub container: GtkBox,
     pub favorite_dropdown: DropDown,
     pub add_favorite_button: Button,
+    pub manage_favorites_button: Button,
+    pub delete_favorite_button: Button,
     pub host_entry: Entry,
     pub port_spin: SpinButton,
     pub username_entry: Entry,
     pub password_entry: PasswordEntry,
     pub passphrase_entry: PasswordEntry,
     pub remember_secrets_check: CheckButton,
+    /// Authentification par ssh-agent : désactive les champs de secrets.
+    pub agent_check: CheckButton,
+    /// Authentification keyboard-interactive (2FA), exclusive de l'agent.
+    pub interactive_check: CheckButton,
     pub key_path_entry: Entry,
     pub key_browse_button: Button,
+    /// Alias de ~/.ssh/config proposés à la saisie de l'hôte.
+    host_aliases: Rc<RefCell<Vec<String>>>,
+    /// Délai maximal de connexion (secondes), dans le popover « Avancé ».
+    pub timeout_spin: SpinButton,
+    /// Mode commande unique (popover « Avancé ») et commande à exécuter.
+    pub single_command_check: CheckButton,
+    pub single_command_entry: Entry,
+    /// Clé privée collée (connexion ponctuelle, jamais enregistrée).
+    pasted_key_view: TextView,
+    /// Redirections de ports locales, éditées dans le popover dédié.
+    port_forwards: Rc<RefCell<Vec<PortForward>>>,
+    forward_list: ListBox,
     favorite_model: StringList,
     favorite_entries: std::cell::RefCell<Vec<SshFavorite>>,
 }
@@ -336,14 +624,91 @@ impl SshPanel {
             .icon_name("bookmark-new-symbolic")
             .tooltip_text("Ajouter ce profil aux favoris")
             .build();
+        let delete_favorite_button = Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Supprimer le favori sélectionné")
+            .build();
+        let manage_favorites_button = Button::builder()
+            .icon_name("document-edit-symbolic")
+            .tooltip_text("Gérer les favoris (renommer, dupliquer, supprimer, ordre)")
+            .build();
 
         // Hôte
         let host_label = Label::new(Some("Hôte :"));
         let host_entry = Entry::builder()
             .placeholder_text("192.168.1.1")
             .width_chars(18)
+            .tooltip_text("Adresse, nom d'hôte ou alias de ~/.ssh/config")
             .build();
 
+        // Autocomplétion des alias de ~/.ssh/config
+        let host_aliases: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+        {
+            let alias_list = ListBox::builder()
+                .selection_mode(gtk4::SelectionMode::None)
+                .build();
+            let alias_popover = Popover::builder()
+                .child(&alias_list)
+                .autohide(false)
+                .has_arrow(false)
+                .position(gtk4::PositionType::Bottom)
+                .build();
+            alias_popover.set_parent(&host_entry);
+            {
+                let alias_popover = alias_popover.clone();
+                host_entry.connect_destroy(move |_| alias_popover.unparent());
+            }
+            {
+                let alias_list = alias_list.clone();
+                let alias_popover = alias_popover.clone();
+                let host_aliases = host_aliases.clone();
+                host_entry.connect_changed(move |entry| {
+                    alias_list.remove_all();
+                    let typed = entry.text().trim().to_lowercase();
+                    // Texte modifié par le programme (favori, restauration) : pas de liste.
+                    let typing = entry.state_flags().contains(gtk4::StateFlags::FOCUS_WITHIN);
+                    let matches: Vec<String> = host_aliases
+                        .borrow()
+                        .iter()
+                        .filter(|alias| {
+                            let alias = alias.to_lowercase();
+                            alias.starts_with(&typed) && alias != typed
+                        })
+                        .take(12)
+                        .cloned()
+                        .collect();
+                    if typed.is_empty() || !typing || matches.is_empty() {
+                        alias_popover.popdown();
+                        return;
+                    }
+                    for alias in matches {
+                        let label = Label::builder()
+                            .label(alias)
+                            .xalign(0.0)
+                            .margin_start(6)
+                            .margin_end(6)
+                            .margin_top(3)
+                            .margin_bottom(3)
+                            .build();
+                        alias_list.append(&label);
+                    }
+                    alias_popover.popup();
+                });
+            }
+            {
+                let entry = host_entry.clone();
+                let alias_popover = alias_popover.clone();
+                alias_list.connect_row_activated(move |_, row| {
+                    if let Some(label) = row.child().and_downcast::<Label>() {
+                        entry.set_text(&label.text());
+                        entry.set_position(-1);
+                    }
+                    alias_popover.popdown();
+                });
+            }
+            host_entry.connect_activate(move |_| alias_popover.popdown());
+        }
+
         // Port
         let port_label = Label::new(Some("Port :"));
         let port_spin = SpinButton::with_range(1.0, 65535.0, 1.0);
@@ -387,9 +752,99 @@ impl SshPanel {
             .tooltip_text("Parcourir...")
             .build();
 
+        // Clé collée (popover)
+        let pasted_key_view = TextView::builder()
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::Char)
+            .build();
+        let pasted_key_scroll = ScrolledWindow::builder()
+            .min_content_width(460)
+            .min_content_height(180)
+            .child(&pasted_key_view)
+            .build();
+        let pasted_key_hint = Label::builder()
+            .label(
+                "Collez une clé privée OpenSSH/PEM (prioritaire sur le fichier, non enregistrée) :",
+            )
+            .xalign(0.0)
+            .build();
+        let pasted_key_clear = Button::builder().label("Effacer").build();
+        pasted_key_clear.set_halign(gtk4::Align::End);
+        let pasted_key_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        pasted_key_box.append(&pasted_key_hint);
+        pasted_key_box.append(&pasted_key_scroll);
+        pasted_key_box.append(&pasted_key_clear);
+        let pasted_key_popover = Popover::builder().child(&pasted_key_box).build();
+        let pasted_key_button = MenuButton::builder()
+            .icon_name("edit-paste-symbolic")
+            .popover(&pasted_key_popover)
+            .tooltip_text("Coller une clé privée pour cette connexion")
+            .build();
+        {
+            let buffer = pasted_key_view.buffer();
+            pasted_key_clear.connect_clicked(move |_| buffer.set_text(""));
+        }
+
+        let agent_check = CheckButton::builder()
+            .label("Utiliser ssh-agent")
+            .tooltip_text("S'authentifier avec les clés chargées dans l'agent SSH (SSH_AUTH_SOCK)")
+            .build();
+        let interactive_check = CheckButton::builder()
+            .label("Interactif (2FA)")
+            .tooltip_text(
+                "Authentification keyboard-interactive : chaque question du serveur \
+                 (mot de passe, code OTP…) est posée dans une boîte de dialogue",
+            )
+            .build();
+        {
+            let secret_widgets: Rc<[gtk4::Widget; 6]> = Rc::new([
+                password_entry.clone().upcast(),
+                passphrase_entry.clone().upcast(),
+                remember_secrets_check.clone().upcast(),
+                key_path_entry.clone().upcast(),
+                key_browse_button.clone().upcast(),
+                pasted_key_button.clone().upcast(),
+            ]);
+            // Les deux modes s'excluent et rendent les champs de secrets inutiles.
+            for (check, other) in [
+                (agent_check.clone(), interactive_check.clone()),
+                (interactive_check.clone(), agent_check.clone()),
+            ] {
+                let secret_widgets = secret_widgets.clone();
+                check.connect_toggled(move |check| {
+                    if check.is_active() {
+                        other.set_active(false);
+                    }
+                    let uses_secrets = !check.is_active() && !other.is_active();
+                    for widget in secret_widgets.iter() {
+                        widget.set_sensitive(uses_secrets);
+                    }
+                });
+            }
+        }
+
+        set_labelled_by(&favorite_dropdown, &favorite_label);
+        set_labelled_by(&host_entry, &host_label);
+        set_labelled_by(&port_spin, &port_label);
+        set_labelled_by(&username_entry, &user_label);
+        set_labelled_by(&password_entry, &pass_label);
+        set_labelled_by(&passphrase_entry, &passphrase_label);
+        set_labelled_by(&key_path_entry, &key_label);
+        set_accessible_label(&add_favorite_button, "Ajouter aux favoris");
+        set_accessible_label(&delete_favorite_button, "Supprimer le favori");
+        set_accessible_label(&manage_favorites_button, "Gérer les favoris");
+        set_accessible_label(&key_browse_button, "Parcourir les clés");
+        set_accessible_label(&pasted_key_button, "Coller une clé privée");
+        set_accessible_label(&pasted_key_view, "Clé privée collée");
+
         container.append(&favorite_label);
         container.append(&favorite_dropdown);
         container.append(&add_favorite_button);
+        container.append(&delete_favorite_button);
+        container.append(&manage_favorites_button);
 
         let sep0 = gtk4::Separator::new(Orientation::Vertical);
         container.append(&sep0);
@@ -413,31 +868,214 @@ impl SshPanel {
         container.append(&passphrase_label);
         container.append(&passphrase_entry);
         container.append(&remember_secrets_check);
+        container.append(&agent_check);
+        container.append(&interactive_check);
 
         let sep3 = gtk4::Separator::new(Orientation::Vertical);
         container.append(&sep3);
 
+        // Paramètres avancés (popover)
+        let timeout_spin = SpinButton::with_range(1.0, 300.0, 1.0);
+        timeout_spin.set_value(10.0);
+        timeout_spin.set_tooltip_text(Some(
+            "Délai maximal d'établissement de la connexion (inactivité : ×3)",
+        ));
+        let timeout_row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        let timeout_label = Label::new(Some("Délai de connexion (s) :"));
+        set_labelled_by(&timeout_spin, &timeout_label);
+        timeout_row.append(&timeout_label);
+        timeout_row.append(&timeout_spin);
+
+        // Mode commande unique : `exec` au lieu d'un shell interactif
+        let single_command_check = CheckButton::builder()
+            .label("Exécuter une seule commande")
+            .tooltip_text("Lance la commande sans PTY ni shell ; la session se ferme à sa fin")
+            .build();
+        let single_command_entry = Entry::builder()
+            .placeholder_text("ex: uname -a")
+            .sensitive(false)
+            .width_chars(28)
+            .build();
+        set_accessible_label(&single_command_entry, "Commande à exécuter");
+        {
+            let entry = single_command_entry.clone();
+            single_command_check.connect_toggled(move |check| {
+                entry.set_sensitive(check.is_active());
+                if check.is_active() {
+                    entry.grab_focus();
+                }
+            });
+        }
+        let advanced_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        advanced_box.append(&timeout_row);
+        advanced_box.append(&single_command_check);
+        advanced_box.append(&single_command_entry);
+        let advanced_popover = Popover::builder().child(&advanced_box).build();
+        let advanced_button = MenuButton::builder()
+            .icon_name("preferences-system-symbolic")
+            .popover(&advanced_popover)
+            .tooltip_text("Paramètres SSH avancés")
+            .build();
+        set_accessible_label(&advanced_button, "Paramètres SSH avancés");
+
+        // Redirections de ports (popover)
+        let port_forwards = Rc::new(RefCell::new(Vec::<PortForward>::new()));
+        let forward_list = ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .build();
+        forward_list.add_css_class("boxed-list");
+        forward_list.set_placeholder(Some(&Label::new(Some("Aucune redirection"))));
+        let forward_local_spin = SpinButton::with_range(1.0, 65535.0, 1.0);
+        forward_local_spin.set_value(8080.0);
+        forward_local_spin.set_tooltip_text(Some("Port local (127.0.0.1)"));
+        let forward_host_entry = Entry::builder()
+            .placeholder_text("hôte distant")
+            .text("localhost")
+            .width_chars(14)
+            .build();
+        let forward_remote_spin = SpinButton::with_range(1.0, 65535.0, 1.0);
+        forward_remote_spin.set_value(80.0);
+        forward_remote_spin.set_tooltip_text(Some("Port distant"));
+        let forward_add_button = Button::builder()
+            .icon_name("list-add-symbolic")
+            .tooltip_text("Ajouter la redirection")
+            .build();
+        set_accessible_label(&forward_local_spin, "Port local");
+        set_accessible_label(&forward_host_entry, "Hôte distant");
+        set_accessible_label(&forward_remote_spin, "Port distant");
+        set_accessible_label(&forward_add_button, "Ajouter la redirection");
+        {
+            let forwards = port_forwards.clone();
+            let list = forward_list.clone();
+            let local_spin = forward_local_spin.clone();
+            let host_entry = forward_host_entry.clone();
+            let remote_spin = forward_remote_spin.clone();
+            forward_add_button.connect_clicked(move |_| {
+                let remote_host = host_entry.text().trim().to_string();
+                if remote_host.is_empty() {
+                    host_entry.add_css_class("error");
+                    return;
+                }
+                host_entry.remove_css_class("error");
+                // Range contrainte à 1-65535 → troncature impossible.
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let forward = PortForward {
+                    local_port: local_spin.value() as u16,
+                    remote_host,
+                    remote_port: remote_spin.value() as u16,
+                };
+                {
+                    let mut forwards = forwards.borrow_mut();
+                    // Un port local ne peut écouter qu'une fois : remplacer.
+                    forwards.retain(|f| f.local_port != forward.local_port);
+                    forwards.push(forward);
+                }
+                rebuild_forward_list(&list, &forwards);
+            });
+        }
+        let forward_add_row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        forward_add_row.append(&forward_local_spin);
+        forward_add_row.append(&Label::new(Some("→")));
+        forward_add_row.append(&forward_host_entry);
+        forward_add_row.append(&Label::new(Some(":")));
+        forward_add_row.append(&forward_remote_spin);
+        forward_add_row.append(&forward_add_button);
+        let forward_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        forward_box.append(
+            &Label::builder()
+                .label("Redirections locales, ouvertes à la prochaine connexion :")
+                .xalign(0.0)
+                .build(),
+        );
+        forward_box.append(&forward_list);
+        forward_box.append(&forward_add_row);
+        let forward_popover = Popover::builder().child(&forward_box).build();
+        let forward_button = MenuButton::builder()
+            .icon_name("network-transmit-receive-symbolic")
+            .popover(&forward_popover)
+            .tooltip_text("Redirections de ports (ssh -L)")
+            .build();
+        set_accessible_label(&forward_button, "Redirections de ports");
+
         container.append(&key_label);
         container.append(&key_path_entry);
         container.append(&key_browse_button);
+        container.append(&pasted_key_button);
+        container.append(&forward_button);
+        container.append(&advanced_button);
 
         Self {
             container,
             favorite_dropdown,
             add_favorite_button,
+            manage_favorites_button,
+            delete_favorite_button,
             host_entry,
             port_spin,
             username_entry,
             password_entry,
             passphrase_entry,
             remember_secrets_check,
+            agent_check,
+            interactive_check,
             key_path_entry,
             key_browse_button,
+            host_aliases,
+            timeout_spin,
+            single_command_check,
+            single_command_entry,
+            pasted_key_view,
+            port_forwards,
+            forward_list,
             favorite_model,
             favorite_entries: std::cell::RefCell::new(Vec::new()),
         }
     }
 
+    /// Retourne les redirections de ports configurées.
+    pub fn port_forwards(&self) -> Vec<PortForward> {
+        self.port_forwards.borrow().clone()
+    }
+
+    /// Remplace les redirections de ports affichées.
+    pub fn set_port_forwards(&self, forwards: &[PortForward]) {
+        self.port_forwards.replace(forwards.to_vec());
+        rebuild_forward_list(&self.forward_list, &self.port_forwards);
+    }
+
+    /// Retourne le délai maximal de connexion (secondes).
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn connect_timeout_secs(&self) -> u64 {
+        self.timeout_spin.value() as u64
+    }
+
+    /// Définit le délai maximal de connexion (secondes).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn set_connect_timeout_secs(&self, secs: u64) {
+        self.timeout_spin.set_value(secs as f64);
+    }
+
+    /// Commande à exécuter à la place du shell, si le mode est activé.
+    ///
+    /// `Some("")` signale le mode activé sans commande saisie.
+    pub fn single_command(&self) -> Option<String> {
+        self.single_command_check
+            .is_active()
+            .then(|| self.single_command_entry.text().trim().to_string())
+    }
+
     /// Retourne l'hôte saisi.
     pub fn host(&self) -> String {
         self.host_entry.text().to_string()
@@ -470,6 +1108,19 @@ impl SshPanel {
         self.key_path_entry.text().to_string()
     }
 
+    /// Retourne la clé privée collée (vide si aucune).
+    pub fn pasted_key(&self) -> String {
+        let buffer = self.pasted_key_view.buffer();
+        buffer
+            .text(&buffer.start_iter(), &buffer.end_iter(), false)
+            .to_string()
+    }
+
+    /// Efface la clé privée collée (sécurité UX).
+    pub fn clear_pasted_key(&self) {
+        self.pasted_key_view.buffer().set_text("");
+    }
+
     /// Efface le mot de passe affiché (sécurité UX).
     pub fn clear_password(&self) {
         self.password_entry.set_text("");
@@ -500,6 +1151,36 @@ impl SshPanel {
         self.remember_secrets_check.set_active(enabled);
     }
 
+    /// Retourne si l'authentification passe par ssh-agent.
+    pub fn use_agent(&self) -> bool {
+        self.agent_check.is_active()
+    }
+
+    /// Active/désactive l'authentification par ssh-agent.
+    pub fn set_use_agent(&self, enabled: bool) {
+        self.agent_check.set_active(enabled);
+    }
+
+    /// Définit les alias de ~/.ssh/config proposés en autocomplétion.
+    pub fn set_host_aliases(&self, aliases: Vec<String>) {
+        *self.host_aliases.borrow_mut() = aliases;
+    }
+
+    /// Cible saisie, au format `utilisateur@hôte:port`.
+    pub fn target(&self) -> String {
+        format!("{}@{}:{}", self.username(), self.host(), self.port())
+    }
+
+    /// Retourne si l'authentification est keyboard-interactive (2FA).
+    pub fn use_interactive(&self) -> bool {
+        self.interactive_check.is_active()
+    }
+
+    /// Active/désactive l'authentification keyboard-interactive.
+    pub fn set_use_interactive(&self, enabled: bool) {
+        self.interactive_check.set_active(enabled);
+    }
+
     /// Applique les paramètres SSH à l'UI.
     pub fn apply_settings(&self, host: &str, port: u16, username: &str, key_path: &str) {
         self.host_entry.set_text(host);
@@ -531,6 +1212,36 @@ impl SshPanel {
         let idx = (selected - 1) as usize;
         self.favorite_entries.borrow().get(idx).cloned()
     }
+
+    /// Sélectionne le favori portant ce nom ; retourne `false` s'il est introuvable.
+    pub fn select_favorite(&self, name: &str) -> bool {
+        let Some(idx) = self
+            .favorite_entries
+            .borrow()
+            .iter()
+            .position(|f| f.name == name)
+        else {
+            return false;
+        };
+        let position = u32::try_from(idx + 1).unwrap_or(u32::MAX);
+        if self.favorite_dropdown.selected() == position {
+            // Déjà sélectionné : forcer la notification pour réappliquer le favori.
+            self.favorite_dropdown.notify("selected");
+        } else {
+            self.favorite_dropdown.set_selected(position);
+        }
+        true
+    }
+
+    /// Affiche les notes du favori sélectionné dans l'infobulle du dropdown.
+    pub fn update_favorite_tooltip(&self) {
+        let tooltip = match self.selected_favorite() {
+            Some(f) if !f.notes.is_empty() => format!("{}\n{}", f.name, f.notes),
+            Some(f) => f.name,
+            None => "Choisir un favori SSH".to_string(),
+        };
+        self.favorite_dropdown.set_tooltip_text(Some(&tooltip));
+    }
 }
 
 // =============================================================================
@@ -545,6 +1256,7 @@ pub struct ConnectionPanel {
     pub ssh_panel: SshPanel,
     pub connect_button: Button,
     pub clear_button: Button,
+    connected: std::cell::Cell<bool>,
 }
 
 impl ConnectionPanel {
@@ -579,6 +1291,7 @@ impl ConnectionPanel {
             .label("Se connecter")
             .icon_name("network-wired-symbolic")
             .build();
+        set_accessible_label(&connect_button, "Se connecter");
         connect_button.add_css_class("suggested-action");
 
         let clear_button = Button::builder()
@@ -600,6 +1313,7 @@ impl ConnectionPanel {
             ssh_panel,
             connect_button,
             clear_button,
+            connected: std::cell::Cell::new(false),
         }
     }
 
@@ -622,5 +1336,82 @@ impl ConnectionPanel {
             self.connect_button.remove_css_class("destructive-action");
             self.connect_button.add_css_class("suggested-action");
         }
+        // `set_icon_name` remplace le texte du bouton : nommer l'état explicitement.
+        set_accessible_label(
+            &self.connect_button,
+            if connected {
+                "Se déconnecter"
+            } else {
+                "Se connecter"
+            },
+        );
+        self.connected.set(connected);
+        self.refresh_connect_availability();
+    }
+
+    /// Désactive « Se connecter » sur l'onglet série sans port détecté,
+    /// avec une infobulle expliquant pourquoi.
+    pub fn refresh_connect_availability(&self) {
+        let no_port =
+            !self.connected.get() && self.is_serial_selected() && !self.serial_panel.has_ports();
+        let reason = no_port.then_some(
+            "Aucun port série détecté : branchez l'adaptateur, vérifiez les droits \
+             d'accès puis cliquez sur Rafraîchir",
+        );
+        self.connect_button.set_sensitive(!no_port);
+        self.connect_button.set_tooltip_text(reason);
+        if let Some(reason) = reason {
+            self.connect_button
+                .update_property(&[Property::Description(reason)]);
+        } else {
+            self.connect_button
+                .reset_property(gtk4::AccessibleProperty::Description);
+        }
+    }
+}
+
+/// Reconstruit la liste des redirections, chaque ligne avec son bouton de
+/// suppression.
+fn rebuild_forward_list(list: &ListBox, forwards: &Rc<RefCell<Vec<PortForward>>>) {
+    list.remove_all();
+    for (index, forward) in forwards.borrow().iter().enumerate() {
+        let row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .margin_start(6)
+            .margin_end(6)
+            .margin_top(3)
+            .margin_bottom(3)
+            .build();
+        let label = Label::builder()
+            .label(forward.to_string())
+            .xalign(0.0)
+            .hexpand(true)
+            .build();
+        let remove_button = Button::builder()
+            .icon_name("list-remove-symbolic")
+            .tooltip_text("Supprimer la redirection")
+            .build();
+        remove_button.add_css_class("flat");
+        set_accessible_label(&remove_button, "Supprimer la redirection");
+        {
+            let list = list.clone();
+            let forwards = forwards.clone();
+            remove_button.connect_clicked(move |_| {
+                {
+                    let mut forwards = forwards.borrow_mut();
+                    if index < forwards.len() {
+                        forwards.remove(index);
+                    }
+                }
+                // Différé : la ligne (et ce bouton) est détruite par la reconstruction.
+                let list = list.clone();
+                let forwards = forwards.clone();
+                gtk4::glib::idle_add_local_once(move || rebuild_forward_list(&list, &forwards));
+            });
+        }
+        row.append(&label);
+        row.append(&remove_button);
+        list.append(&row);
     }
 }
@@ -0,0 +1,210 @@
+// =============================================================================
+// Fichier : favorites_dialog.rs
+// Rôle    : Fenêtre de gestion des favoris SSH (renommer, dupliquer,
+//           supprimer, réordonner)
+// =============================================================================
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Entry, Label, ListBox, Orientation, ScrolledWindow};
+
+use crate::core::settings::SshFavorite;
+
+/// Rappel recevant la liste des favoris modifiée.
+type FavoritesCallback = Box<dyn Fn(&[SshFavorite])>;
+
+/// État partagé de la fenêtre : copie de travail des favoris et champs de nom.
+struct FavoritesEditor {
+    list: ListBox,
+    favorites: RefCell<Vec<SshFavorite>>,
+    name_entries: RefCell<Vec<Entry>>,
+    on_change: FavoritesCallback,
+}
+
+impl FavoritesEditor {
+    /// Reporte les noms saisis dans la copie de travail.
+    ///
+    /// Retourne `true` si au moins un nom a changé.
+    fn sync_names(&self) -> bool {
+        let mut changed = false;
+        let mut favorites = self.favorites.borrow_mut();
+        for (favorite, entry) in favorites.iter_mut().zip(self.name_entries.borrow().iter()) {
+            let name = entry.text().trim().to_string();
+            if !name.is_empty() && name != favorite.name {
+                favorite.name = name;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Applique une modification de la liste, notifie l'appelant et reconstruit l'affichage.
+    fn apply(self: &Rc<Self>, op: impl FnOnce(&mut Vec<SshFavorite>)) {
+        self.sync_names();
+        op(&mut self.favorites.borrow_mut());
+        (self.on_change)(&self.favorites.borrow());
+        self.rebuild();
+    }
+
+    /// Reconstruit une ligne par favori.
+    fn rebuild(self: &Rc<Self>) {
+        self.list.remove_all();
+        let favorites = self.favorites.borrow();
+        let last = favorites.len().saturating_sub(1);
+        let mut entries = Vec::with_capacity(favorites.len());
+
+        if favorites.is_empty() {
+            self.list
+                .append(&Label::new(Some("Aucun favori SSH enregistré.")));
+        }
+
+        for (idx, favorite) in favorites.iter().enumerate() {
+            let row = GtkBox::builder()
+                .orientation(Orientation::Horizontal)
+                .spacing(6)
+                .margin_top(4)
+                .margin_bottom(4)
+                .build();
+
+            let name_entry = Entry::builder()
+                .text(favorite.name.as_str())
+                .hexpand(true)
+                .build();
+            let target = Label::new(Some(&format!(
+                "{}@{}:{}",
+                favorite.username, favorite.host, favorite.port
+            )));
+            target.add_css_class("dim-label");
+
+            let up_button = Button::builder()
+                .icon_name("go-up-symbolic")
+                .tooltip_text("Monter")
+                .sensitive(idx > 0)
+                .build();
+            let down_button = Button::builder()
+                .icon_name("go-down-symbolic")
+                .tooltip_text("Descendre")
+                .sensitive(idx < last)
+                .build();
+            let duplicate_button = Button::builder()
+                .icon_name("edit-copy-symbolic")
+                .tooltip_text("Dupliquer")
+                .build();
+            let delete_button = Button::builder()
+                .icon_name("user-trash-symbolic")
+                .tooltip_text("Supprimer")
+                .build();
+            delete_button.add_css_class("destructive-action");
+
+            {
+                let editor = self.clone();
+                up_button.connect_clicked(move |_| editor.apply(|f| f.swap(idx - 1, idx)));
+            }
+            {
+                let editor = self.clone();
+                down_button.connect_clicked(move |_| editor.apply(|f| f.swap(idx, idx + 1)));
+            }
+            {
+                let editor = self.clone();
+                duplicate_button.connect_clicked(move |_| {
+                    editor.apply(|f| {
+                        let mut copy = f[idx].clone();
+                        copy.name = format!("{} (copie)", copy.name);
+                        f.insert(idx + 1, copy);
+                    });
+                });
+            }
+            {
+                let editor = self.clone();
+                delete_button.connect_clicked(move |_| {
+                    editor.apply(|f| {
+                        f.remove(idx);
+                    });
+                });
+            }
+
+            row.append(&name_entry);
+            row.append(&target);
+            row.append(&up_button);
+            row.append(&down_button);
+            row.append(&duplicate_button);
+            row.append(&delete_button);
+            self.list.append(&row);
+            entries.push(name_entry);
+        }
+
+        *self.name_entries.borrow_mut() = entries;
+    }
+}
+
+/// Ouvre la fenêtre de gestion des favoris SSH.
+///
+/// `on_change` reçoit la nouvelle liste après chaque modification (et à la
+/// fermeture si des noms ont été édités) ; l'appelant la persiste.
+pub fn open_favorites_dialog(
+    parent: &impl IsA<gtk4::Window>,
+    favorites: Vec<SshFavorite>,
+    on_change: impl Fn(&[SshFavorite]) + 'static,
+) {
+    let dialog = gtk4::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("Gérer les favoris SSH")
+        .default_width(640)
+        .default_height(400)
+        .build();
+
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let list = ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .build();
+    let scroll = ScrolledWindow::builder().vexpand(true).child(&list).build();
+
+    let editor = Rc::new(FavoritesEditor {
+        list,
+        favorites: RefCell::new(favorites),
+        name_entries: RefCell::new(Vec::new()),
+        on_change: Box::new(on_change),
+    });
+    editor.rebuild();
+
+    let actions = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .halign(gtk4::Align::End)
+        .build();
+    let close_button = Button::builder().label("Fermer").build();
+    actions.append(&close_button);
+
+    content.append(&scroll);
+    content.append(&actions);
+
+    {
+        let dialog = dialog.clone();
+        close_button.connect_clicked(move |_| {
+            dialog.close();
+        });
+    }
+
+    // Les renommages sont validés à la fermeture.
+    dialog.connect_close_request(move |_| {
+        if editor.sync_names() {
+            (editor.on_change)(&editor.favorites.borrow());
+        }
+        // Casse le cycle Rc (lignes → closures → éditeur).
+        editor.list.remove_all();
+        gtk4::glib::Propagation::Proceed
+    });
+
+    dialog.set_child(Some(&content));
+    dialog.present();
+}
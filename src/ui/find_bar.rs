@@ -0,0 +1,375 @@
+// =============================================================================
+// Fichier : find_bar.rs
+// Rôle    : Barre de recherche dans le texte du terminal (Ctrl+F)
+// =============================================================================
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use gtk4::prelude::*;
+use gtk4::{
+    gdk, glib, Box as GtkBox, Button, EventControllerKey, Label, Orientation, PropagationPhase,
+    SearchBar, SearchEntry, TextBuffer, TextTag, TextView, ToggleButton,
+};
+
+/// Nom du tag des occurrences trouvées.
+const MATCH_TAG: &str = "find_match";
+
+/// Nom du tag de l'occurrence sélectionnée.
+const CURRENT_TAG: &str = "find_current";
+
+/// Options de recherche.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FindOptions {
+    /// Respecter la casse.
+    pub case_sensitive: bool,
+    /// Le motif est une expression régulière (sinon, texte littéral).
+    pub regex: bool,
+}
+
+/// Occurrence atteinte : rang (à partir de 1) parmi `total`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FindResult {
+    pub index: usize,
+    pub total: usize,
+}
+
+/// Rappel notifiant le changement du défilement automatique.
+type AutoScrollCallback = Box<dyn Fn(bool)>;
+
+/// Barre de recherche, masquée par défaut au-dessus du terminal.
+pub struct FindBar {
+    pub search_bar: SearchBar,
+    entry: SearchEntry,
+    case_toggle: ToggleButton,
+    regex_toggle: ToggleButton,
+    counter: Label,
+    buffer: TextBuffer,
+    text_view: TextView,
+    auto_scroll_enabled: Rc<Cell<bool>>,
+    /// Début (offset en caractères) de l'occurrence sélectionnée ; la
+    /// recherche suivante repart de là.
+    current: Cell<Option<i32>>,
+    /// Défilement automatique à l'ouverture, rétabli à la fermeture.
+    saved_auto_scroll: Cell<Option<bool>>,
+    on_auto_scroll: RefCell<Option<AutoScrollCallback>>,
+}
+
+impl FindBar {
+    /// Crée la barre pour `text_view` ; une occurrence affichée coupe
+    /// `auto_scroll_enabled` pour que la vue ne revienne pas en bas, jusqu'à
+    /// la fermeture de la barre.
+    pub fn new(text_view: &TextView, auto_scroll_enabled: Rc<Cell<bool>>) -> Rc<Self> {
+        let buffer = text_view.buffer();
+        let tag_table = buffer.tag_table();
+        let match_tag = TextTag::builder()
+            .name(MATCH_TAG)
+            .background("rgba(229, 192, 123, 0.45)")
+            .build();
+        tag_table.add(&match_tag);
+        let current_tag = TextTag::builder()
+            .name(CURRENT_TAG)
+            .background("#E5A000")
+            .foreground("black")
+            .build();
+        tag_table.add(&current_tag);
+
+        let entry = SearchEntry::builder()
+            .placeholder_text("Rechercher dans le terminal")
+            .width_chars(30)
+            .build();
+        let case_toggle = ToggleButton::builder()
+            .label("Aa")
+            .tooltip_text("Respecter la casse")
+            .build();
+        let regex_toggle = ToggleButton::builder()
+            .label(".*")
+            .tooltip_text("Expression régulière")
+            .build();
+        let previous_button = Button::builder()
+            .icon_name("go-up-symbolic")
+            .tooltip_text("Occurrence précédente (Maj+Entrée)")
+            .build();
+        let next_button = Button::builder()
+            .icon_name("go-down-symbolic")
+            .tooltip_text("Occurrence suivante (Entrée)")
+            .build();
+        let counter = Label::new(None);
+        counter.add_css_class("dim-label");
+
+        let row = GtkBox::new(Orientation::Horizontal, 6);
+        row.append(&entry);
+        row.append(&case_toggle);
+        row.append(&regex_toggle);
+        row.append(&previous_button);
+        row.append(&next_button);
+        row.append(&counter);
+
+        let search_bar = SearchBar::builder()
+            .child(&row)
+            .show_close_button(true)
+            .build();
+        search_bar.connect_entry(&entry);
+
+        let bar = Rc::new(Self {
+            search_bar,
+            entry,
+            case_toggle,
+            regex_toggle,
+            counter,
+            buffer,
+            text_view: text_view.clone(),
+            auto_scroll_enabled,
+            current: Cell::new(None),
+            saved_auto_scroll: Cell::new(None),
+            on_auto_scroll: RefCell::new(None),
+        });
+        bar.connect_signals(&previous_button, &next_button);
+        bar
+    }
+
+    fn connect_signals(self: &Rc<Self>, previous_button: &Button, next_button: &Button) {
+        let weak = Rc::downgrade(self);
+        let on = move |action: fn(&Self)| {
+            let weak: Weak<Self> = weak.clone();
+            move || {
+                if let Some(bar) = weak.upgrade() {
+                    action(&bar);
+                }
+            }
+        };
+
+        // Saisie ou changement d'option : la recherche repart du curseur.
+        let restart = on(Self::restart);
+        self.entry.connect_search_changed(move |_| restart());
+        for toggle in [&self.case_toggle, &self.regex_toggle] {
+            let restart = on(Self::restart);
+            toggle.connect_toggled(move |_| restart());
+        }
+
+        let next = on(|bar| bar.step(true));
+        self.entry.connect_activate(move |_| next());
+        let next = on(|bar| bar.step(true));
+        self.entry.connect_next_match(move |_| next());
+        let next = on(|bar| bar.step(true));
+        next_button.connect_clicked(move |_| next());
+        let previous = on(|bar| bar.step(false));
+        self.entry.connect_previous_match(move |_| previous());
+        let previous = on(|bar| bar.step(false));
+        previous_button.connect_clicked(move |_| previous());
+
+        // Maj+Entrée : `activate` ne distingue pas la touche Maj.
+        let key = EventControllerKey::new();
+        key.set_propagation_phase(PropagationPhase::Capture);
+        let previous = on(|bar| bar.step(false));
+        key.connect_key_pressed(move |_, keyval, _, state| {
+            let enter = matches!(keyval, gdk::Key::Return | gdk::Key::KP_Enter);
+            if enter && state.contains(gdk::ModifierType::SHIFT_MASK) {
+                previous();
+                return glib::Propagation::Stop;
+            }
+            glib::Propagation::Proceed
+        });
+        self.entry.add_controller(key);
+
+        // Fermeture (Échap, bouton) : les surlignages disparaissent et le
+        // défilement automatique reprend son état d'avant l'ouverture.
+        let close = on(|bar| {
+            bar.clear_highlight();
+            bar.current.set(None);
+            bar.counter.set_label("");
+            if let Some(previous) = bar.saved_auto_scroll.take() {
+                bar.set_auto_scroll(previous);
+            }
+        });
+        self.search_bar
+            .connect_search_mode_enabled_notify(move |search_bar| {
+                if !search_bar.is_search_mode() {
+                    close();
+                }
+            });
+    }
+
+    /// Ouvre la barre et place le focus dans le champ, texte sélectionné.
+    pub fn show(&self) {
+        if !self.search_bar.is_search_mode() {
+            self.saved_auto_scroll
+                .set(Some(self.auto_scroll_enabled.get()));
+        }
+        self.search_bar.set_search_mode(true);
+        self.entry.grab_focus();
+        self.entry.select_region(0, -1);
+    }
+
+    /// Appelle `on_change(actif)` quand la barre coupe ou rétablit le
+    /// défilement automatique (pour synchroniser la case « arrêt du défilement »).
+    pub fn connect_auto_scroll_changed(&self, on_change: impl Fn(bool) + 'static) {
+        *self.on_auto_scroll.borrow_mut() = Some(Box::new(on_change));
+    }
+
+    fn set_auto_scroll(&self, enabled: bool) {
+        if self.auto_scroll_enabled.replace(enabled) == enabled {
+            return;
+        }
+        if let Some(on_change) = self.on_auto_scroll.borrow().as_ref() {
+            on_change(enabled);
+        }
+    }
+
+    /// Sélectionne l'occurrence de `query` qui suit la précédente (ou le
+    /// curseur), en bouclant, et l'amène à l'écran.
+    ///
+    /// Retourne `None` s'il n'y a aucune occurrence ; une expression
+    /// régulière invalide est une erreur.
+    pub fn find_next(
+        &self,
+        query: &str,
+        options: FindOptions,
+    ) -> Result<Option<FindResult>, regex::Error> {
+        self.find(query, options, true)
+    }
+
+    /// Comme [`Self::find_next`], vers le début du buffer.
+    pub fn find_prev(
+        &self,
+        query: &str,
+        options: FindOptions,
+    ) -> Result<Option<FindResult>, regex::Error> {
+        self.find(query, options, false)
+    }
+
+    fn options(&self) -> FindOptions {
+        FindOptions {
+            case_sensitive: self.case_toggle.is_active(),
+            regex: self.regex_toggle.is_active(),
+        }
+    }
+
+    fn restart(&self) {
+        self.current.set(None);
+        self.step(true);
+    }
+
+    /// Recherche depuis la barre et met le compteur à jour.
+    fn step(&self, forward: bool) {
+        let query = self.entry.text();
+        let result = if forward {
+            self.find_next(&query, self.options())
+        } else {
+            self.find_prev(&query, self.options())
+        };
+        let label = match result {
+            Ok(Some(result)) => format!("{} sur {}", result.index, result.total),
+            Ok(None) if query.is_empty() => String::new(),
+            Ok(None) => "Aucun résultat".to_string(),
+            Err(_) => "Expression invalide".to_string(),
+        };
+        self.counter.set_label(&label);
+    }
+
+    fn find(
+        &self,
+        query: &str,
+        options: FindOptions,
+        forward: bool,
+    ) -> Result<Option<FindResult>, regex::Error> {
+        self.clear_highlight();
+        if query.is_empty() {
+            self.current.set(None);
+            return Ok(None);
+        }
+        let pattern = if options.regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        let re = regex::RegexBuilder::new(&pattern)
+            .case_insensitive(!options.case_sensitive)
+            .build()?;
+
+        // `slice` garde un caractère par position : offsets alignés sur le buffer.
+        let (start, end) = self.buffer.bounds();
+        let matches = char_ranges(&re, &self.buffer.slice(&start, &end, true));
+        if matches.is_empty() {
+            self.current.set(None);
+            return Ok(None);
+        }
+
+        // Sans occurrence précédente, une occurrence au curseur même compte.
+        let (origin, inclusive) = match self.current.get() {
+            Some(offset) => (offset, false),
+            None => (self.buffer.cursor_position(), true),
+        };
+        let index = if forward {
+            matches
+                .iter()
+                .position(|&(s, _)| s > origin || (inclusive && s == origin))
+                .unwrap_or(0)
+        } else {
+            matches
+                .iter()
+                .rposition(|&(s, _)| s < origin || (inclusive && s == origin))
+                .unwrap_or(matches.len() - 1)
+        };
+
+        // Les tags créés ensuite (couleurs étendues) passeraient devant.
+        let tag_table = self.buffer.tag_table();
+        let (Some(match_tag), Some(current_tag)) =
+            (tag_table.lookup(MATCH_TAG), tag_table.lookup(CURRENT_TAG))
+        else {
+            return Ok(None);
+        };
+        match_tag.set_priority(tag_table.size() - 2);
+        current_tag.set_priority(tag_table.size() - 1);
+        for &(s, e) in &matches {
+            self.buffer.apply_tag(
+                &match_tag,
+                &self.buffer.iter_at_offset(s),
+                &self.buffer.iter_at_offset(e),
+            );
+        }
+        let (match_start, match_end) = matches[index];
+        let start = self.buffer.iter_at_offset(match_start);
+        self.buffer
+            .apply_tag(&current_tag, &start, &self.buffer.iter_at_offset(match_end));
+        self.current.set(Some(match_start));
+
+        // Le défilement automatique ramènerait aussitôt la vue en bas.
+        self.set_auto_scroll(false);
+        self.buffer.place_cursor(&start);
+        let mark = self.buffer.create_mark(None, &start, true);
+        self.text_view.scroll_to_mark(&mark, 0.0, true, 0.0, 0.3);
+        self.buffer.delete_mark(&mark);
+
+        Ok(Some(FindResult {
+            index: index + 1,
+            total: matches.len(),
+        }))
+    }
+
+    fn clear_highlight(&self) {
+        let (start, end) = self.buffer.bounds();
+        self.buffer.remove_tag_by_name(MATCH_TAG, &start, &end);
+        self.buffer.remove_tag_by_name(CURRENT_TAG, &start, &end);
+    }
+}
+
+/// Occurrences non vides de `re` dans `text`, en offsets de caractères
+/// `(début, fin)` comme ceux de `TextBuffer`.
+fn char_ranges(re: &regex::Regex, text: &str) -> Vec<(i32, i32)> {
+    let to_offset = |chars: usize| i32::try_from(chars).unwrap_or(i32::MAX);
+    let mut ranges = Vec::new();
+    let mut chars = 0;
+    let mut last = 0;
+    for m in re.find_iter(text) {
+        if m.is_empty() {
+            continue;
+        }
+        chars += text[last..m.start()].chars().count();
+        let len = m.as_str().chars().count();
+        ranges.push((to_offset(chars), to_offset(chars + len)));
+        chars += len;
+        last = m.end();
+    }
+    ranges
+}
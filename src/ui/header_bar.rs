@@ -3,11 +3,18 @@
 // Rôle    : Barre d'en-tête avec menu et actions rapides
 // =============================================================================
 
+use std::cell::Cell;
+use std::time::Duration;
+
+use gtk4::accessible::Property;
 use gtk4::gio;
 use gtk4::prelude::*;
-use gtk4::{Button, Label, MenuButton, PopoverMenu};
+use gtk4::{
+    AccessibleAnnouncementPriority, AccessibleRole, Button, Label, MenuButton, PopoverMenu,
+};
 use libadwaita::HeaderBar;
 
+use crate::core::connection::LatencyStats;
 use crate::ui::theme::Theme;
 
 /// Barre d'en-tête de l'application.
@@ -15,6 +22,20 @@ pub struct AppHeaderBar {
     pub header_bar: HeaderBar,
     pub status_label: Label,
     pub save_log_button: Button,
+    /// Bouton Connecter/Déconnecter visible uniquement en mode compact.
+    pub connect_button: Button,
+    /// Indicateur du mode lecture seule.
+    pub read_only_label: Label,
+    /// Indicateur d'affichage en retard (lecture suspendue par l'UI).
+    pub stalled_label: Label,
+    /// Dernière latence requête/réponse mesurée (masqué sans mesure).
+    pub latency_label: Label,
+    /// Statistiques figées (vue remontée dans l'historique).
+    stats_frozen: Cell<bool>,
+    /// Dernières statistiques reçues pendant le gel, appliquées à la reprise.
+    pending_stalls: Cell<Option<(u64, u64)>>,
+    /// Dernière latence reçue pendant le gel, appliquée à la reprise.
+    pending_latency: Cell<Option<(Duration, LatencyStats)>>,
 }
 
 impl AppHeaderBar {
@@ -22,7 +43,11 @@ impl AppHeaderBar {
         let header_bar = HeaderBar::new();
 
         // Label de statut à gauche
-        let status_label = Label::builder().label("Déconnecté").build();
+        let status_label = Label::builder()
+            .label("Déconnecté")
+            .accessible_role(AccessibleRole::Status)
+            .build();
+        status_label.update_property(&[Property::Description("État de la connexion")]);
         status_label.add_css_class("status-disconnected");
         header_bar.pack_start(&status_label);
 
@@ -31,6 +56,35 @@ impl AppHeaderBar {
             .icon_name("document-save-symbolic")
             .tooltip_text("Sauvegarder les logs")
             .build();
+        save_log_button.update_property(&[Property::Label("Sauvegarder les logs")]);
+
+        // Connexion rapide (mode compact, panneau de connexion masqué)
+        let connect_button = Button::builder()
+            .icon_name("network-wired-symbolic")
+            .tooltip_text("Se connecter")
+            .visible(false)
+            .build();
+        connect_button.update_property(&[Property::Label("Se connecter")]);
+        header_bar.pack_start(&connect_button);
+
+        // Indicateur lecture seule (masqué par défaut)
+        let read_only_label = Label::builder()
+            .label("LECTURE SEULE")
+            .tooltip_text("Aucune donnée ne peut être envoyée")
+            .visible(false)
+            .build();
+        read_only_label.add_css_class("warning");
+        header_bar.pack_start(&read_only_label);
+
+        // Indicateur de retard d'affichage (masqué par défaut)
+        let stalled_label = Label::builder().label("EN RETARD").visible(false).build();
+        stalled_label.add_css_class("warning");
+        header_bar.pack_start(&stalled_label);
+
+        // Latence requête/réponse (masquée tant qu'aucune mesure)
+        let latency_label = Label::builder().visible(false).build();
+        latency_label.add_css_class("numeric");
+        header_bar.pack_start(&latency_label);
 
         // Menu hamburger
         let main_menu = gio::Menu::new();
@@ -49,6 +103,7 @@ impl AppHeaderBar {
         main_menu.append(Some("Outils"), Some("win.open-tools"));
         main_menu.append(Some("Sauvegarder les logs"), Some("win.save-logs"));
         main_menu.append(Some("Effacer le terminal"), Some("win.clear-terminal"));
+        main_menu.append(Some("Mode compact"), Some("win.compact-mode"));
 
         let sep = gio::Menu::new();
         sep.append(Some("À propos"), Some("win.about"));
@@ -60,6 +115,7 @@ impl AppHeaderBar {
             .popover(&popover)
             .tooltip_text("Menu")
             .build();
+        menu_button.update_property(&[Property::Label("Menu principal")]);
 
         header_bar.pack_end(&menu_button);
         header_bar.pack_end(&save_log_button);
@@ -68,11 +124,104 @@ impl AppHeaderBar {
             header_bar,
             status_label,
             save_log_button,
+            connect_button,
+            read_only_label,
+            stalled_label,
+            latency_label,
+            stats_frozen: Cell::new(false),
+            pending_stalls: Cell::new(None),
+            pending_latency: Cell::new(None),
+        }
+    }
+
+    /// Met à jour l'icône du bouton de connexion rapide.
+    pub fn set_connected(&self, connected: bool) {
+        let (icon, label) = if connected {
+            ("network-offline-symbolic", "Se déconnecter")
+        } else {
+            ("network-wired-symbolic", "Se connecter")
+        };
+        self.connect_button.set_icon_name(icon);
+        self.connect_button.set_tooltip_text(Some(label));
+        // Bouton sans texte : le lecteur d'écran lit ce nom, pas l'icône.
+        self.connect_button
+            .update_property(&[Property::Label(label)]);
+    }
+
+    /// Affiche ou masque l'indicateur de lecture seule.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only_label.set_visible(read_only);
+    }
+
+    /// Affiche l'indicateur de retard avec ses compteurs (`None` = masqué).
+    ///
+    /// Pendant un gel, les compteurs sont retenus et affichés à la reprise ;
+    /// le masquage (déconnexion, réception revenue sans retard) s'applique
+    /// toujours immédiatement.
+    pub fn set_read_stalled(&self, stalls: Option<(u64, u64)>) {
+        if self.stats_frozen.get() && stalls.is_some() {
+            self.pending_stalls.set(stalls);
+            return;
+        }
+        self.pending_stalls.set(None);
+        match stalls {
+            Some((count, total_ms)) => {
+                self.stalled_label.set_tooltip_text(Some(&format!(
+                    "L'affichage ne suit pas le débit : lecture suspendue {count} fois \
+                     ({total_ms} ms au total). Aucune donnée perdue."
+                )));
+                self.stalled_label.set_visible(true);
+            }
+            None => self.stalled_label.set_visible(false),
+        }
+    }
+
+    /// Affiche la dernière latence mesurée et le bilan de la session
+    /// (`None` = masqué). Suit le gel des statistiques comme `set_read_stalled`.
+    pub fn set_latency(&self, latency: Option<(Duration, LatencyStats)>) {
+        if self.stats_frozen.get() && latency.is_some() {
+            self.pending_latency.set(latency);
+            return;
+        }
+        self.pending_latency.set(None);
+        match latency {
+            Some((last, stats)) => {
+                self.latency_label
+                    .set_label(&format!("⏱ {}", LatencyStats::format_ms(last)));
+                self.latency_label
+                    .set_tooltip_text(Some(&format!("Latence requête/réponse : {stats}")));
+                self.latency_label.set_visible(true);
+            }
+            None => self.latency_label.set_visible(false),
+        }
+    }
+
+    /// Fige (ou reprend) la mise à jour des statistiques en direct.
+    pub fn set_stats_frozen(&self, frozen: bool) {
+        if self.stats_frozen.replace(frozen) == frozen {
+            return;
+        }
+        if frozen {
+            self.stalled_label.add_css_class("dim-label");
+            self.latency_label.add_css_class("dim-label");
+        } else {
+            self.stalled_label.remove_css_class("dim-label");
+            self.latency_label.remove_css_class("dim-label");
+            if let Some(stalls) = self.pending_stalls.take() {
+                self.set_read_stalled(Some(stalls));
+            }
+            if let Some(latency) = self.pending_latency.take() {
+                self.set_latency(Some(latency));
+            }
         }
     }
 
     /// Met à jour le label de statut.
     pub fn set_status(&self, text: &str, connected: bool) {
+        if self.status_label.label() != text {
+            self.status_label
+                .announce(text, AccessibleAnnouncementPriority::Medium);
+        }
         self.status_label.set_label(text);
         if connected {
             self.status_label.remove_css_class("status-disconnected");
@@ -0,0 +1,204 @@
+// =============================================================================
+// Fichier : highlight_dialog.rs
+// Rôle    : Éditeur des règles de coloration des lignes reçues
+// =============================================================================
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{
+    gdk, Box as GtkBox, Button, ColorDialog, ColorDialogButton, Entry, Label, ListBox, Orientation,
+    ScrolledWindow,
+};
+
+use crate::core::settings::HighlightRule;
+
+/// Ligne de l'éditeur : motif + couleur.
+struct RuleRow {
+    row: GtkBox,
+    pattern_entry: Entry,
+    color_button: ColorDialogButton,
+}
+
+impl RuleRow {
+    /// Convertit la ligne en règle (`None` si le motif est vide).
+    fn to_rule(&self) -> Option<HighlightRule> {
+        let pattern = self.pattern_entry.text().trim().to_string();
+        if pattern.is_empty() {
+            return None;
+        }
+        Some(HighlightRule {
+            pattern,
+            color: rgba_to_hex(&self.color_button.rgba()),
+        })
+    }
+}
+
+/// Ouvre l'éditeur des règles de coloration.
+///
+/// `on_change` reçoit la liste des règles à la fermeture ; l'appelant la
+/// persiste et l'applique au terminal.
+pub fn open_highlight_rules_dialog(
+    parent: &impl IsA<gtk4::Window>,
+    rules: &[HighlightRule],
+    on_change: impl Fn(&[HighlightRule]) + 'static,
+) {
+    let dialog = gtk4::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("Règles de coloration")
+        .default_width(560)
+        .default_height(360)
+        .build();
+
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let hint = Label::builder()
+        .label("Expression régulière → couleur de la ligne (la première règle qui correspond l'emporte).")
+        .xalign(0.0)
+        .wrap(true)
+        .build();
+    hint.add_css_class("dim-label");
+
+    let list = ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .build();
+    let scroll = ScrolledWindow::builder().vexpand(true).child(&list).build();
+
+    let rows: Rc<RefCell<Vec<RuleRow>>> = Rc::new(RefCell::new(Vec::new()));
+    for rule in rules {
+        add_rule_row(&list, &rows, rule);
+    }
+
+    let actions = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    let add_button = Button::builder()
+        .icon_name("list-add-symbolic")
+        .label("Ajouter une règle")
+        .build();
+    let spacer = GtkBox::builder().hexpand(true).build();
+    let close_button = Button::builder().label("Fermer").build();
+    actions.append(&add_button);
+    actions.append(&spacer);
+    actions.append(&close_button);
+
+    content.append(&hint);
+    content.append(&scroll);
+    content.append(&actions);
+
+    {
+        let list = list.clone();
+        let rows = rows.clone();
+        add_button.connect_clicked(move |_| {
+            add_rule_row(
+                &list,
+                &rows,
+                &HighlightRule {
+                    pattern: String::new(),
+                    color: "#F1C40F".to_string(),
+                },
+            );
+        });
+    }
+
+    {
+        let dialog = dialog.clone();
+        close_button.connect_clicked(move |_| {
+            dialog.close();
+        });
+    }
+
+    dialog.connect_close_request(move |_| {
+        let updated: Vec<HighlightRule> =
+            rows.borrow().iter().filter_map(RuleRow::to_rule).collect();
+        on_change(&updated);
+        // Casse le cycle Rc (lignes → closures → liste des lignes).
+        rows.borrow_mut().clear();
+        list.remove_all();
+        gtk4::glib::Propagation::Proceed
+    });
+
+    dialog.set_child(Some(&content));
+    dialog.present();
+}
+
+/// Ajoute une ligne d'édition pour `rule`.
+fn add_rule_row(list: &ListBox, rows: &Rc<RefCell<Vec<RuleRow>>>, rule: &HighlightRule) {
+    let row = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .margin_top(4)
+        .margin_bottom(4)
+        .build();
+
+    let pattern_entry = Entry::builder()
+        .text(rule.pattern.as_str())
+        .placeholder_text("Ex: ERROR|FAIL")
+        .hexpand(true)
+        .build();
+    let color_button = ColorDialogButton::new(Some(ColorDialog::new()));
+    if let Ok(rgba) = gdk::RGBA::parse(rule.color.as_str()) {
+        color_button.set_rgba(&rgba);
+    }
+    let delete_button = Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text("Supprimer")
+        .build();
+
+    // Validation en direct de l'expression régulière.
+    pattern_entry.connect_changed(|entry| {
+        if regex::Regex::new(&entry.text()).is_ok() {
+            entry.remove_css_class("error");
+        } else {
+            entry.add_css_class("error");
+        }
+    });
+
+    {
+        let list = list.clone();
+        let rows = rows.clone();
+        let row = row.downgrade();
+        delete_button.connect_clicked(move |_| {
+            let Some(row) = row.upgrade() else {
+                return;
+            };
+            rows.borrow_mut().retain(|r| r.row != row);
+            if let Some(list_row) = row.parent() {
+                list.remove(&list_row);
+            }
+        });
+    }
+
+    row.append(&pattern_entry);
+    row.append(&color_button);
+    row.append(&delete_button);
+    list.append(&row);
+
+    rows.borrow_mut().push(RuleRow {
+        row,
+        pattern_entry,
+        color_button,
+    });
+}
+
+/// Convertit une couleur GDK en `#RRGGBB`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn rgba_to_hex(rgba: &gdk::RGBA) -> String {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02X}{:02X}{:02X}",
+        channel(rgba.red()),
+        channel(rgba.green()),
+        channel(rgba.blue())
+    )
+}
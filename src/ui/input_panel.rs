@@ -3,8 +3,20 @@
 // Rôle    : Barre de saisie et envoi de commandes
 // =============================================================================
 
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk4::accessible::{Property, Relation};
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, CheckButton, DropDown, Entry, Label, Orientation, StringList};
+use gtk4::{
+    gdk, glib, Box as GtkBox, Button, CheckButton, DropDown, Entry, EventControllerKey, Label,
+    Orientation, Popover, PropagationPhase, StringList,
+};
+
+use crate::core::at_commands;
+
+/// Nombre maximal de complétions AT affichées.
+const MAX_AT_COMPLETIONS: usize = 12;
 
 /// Panneau de saisie en bas de la fenêtre.
 ///
@@ -14,7 +26,16 @@ pub struct InputPanel {
     pub entry: Entry,
     pub send_button: Button,
     pub line_ending_dropdown: DropDown,
+    /// Saisie interprétée en hexadécimal (`48 65 0D`).
+    pub hex_checkbox: CheckButton,
     pub stop_scroll_checkbox: CheckButton,
+    /// Nombre d'envois en attente (masqué quand la file est vide).
+    queue_label: Label,
+    /// Cible de la connexion active, rappelée dans le placeholder.
+    target: std::cell::RefCell<Option<String>>,
+    read_only: std::cell::Cell<bool>,
+    /// Mode AT : majuscules et complétion des commandes par Tab.
+    at_mode: Rc<Cell<bool>>,
 }
 
 impl InputPanel {
@@ -32,14 +53,31 @@ impl InputPanel {
         // Champ de saisie
         let entry = Entry::builder()
             .placeholder_text("Tapez votre commande ici...")
+            .tooltip_text("Astuce : « @5 commande » envoie la commande 5 fois")
             .hexpand(true)
             .build();
         entry.add_css_class("input-entry");
+        entry.update_property(&[
+            Property::Label("Commande à envoyer"),
+            Property::Description("Entrée envoie la commande ; « @5 commande » la répète 5 fois"),
+        ]);
 
         // Sélecteur de fin de ligne
         let le_label = Label::new(Some("Fin :"));
         let line_endings = StringList::new(&["LF (\\n)", "CR (\\r)", "CRLF (\\r\\n)", "Aucun"]);
         let line_ending_dropdown = DropDown::builder().model(&line_endings).selected(0).build();
+        line_ending_dropdown.update_relation(&[Relation::LabelledBy(&[le_label.upcast_ref()])]);
+        line_ending_dropdown
+            .update_property(&[Property::Description("Fin de ligne ajoutée à l'envoi")]);
+
+        // Mode d'envoi hexadécimal
+        let hex_checkbox = CheckButton::builder()
+            .label("Hex")
+            .tooltip_text(
+                "Envoie les octets saisis en hexadécimal (ex. 48 65 0D), sans fin de ligne",
+            )
+            .build();
+        hex_checkbox.update_property(&[Property::Label("Envoi hexadécimal")]);
 
         // Bouton Envoyer
         let send_button = Button::builder()
@@ -57,16 +95,124 @@ impl InputPanel {
         container.append(&entry);
         container.append(&le_label);
         container.append(&line_ending_dropdown);
+        container.append(&hex_checkbox);
+        // Profondeur de la file d'envoi
+        let queue_label = Label::builder()
+            .tooltip_text("Envois en attente de transmission")
+            .visible(false)
+            .build();
+        queue_label.add_css_class("dim-label");
+
         container.append(&stop_scroll_checkbox);
+        container.append(&queue_label);
         container.append(&send_button);
 
+        let at_mode = Rc::new(Cell::new(false));
+        Self::setup_at_mode(&entry, &at_mode);
+
         Self {
             container,
             entry,
             send_button,
             line_ending_dropdown,
+            hex_checkbox,
             stop_scroll_checkbox,
+            queue_label,
+            target: std::cell::RefCell::new(None),
+            read_only: std::cell::Cell::new(false),
+            at_mode,
+        }
+    }
+
+    /// Majuscules automatiques et complétion par Tab en mode AT.
+    fn setup_at_mode(entry: &Entry, at_mode: &Rc<Cell<bool>>) {
+        {
+            let at_mode = at_mode.clone();
+            entry.connect_changed(move |entry| {
+                if !at_mode.get() {
+                    return;
+                }
+                // Différé : la position du curseur n'est à jour qu'après l'insertion.
+                let entry = entry.clone();
+                glib::idle_add_local_once(move || {
+                    let text = entry.text();
+                    let upper = at_commands::uppercase_command(&text);
+                    if upper != text.as_str() {
+                        // Compté sur le texte converti : `ß` devient `SS`.
+                        let position = usize::try_from(entry.position()).unwrap_or(0);
+                        let position = at_commands::uppercase_cursor(&text, position);
+                        entry.set_text(&upper);
+                        entry.set_position(i32::try_from(position).unwrap_or(-1));
+                    }
+                });
+            });
+        }
+
+        let label = Label::builder().xalign(0.0).build();
+        label.add_css_class("monospace");
+        let popover = Popover::builder().child(&label).autohide(true).build();
+        popover.set_parent(entry);
+        {
+            let popover = popover.clone();
+            entry.connect_destroy(move |_| popover.unparent());
+        }
+
+        let key = EventControllerKey::new();
+        key.set_propagation_phase(PropagationPhase::Capture);
+        {
+            let at_mode = at_mode.clone();
+            let entry = entry.clone();
+            key.connect_key_pressed(move |_, keyval, _, _| {
+                if !at_mode.get() || keyval != gdk::Key::Tab {
+                    return glib::Propagation::Proceed;
+                }
+                let candidates = at_commands::completions(&entry.text());
+                if let Some(prefix) = at_commands::common_prefix(&candidates) {
+                    if prefix.len() > entry.text().trim_start().len() {
+                        entry.set_text(prefix);
+                        entry.set_position(-1);
+                    }
+                }
+                if candidates.len() > 1 {
+                    let mut lines: Vec<String> = candidates
+                        .iter()
+                        .take(MAX_AT_COMPLETIONS)
+                        .map(|c| format!("{:<16} {}", c.command, c.description))
+                        .collect();
+                    if candidates.len() > MAX_AT_COMPLETIONS {
+                        lines.push(format!(
+                            "… {} autres",
+                            candidates.len() - MAX_AT_COMPLETIONS
+                        ));
+                    }
+                    label.set_label(&lines.join("\n"));
+                    popover.popup();
+                } else {
+                    popover.popdown();
+                }
+                glib::Propagation::Stop
+            });
         }
+        entry.add_controller(key);
+    }
+
+    /// Active le mode AT (majuscules, complétion par Tab).
+    pub fn set_at_mode(&self, enabled: bool) {
+        self.at_mode.set(enabled);
+        self.entry.set_tooltip_text(Some(if enabled {
+            "Mode AT : Tab complète la commande"
+        } else {
+            "Astuce : « @5 commande » envoie la commande 5 fois"
+        }));
+        if enabled {
+            let text = self.entry.text();
+            self.entry.set_text(&at_commands::uppercase_command(&text));
+        }
+    }
+
+    /// Indique si le mode AT est actif.
+    pub fn at_mode(&self) -> bool {
+        self.at_mode.get()
     }
 
     /// Retourne le texte saisi.
@@ -89,6 +235,42 @@ impl InputPanel {
         }
     }
 
+    /// Indique si la saisie doit être envoyée en hexadécimal.
+    pub fn hex_mode(&self) -> bool {
+        self.hex_checkbox.is_active()
+    }
+
+    /// Désactive la saisie et l'envoi en mode lecture seule.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.entry.set_sensitive(!read_only);
+        self.send_button.set_sensitive(!read_only);
+        self.read_only.set(read_only);
+        self.refresh_placeholder();
+    }
+
+    /// Rappelle la cible des envois (`→ user@hôte:22`) ; `None` hors connexion.
+    pub fn set_target(&self, target: Option<&str>) {
+        self.target.replace(target.map(str::to_string));
+        self.refresh_placeholder();
+    }
+
+    fn refresh_placeholder(&self) {
+        let placeholder = if self.read_only.get() {
+            "Lecture seule — envoi désactivé".to_string()
+        } else if let Some(target) = self.target.borrow().as_deref() {
+            format!("→ {target}")
+        } else {
+            "Tapez votre commande ici...".to_string()
+        };
+        self.entry.set_placeholder_text(Some(&placeholder));
+    }
+
+    /// Affiche le nombre d'envois en attente.
+    pub fn set_queue_depth(&self, depth: usize) {
+        self.queue_label.set_visible(depth > 0);
+        self.queue_label.set_label(&format!("File : {depth}"));
+    }
+
     /// Remet le focus sur le champ de saisie.
     pub fn grab_focus(&self) {
         self.entry.grab_focus();
@@ -0,0 +1,206 @@
+// =============================================================================
+// Fichier : known_hosts_dialog.rs
+// Rôle    : Fenêtre de consultation des hôtes SSH connus (~/.ssh/known_hosts)
+// =============================================================================
+
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{Box as GtkBox, Button, Label, ListBox, Orientation, ScrolledWindow, SearchEntry};
+
+use crate::core::known_hosts::{self, KnownHostEntry};
+
+/// État partagé de la fenêtre.
+struct KnownHostsView {
+    list: ListBox,
+    filter: SearchEntry,
+    status: Label,
+}
+
+impl KnownHostsView {
+    /// Relit le fichier et reconstruit une ligne par entrée correspondant au filtre.
+    fn reload(self: &Rc<Self>) {
+        self.list.remove_all();
+
+        let entries = match known_hosts::list_known_hosts() {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.status.set_label(&format!("Erreur : {e}"));
+                return;
+            }
+        };
+
+        let needle = self.filter.text().to_lowercase();
+        let shown: Vec<&KnownHostEntry> = entries
+            .iter()
+            .filter(|entry| {
+                needle.is_empty()
+                    || entry.hosts.to_lowercase().contains(&needle)
+                    || entry.fingerprint.to_lowercase().contains(&needle)
+            })
+            .collect();
+
+        if shown.is_empty() {
+            self.list.append(&Label::new(Some("Aucun hôte connu.")));
+        }
+        for entry in shown {
+            self.list.append(&self.build_row(entry));
+        }
+
+        let path =
+            known_hosts::known_hosts_path().map_or_else(String::new, |p| p.display().to_string());
+        self.status
+            .set_label(&format!("{} entrée(s) — {path}", entries.len()));
+    }
+
+    /// Ligne : hôtes, type de clé, empreinte et bouton de suppression.
+    fn build_row(self: &Rc<Self>, entry: &KnownHostEntry) -> GtkBox {
+        let row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(8)
+            .margin_top(4)
+            .margin_bottom(4)
+            .build();
+
+        let details = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        let mut title = if entry.hashed {
+            "(nom d'hôte haché)".to_string()
+        } else {
+            entry.hosts.clone()
+        };
+        if let Some(marker) = &entry.marker {
+            title = format!("{marker} {title}");
+        }
+        let host_label = Label::builder()
+            .label(format!("{title}  ·  {}", entry.key_type))
+            .xalign(0.0)
+            .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+            .build();
+        let fingerprint_label = Label::builder()
+            .label(if entry.fingerprint.is_empty() {
+                "Clé illisible"
+            } else {
+                entry.fingerprint.as_str()
+            })
+            .xalign(0.0)
+            .selectable(true)
+            .build();
+        fingerprint_label.add_css_class("dim-label");
+        fingerprint_label.add_css_class("monospace");
+        details.append(&host_label);
+        details.append(&fingerprint_label);
+
+        let delete_button = Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text(format!("Supprimer la ligne {}", entry.line))
+            .valign(gtk4::Align::Center)
+            .build();
+        delete_button.add_css_class("destructive-action");
+        {
+            let view = self.clone();
+            let entry = entry.clone();
+            delete_button.connect_clicked(move |_| {
+                if let Err(e) = known_hosts::remove_known_host(&entry) {
+                    view.status.set_label(&format!("Erreur : {e}"));
+                    return;
+                }
+                view.reload();
+            });
+        }
+
+        row.append(&details);
+        row.append(&delete_button);
+        row
+    }
+}
+
+/// Ouvre la liste des hôtes SSH connus.
+///
+/// Supprimer une entrée permet d'accepter à nouveau la clé d'un serveur
+/// réinstallé (équivalent de `ssh-keygen -R`).
+pub fn open_known_hosts_dialog(parent: &impl IsA<gtk4::Window>) {
+    let dialog = gtk4::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .title("Hôtes SSH connus")
+        .default_width(680)
+        .default_height(420)
+        .build();
+
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let filter = SearchEntry::builder()
+        .placeholder_text("Filtrer par hôte ou empreinte")
+        .build();
+    let list = ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .build();
+    let scroll = ScrolledWindow::builder().vexpand(true).child(&list).build();
+    let status = Label::builder().xalign(0.0).wrap(true).build();
+    status.add_css_class("dim-label");
+
+    let view = Rc::new(KnownHostsView {
+        list,
+        filter,
+        status,
+    });
+    view.reload();
+
+    let actions = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    let reload_button = Button::builder()
+        .icon_name("view-refresh-symbolic")
+        .tooltip_text("Recharger")
+        .build();
+    let spacer = GtkBox::builder().hexpand(true).build();
+    let close_button = Button::builder().label("Fermer").build();
+    actions.append(&reload_button);
+    actions.append(&spacer);
+    actions.append(&close_button);
+
+    content.append(&view.filter);
+    content.append(&scroll);
+    content.append(&view.status);
+    content.append(&actions);
+
+    {
+        // Référence faible : le champ de filtre appartient à la vue.
+        let weak = Rc::downgrade(&view);
+        view.filter.connect_search_changed(move |_| {
+            if let Some(view) = weak.upgrade() {
+                view.reload();
+            }
+        });
+    }
+    {
+        let view = view.clone();
+        reload_button.connect_clicked(move |_| view.reload());
+    }
+    {
+        let dialog = dialog.clone();
+        close_button.connect_clicked(move |_| {
+            dialog.close();
+        });
+    }
+
+    dialog.connect_close_request(move |_| {
+        // Casse le cycle Rc (lignes → closures → vue).
+        view.list.remove_all();
+        gtk4::glib::Propagation::Proceed
+    });
+
+    dialog.set_child(Some(&content));
+    dialog.present();
+}
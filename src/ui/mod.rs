@@ -1,6 +1,11 @@
 pub mod connection_panel;
+pub mod favorites_dialog;
+pub mod find_bar;
 pub mod header_bar;
+pub mod highlight_dialog;
 pub mod input_panel;
+pub mod known_hosts_dialog;
+pub mod sftp_dialog;
 pub mod terminal_panel;
 pub mod theme;
 pub mod tools_dialog;
@@ -0,0 +1,436 @@
+// =============================================================================
+// Fichier : sftp_dialog.rs
+// Rôle    : Explorateur de fichiers distants (SFTP) pour une session SSH
+//
+// La logique SFTP est dans core/sftp.rs ; cette fenêtre envoie des
+// `SftpCommand` et affiche les `SftpEvent` reçus.
+// =============================================================================
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::prelude::*;
+use gtk4::{
+    gio, glib, Box as GtkBox, Button, Entry, FileDialog, Label, ListBox, Orientation, ProgressBar,
+    ScrolledWindow,
+};
+
+use crate::core::sftp::{
+    join_remote, parent_remote, SftpCommand, SftpEntry, SftpEvent, SftpHandle, TransferDirection,
+};
+
+/// État partagé de la fenêtre.
+struct SftpView {
+    window: gtk4::Window,
+    list: ListBox,
+    path_entry: Entry,
+    status: Label,
+    progress: ProgressBar,
+    commands: tokio::sync::mpsc::Sender<SftpCommand>,
+    /// Dossier affiché (chemin canonique renvoyé par le serveur).
+    current_dir: RefCell<String>,
+    /// Dossier de départ du serveur, connu après `Ready`.
+    home: RefCell<String>,
+    entries: RefCell<Vec<SftpEntry>>,
+    /// Appelé à chaque dossier affiché (mémorisation par favori).
+    on_dir_changed: Box<dyn Fn(&str)>,
+}
+
+impl SftpView {
+    /// Envoie une commande ; la session fermée est signalée dans le statut.
+    fn send(&self, command: SftpCommand) {
+        if self.commands.try_send(command).is_err() {
+            self.status
+                .set_label("Session SFTP occupée ou fermée, réessayez.");
+        }
+    }
+
+    fn list_dir(&self, path: &str) {
+        self.status.set_label(&format!("Lecture de {path}…"));
+        self.send(SftpCommand::ListDir(path.to_string()));
+    }
+
+    fn refresh(&self) {
+        let current = self.current_dir.borrow().clone();
+        self.list_dir(&current);
+    }
+
+    /// Traite un événement de la session.
+    #[allow(clippy::cast_precision_loss)]
+    fn handle_event(&self, event: SftpEvent) {
+        match event {
+            SftpEvent::Ready { home } => {
+                // Dossier demandé par l'appelant déjà envoyé : le départ par
+                // défaut n'est utilisé que si rien n'est affiché.
+                if self.current_dir.borrow().is_empty() {
+                    self.list_dir(&home);
+                }
+                self.home.replace(home);
+            }
+            SftpEvent::Listing { path, entries } => {
+                self.show_listing(&path, entries);
+                (self.on_dir_changed)(&path);
+            }
+            SftpEvent::Progress {
+                name,
+                direction,
+                done,
+                total,
+            } => {
+                self.progress.set_visible(true);
+                match total.filter(|&t| t > 0) {
+                    Some(total) => {
+                        self.progress.set_fraction(done as f64 / total as f64);
+                        self.progress.set_text(Some(&format!(
+                            "{} {name} : {} / {}",
+                            direction_label(direction),
+                            format_size(done),
+                            format_size(total)
+                        )));
+                    }
+                    None => {
+                        self.progress.pulse();
+                        self.progress.set_text(Some(&format!(
+                            "{} {name} : {}",
+                            direction_label(direction),
+                            format_size(done)
+                        )));
+                    }
+                }
+            }
+            SftpEvent::TransferDone {
+                name,
+                direction,
+                bytes,
+            } => {
+                self.progress.set_visible(false);
+                self.status.set_label(&format!(
+                    "✓ {name} {} ({})",
+                    match direction {
+                        TransferDirection::Download => "téléchargé",
+                        TransferDirection::Upload => "envoyé",
+                    },
+                    format_size(bytes)
+                ));
+                if direction == TransferDirection::Upload {
+                    self.refresh();
+                }
+            }
+            SftpEvent::Error(message) => {
+                self.progress.set_visible(false);
+                self.status.set_label(&format!("Erreur : {message}"));
+                // Dossier mémorisé disparu : repartir du dossier personnel.
+                let home = self.home.borrow().clone();
+                if self.path_entry.text().is_empty() && !home.is_empty() {
+                    self.list_dir(&home);
+                }
+            }
+            SftpEvent::Closed(reason) => {
+                self.progress.set_visible(false);
+                self.status.set_label(&reason.map_or_else(
+                    || "Session SFTP fermée.".to_string(),
+                    |reason| format!("Session SFTP fermée : {reason}"),
+                ));
+                self.list.set_sensitive(false);
+                self.path_entry.set_sensitive(false);
+            }
+        }
+    }
+
+    /// Reconstruit la liste : dossiers puis fichiers, avec taille et date.
+    fn show_listing(&self, path: &str, entries: Vec<SftpEntry>) {
+        self.current_dir.replace(path.to_string());
+        self.path_entry.set_text(path);
+        self.list.remove_all();
+        for entry in &entries {
+            self.list.append(&build_row(entry));
+        }
+        self.status.set_label(&format!(
+            "{} élément(s) — cliquer un dossier pour l'ouvrir, un fichier pour le télécharger",
+            entries.len()
+        ));
+        self.entries.replace(entries);
+    }
+
+    /// Ligne activée : ouvre le dossier ou propose d'enregistrer le fichier.
+    fn activate(self: &Rc<Self>, index: usize) {
+        let Some(entry) = self.entries.borrow().get(index).cloned() else {
+            return;
+        };
+        let remote = join_remote(&self.current_dir.borrow(), &entry.name);
+        if entry.is_dir {
+            self.list_dir(&remote);
+            return;
+        }
+
+        let dialog = FileDialog::builder()
+            .title("Enregistrer le fichier distant")
+            .initial_name(entry.name.as_str())
+            .build();
+        let view = self.clone();
+        dialog.save(Some(&self.window), gio::Cancellable::NONE, move |result| {
+            let Some(local) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            view.status
+                .set_label(&format!("Téléchargement de {}…", entry.name));
+            view.send(SftpCommand::Download { remote, local });
+        });
+    }
+
+    /// Choisit un fichier local et l'envoie dans le dossier affiché.
+    fn choose_upload(self: &Rc<Self>) {
+        let dialog = FileDialog::builder()
+            .title("Envoyer un fichier sur le serveur")
+            .build();
+        let view = self.clone();
+        dialog.open(Some(&self.window), gio::Cancellable::NONE, move |result| {
+            let Some(local) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            let Some(name) = local.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+                return;
+            };
+            let remote = join_remote(&view.current_dir.borrow(), &name);
+            view.status.set_label(&format!("Envoi de {name}…"));
+            view.send(SftpCommand::Upload { local, remote });
+        });
+    }
+}
+
+/// Ligne : icône, nom, taille et date de modification.
+fn build_row(entry: &SftpEntry) -> GtkBox {
+    let row = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .margin_top(4)
+        .margin_bottom(4)
+        .margin_start(6)
+        .margin_end(6)
+        .build();
+    let icon = gtk4::Image::from_icon_name(if entry.is_dir {
+        "folder-symbolic"
+    } else {
+        "text-x-generic-symbolic"
+    });
+    let name = Label::builder()
+        .label(entry.name.as_str())
+        .xalign(0.0)
+        .hexpand(true)
+        .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+        .build();
+    let size = Label::builder()
+        .label(if entry.is_dir {
+            String::new()
+        } else {
+            entry.size.map(format_size).unwrap_or_default()
+        })
+        .xalign(1.0)
+        .width_chars(10)
+        .build();
+    size.add_css_class("dim-label");
+    let modified = Label::builder()
+        .label(
+            entry
+                .modified
+                .and_then(|secs| chrono::DateTime::from_timestamp(i64::from(secs), 0))
+                .map(|date| {
+                    date.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default(),
+        )
+        .width_chars(16)
+        .build();
+    modified.add_css_class("dim-label");
+
+    row.append(&icon);
+    row.append(&name);
+    row.append(&size);
+    row.append(&modified);
+    row
+}
+
+const fn direction_label(direction: TransferDirection) -> &'static str {
+    match direction {
+        TransferDirection::Download => "Téléchargement",
+        TransferDirection::Upload => "Envoi",
+    }
+}
+
+/// Taille lisible : octets, Kio ou Mio.
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} o")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} Kio", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} Mio", bytes as f64 / 1024.0 / 1024.0)
+    }
+}
+
+/// Ouvre l'explorateur SFTP sur une session déjà lancée.
+///
+/// `start_dir` (dossier mémorisé du favori) est affiché en premier s'il est
+/// renseigné ; `on_dir_changed` reçoit chaque dossier visité. Fermer la
+/// fenêtre ferme le canal SFTP, la session SSH reste ouverte.
+pub fn open_sftp_dialog(
+    parent: &impl IsA<gtk4::Window>,
+    title: &str,
+    handle: SftpHandle,
+    start_dir: Option<String>,
+    on_dir_changed: impl Fn(&str) + 'static,
+) {
+    let dialog = gtk4::Window::builder()
+        .transient_for(parent)
+        .title(format!("Fichiers distants — {title}"))
+        .default_width(720)
+        .default_height(480)
+        .build();
+
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+
+    let up_button = Button::builder()
+        .icon_name("go-up-symbolic")
+        .tooltip_text("Dossier parent")
+        .build();
+    let path_entry = Entry::builder()
+        .hexpand(true)
+        .placeholder_text("Chemin distant")
+        .build();
+    let refresh_button = Button::builder()
+        .icon_name("view-refresh-symbolic")
+        .tooltip_text("Actualiser")
+        .build();
+    let path_row = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    path_row.append(&up_button);
+    path_row.append(&path_entry);
+    path_row.append(&refresh_button);
+
+    let list = ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .build();
+    list.add_css_class("boxed-list");
+    let scroll = ScrolledWindow::builder().vexpand(true).child(&list).build();
+    let progress = ProgressBar::builder()
+        .show_text(true)
+        .visible(false)
+        .build();
+    let status = Label::builder()
+        .label("Ouverture de la session SFTP…")
+        .xalign(0.0)
+        .wrap(true)
+        .build();
+    status.add_css_class("dim-label");
+
+    let actions = GtkBox::builder()
+        .orientation(Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    let upload_button = Button::builder()
+        .label("Envoyer un fichier…")
+        .tooltip_text("Copier un fichier local dans le dossier affiché")
+        .build();
+    let spacer = GtkBox::builder().hexpand(true).build();
+    let close_button = Button::builder().label("Fermer").build();
+    actions.append(&upload_button);
+    actions.append(&spacer);
+    actions.append(&close_button);
+
+    content.append(&path_row);
+    content.append(&scroll);
+    content.append(&progress);
+    content.append(&status);
+    content.append(&actions);
+
+    let SftpHandle { commands, events } = handle;
+    let view = Rc::new(SftpView {
+        window: dialog.clone(),
+        list,
+        path_entry,
+        status,
+        progress,
+        commands,
+        current_dir: RefCell::new(String::new()),
+        home: RefCell::new(String::new()),
+        entries: RefCell::new(Vec::new()),
+        on_dir_changed: Box::new(on_dir_changed),
+    });
+    if let Some(dir) = start_dir.filter(|d| !d.trim().is_empty()) {
+        // Envoyé avant `Ready` : traité dès l'initialisation terminée.
+        view.current_dir.replace(dir.clone());
+        view.list_dir(&dir);
+    }
+
+    {
+        // Référence faible : la tâche ne doit pas garder la vue en vie.
+        let weak = Rc::downgrade(&view);
+        glib::spawn_future_local(async move {
+            while let Ok(event) = events.recv().await {
+                let Some(view) = weak.upgrade() else {
+                    break;
+                };
+                view.handle_event(event);
+            }
+        });
+    }
+    {
+        let weak = Rc::downgrade(&view);
+        view.list.connect_row_activated(move |_, row| {
+            if let (Some(view), Ok(index)) = (weak.upgrade(), usize::try_from(row.index())) {
+                view.activate(index);
+            }
+        });
+    }
+    {
+        let weak = Rc::downgrade(&view);
+        view.path_entry.connect_activate(move |entry| {
+            if let Some(view) = weak.upgrade() {
+                view.list_dir(entry.text().trim());
+            }
+        });
+    }
+    {
+        let view = view.clone();
+        up_button.connect_clicked(move |_| {
+            let parent = parent_remote(&view.current_dir.borrow());
+            view.list_dir(&parent);
+        });
+    }
+    {
+        let view = view.clone();
+        refresh_button.connect_clicked(move |_| view.refresh());
+    }
+    {
+        let view = view.clone();
+        upload_button.connect_clicked(move |_| view.choose_upload());
+    }
+    {
+        let dialog = dialog.clone();
+        close_button.connect_clicked(move |_| {
+            dialog.close();
+        });
+    }
+
+    dialog.connect_close_request(move |_| {
+        // Ferme le canal SFTP ; un transfert en cours se termine d'abord.
+        let _ = view.commands.try_send(SftpCommand::Close);
+        view.list.remove_all();
+        glib::Propagation::Proceed
+    });
+
+    dialog.set_child(Some(&content));
+    dialog.present();
+}
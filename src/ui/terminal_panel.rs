@@ -7,9 +7,174 @@ use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use gtk4::prelude::*;
-use gtk4::{ScrolledWindow, TextBuffer, TextTag, TextTagTable, TextView};
+use gtk4::{glib, ScrolledWindow, TextBuffer, TextIter, TextMark, TextTag, TextTagTable, TextView};
 use vte::{Parser, Perform};
 
+use crate::core::char_width;
+use crate::core::framing::{self, FrameDecoder, FramingMode};
+use crate::core::line_assembler::{LineAssembler, LineDelimiter};
+use crate::core::logger;
+use crate::core::settings::HighlightRule;
+use crate::core::sgr::{self, AnsiColor, SgrAttribute};
+use crate::ui::find_bar::FindBar;
+
+/// Forme du curseur dessiné à la position logique d'écriture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorStyle {
+    None,
+    Block,
+    Underline,
+    Beam,
+}
+
+impl CursorStyle {
+    /// Parse depuis la valeur persistée.
+    pub fn from_str_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "block" | "bloc" => Self::Block,
+            "underline" => Self::Underline,
+            "beam" | "bar" => Self::Beam,
+            _ => Self::None,
+        }
+    }
+
+    /// Identifiant persisté dans les paramètres.
+    pub const fn id(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Block => "block",
+            Self::Underline => "underline",
+            Self::Beam => "beam",
+        }
+    }
+
+    /// Nom d'affichage.
+    pub const fn display_name(&self) -> &str {
+        match self {
+            Self::None => "Masqué",
+            Self::Block => "Bloc",
+            Self::Underline => "Souligné",
+            Self::Beam => "Barre verticale",
+        }
+    }
+
+    /// Liste de tous les styles.
+    pub const fn all() -> &'static [Self] {
+        &[Self::None, Self::Block, Self::Underline, Self::Beam]
+    }
+}
+
+/// Traitement des octets NUL (`\x00`) reçus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NulHandling {
+    /// Supprimés de l'affichage.
+    Strip,
+    /// Affichés en notation caret `^@`.
+    Caret,
+    /// Conservés, représentés par `␀` : un buffer GTK ne peut pas contenir U+0000.
+    PassThrough,
+}
+
+impl NulHandling {
+    /// Parse depuis la valeur persistée.
+    pub fn from_str_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "strip" => Self::Strip,
+            "pass" | "passthrough" => Self::PassThrough,
+            _ => Self::Caret,
+        }
+    }
+
+    /// Identifiant persisté dans les paramètres.
+    pub const fn id(&self) -> &str {
+        match self {
+            Self::Strip => "strip",
+            Self::Caret => "caret",
+            Self::PassThrough => "pass",
+        }
+    }
+
+    /// Nom d'affichage.
+    pub const fn display_name(&self) -> &str {
+        match self {
+            Self::Strip => "Supprimer",
+            Self::Caret => "Notation caret (^@)",
+            Self::PassThrough => "Conserver (␀)",
+        }
+    }
+
+    /// Liste de tous les modes.
+    pub const fn all() -> &'static [Self] {
+        &[Self::Strip, Self::Caret, Self::PassThrough]
+    }
+}
+
+/// Traitement du saut de page (`\x0C`) reçu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormFeedHandling {
+    /// Ignoré (flux binaires où l'octet apparaît par hasard).
+    Ignore,
+    /// Affiché en notation caret `^L`.
+    Literal,
+    /// Efface l'écran visible ; l'ancien contenu reste dans l'historique.
+    Clear,
+}
+
+impl FormFeedHandling {
+    /// Parse depuis la valeur persistée.
+    pub fn from_str_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "literal" => Self::Literal,
+            "clear" => Self::Clear,
+            _ => Self::Ignore,
+        }
+    }
+
+    /// Identifiant persisté dans les paramètres.
+    pub const fn id(&self) -> &str {
+        match self {
+            Self::Ignore => "ignore",
+            Self::Literal => "literal",
+            Self::Clear => "clear",
+        }
+    }
+
+    /// Nom d'affichage.
+    pub const fn display_name(&self) -> &str {
+        match self {
+            Self::Ignore => "Ignorer",
+            Self::Literal => "Afficher (^L)",
+            Self::Clear => "Effacer l'écran",
+        }
+    }
+
+    /// Liste de tous les modes.
+    pub const fn all() -> &'static [Self] {
+        &[Self::Ignore, Self::Literal, Self::Clear]
+    }
+}
+
+/// Hauteur d'écran supposée tant que la vue n'est pas dimensionnée.
+const DEFAULT_SCREEN_ROWS: usize = 24;
+
+/// Délai de stabilisation d'un redimensionnement avant de le signaler.
+const RESIZE_DEBOUNCE_MS: u64 = 150;
+
+/// Marge (pixels) sous laquelle la vue est considérée en bas du terminal.
+const SCROLL_BOTTOM_TOLERANCE: f64 = 4.0;
+
+/// Intervalle de clignotement du curseur.
+const CURSOR_BLINK_MS: u64 = 530;
+
+/// Tag des trames délimitées, affichées en bloc.
+const FRAME_TAG: &str = "frame";
+
+/// Tag de fond des lignes portant un marque-page.
+const BOOKMARK_TAG: &str = "bookmark";
+
+/// Longueur maximale de l'extrait affiché dans la liste des marque-pages.
+const BOOKMARK_SNIPPET_CHARS: usize = 80;
+
 /// Panneau d'affichage du terminal.
 ///
 /// Contient un `TextView` en lecture seule avec auto-scroll et gestion
@@ -18,22 +183,124 @@ pub struct TerminalPanel {
     pub container: ScrolledWindow,
     pub text_view: TextView,
     pub buffer: TextBuffer,
-    pub max_lines: u32,
+    max_lines: Cell<u32>,
     auto_scroll_enabled: Rc<Cell<bool>>,
+    /// Capture du démarrage : ni défilement ni coupe du scrollback, pour
+    /// que les premiers messages restent à l'écran.
+    capture_hold: Cell<bool>,
+    tx_timestamps_enabled: Cell<bool>,
+    /// Affiche les octets de contrôle en notation caret (`^C`, `^[`…) au lieu de les interpréter.
+    control_chars_visible: Cell<bool>,
+    /// Règles de coloration compilées et leur tag (`hl_<n>`).
+    highlight_rules: RefCell<Vec<(regex::Regex, TextTag)>>,
+    /// Flux reçu → texte avant le parseur ANSI : les octets invalides y
+    /// deviennent des marqueurs `\xNN`, conservés dans les journaux.
+    utf8_decoder: RefCell<logger::StreamDecoder>,
+    /// Décodage de trame (SLIP, COBS…) : hors `None`, chaque trame est
+    /// affichée en bloc hex/ASCII au lieu du flux terminal.
+    frame_decoder: RefCell<FrameDecoder>,
+    /// Numéro de la prochaine trame affichée (depuis la connexion).
+    frame_count: Cell<u64>,
+    /// Vue hexadécimale : les octets reçus sont affichés en vidage hex/ASCII.
+    hex_view: Cell<bool>,
+    /// Offset du prochain octet reçu en vue hexadécimale (depuis la connexion).
+    hex_offset: Cell<u64>,
     ansi_parser: Rc<RefCell<Parser>>,
     ansi_performer: Rc<RefCell<AnsiPerformer>>,
+    /// Curseur dessiné en surimpression du `TextView` (le curseur GTK,
+    /// lié au focus et à la sélection, reste masqué).
+    cursor_widget: gtk4::Box,
+    cursor_style: Rc<Cell<CursorStyle>>,
+    cursor_blink: Rc<Cell<bool>>,
+    /// Marque-pages : une marque (gravité gauche) au début de chaque ligne marquée.
+    bookmarks: RefCell<Vec<TextMark>>,
+    /// Barre de recherche (Ctrl+F), à placer au-dessus de `container`.
+    pub find_bar: Rc<FindBar>,
 }
 
 struct AnsiPerformer {
     buffer: TextBuffer,
     pending_text: String,
-    current_fg: Option<u8>,
-    current_bg: Option<u8>,
+    current_fg: Option<AnsiColor>,
+    current_bg: Option<AnsiColor>,
     bold: bool,
     italic: bool,
     underline: bool,
+    /// Recul du curseur logique par rapport à la fin du buffer (en caractères,
+    /// sauts de ligne compris). Les prochains caractères imprimés écrasent le
+    /// texte de la ligne du curseur.
+    cursor_back: usize,
+    /// Cible de l'hyperlien OSC 8 en cours (`None` hors lien).
+    current_link: Option<String>,
+    /// Largeur des taquets de tabulation ; 0 = `\t` inséré tel quel.
+    tab_width: usize,
+    /// Rendu des octets NUL.
+    nul_handling: NulHandling,
+    /// Rendu du saut de page.
+    form_feed: FormFeedHandling,
+    /// Lignes visibles : l'« écran » adressé par les séquences de curseur est
+    /// formé des `screen_rows` dernières lignes du buffer.
+    screen_rows: usize,
+    /// Horodatage `[HH:MM:SS.mmm]` devant chaque ligne reçue.
+    rx_timestamps: bool,
+    /// Découpage en lignes du texte sorti du parseur : une ligne du buffer
+    /// = une ligne logique reçue.
+    lines: LineAssembler,
+}
+
+/// Préfixe des tags d'hyperlien : `link:<url>`.
+const LINK_TAG_PREFIX: &str = "link:";
+
+/// Indique si un lien reçu du distant peut être ouvert : seuls `http` et
+/// `https` le sont (pas de `file:`, `smb:` ni de gestionnaire personnalisé).
+fn is_openable_link(url: &str) -> bool {
+    url.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https")
+    })
+}
+
+/// Caractères de ponctuation considérés comme faisant partie d'un « mot »
+/// au double-clic (chemins, noms d'hôte, URL).
+const WORD_EXTRA_CHARS: &str = "/.-_~:@";
+
+/// Indique si `c` fait partie d'un mot sélectionnable par double-clic.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || WORD_EXTRA_CHARS.contains(c)
+}
+
+/// Notation caret d'un caractère de contrôle C0 ou DEL (`\x03` → `^C`, `\x1b` → `^[`).
+fn caret_notation(c: char) -> Option<String> {
+    match c {
+        '\x7f' => Some("^?".to_string()),
+        '\x00'..='\x1f' => u8::try_from(c)
+            .ok()
+            .map(|b| format!("^{}", char::from(b + 0x40))),
+        _ => None,
+    }
+}
+
+/// Paramètre `index` d'une séquence CSI ; absent ou nul, il vaut `default`.
+fn csi_param(params: &vte::Params, index: usize, default: u16) -> u16 {
+    params
+        .iter()
+        .nth(index)
+        .and_then(|param| param.first().copied())
+        .filter(|&value| value != 0)
+        .unwrap_or(default)
 }
 
+/// Fin de la ligne de `iter`, avant le saut de ligne.
+fn line_end(iter: TextIter) -> TextIter {
+    let mut end = iter;
+    if !end.ends_line() {
+        end.forward_to_line_end();
+    }
+    end
+}
+
+/// Longueur maximale acceptée pour une URL OSC 8 (au-delà, la séquence est ignorée).
+const MAX_LINK_LEN: usize = 2048;
+
 impl AnsiPerformer {
     const fn new(buffer: TextBuffer) -> Self {
         Self {
@@ -44,22 +311,34 @@ impl AnsiPerformer {
             bold: false,
             italic: false,
             underline: false,
+            cursor_back: 0,
+            current_link: None,
+            tab_width: 0,
+            nul_handling: NulHandling::Caret,
+            form_feed: FormFeedHandling::Ignore,
+            screen_rows: DEFAULT_SCREEN_ROWS,
+            rx_timestamps: false,
+            lines: LineAssembler::new(LineDelimiter::Lf),
         }
     }
 
+    /// Replace le curseur logique en fin de buffer (texte ajouté hors parseur).
+    fn reset_cursor(&mut self) {
+        self.cursor_back = 0;
+    }
+
     fn flush(&mut self) {
         if self.pending_text.is_empty() {
             return;
         }
 
-        let mut end_iter = self.buffer.end_iter();
         let mut tag_names = Vec::new();
 
         if let Some(fg) = self.current_fg {
-            tag_names.push(format!("fg_{fg}"));
+            tag_names.push(self.color_tag_name(fg, false));
         }
         if let Some(bg) = self.current_bg {
-            tag_names.push(format!("bg_{bg}"));
+            tag_names.push(self.color_tag_name(bg, true));
         }
         if self.bold {
             tag_names.push("bold".to_string());
@@ -71,91 +350,436 @@ impl AnsiPerformer {
             tag_names.push("underline".to_string());
         }
 
+        let tag_table = self.buffer.tag_table();
+        if let Some(url) = &self.current_link {
+            let name = format!("{LINK_TAG_PREFIX}{url}");
+            if tag_table.lookup(&name).is_none() {
+                let link_tag = TextTag::builder()
+                    .name(name.as_str())
+                    .foreground("#5C9CFF")
+                    .underline(gtk4::pango::Underline::Single)
+                    .build();
+                tag_table.add(&link_tag);
+            }
+            tag_names.push(name);
+        }
+
+        let mut insert_iter = self.cursor_iter();
+        if self.cursor_back > 0 {
+            // Mode écrasement : remplacer les caractères sous le curseur, sans
+            // déborder sur la ligne suivante.
+            let under =
+                usize::try_from(line_end(insert_iter).offset() - insert_iter.offset()).unwrap_or(0);
+            let overwrite = self.pending_text.chars().count().min(under);
+            let mut end = insert_iter;
+            end.forward_chars(i32::try_from(overwrite).unwrap_or(i32::MAX));
+            self.buffer.delete(&mut insert_iter, &mut end);
+            self.cursor_back -= overwrite;
+        }
+
         if tag_names.is_empty() {
-            self.buffer.insert(&mut end_iter, &self.pending_text);
+            self.buffer.insert(&mut insert_iter, &self.pending_text);
         } else {
-            let tag_table = self.buffer.tag_table();
             let tags: Vec<TextTag> = tag_names
                 .iter()
                 .filter_map(|name| tag_table.lookup(name))
                 .collect();
             let tags_refs: Vec<&TextTag> = tags.iter().collect();
             self.buffer
-                .insert_with_tags(&mut end_iter, &self.pending_text, &tags_refs);
+                .insert_with_tags(&mut insert_iter, &self.pending_text, &tags_refs);
         }
 
         self.pending_text.clear();
     }
-}
 
-impl Perform for AnsiPerformer {
-    fn print(&mut self, c: char) {
+    /// Nom du tag de premier plan (ou de fond) de `color`, créé au premier
+    /// usage pour les couleurs hors des 16 de base.
+    fn color_tag_name(&self, color: AnsiColor, background: bool) -> String {
+        let prefix = if background { "bg" } else { "fg" };
+        let rgb = match (color, color.extended_rgb()) {
+            (_, Some(rgb)) => rgb,
+            (AnsiColor::Indexed(index), None) => return format!("{prefix}_{index}"),
+            (AnsiColor::Rgb(rgb), None) => rgb,
+        };
+        let hex = format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2]);
+        let name = format!("{prefix}_{hex}");
+        let tag_table = self.buffer.tag_table();
+        if tag_table.lookup(&name).is_none() {
+            let builder = TextTag::builder().name(name.as_str());
+            let tag = if background {
+                builder.background(hex.as_str())
+            } else {
+                builder.foreground(hex.as_str())
+            }
+            .build();
+            tag_table.add(&tag);
+        }
+        name
+    }
+
+    /// Position du curseur logique dans le buffer.
+    fn cursor_iter(&self) -> TextIter {
+        let mut iter = self.buffer.end_iter();
+        iter.backward_chars(i32::try_from(self.cursor_back).unwrap_or(i32::MAX));
+        iter
+    }
+
+    /// Ligne du buffer affichée en haut de l'écran.
+    fn screen_top(&self) -> i32 {
+        let rows = i32::try_from(self.screen_rows).unwrap_or(i32::MAX);
+        (self.buffer.end_iter().line() + 1 - rows).max(0)
+    }
+
+    /// Colonne affichée du curseur : un caractère large en occupe deux.
+    fn cursor_column(&self) -> usize {
+        let cursor = self.cursor_iter();
+        let mut line_start = cursor;
+        line_start.set_line_offset(0);
+        char_width::str_width(&self.buffer.text(&line_start, &cursor, false))
+    }
+
+    /// Place le curseur en (`line`, `column`) du buffer, en complétant par
+    /// des lignes vides ou des espaces si la position n'existe pas encore.
+    ///
+    /// `column` est une colonne affichée (`char_width`), convertie en
+    /// caractères d'après le texte de la ligne.
+    fn move_to(&mut self, line: i32, column: usize) {
+        self.flush();
+        let last = self.buffer.end_iter().line();
+        if line > last {
+            let newlines = usize::try_from(line - last).unwrap_or(0);
+            self.buffer
+                .insert(&mut self.buffer.end_iter(), &"\n".repeat(newlines));
+        }
+        let start = self
+            .buffer
+            .iter_at_line(line.max(0))
+            .unwrap_or_else(|| self.buffer.end_iter());
+        let mut end = line_end(start);
+        let (index, padding) =
+            char_width::column_to_index(&self.buffer.text(&start, &end, false), column);
+        if padding > 0 {
+            self.buffer.insert(&mut end, &" ".repeat(padding));
+        }
+        let mut target = self
+            .buffer
+            .iter_at_line(line.max(0))
+            .unwrap_or_else(|| self.buffer.end_iter());
+        target.forward_chars(i32::try_from(index).unwrap_or(i32::MAX));
+        self.cursor_back =
+            usize::try_from(self.buffer.end_iter().offset() - target.offset()).unwrap_or(0);
+    }
+
+    /// Remplace `[start, end[` par des espaces sur la même largeur affichée
+    /// (la zone précède toujours le curseur, compté depuis la fin : il ne
+    /// bouge pas).
+    fn blank(&self, start: i32, end: i32) {
+        if end <= start {
+            return;
+        }
+        let mut from = self.buffer.iter_at_offset(start);
+        let mut to = self.buffer.iter_at_offset(end);
+        let width = char_width::str_width(&self.buffer.text(&from, &to, false));
+        self.buffer.delete(&mut from, &mut to);
+        self.buffer.insert(&mut from, &" ".repeat(width));
+    }
+
+    /// `CSI K` : 0 efface jusqu'à la fin de ligne, 1 jusqu'au curseur
+    /// inclus, 2 toute la ligne.
+    fn erase_in_line(&mut self, mode: u16) {
+        self.flush();
+        let cursor = self.cursor_iter();
+        let mut start = cursor;
+        start.set_line_offset(0);
+        let end = line_end(cursor);
+        match mode {
+            0 | 2 => {
+                if mode == 2 {
+                    self.blank(start.offset(), cursor.offset());
+                }
+                let mut from = self.cursor_iter();
+                let mut to = from;
+                to.forward_chars(end.offset() - cursor.offset());
+                self.buffer.delete(&mut from, &mut to);
+                self.cursor_back -= usize::try_from(end.offset() - cursor.offset()).unwrap_or(0);
+            }
+            1 => self.blank(start.offset(), (cursor.offset() + 1).min(end.offset())),
+            _ => {}
+        }
+    }
+
+    /// `CSI J` : 0 efface jusqu'à la fin de l'écran, 1 depuis le haut de
+    /// l'écran jusqu'au curseur, 2 tout l'écran. L'écran effacé est repoussé
+    /// dans l'historique, comme au saut de page ; 3 (historique) est ignoré.
+    fn erase_in_display(&mut self, mode: u16) {
+        self.flush();
+        match mode {
+            0 => {
+                let mut from = self.cursor_iter();
+                self.buffer.delete(&mut from, &mut self.buffer.end_iter());
+                self.cursor_back = 0;
+            }
+            1 => {
+                let cursor_line = self.cursor_iter().line();
+                for line in self.screen_top()..cursor_line {
+                    if let Some(start) = self.buffer.iter_at_line(line) {
+                        self.blank(start.offset(), line_end(start).offset());
+                    }
+                }
+                self.erase_in_line(1);
+            }
+            2 => {
+                let row = (self.cursor_iter().line() - self.screen_top()).max(0);
+                let column = self.cursor_column();
+                self.push_screen_to_history();
+                self.move_to(self.screen_top() + row, column);
+            }
+            _ => {}
+        }
+    }
+
+    /// Repousse l'écran courant dans l'historique par une page de lignes
+    /// vides ; le curseur passe en fin de buffer.
+    fn push_screen_to_history(&mut self) {
+        self.buffer
+            .insert(&mut self.buffer.end_iter(), &"\n".repeat(self.screen_rows));
+        self.cursor_back = 0;
+    }
+
+    /// Retour chariot : curseur en début de sa ligne.
+    fn carriage_return(&mut self) {
+        self.flush();
+        let cursor = self.cursor_iter();
+        self.cursor_back += usize::try_from(cursor.line_offset()).unwrap_or(0);
+    }
+
+    /// Saut de ligne : en dernière ligne, l'écriture reprend en fin de buffer
+    /// sur une nouvelle ligne ; au-dessus, le curseur descend d'une ligne.
+    fn line_feed(&mut self) {
+        self.flush();
+        let cursor = self.cursor_iter();
+        if cursor.line() == self.buffer.end_iter().line() {
+            self.cursor_back = 0;
+            self.pending_text.push('\n');
+        } else {
+            let column = self.cursor_column();
+            self.move_to(cursor.line() + 1, column);
+        }
+    }
+
+    /// Séquences de déplacement du curseur (`H`, `f`, `A`-`D`, `G`, `d`).
+    fn move_cursor(&mut self, params: &vte::Params, action: char) {
+        self.flush();
+        let line = self.cursor_iter().line();
+        let column = self.cursor_column();
+        let top = self.screen_top();
+        let count = csi_param(params, 0, 1);
+        let bottom = top + i32::try_from(self.screen_rows).unwrap_or(i32::MAX) - 1;
+        match action {
+            'H' | 'f' => {
+                let column = usize::from(csi_param(params, 1, 1) - 1);
+                self.move_to((top + i32::from(count) - 1).min(bottom), column);
+            }
+            'A' => self.move_to((line - i32::from(count)).max(top), column),
+            'B' => self.move_to((line + i32::from(count)).min(bottom), column),
+            'C' => self.move_to(line, column + usize::from(count)),
+            'D' => self.move_to(line, column.saturating_sub(usize::from(count))),
+            'G' => self.move_to(line, usize::from(count - 1)),
+            'd' => self.move_to((top + i32::from(count) - 1).min(bottom), column),
+            _ => {}
+        }
+    }
+
+    /// Vrai si le prochain caractère imprimé commence une ligne.
+    fn at_line_start(&self) -> bool {
+        if self.pending_text.is_empty() {
+            self.cursor_back == 0 && self.buffer.end_iter().starts_line()
+        } else {
+            self.pending_text.ends_with('\n')
+        }
+    }
+
+    /// Insère l'heure de réception en tête de ligne, avec le style système.
+    fn timestamp_line(&mut self) {
+        self.flush();
+        let timestamp = format!("[{}] ", chrono::Local::now().format("%H:%M:%S%.3f"));
+        let mut end = self.buffer.end_iter();
+        match self.buffer.tag_table().lookup("system") {
+            Some(tag) => self.buffer.insert_with_tags(&mut end, &timestamp, &[&tag]),
+            None => self.buffer.insert(&mut end, &timestamp),
+        }
+    }
+
+    /// Saut de page : en mode effacement, l'écran est effacé comme par `ED 2`
+    /// (repoussé dans l'historique) et la suite s'affiche en haut à gauche.
+    fn form_feed(&mut self) {
+        match self.form_feed {
+            FormFeedHandling::Ignore => {}
+            FormFeedHandling::Literal => self.pending_text.push_str("^L"),
+            FormFeedHandling::Clear => {
+                self.flush();
+                self.push_screen_to_history();
+                self.move_to(self.screen_top(), 0);
+            }
+        }
+    }
+
+    /// Tabulation : espaces jusqu'au prochain taquet (`tab_width` colonnes).
+    fn tab(&mut self) {
+        if self.tab_width == 0 {
+            self.pending_text.push('\t');
+            return;
+        }
+        // Colonnes affichées, pas caractères : un idéogramme en occupe deux.
+        let column = match self.pending_text.rfind('\n') {
+            Some(idx) => char_width::str_width(&self.pending_text[idx + 1..]),
+            None => self.cursor_column() + char_width::str_width(&self.pending_text),
+        };
+        let spaces = self.tab_width - column % self.tab_width;
+        self.pending_text.push_str(&" ".repeat(spaces));
+    }
+
+    /// Backspace : recule le curseur d'un caractère sans sortir de la ligne.
+    fn backspace(&mut self) {
+        self.flush();
+        if !self.cursor_iter().starts_line() {
+            self.cursor_back += 1;
+        }
+    }
+
+    /// OSC 8 : `8;params;url` ouvre un lien, une URL vide le ferme.
+    ///
+    /// Les séquences incomplètes ou trop longues sont ignorées, de même que
+    /// les liens autres que http(s) ; un lien jamais fermé l'est au prochain
+    /// `clear()`.
+    fn hyperlink(&mut self, params: &[&[u8]]) {
+        if params.len() < 3 {
+            return;
+        }
+        self.flush();
+
+        // L'URL peut elle-même contenir des ';' découpés par le parseur.
+        let url = params[2..]
+            .iter()
+            .map(|part| String::from_utf8_lossy(part))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        self.current_link = if url.len() > MAX_LINK_LEN || !is_openable_link(&url) {
+            None
+        } else {
+            Some(url)
+        };
+    }
+
+    /// Passe un caractère sorti du parseur par le découpage en lignes, puis
+    /// l'affiche ou l'interprète.
+    fn receive(&mut self, c: char) {
+        for c in self.lines.push(c).into_iter().flatten() {
+            match u8::try_from(c) {
+                Ok(byte) if byte.is_ascii_control() => self.control(byte),
+                _ => self.print_char(c),
+            }
+        }
+    }
+
+    /// Avant une séquence d'échappement : le `\r` retenu par le découpage
+    /// s'applique d'abord.
+    fn flush_pending_line_end(&mut self) {
+        if let Some(c) = self.lines.take_pending() {
+            self.receive(c);
+        }
+    }
+
+    fn print_char(&mut self, c: char) {
+        // `print` n'est appelé qu'une séquence d'échappement terminée : l'heure
+        // ne peut pas s'insérer au milieu d'une séquence coupée entre deux lectures.
+        if self.rx_timestamps && self.at_line_start() {
+            self.timestamp_line();
+        }
         self.pending_text.push(c);
     }
 
-    fn execute(&mut self, byte: u8) {
+    fn control(&mut self, byte: u8) {
         match byte {
-            b'\n' | b'\r' | b'\t' | b'\x08' => {
-                self.pending_text.push(byte as char);
-            }
+            b'\x08' => self.backspace(),
+            b'\n' => self.line_feed(),
+            b'\r' => self.carriage_return(),
+            b'\t' => self.tab(),
+            b'\0' => match self.nul_handling {
+                NulHandling::Strip => {}
+                NulHandling::Caret => self.pending_text.push_str("^@"),
+                NulHandling::PassThrough => self.pending_text.push('\u{2400}'),
+            },
+            b'\x0C' => self.form_feed(),
             _ => {}
         }
     }
+}
+
+impl Perform for AnsiPerformer {
+    fn print(&mut self, c: char) {
+        self.receive(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        self.receive(char::from(byte));
+    }
 
     fn hook(&mut self, _params: &vte::Params, _intermediates: &[u8], _ignore: bool, _action: char) {
     }
     fn put(&mut self, _byte: u8) {}
     fn unhook(&mut self) {}
-    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {}
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        self.flush_pending_line_end();
+        if params.first() == Some(&&b"8"[..]) {
+            self.hyperlink(params);
+        }
+    }
 
     fn csi_dispatch(
         &mut self,
         params: &vte::Params,
-        _intermediates: &[u8],
+        intermediates: &[u8],
         _ignore: bool,
         action: char,
     ) {
+        self.flush_pending_line_end();
+        // Les variantes privées ou à intermédiaires (`CSI ? …`, `CSI > 4;2 m`…)
+        // ne sont pas gérées : ce ne sont pas des attributs SGR.
+        if !intermediates.is_empty() {
+            return;
+        }
+        match action {
+            'H' | 'f' | 'A' | 'B' | 'C' | 'D' | 'G' | 'd' => self.move_cursor(params, action),
+            'K' => self.erase_in_line(csi_param(params, 0, 0)),
+            'J' => self.erase_in_display(csi_param(params, 0, 0)),
+            _ => {}
+        }
         if action == 'm' {
             self.flush();
-            let mut has_params = false;
-            for param in params {
-                has_params = true;
-                let p = if param.is_empty() { 0 } else { param[0] };
-                match p {
-                    0 => {
+            for attribute in sgr::parse_sgr(params.iter()) {
+                match attribute {
+                    SgrAttribute::Reset => {
                         self.current_fg = None;
                         self.current_bg = None;
                         self.bold = false;
                         self.italic = false;
                         self.underline = false;
                     }
-                    1 => self.bold = true,
-                    3 => self.italic = true,
-                    4 => self.underline = true,
-                    22 => self.bold = false,
-                    23 => self.italic = false,
-                    24 => self.underline = false,
-                    // Les plages de match garantissent que le résultat tient dans u8 (0-15).
-                    30..=37 => self.current_fg = Some(u8::try_from(p - 30).unwrap_or(0)),
-                    39 => self.current_fg = None,
-                    40..=47 => self.current_bg = Some(u8::try_from(p - 40).unwrap_or(0)),
-                    49 => self.current_bg = None,
-                    90..=97 => self.current_fg = Some(u8::try_from(p - 90 + 8).unwrap_or(8)),
-                    100..=107 => self.current_bg = Some(u8::try_from(p - 100 + 8).unwrap_or(8)),
-                    _ => {}
+                    SgrAttribute::Bold(on) => self.bold = on,
+                    SgrAttribute::Italic(on) => self.italic = on,
+                    SgrAttribute::Underline(on) => self.underline = on,
+                    SgrAttribute::Foreground(color) => self.current_fg = color,
+                    SgrAttribute::Background(color) => self.current_bg = color,
                 }
             }
-            if !has_params {
-                self.current_fg = None;
-                self.current_bg = None;
-                self.bold = false;
-                self.italic = false;
-                self.underline = false;
-            }
         }
     }
 
-    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {}
+    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {
+        self.flush_pending_line_end();
+    }
 }
 
 impl TerminalPanel {
@@ -174,6 +798,13 @@ impl TerminalPanel {
         let rx_tag = gtk4::TextTag::builder().name("rx").build();
         tag_table.add(&rx_tag);
 
+        // Tag pour la sortie d'erreur distante (stderr SSH)
+        let stderr_tag = gtk4::TextTag::builder()
+            .name("stderr")
+            .foreground("#ff7777")
+            .build();
+        tag_table.add(&stderr_tag);
+
         // Tag pour les messages système
         let sys_tag = gtk4::TextTag::builder()
             .name("system")
@@ -226,6 +857,29 @@ impl TerminalPanel {
             .build();
         tag_table.add(&underline_tag);
 
+        // Tag pour les caractères de contrôle affichés en notation caret
+        let control_tag = gtk4::TextTag::builder()
+            .name("control")
+            .foreground("#C678DD")
+            .weight(700)
+            .build();
+        tag_table.add(&control_tag);
+
+        // Bloc d'une trame délimitée (décodage « Délimiteurs début/fin »)
+        let frame_tag = gtk4::TextTag::builder()
+            .name(FRAME_TAG)
+            .paragraph_background("rgba(128, 128, 128, 0.12)")
+            .left_margin(24)
+            .build();
+        tag_table.add(&frame_tag);
+
+        // Fond des lignes marquées d'un marque-page (Ctrl+B)
+        let bookmark_tag = gtk4::TextTag::builder()
+            .name(BOOKMARK_TAG)
+            .paragraph_background("rgba(97, 175, 239, 0.25)")
+            .build();
+        tag_table.add(&bookmark_tag);
+
         let buffer = TextBuffer::new(Some(&tag_table));
 
         let text_view = TextView::builder()
@@ -243,6 +897,12 @@ impl TerminalPanel {
             .build();
 
         text_view.add_css_class("terminal-view");
+        text_view.update_property(&[
+            gtk4::accessible::Property::Label("Sortie du terminal"),
+            gtk4::accessible::Property::ReadOnly(true),
+        ]);
+        Self::setup_link_click(&text_view);
+        Self::setup_word_selection(&text_view);
 
         let container = ScrolledWindow::builder()
             .vexpand(true)
@@ -250,38 +910,291 @@ impl TerminalPanel {
             .child(&text_view)
             .build();
 
+        let cursor_widget = gtk4::Box::builder()
+            .can_target(false)
+            .visible(false)
+            .build();
+        cursor_widget.add_css_class("terminal-cursor");
+        text_view.add_overlay(&cursor_widget, 0, 0);
         let auto_scroll_enabled = Rc::new(Cell::new(true));
         let ansi_parser = Rc::new(RefCell::new(Parser::new()));
         let ansi_performer = Rc::new(RefCell::new(AnsiPerformer::new(buffer.clone())));
 
+        // Clignotement ; la position est aussi recalculée (redimensionnement).
+        let cursor_style = Rc::new(Cell::new(CursorStyle::None));
+        let cursor_blink = Rc::new(Cell::new(true));
+        {
+            let view = text_view.downgrade();
+            let weak = cursor_widget.downgrade();
+            let performer = ansi_performer.clone();
+            let style = cursor_style.clone();
+            let blink = cursor_blink.clone();
+            glib::timeout_add_local(
+                std::time::Duration::from_millis(CURSOR_BLINK_MS),
+                move || {
+                    let (Some(view), Some(widget)) = (view.upgrade(), weak.upgrade()) else {
+                        return glib::ControlFlow::Break;
+                    };
+                    let back = performer.borrow().cursor_back;
+                    Self::place_cursor(&view, &widget, style.get(), back);
+                    let hidden = blink.get() && widget.opacity() > 0.5;
+                    widget.set_opacity(if hidden { 0.0 } else { 1.0 });
+                    glib::ControlFlow::Continue
+                },
+            );
+        }
+
+        let find_bar = FindBar::new(&text_view, auto_scroll_enabled.clone());
+
         Self {
             container,
             text_view,
             buffer,
-            max_lines,
+            max_lines: Cell::new(max_lines),
             auto_scroll_enabled,
+            capture_hold: Cell::new(false),
+            tx_timestamps_enabled: Cell::new(false),
+            control_chars_visible: Cell::new(false),
+            highlight_rules: RefCell::new(Vec::new()),
+            utf8_decoder: RefCell::new(logger::StreamDecoder::default()),
+            frame_decoder: RefCell::new(FrameDecoder::new(FramingMode::None)),
+            frame_count: Cell::new(0),
+            hex_view: Cell::new(false),
+            hex_offset: Cell::new(0),
             ansi_parser,
             ansi_performer,
+            cursor_widget,
+            cursor_style,
+            cursor_blink,
+            bookmarks: RefCell::new(Vec::new()),
+            find_bar,
         }
     }
 
+    /// Double-clic : étend la sélection de GTK aux chemins et noms d'hôte
+    /// complets (`/`, `.`, `-`, `_`… font partie du mot).
+    ///
+    /// Le triple-clic (sélection de ligne) reste géré par GTK.
+    fn setup_word_selection(text_view: &TextView) {
+        let gesture = gtk4::GestureClick::new();
+        let view = text_view.clone();
+        gesture.connect_released(move |_, n_press, x, y| {
+            if n_press != 2 {
+                return;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let (bx, by) =
+                view.window_to_buffer_coords(gtk4::TextWindowType::Widget, x as i32, y as i32);
+            let Some(iter) = view.iter_at_location(bx, by) else {
+                return;
+            };
+            if !is_word_char(iter.char()) {
+                return;
+            }
+
+            let mut start = iter;
+            while start.backward_char() {
+                if !is_word_char(start.char()) {
+                    start.forward_char();
+                    break;
+                }
+            }
+            let mut end = iter;
+            while is_word_char(end.char()) && end.forward_char() {}
+
+            // Ponctuation finale (fin de phrase) exclue : "voir /etc/hosts."
+            loop {
+                let mut prev = end;
+                if !prev.backward_char() || prev <= start || !matches!(prev.char(), '.' | ':') {
+                    break;
+                }
+                end = prev;
+            }
+
+            view.buffer().select_range(&start, &end);
+        });
+        text_view.add_controller(gesture);
+    }
+
+    /// Ouvre l'URL d'un hyperlien OSC 8 cliqué dans le terminal.
+    fn setup_link_click(text_view: &TextView) {
+        let gesture = gtk4::GestureClick::new();
+        let view = text_view.clone();
+        gesture.connect_released(move |gesture, _n_press, x, y| {
+            // Ne pas ouvrir le lien à la fin d'une sélection.
+            if view.buffer().has_selection() {
+                return;
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let (bx, by) =
+                view.window_to_buffer_coords(gtk4::TextWindowType::Widget, x as i32, y as i32);
+            let Some(iter) = view.iter_at_location(bx, by) else {
+                return;
+            };
+            let Some(url) = iter.tags().iter().find_map(|tag| {
+                tag.name()
+                    .and_then(|name| name.strip_prefix(LINK_TAG_PREFIX).map(str::to_string))
+            }) else {
+                return;
+            };
+            if !is_openable_link(&url) {
+                return;
+            }
+
+            gesture.set_state(gtk4::EventSequenceState::Claimed);
+            let window = view.root().and_downcast::<gtk4::Window>();
+            gtk4::UriLauncher::new(&url).launch(
+                window.as_ref(),
+                gtk4::gio::Cancellable::NONE,
+                move |result| {
+                    if let Err(e) = result {
+                        log::warn!("Impossible d'ouvrir le lien {url}: {e}");
+                    }
+                },
+            );
+        });
+        text_view.add_controller(gesture);
+    }
+
     /// Ajoute des données reçues (RX) au terminal en parsant les séquences ANSI.
     pub fn append_ansi(&self, data: &[u8]) {
-        let mut parser = self.ansi_parser.borrow_mut();
-        let mut performer = self.ansi_performer.borrow_mut();
+        if self.frame_decoder.borrow().mode() != FramingMode::None {
+            self.append_frames(data);
+            return;
+        }
+        if self.hex_view.get() {
+            self.append_hexdump(data);
+            return;
+        }
 
-        parser.advance(&mut *performer, data);
-        performer.flush();
+        // La dernière ligne, éventuellement incomplète, sera colorée une fois terminée.
+        let first_line = self.buffer.end_iter().line();
+
+        if self.control_chars_visible.get() {
+            self.append_caret_notation(data);
+        } else {
+            // Le performer ramène le délimiteur configuré à `\n` : les
+            // fonctions par ligne (coloration, scrollback) travaillent sur
+            // les lignes du buffer.
+            let text = self.utf8_decoder.borrow_mut().decode(data);
+            let mut parser = self.ansi_parser.borrow_mut();
+            let mut performer = self.ansi_performer.borrow_mut();
+            performer.screen_rows = self
+                .grid_size()
+                .and_then(|(_, rows)| usize::try_from(rows).ok())
+                .filter(|&rows| rows > 0)
+                .unwrap_or(DEFAULT_SCREEN_ROWS);
+
+            parser.advance(&mut *performer, text.as_bytes());
+            performer.flush();
+        }
+        self.highlight_completed_lines(first_line);
 
         self.trim_scrollback();
-        if self.auto_scroll_enabled.get() {
+        if self.auto_scroll_enabled.get() && !self.capture_hold.get() {
             self.scroll_to_bottom();
         }
+        self.update_cursor();
+    }
+
+    /// Applique la première règle de coloration correspondante à chaque
+    /// ligne terminée depuis `first_line`.
+    fn highlight_completed_lines(&self, first_line: i32) {
+        let rules = self.highlight_rules.borrow();
+        if rules.is_empty() {
+            return;
+        }
+
+        for line in first_line..self.buffer.end_iter().line() {
+            let Some(start) = self.buffer.iter_at_line(line) else {
+                continue;
+            };
+            let mut end = start;
+            end.forward_to_line_end();
+            let text = self.buffer.text(&start, &end, false);
+            if let Some((_, tag)) = rules.iter().find(|(re, _)| re.is_match(&text)) {
+                self.buffer.apply_tag(tag, &start, &end);
+            }
+        }
+    }
+
+    /// Remplace les règles de coloration des lignes reçues.
+    ///
+    /// Les expressions invalides sont ignorées (avertissement dans les logs).
+    pub fn set_highlight_rules(&self, rules: &[HighlightRule]) {
+        let tag_table = self.buffer.tag_table();
+        for (_, tag) in self.highlight_rules.borrow_mut().drain(..) {
+            tag_table.remove(&tag);
+        }
+
+        let compiled = rules
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| match regex::Regex::new(&rule.pattern) {
+                Ok(re) => {
+                    let tag = TextTag::builder()
+                        .name(format!("hl_{i}"))
+                        .foreground(rule.color.as_str())
+                        .build();
+                    tag_table.add(&tag);
+                    Some((re, tag))
+                }
+                Err(e) => {
+                    log::warn!("Règle de coloration ignorée ({}) : {e}", rule.pattern);
+                    None
+                }
+            })
+            .collect();
+        *self.highlight_rules.borrow_mut() = compiled;
+    }
+
+    /// Ajoute des données brutes en rendant les octets de contrôle visibles.
+    ///
+    /// Les sauts de ligne restent des sauts de ligne (précédés de `^J`) pour
+    /// conserver un affichage lisible.
+    fn append_caret_notation(&self, data: &[u8]) {
+        self.ansi_performer.borrow_mut().reset_cursor();
+        let control_tag = self.buffer.tag_table().lookup("control");
+        let mut plain = String::new();
+
+        for c in logger::decode_lossy(data).chars() {
+            let Some(caret) = caret_notation(c) else {
+                plain.push(c);
+                continue;
+            };
+
+            let mut end = self.buffer.end_iter();
+            self.buffer.insert(&mut end, &plain);
+            plain.clear();
+            if let Some(tag) = &control_tag {
+                self.buffer.insert_with_tags(&mut end, &caret, &[tag]);
+            } else {
+                self.buffer.insert(&mut end, &caret);
+            }
+            if c == '\n' {
+                self.buffer.insert(&mut end, "\n");
+            }
+        }
+
+        let mut end = self.buffer.end_iter();
+        self.buffer.insert(&mut end, &plain);
     }
 
     /// Ajoute du texte envoyé (TX) au terminal — écho local.
+    ///
+    /// Préfixé par l'heure si l'horodatage TX est activé.
     pub fn append_sent(&self, text: &str) {
-        self.append_with_tag(text, "tx");
+        if self.tx_timestamps_enabled.get() {
+            let timestamp = chrono::Local::now().format("%H:%M:%S%.3f");
+            self.append_with_tag(&format!("[{timestamp}] {text}"), "tx");
+        } else {
+            self.append_with_tag(text, "tx");
+        }
+    }
+
+    /// Ajoute la sortie d'erreur distante (stderr), sans interprétation ANSI.
+    pub fn append_stderr(&self, data: &[u8]) {
+        self.append_with_tag(&logger::decode_lossy(data), "stderr");
     }
 
     /// Ajoute un message système.
@@ -298,6 +1211,7 @@ impl TerminalPanel {
 
     /// Ajoute du texte avec un tag donné et fait défiler vers le bas.
     fn append_with_tag(&self, text: &str, tag_name: &str) {
+        self.ansi_performer.borrow_mut().reset_cursor();
         let mut end_iter = self.buffer.end_iter();
 
         let tag_table = self.buffer.tag_table();
@@ -311,25 +1225,64 @@ impl TerminalPanel {
         self.trim_scrollback();
 
         // Auto-scroll vers le bas
-        if self.auto_scroll_enabled.get() {
+        if self.auto_scroll_enabled.get() && !self.capture_hold.get() {
             self.scroll_to_bottom();
         }
+        self.update_cursor();
     }
 
     /// Supprime les anciennes lignes au-delà de la limite de scrollback.
+    ///
+    /// Seules des lignes logiques complètes sont supprimées : la coupure se
+    /// fait au début de la première ligne conservée (juste après un `\n`),
+    /// donc jamais au milieu d'un caractère multioctet ni d'un graphème
+    /// (CJK, emoji, caractères combinants).
     fn trim_scrollback(&self) {
-        let line_count = self.buffer.line_count();
-        let max_lines_i32 = i32::try_from(self.max_lines).unwrap_or(i32::MAX);
-        if line_count > max_lines_i32 {
-            let lines_to_remove = line_count - max_lines_i32;
-            let mut start = self.buffer.start_iter();
-            let mut end = self.buffer.iter_at_line(lines_to_remove).unwrap_or(start);
-            // S'assurer que end est bien au début de la ligne
-            if end.line_offset() != 0 {
-                end.forward_to_line_end();
-                end.forward_char();
+        if self.capture_hold.get() {
+            return;
+        }
+        // Au moins une ligne est conservée (la ligne en cours d'écriture).
+        let max_lines = i32::try_from(self.max_lines.get().max(1)).unwrap_or(i32::MAX);
+        let excess = self.buffer.line_count() - max_lines;
+        if excess <= 0 {
+            return;
+        }
+
+        let Some(mut end) = self.buffer.iter_at_line(excess) else {
+            return;
+        };
+        self.drop_bookmarks_before(&end);
+        let mut start = self.buffer.start_iter();
+        self.buffer.delete(&mut start, &mut end);
+        self.prune_link_tags();
+    }
+
+    /// Retire de la table les tags d'hyperlien qui ne s'appliquent plus à
+    /// aucun texte (historique coupé ou effacé), sauf celui du lien ouvert :
+    /// sans cela, chaque URL reçue resterait en mémoire jusqu'à la fermeture.
+    fn prune_link_tags(&self) {
+        let open_link = self
+            .ansi_performer
+            .borrow()
+            .current_link
+            .as_ref()
+            .map(|url| format!("{LINK_TAG_PREFIX}{url}"));
+        let tag_table = self.buffer.tag_table();
+        let mut orphans = Vec::new();
+        tag_table.foreach(|tag| {
+            let Some(name) = tag.name() else {
+                return;
+            };
+            if !name.starts_with(LINK_TAG_PREFIX) || open_link.as_deref() == Some(name.as_str()) {
+                return;
+            }
+            let mut iter = self.buffer.start_iter();
+            if !iter.has_tag(tag) && !iter.forward_to_tag_toggle(Some(tag)) {
+                orphans.push(tag.clone());
             }
-            self.buffer.delete(&mut start, &mut end);
+        });
+        for tag in orphans {
+            tag_table.remove(&tag);
         }
     }
 
@@ -343,10 +1296,281 @@ impl TerminalPanel {
         self.buffer.delete_mark(&end_mark);
     }
 
+    /// Décode `data` en trames et affiche chacune en bloc : hex/ASCII pour
+    /// les protocoles binaires, texte pour les trames délimitées.
+    fn append_frames(&self, data: &[u8]) {
+        let (frames, delimited) = {
+            let mut decoder = self.frame_decoder.borrow_mut();
+            (decoder.push(data), decoder.mode() == FramingMode::Delimited)
+        };
+        for frame in frames {
+            match frame {
+                Ok(payload) => {
+                    let index = self.frame_count.get() + 1;
+                    self.frame_count.set(index);
+                    let timestamp = chrono::Local::now().format("%H:%M:%S%.3f");
+                    let header = format!(
+                        "[{timestamp}] Trame #{index} — {} octet(s)\n",
+                        payload.len()
+                    );
+                    self.append_with_tag(&header, "system");
+                    if delimited {
+                        let mut text = logger::decode_lossy(&payload);
+                        text.push('\n');
+                        self.append_with_tag(&text, FRAME_TAG);
+                    } else {
+                        self.append_with_tag(&framing::hex_dump(&payload), "rx");
+                    }
+                }
+                Err(e) => self.append_error(&e),
+            }
+        }
+    }
+
+    /// Affiche `data` en vidage hexadécimal ; l'offset continue d'un appel
+    /// à l'autre, un paquet reçu en plusieurs lectures reste lisible.
+    pub fn append_hexdump(&self, data: &[u8]) {
+        let offset = self.hex_offset.get();
+        self.hex_offset.set(offset + data.len() as u64);
+        self.append_with_tag(&framing::hex_dump_at(data, offset), "rx");
+    }
+
+    /// Active ou coupe la vue hexadécimale. Le contenu déjà affiché n'est pas
+    /// reformaté : le changement s'applique aux octets reçus ensuite.
+    pub fn set_hex_view(&self, enabled: bool) {
+        if self.hex_view.replace(enabled) == enabled {
+            return;
+        }
+        // Le vidage commence toujours en début de ligne.
+        if enabled && !self.buffer.end_iter().starts_line() {
+            self.append_with_tag("\n", "rx");
+        }
+    }
+
     /// Efface tout le contenu du terminal.
     pub fn clear(&self) {
+        {
+            let mut performer = self.ansi_performer.borrow_mut();
+            performer.reset_cursor();
+            performer.current_link = None;
+            performer.lines.clear();
+        }
+        self.utf8_decoder.borrow_mut().clear();
+        self.frame_decoder.borrow_mut().reset();
+        self.frame_count.set(0);
+        for mark in self.bookmarks.take() {
+            self.buffer.delete_mark(&mark);
+        }
         self.buffer
             .delete(&mut self.buffer.start_iter(), &mut self.buffer.end_iter());
+        self.prune_link_tags();
+        self.update_cursor();
+    }
+
+    /// Ligne visée : début de la sélection, sinon ligne cliquée.
+    fn target_line(&self) -> i32 {
+        self.buffer
+            .selection_bounds()
+            .map_or_else(
+                || self.buffer.iter_at_mark(&self.buffer.get_insert()),
+                |(start, _)| start,
+            )
+            .line()
+    }
+
+    /// Pose ou retire un marque-page sur la ligne visée.
+    ///
+    /// Retourne `true` si un marque-page a été posé.
+    pub fn toggle_bookmark(&self) -> bool {
+        let line = self.target_line();
+        let Some(start) = self.buffer.iter_at_line(line) else {
+            return false;
+        };
+        let mut end = start;
+        end.forward_line();
+
+        let mut bookmarks = self.bookmarks.borrow_mut();
+        if let Some(pos) = bookmarks
+            .iter()
+            .position(|mark| self.buffer.iter_at_mark(mark).line() == line)
+        {
+            let mark = bookmarks.remove(pos);
+            self.buffer.delete_mark(&mark);
+            self.buffer.remove_tag_by_name(BOOKMARK_TAG, &start, &end);
+            return false;
+        }
+        bookmarks.push(self.buffer.create_mark(None, &start, true));
+        self.buffer.apply_tag_by_name(BOOKMARK_TAG, &start, &end);
+        true
+    }
+
+    /// Marque-pages par ordre de ligne : `(ligne, extrait du texte)`.
+    pub fn bookmarks(&self) -> Vec<(i32, String)> {
+        let mut lines: Vec<i32> = self
+            .bookmarks
+            .borrow()
+            .iter()
+            .map(|mark| self.buffer.iter_at_mark(mark).line())
+            .collect();
+        lines.sort_unstable();
+        lines.dedup();
+        lines
+            .into_iter()
+            .map(|line| (line, self.line_snippet(line)))
+            .collect()
+    }
+
+    /// Va au marque-page suivant (ou précédent), en bouclant.
+    ///
+    /// Retourne la ligne atteinte, `None` s'il n'y a aucun marque-page.
+    pub fn goto_bookmark(&self, forward: bool) -> Option<i32> {
+        let current = self.target_line();
+        let lines: Vec<i32> = self.bookmarks().into_iter().map(|(line, _)| line).collect();
+        let line = if forward {
+            lines
+                .iter()
+                .find(|&&l| l > current)
+                .or_else(|| lines.first())
+        } else {
+            lines
+                .iter()
+                .rev()
+                .find(|&&l| l < current)
+                .or_else(|| lines.last())
+        };
+        let line = *line?;
+        self.scroll_to_line(line);
+        Some(line)
+    }
+
+    /// Ouvre la barre de recherche.
+    pub fn show_find_bar(&self) {
+        self.find_bar.show();
+    }
+
+    /// Affiche la ligne `line` et y place la marque d'insertion.
+    pub fn scroll_to_line(&self, line: i32) {
+        let Some(iter) = self.buffer.iter_at_line(line) else {
+            return;
+        };
+        self.buffer.place_cursor(&iter);
+        let mark = self.buffer.create_mark(None, &iter, true);
+        self.text_view.scroll_to_mark(&mark, 0.0, true, 0.0, 0.3);
+        self.buffer.delete_mark(&mark);
+    }
+
+    /// Texte de la ligne `line`, raccourci pour une liste.
+    fn line_snippet(&self, line: i32) -> String {
+        let Some(start) = self.buffer.iter_at_line(line) else {
+            return String::new();
+        };
+        let mut end = start;
+        if !end.ends_line() {
+            end.forward_to_line_end();
+        }
+        let text = self.buffer.text(&start, &end, false);
+        let text = text.trim();
+        if text.chars().count() > BOOKMARK_SNIPPET_CHARS {
+            let cut: String = text.chars().take(BOOKMARK_SNIPPET_CHARS).collect();
+            format!("{cut}…")
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Supprime les marque-pages situés avant `end` (texte sur le point
+    /// d'être effacé) : sans cela, leurs marques glisseraient en tête du buffer.
+    fn drop_bookmarks_before(&self, end: &gtk4::TextIter) {
+        self.bookmarks.borrow_mut().retain(|mark| {
+            let keep = self.buffer.iter_at_mark(mark) >= *end;
+            if !keep {
+                self.buffer.delete_mark(mark);
+            }
+            keep
+        });
+    }
+
+    /// Limite de lignes conservées.
+    pub fn max_lines(&self) -> u32 {
+        self.max_lines.get()
+    }
+
+    /// Change la limite de scrollback ; l'excédent est supprimé aussitôt.
+    pub fn set_max_lines(&self, max_lines: u32) {
+        self.max_lines.set(max_lines);
+        self.trim_scrollback();
+    }
+
+    /// Occupation actuelle du scrollback `(lignes, caractères)`.
+    pub fn scrollback_usage(&self) -> (u32, u32) {
+        (
+            u32::try_from(self.buffer.line_count()).unwrap_or(0),
+            u32::try_from(self.buffer.char_count()).unwrap_or(0),
+        )
+    }
+
+    /// Efface l'historique au-dessus de la zone visible, en conservant l'écran.
+    pub fn clear_scrollback(&self) {
+        let top = self.container.vadjustment().value();
+        #[allow(clippy::cast_possible_truncation)]
+        let Some(first_visible) = self.text_view.iter_at_location(0, top as i32) else {
+            return;
+        };
+        let Some(mut end) = self.buffer.iter_at_line(first_visible.line()) else {
+            return;
+        };
+        self.drop_bookmarks_before(&end);
+        let mut start = self.buffer.start_iter();
+        self.buffer.delete(&mut start, &mut end);
+        self.prune_link_tags();
+    }
+
+    /// Taille de la grille visible `(colonnes, lignes)`, calculée à partir
+    /// des métriques de la police (chasse fixe) et de la zone affichée.
+    ///
+    /// Retourne `None` tant que le widget n'est pas dimensionné.
+    pub fn grid_size(&self) -> Option<(u32, u32)> {
+        grid_size_of(&self.text_view)
+    }
+
+    /// Appelle `on_resize(colonnes, lignes)` quand la grille visible change,
+    /// une fois le redimensionnement stabilisé : un glissement continu de la
+    /// fenêtre ne produit qu'un seul appel.
+    ///
+    /// `on_resize` retourne `true` si la taille a été transmise ; sinon elle
+    /// sera proposée de nouveau au prochain redimensionnement, même identique.
+    pub fn connect_grid_resized(&self, on_resize: impl Fn(u32, u32) -> bool + 'static) {
+        let on_resize = Rc::new(on_resize);
+        let last_size = Rc::new(Cell::new(self.grid_size()));
+        let pending: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+        for adjustment in [self.container.hadjustment(), self.container.vadjustment()] {
+            let text_view = self.text_view.clone();
+            let on_resize = on_resize.clone();
+            let last_size = last_size.clone();
+            let pending = pending.clone();
+            adjustment.connect_page_size_notify(move |_| {
+                if let Some(source) = pending.borrow_mut().take() {
+                    source.remove();
+                }
+                let text_view = text_view.clone();
+                let on_resize = on_resize.clone();
+                let last_size = last_size.clone();
+                let pending_done = pending.clone();
+                let source = glib::timeout_add_local_once(
+                    std::time::Duration::from_millis(RESIZE_DEBOUNCE_MS),
+                    move || {
+                        pending_done.borrow_mut().take();
+                        let size = grid_size_of(&text_view);
+                        if let Some((cols, rows)) = size {
+                            if last_size.get() != size && on_resize(cols, rows) {
+                                last_size.set(size);
+                            }
+                        }
+                    },
+                );
+                *pending.borrow_mut() = Some(source);
+            });
+        }
     }
 
     /// Retourne tout le texte du terminal.
@@ -356,14 +1580,267 @@ impl TerminalPanel {
             .to_string()
     }
 
+    /// Texte sélectionné (celui que « Copier » placerait dans le presse-papiers).
+    pub fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.buffer.selection_bounds()?;
+        Some(self.buffer.text(&start, &end, false).to_string())
+    }
+
+    /// Convertit les tabulations reçues en espaces (`None` = `\t` conservé).
+    pub fn set_tab_expansion(&self, width: Option<u32>) {
+        self.ansi_performer.borrow_mut().tab_width =
+            width.map_or(0, |w| usize::try_from(w.clamp(1, 32)).unwrap_or(8));
+    }
+
+    /// Choisit le rendu du saut de page reçu.
+    pub fn set_form_feed_handling(&self, handling: FormFeedHandling) {
+        self.ansi_performer.borrow_mut().form_feed = handling;
+    }
+
+    /// Choisit le rendu des octets NUL reçus.
+    pub fn set_nul_handling(&self, handling: NulHandling) {
+        self.ansi_performer.borrow_mut().nul_handling = handling;
+    }
+
+    /// Change la forme du curseur (`None` = masqué).
+    pub fn set_cursor_style(&self, style: CursorStyle) {
+        self.cursor_style.set(style);
+        self.update_cursor();
+    }
+
+    /// Active/désactive le clignotement du curseur.
+    pub fn set_cursor_blink(&self, blink: bool) {
+        self.cursor_blink.set(blink);
+        self.cursor_widget.set_opacity(1.0);
+    }
+
+    /// Replace le curseur sur la position logique d'écriture.
+    fn update_cursor(&self) {
+        let back = self.ansi_performer.borrow().cursor_back;
+        Self::place_cursor(
+            &self.text_view,
+            &self.cursor_widget,
+            self.cursor_style.get(),
+            back,
+        );
+    }
+
+    /// Dessine le curseur `style` sur le caractère situé `cursor_back`
+    /// caractères avant la fin du buffer (cellule vide en fin de ligne).
+    fn place_cursor(
+        text_view: &TextView,
+        widget: &gtk4::Box,
+        style: CursorStyle,
+        cursor_back: usize,
+    ) {
+        if style == CursorStyle::None {
+            widget.set_visible(false);
+            return;
+        }
+
+        let buffer = text_view.buffer();
+        let mut iter = buffer.end_iter();
+        iter.backward_chars(i32::try_from(cursor_back).unwrap_or(i32::MAX));
+        let rect = text_view.iter_location(&iter);
+
+        // Largeur d'une cellule : caractère sous le curseur, sinon métrique de la police.
+        let mut next = iter;
+        let width = if !iter.ends_line() && next.forward_char() {
+            text_view.iter_location(&next).x() - rect.x()
+        } else {
+            0
+        };
+        let width = if width > 0 {
+            width
+        } else {
+            let metrics = text_view.pango_context().metrics(None, None);
+            (metrics.approximate_digit_width() / gtk4::pango::SCALE).max(1)
+        };
+        let height = rect.height().max(1);
+
+        let (x, y, w, h) = match style {
+            CursorStyle::Block => (rect.x(), rect.y(), width, height),
+            CursorStyle::Underline => (rect.x(), rect.y() + height - 2, width, 2),
+            CursorStyle::Beam | CursorStyle::None => (rect.x(), rect.y(), 2, height),
+        };
+        widget.set_size_request(w, h);
+        text_view.move_overlay(widget, x, y);
+        widget.set_visible(true);
+    }
+
+    /// Suspend (ou reprend) le défilement et la coupe du scrollback.
+    ///
+    /// À la reprise, l'historique est ramené à la limite et la vue rejoint
+    /// la fin si le défilement automatique est actif.
+    pub fn set_capture_hold(&self, hold: bool) {
+        if self.capture_hold.replace(hold) == hold || hold {
+            return;
+        }
+        self.trim_scrollback();
+        if self.auto_scroll_enabled.get() {
+            self.scroll_to_bottom();
+        }
+    }
+
+    /// Indique si la vue est remontée dans l'historique (fin du buffer hors écran).
+    pub fn is_scrolled_up(&self) -> bool {
+        let adj = self.container.vadjustment();
+        adj.value() + adj.page_size() < adj.upper() - SCROLL_BOTTOM_TOLERANCE
+    }
+
     /// Active/désactive le défilement automatique.
     pub fn set_auto_scroll_enabled(&self, enabled: bool) {
         self.auto_scroll_enabled.set(enabled);
     }
 
+    /// Active/désactive l'affichage des caractères de contrôle (notation caret).
+    pub fn set_control_chars_visible(&self, visible: bool) {
+        self.control_chars_visible.set(visible);
+    }
+
+    /// Change le délimiteur de fin de ligne des données reçues.
+    pub fn set_line_delimiter(&self, delimiter: LineDelimiter) {
+        self.ansi_performer
+            .borrow_mut()
+            .lines
+            .set_delimiter(delimiter);
+    }
+
+    /// Change le décodage de trame des données reçues.
+    pub fn set_framing_mode(&self, mode: FramingMode) {
+        self.frame_decoder.borrow_mut().set_mode(mode);
+        self.frame_count.set(0);
+    }
+
+    /// Marqueurs de début et de fin du décodage par délimiteurs.
+    pub fn set_frame_delimiters(&self, start: Vec<u8>, end: Vec<u8>) {
+        self.frame_decoder.borrow_mut().set_delimiters(start, end);
+    }
+
+    /// Abandonne la trame partielle, renumérote et remet l'offset
+    /// hexadécimal à zéro (nouvelle connexion).
+    pub fn reset_framing(&self) {
+        self.frame_decoder.borrow_mut().reset();
+        self.frame_count.set(0);
+        self.hex_offset.set(0);
+    }
+
+    /// Active/désactive l'horodatage des lignes reçues (RX).
+    pub fn set_rx_timestamps_enabled(&self, enabled: bool) {
+        self.ansi_performer.borrow_mut().rx_timestamps = enabled;
+    }
+
+    /// Active/désactive l'horodatage de l'écho TX.
+    pub fn set_tx_timestamps_enabled(&self, enabled: bool) {
+        self.tx_timestamps_enabled.set(enabled);
+    }
+
     /// Retourne un handle partagé de l'état auto-scroll.
     #[allow(dead_code)]
     pub fn auto_scroll_handle(&self) -> Rc<Cell<bool>> {
         self.auto_scroll_enabled.clone()
     }
 }
+
+/// Grille visible `(colonnes, lignes)` de `text_view` (voir `TerminalPanel::grid_size`).
+fn grid_size_of(text_view: &TextView) -> Option<(u32, u32)> {
+    // Largeur d'une cellule : celle des chiffres. `approximate_char_width`
+    // est une moyenne qui gonfle avec une locale CJK et fausse les colonnes.
+    let metrics = text_view.pango_context().metrics(None, None);
+    let char_width = metrics.approximate_digit_width();
+    let line_height = metrics.ascent() + metrics.descent();
+    if char_width <= 0 || line_height <= 0 {
+        return None;
+    }
+
+    let rect = text_view.visible_rect();
+    let width = rect.width() - text_view.left_margin() - text_view.right_margin();
+    let height = rect.height() - text_view.top_margin() - text_view.bottom_margin();
+    let cols = u32::try_from(width * gtk4::pango::SCALE / char_width).ok()?;
+    let rows = u32::try_from(height * gtk4::pango::SCALE / line_height).ok()?;
+    (cols > 0 && rows > 0).then_some((cols, rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Passe `data` dans le parseur ANSI, avec un écran de `rows` lignes, et
+    /// renvoie le texte du buffer.
+    fn render(data: &[u8], rows: usize) -> String {
+        let buffer = TextBuffer::new(None::<&TextTagTable>);
+        let mut performer = AnsiPerformer::new(buffer.clone());
+        performer.screen_rows = rows;
+        Parser::new().advance(&mut performer, data);
+        performer.flush();
+        buffer
+            .text(&buffer.start_iter(), &buffer.end_iter(), false)
+            .to_string()
+    }
+
+    #[test]
+    fn cursor_addressing_counts_display_columns() {
+        gtk4::test_synced(|| {
+            // Colonne 3 (1-based) : le deuxième idéogramme, pas le `a`.
+            assert_eq!(render("漢字ab\x1b[1;3HX".as_bytes(), 4), "漢Xab");
+            assert_eq!(render("漢字ab\x1b[5GX".as_bytes(), 4), "漢字Xb");
+            // CUF depuis un caractère large, puis complétion par des espaces.
+            assert_eq!(render("漢\x1b[2CX".as_bytes(), 4), "漢  X");
+            // La colonne est conservée d'une ligne à l'autre en largeur affichée.
+            assert_eq!(
+                render("漢字\r\nabcdef\x1b[1;1H\x1b[4C\x1b[BX".as_bytes(), 4),
+                "漢字\nabcdXf"
+            );
+            assert_eq!(render("ab\x1b[1;10HX".as_bytes(), 4), "ab       X");
+        });
+    }
+
+    #[test]
+    fn erase_sequences_keep_display_columns() {
+        gtk4::test_synced(|| {
+            // EL 0 : du curseur à la fin de ligne.
+            assert_eq!(render("漢字ab\x1b[1;3H\x1b[K".as_bytes(), 4), "漢");
+            // EL 1 : du début de ligne au curseur inclus, sur la même largeur.
+            assert_eq!(render("漢字ab\x1b[1;5H\x1b[1K".as_bytes(), 4), "     b");
+            // EL 2 : toute la ligne, le curseur reste en colonne 4.
+            assert_eq!(render("漢字ab\x1b[1;5H\x1b[2KX".as_bytes(), 4), "    X");
+            // ED 0 : du curseur à la fin de l'écran.
+            assert_eq!(render("ab\r\ncd\x1b[1;2H\x1b[J".as_bytes(), 4), "a");
+        });
+    }
+
+    #[test]
+    fn form_feed_clear_writes_from_the_top_of_a_blank_screen() {
+        gtk4::test_synced(|| {
+            let buffer = TextBuffer::new(None::<&TextTagTable>);
+            let mut performer = AnsiPerformer::new(buffer.clone());
+            performer.screen_rows = 3;
+            performer.form_feed = FormFeedHandling::Clear;
+            Parser::new().advance(&mut performer, b"ab\r\ncd\x0cX\r\nY");
+            performer.flush();
+            let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+            // L'ancien écran reste dans l'historique, l'écran vierge fait trois
+            // lignes et l'écriture reprend sur sa première ligne.
+            assert_eq!(text.as_str(), "ab\ncd\nX\nY\n");
+            assert_eq!(performer.screen_top(), 2);
+            assert_eq!(buffer.end_iter().line(), 4);
+        });
+    }
+
+    #[test]
+    fn scrollback_trim_keeps_wide_and_emoji_lines_whole() {
+        gtk4::test_synced(|| {
+            let panel = TerminalPanel::new(3);
+            let data = "一二三\n👨\u{200d}👩\u{200d}👧 👍🏽\n漢字かな\ne\u{301}🇫🇷\n".as_bytes();
+            // Lectures coupées au milieu des séquences UTF-8.
+            for chunk in data.chunks(5) {
+                panel.append_ansi(chunk);
+            }
+            assert_eq!(panel.get_text(), "漢字かな\ne\u{301}🇫🇷\n");
+
+            panel.append_ansi("👨\u{200d}👩\u{200d}👧".as_bytes());
+            panel.append_ansi("\n漢".as_bytes());
+            assert_eq!(panel.get_text(), "e\u{301}🇫🇷\n👨\u{200d}👩\u{200d}👧\n漢");
+        });
+    }
+}
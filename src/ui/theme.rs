@@ -1,24 +1,53 @@
 // =============================================================================
 // Fichier : theme.rs
-// Rôle    : Gestionnaire de thèmes (Clair, Sombre, Hacker)
+// Rôle    : Gestionnaire de thèmes (Automatique, Clair, Sombre, Hacker,
+//           Contraste élevé)
 // =============================================================================
 
-use gtk4::CssProvider;
+use std::cell::RefCell;
+
+use gtk4::prelude::*;
+use gtk4::{glib, CssProvider};
+
+thread_local! {
+    /// Fournisseur CSS actif, remplacé à chaque changement de thème.
+    static ACTIVE_PROVIDER: RefCell<Option<CssProvider>> = const { RefCell::new(None) };
+    /// Abonnement à `notify::dark` tant que le thème automatique est actif.
+    static AUTO_HANDLER: RefCell<Option<glib::SignalHandlerId>> = const { RefCell::new(None) };
+}
+
+/// Police des zones en chasse fixe, commune à tous les thèmes.
+///
+/// Les premières polices couvrent les tracés de boîtes (`─│┌`), les
+/// dernières les idéogrammes sur deux cellules ; sans cette chaîne, Pango
+/// peut tomber sur une police proportionnelle et décaler les tableaux.
+const MONOSPACE_CSS: &str = r#"
+    .terminal-view, .input-entry {
+        font-family: "DejaVu Sans Mono", "Noto Sans Mono", "Cascadia Mono", "Consolas",
+            "Noto Sans Mono CJK SC", "Monospace";
+    }
+"#;
 
 /// Thèmes disponibles.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Theme {
+    /// Suit la préférence clair/sombre du système.
+    Auto,
     Light,
     Dark,
     Hacker,
+    /// Couleurs franches et texte gras pour les utilisateurs malvoyants.
+    HighContrast,
 }
 
 impl Theme {
     /// Convertit depuis une chaîne.
     pub fn from_str_name(s: &str) -> Self {
         match s.to_lowercase().as_str() {
+            "auto" | "system" | "automatique" => Self::Auto,
             "light" | "clair" => Self::Light,
             "hacker" | "matrix" => Self::Hacker,
+            "high-contrast" | "contraste" => Self::HighContrast,
             _ => Self::Dark,
         }
     }
@@ -26,24 +55,34 @@ impl Theme {
     /// Nom d'affichage.
     pub const fn display_name(&self) -> &str {
         match self {
+            Self::Auto => "Automatique (système)",
             Self::Light => "Clair",
             Self::Dark => "Sombre",
             Self::Hacker => "Hacker",
+            Self::HighContrast => "Contraste élevé",
         }
     }
 
     /// Nom technique.
     pub const fn id(&self) -> &str {
         match self {
+            Self::Auto => "auto",
             Self::Light => "light",
             Self::Dark => "dark",
             Self::Hacker => "hacker",
+            Self::HighContrast => "high-contrast",
         }
     }
 
     /// Liste de tous les thèmes.
     pub const fn all() -> &'static [Self] {
-        &[Self::Light, Self::Dark, Self::Hacker]
+        &[
+            Self::Auto,
+            Self::Light,
+            Self::Dark,
+            Self::Hacker,
+            Self::HighContrast,
+        ]
     }
 }
 
@@ -55,31 +94,64 @@ impl ThemeManager {
     pub fn apply(theme: Theme) {
         // Configurer le color scheme Adwaita
         let style_manager = libadwaita::StyleManager::default();
+        if let Some(handler) = AUTO_HANDLER.with(|h| h.borrow_mut().take()) {
+            style_manager.disconnect(handler);
+        }
         match theme {
+            Theme::Auto => {
+                style_manager.set_color_scheme(libadwaita::ColorScheme::Default);
+                // Le système peut basculer clair/sombre à tout moment.
+                let handler = style_manager.connect_dark_notify(|sm| {
+                    Self::load_css(Self::system_variant(sm));
+                });
+                AUTO_HANDLER.with(|h| *h.borrow_mut() = Some(handler));
+            }
             Theme::Light => {
                 style_manager.set_color_scheme(libadwaita::ColorScheme::ForceLight);
             }
-            Theme::Dark | Theme::Hacker => {
+            Theme::Dark | Theme::Hacker | Theme::HighContrast => {
                 style_manager.set_color_scheme(libadwaita::ColorScheme::ForceDark);
             }
         }
 
         // CSS personnalisé par thème
-        let css = Self::css_for_theme(theme);
-        let provider = CssProvider::new();
-        provider.load_from_string(&css);
-
-        if let Some(display) = gtk4::gdk::Display::default() {
-            gtk4::style_context_add_provider_for_display(
-                &display,
-                &provider,
-                gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
-            );
+        if theme == Theme::Auto {
+            Self::load_css(Self::system_variant(&style_manager));
+        } else {
+            Self::load_css(theme);
         }
 
         log::info!("Thème appliqué : {}", theme.display_name());
     }
 
+    /// Variante clair/sombre actuellement choisie par le système.
+    fn system_variant(style_manager: &libadwaita::StyleManager) -> Theme {
+        if style_manager.is_dark() {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
+    }
+
+    /// Remplace le CSS personnalisé actif par celui de `theme`.
+    fn load_css(theme: Theme) {
+        let Some(display) = gtk4::gdk::Display::default() else {
+            return;
+        };
+
+        let provider = CssProvider::new();
+        provider.load_from_string(&format!("{MONOSPACE_CSS}{}", Self::css_for_theme(theme)));
+        gtk4::style_context_add_provider_for_display(
+            &display,
+            &provider,
+            gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+        );
+
+        if let Some(previous) = ACTIVE_PROVIDER.with(|p| p.borrow_mut().replace(provider)) {
+            gtk4::style_context_remove_provider_for_display(&display, &previous);
+        }
+    }
+
     /// Génère le CSS personnalisé pour un thème donné.
     fn css_for_theme(theme: Theme) -> String {
         match theme {
@@ -87,12 +159,13 @@ impl ThemeManager {
                 .terminal-view {
                     background-color: #fafafa;
                     color: #2e2e2e;
-                    font-family: "Monospace";
                     font-size: 11pt;
                     padding: 8px;
                 }
+                .terminal-cursor {
+                    background-color: alpha(#2e2e2e, 0.6);
+                }
                 .input-entry {
-                    font-family: "Monospace";
                     font-size: 11pt;
                     min-height: 36px;
                 }
@@ -110,16 +183,17 @@ impl ThemeManager {
             "#
             .to_string(),
 
-            Theme::Dark => r#"
+            Theme::Dark | Theme::Auto => r#"
                 .terminal-view {
                     background-color: #1e1e2e;
                     color: #cdd6f4;
-                    font-family: "Monospace";
                     font-size: 11pt;
                     padding: 8px;
                 }
+                .terminal-cursor {
+                    background-color: alpha(#cdd6f4, 0.6);
+                }
                 .input-entry {
-                    font-family: "Monospace";
                     font-size: 11pt;
                     min-height: 36px;
                 }
@@ -141,13 +215,14 @@ impl ThemeManager {
                 .terminal-view {
                     background-color: #0a0a0a;
                     color: #00ff41;
-                    font-family: "Monospace";
                     font-size: 11pt;
                     padding: 8px;
                     text-shadow: 0 0 3px rgba(0, 255, 65, 0.3);
                 }
+                .terminal-cursor {
+                    background-color: alpha(#00ff41, 0.6);
+                }
                 .input-entry {
-                    font-family: "Monospace";
                     font-size: 11pt;
                     min-height: 36px;
                     color: #00ff41;
@@ -169,6 +244,52 @@ impl ThemeManager {
                 }
             "#
             .to_string(),
+
+            // Noir/blanc/jaune, texte gras et focus très visible : pensé pour
+            // la basse vision, pas pour l'esthétique.
+            Theme::HighContrast => r#"
+                .terminal-view {
+                    background-color: #000000;
+                    color: #ffffff;
+                    font-size: 12pt;
+                    font-weight: bold;
+                    padding: 8px;
+                }
+                .terminal-view text selection {
+                    background-color: #ffff00;
+                    color: #000000;
+                }
+                .terminal-cursor {
+                    background-color: #ffff00;
+                }
+                .input-entry {
+                    font-size: 12pt;
+                    font-weight: bold;
+                    min-height: 40px;
+                    color: #ffffff;
+                    background-color: #000000;
+                    border: 2px solid #ffffff;
+                }
+                .connection-panel {
+                    padding: 6px 12px;
+                }
+                .status-connected {
+                    color: #00ff00;
+                    font-weight: bold;
+                }
+                .status-disconnected {
+                    color: #ff4040;
+                    font-weight: bold;
+                }
+                button, entry, dropdown, spinbutton {
+                    font-weight: bold;
+                }
+                *:focus-visible {
+                    outline: 3px solid #ffff00;
+                    outline-offset: 1px;
+                }
+            "#
+            .to_string(),
         }
     }
 }
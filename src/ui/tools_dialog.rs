@@ -3,12 +3,26 @@
 // Rôle    : Fenêtre d'outils (calculatrice + convertisseur de base)
 // =============================================================================
 
+use std::cell::Cell;
+use std::rc::Rc;
+
 use anyhow::Context;
 use gtk4::prelude::*;
 use gtk4::{Box as GtkBox, Button, DropDown, Entry, Label, Orientation, StringList};
 
+/// Ouvre la fenêtre d'outils.
+///
+/// `can_send` indique si une connexion active accepte l'envoi (interrogé à
+/// chaque conversion et à chaque envoi : la connexion peut changer pendant que
+/// la fenêtre est ouverte) ; `on_send` transmet les octets d'une valeur
+/// convertie et retourne `true` en cas de succès.
 #[allow(clippy::too_many_lines)]
-pub fn open_tools_dialog(parent: &impl IsA<gtk4::Window>) {
+pub fn open_tools_dialog(
+    parent: &impl IsA<gtk4::Window>,
+    can_send: impl Fn() -> bool + 'static,
+    on_send: impl Fn(Vec<u8>) -> bool + 'static,
+) {
+    let can_send = Rc::new(can_send);
     let dialog = gtk4::Window::builder()
         .transient_for(parent)
         .modal(true)
@@ -61,10 +75,16 @@ pub fn open_tools_dialog(parent: &impl IsA<gtk4::Window>) {
         .hexpand(true)
         .build();
     let convert_button = Button::builder().label("Convertir").build();
+    let send_button = Button::builder()
+        .label("Envoyer")
+        .tooltip_text("Envoyer la valeur convertie (octets big-endian)")
+        .sensitive(false)
+        .build();
 
     conv_row.append(&base_dropdown);
     conv_row.append(&value_entry);
     conv_row.append(&convert_button);
+    conv_row.append(&send_button);
 
     let conv_dec = Label::builder().label("DEC: -").xalign(0.0).build();
     let conv_hex = Label::builder().label("HEX: -").xalign(0.0).build();
@@ -107,13 +127,19 @@ pub fn open_tools_dialog(parent: &impl IsA<gtk4::Window>) {
         });
     }
 
+    // Dernière valeur convertie avec succès (source du bouton Envoyer).
+    let last_value: Rc<Cell<Option<i128>>> = Rc::new(Cell::new(None));
+
     {
         let value_entry = value_entry;
         let base_dropdown = base_dropdown;
+        let send_button = send_button.clone();
+        let last_value = last_value.clone();
         let conv_dec = conv_dec;
         let conv_hex = conv_hex;
         let conv_bin = conv_bin;
-        let conv_error = conv_error;
+        let conv_error = conv_error.clone();
+        let can_send = can_send.clone();
 
         convert_button.connect_clicked(move |_| {
             let input = value_entry.text().trim().to_string();
@@ -134,8 +160,35 @@ pub fn open_tools_dialog(parent: &impl IsA<gtk4::Window>) {
                     conv_hex.set_label(&format!("HEX: {}", format_hex(value)));
                     conv_bin.set_label(&format!("BIN: {}", format_bin(value)));
                     conv_error.set_label("");
+                    last_value.set(Some(value));
+                    send_button.set_sensitive(can_send() && value >= 0);
+                }
+                Err(e) => {
+                    conv_error.set_label(&format!("Erreur: {e}"));
+                    last_value.set(None);
+                    send_button.set_sensitive(false);
                 }
-                Err(e) => conv_error.set_label(&format!("Erreur: {e}")),
+            }
+        });
+    }
+
+    {
+        let conv_error = conv_error;
+        send_button.connect_clicked(move |button| {
+            let Some(value) = last_value.get() else {
+                return;
+            };
+            if !can_send() {
+                conv_error.set_label("Envoi indisponible (non connecté ou lecture seule)");
+                button.set_sensitive(false);
+                return;
+            }
+            let Some(bytes) = value_to_bytes(value) else {
+                conv_error.set_label("Erreur: seules les valeurs positives sont envoyables");
+                return;
+            };
+            if on_send(bytes) {
+                conv_error.set_label("Valeur envoyée.");
             }
         });
     }
@@ -187,3 +240,14 @@ fn format_bin(value: i128) -> String {
         format!("0b{value:b}")
     }
 }
+
+/// Octets big-endian minimaux d'une valeur positive (au moins un octet).
+fn value_to_bytes(value: i128) -> Option<Vec<u8>> {
+    let value = u128::try_from(value).ok()?;
+    let bytes = value.to_be_bytes();
+    let first = bytes
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(bytes.len() - 1);
+    Some(bytes[first..].to_vec())
+}
@@ -3,29 +3,76 @@
 // Rôle    : Fenêtre principale — orchestre tous les composants
 // =============================================================================
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
 
 use gtk4::prelude::*;
-use gtk4::{gio, glib, Box as GtkBox, FileDialog, Orientation};
+use gtk4::{gdk, gio, glib, Box as GtkBox, FileDialog, Orientation};
 use libadwaita::prelude::*;
 use tokio::runtime::Runtime;
 
+use crate::core::capture::CaptureOptions;
 use crate::core::connection::{
     spawn_connection_actor, Connection, ConnectionCommand, ConnectionEvent, ConnectionType,
+    HostKeyDecision,
 };
+use crate::core::connection_uri::ConnectionUri;
+use crate::core::framing::{self, FramingMode};
+use crate::core::intelhex;
+use crate::core::keymap::{self, BackspaceMode, KeyModifiers, TerminalKey};
+use crate::core::line_assembler::{LineDelimiter, LineEndingDetector};
+use crate::core::logger;
+use crate::core::reconnect::{self, BackoffStrategy};
 use crate::core::secrets;
-use crate::core::serial_manager::{SerialConfig, SerialManager};
-use crate::core::settings::{SettingsManager, SshFavorite};
+use crate::core::serial_manager::{
+    find_port_by_identity, list_serial_ports, SerialConfig, SerialManager, UsbIdentity,
+};
+use crate::core::settings::{
+    AppSettings, SendMacro, SettingsManager, SshFavorite, DEFAULT_PROFILE,
+};
+use crate::core::ssh_config;
 use crate::core::ssh_manager::{SshAuthMethod, SshConfig, SshManager};
 use crate::ui::connection_panel::ConnectionPanel;
+use crate::ui::favorites_dialog::open_favorites_dialog;
 use crate::ui::header_bar::AppHeaderBar;
+use crate::ui::highlight_dialog::open_highlight_rules_dialog;
 use crate::ui::input_panel::InputPanel;
-use crate::ui::terminal_panel::TerminalPanel;
+use crate::ui::known_hosts_dialog::open_known_hosts_dialog;
+use crate::ui::sftp_dialog::open_sftp_dialog;
+use crate::ui::terminal_panel::{CursorStyle, FormFeedHandling, NulHandling, TerminalPanel};
 use crate::ui::theme::{Theme, ThemeManager};
 use crate::ui::tools_dialog::open_tools_dialog;
 
+/// Option booléenne du menu : action, lecture du paramètre, application à l'UI.
+type ToggleBinding = (
+    gio::SimpleAction,
+    fn(&AppSettings) -> bool,
+    fn(&MainWindow, bool),
+);
+
+/// Demande interactive émise par l'acteur pendant la connexion.
+///
+/// L'acteur attend la réponse sur un canal oneshot : abandonner la demande
+/// (drop) ferme le canal, ce que l'acteur traite comme un refus.
+enum InteractivePrompt {
+    HostKey {
+        host: String,
+        key_type: String,
+        fingerprint: String,
+        is_key_changed: bool,
+        decision_tx: tokio::sync::oneshot::Sender<HostKeyDecision>,
+    },
+    Auth {
+        prompts: Vec<(String, bool)>,
+        response_tx: tokio::sync::oneshot::Sender<Vec<String>>,
+    },
+    Passphrase {
+        key_path: String,
+        response_tx: tokio::sync::oneshot::Sender<String>,
+    },
+}
+
 /// Fenêtre principale de l'application `SerialSSHTerm`.
 pub struct MainWindow {
     pub window: libadwaita::ApplicationWindow,
@@ -35,6 +82,64 @@ pub struct MainWindow {
     pub input: InputPanel,
     settings: Rc<RefCell<SettingsManager>>,
     connection_tx: RefCell<Option<tokio::sync::mpsc::Sender<ConnectionCommand>>>,
+    /// Type de la connexion établie (`None` tant que non connecté).
+    active_connection: Cell<Option<ConnectionType>>,
+    /// Horodatage de l'établissement de la connexion courante.
+    connected_since: Cell<Option<chrono::DateTime<chrono::Local>>>,
+    /// Description de la connexion courante (ex: `user@hôte:22`).
+    connection_description: RefCell<String>,
+    /// Incrémenté à chaque connexion : identifie le pompage d'événements courant.
+    connection_generation: Cell<u64>,
+    /// Nombre de tentatives de reconnexion automatique consécutives.
+    reconnect_attempt: Cell<u32>,
+    /// Compte à rebours de la prochaine reconnexion automatique.
+    reconnect_timer: RefCell<Option<glib::SourceId>>,
+    /// Fin programmée de la capture du démarrage en cours.
+    boot_capture_timer: RefCell<Option<glib::SourceId>>,
+    /// Effacement programmé des secrets saisis pendant une session SSH.
+    password_clear_timer: RefCell<Option<glib::SourceId>>,
+    /// Secret de la connexion SSH établie, réutilisé par la reconnexion automatique.
+    session_secret: RefCell<Option<SessionSecret>>,
+    /// Détection de la fin de ligne, active au début de chaque connexion.
+    line_ending_detector: RefCell<Option<LineEndingDetector>>,
+    /// Adaptateur USB de la dernière connexion série et son chemin, pour le
+    /// retrouver s'il revient ailleurs après un rebranchement.
+    serial_identity: RefCell<Option<(UsbIdentity, String)>>,
+    /// Secret de la tentative en cours, retenu une fois la connexion établie.
+    pending_secret: RefCell<Option<SessionSecret>>,
+    /// Passphrase saisie avec « mémoriser » (chemin de la clé, passphrase),
+    /// enregistrée seulement quand la clé s'est chargée avec elle.
+    passphrase_to_remember: RefCell<Option<(String, String)>>,
+    /// Maj enfoncée au lancement de la connexion : commandes du favori ignorées.
+    skip_on_connect_commands: Cell<bool>,
+    /// Fermeture déjà confirmée par l'utilisateur : ne plus redemander.
+    close_confirmed: Cell<bool>,
+    /// Session SSH en mode commande unique : sa fin n'est pas une perte de
+    /// connexion à rétablir.
+    single_command_session: Cell<bool>,
+    /// Envois en attente quand le canal de commandes est plein (ordre préservé).
+    send_queue: RefCell<std::collections::VecDeque<Vec<u8>>>,
+    /// Vrai tant que le timer de vidange de `send_queue` est actif.
+    send_queue_draining: Cell<bool>,
+    /// Échos TX en attente, un lot par `SendData` transmis : affichés à la
+    /// réception de `DataSent`, entre les données reçues avant et après.
+    pending_echoes: RefCell<std::collections::VecDeque<Vec<String>>>,
+    /// Évite d'empiler plusieurs dialogues de reconnexion série.
+    reconfigure_prompt_open: Cell<bool>,
+    /// Reconnexion série à lancer dès l'arrêt de l'acteur courant.
+    reconnect_when_closed: Cell<bool>,
+    /// Demandes interactives en attente (génération de connexion, demande),
+    /// présentées une à une dans l'ordre d'arrivée.
+    prompt_queue: RefCell<std::collections::VecDeque<(u64, InteractivePrompt)>>,
+    /// Dialogue de la demande interactive en cours.
+    active_prompt: RefCell<Option<libadwaita::AlertDialog>>,
+    /// Options booléennes du menu, resynchronisées au changement de profil.
+    toggle_actions: RefCell<Vec<ToggleBinding>>,
+    /// Sous-menu listant les profils de configuration.
+    profiles_menu: gio::Menu,
+    /// Menu Macros et nombre d'actions `win.macro-<n>` enregistrées.
+    macros_menu: gio::Menu,
+    macro_count: Cell<usize>,
     runtime: Arc<Runtime>,
     /// Overlay Adwaita pour les notifications non-bloquantes (Toast).
     toast_overlay: libadwaita::ToastOverlay,
@@ -43,8 +148,10 @@ pub struct MainWindow {
 impl MainWindow {
     /// Construit et affiche la fenêtre principale.
     #[allow(clippy::too_many_lines)]
-    pub fn new(app: &libadwaita::Application) -> Rc<Self> {
-        let settings = Rc::new(RefCell::new(SettingsManager::new()));
+    pub fn new(app: &libadwaita::Application, profile: Option<&str>) -> Rc<Self> {
+        let settings = Rc::new(RefCell::new(SettingsManager::with_profile(
+            profile.unwrap_or(DEFAULT_PROFILE),
+        )));
         let s = settings.borrow();
 
         let runtime = Arc::new(Runtime::new().expect("Impossible de créer le runtime Tokio"));
@@ -74,17 +181,242 @@ impl MainWindow {
 
         let file_menu = gio::Menu::new();
         file_menu.append(Some("Sauvegarder les logs"), Some("win.save-logs"));
+        file_menu.append(
+            Some("Importer des favoris SSH…"),
+            Some("win.import-ssh-favorites"),
+        );
+        file_menu.append(
+            Some("Reconnecter au dernier favori"),
+            Some("win.reconnect-last-favorite"),
+        );
+        file_menu.append(
+            Some("Exporter cette connexion…"),
+            Some("win.export-connection"),
+        );
+        file_menu.append(
+            Some("Dupliquer la connexion dans une nouvelle fenêtre"),
+            Some("win.duplicate-connection"),
+        );
+        file_menu.append(
+            Some("Sauvegarder les logs à la déconnexion"),
+            Some("win.auto-save-logs"),
+        );
+        let capture_menu = gio::Menu::new();
+        capture_menu.append(Some("Capturer dans un fichier…"), Some("win.raw-capture"));
+        let capture_options = gio::Menu::new();
+        capture_options.append(Some("Inclure les envois (TX)"), Some("win.raw-capture-tx"));
+        capture_options.append(
+            Some("Marquer la direction (RX/TX)"),
+            Some("win.raw-capture-tagged"),
+        );
+        capture_options.append(
+            Some("Horodater les échanges"),
+            Some("win.raw-capture-timestamps"),
+        );
+        capture_menu.append_section(Some("Options"), &capture_options);
+        file_menu.append_submenu(Some("Capture brute des octets"), &capture_menu);
+        let profiles_menu = gio::Menu::new();
+        let profile_submenu = gio::Menu::new();
+        profile_submenu.append_section(None, &profiles_menu);
+        let new_profile_section = gio::Menu::new();
+        new_profile_section.append(Some("Nouveau profil…"), Some("win.new-profile"));
+        profile_submenu.append_section(None, &new_profile_section);
+        file_menu.append_submenu(Some("Profil"), &profile_submenu);
+        let folders_section = gio::Menu::new();
+        folders_section.append(
+            Some("Ouvrir le dossier de configuration"),
+            Some("win.open-config-dir"),
+        );
+        folders_section.append(Some("Ouvrir les journaux"), Some("win.open-log-dir"));
+        file_menu.append_section(None, &folders_section);
+        file_menu.append(Some("Tout déconnecter"), Some("win.disconnect-all"));
+        file_menu.append(
+            Some("Confirmer la fermeture si connecté"),
+            Some("win.confirm-quit-connected"),
+        );
         file_menu.append(Some("Quitter"), Some("win.close"));
         menubar_model.append_submenu(Some("Fichier"), &file_menu);
 
         let edit_menu = gio::Menu::new();
         edit_menu.append(Some("Effacer le terminal"), Some("win.clear-terminal"));
+        edit_menu.append(
+            Some("Effacer l'historique (garder l'écran)"),
+            Some("win.clear-scrollback"),
+        );
+        edit_menu.append(
+            Some("Taille de l'historique…"),
+            Some("win.scrollback-limit"),
+        );
+        edit_menu.append(Some("Rechercher…"), Some("win.find"));
+        edit_menu.append(Some("Coller et envoyer"), Some("win.paste-send"));
+        edit_menu.append(
+            Some("Seuil de confirmation d'envoi…"),
+            Some("win.paste-confirm-threshold"),
+        );
+        edit_menu.append(Some("Renvoyer la sélection"), Some("win.resend-selection"));
+        let bookmark_menu = gio::Menu::new();
+        bookmark_menu.append(
+            Some("Marquer / démarquer la ligne"),
+            Some("win.toggle-bookmark"),
+        );
+        bookmark_menu.append(Some("Marque-page suivant"), Some("win.next-bookmark"));
+        bookmark_menu.append(Some("Marque-page précédent"), Some("win.previous-bookmark"));
+        bookmark_menu.append(Some("Liste des marque-pages…"), Some("win.show-bookmarks"));
+        edit_menu.append_submenu(Some("Marque-pages"), &bookmark_menu);
+        edit_menu.append(Some("Horodater les envois (TX)"), Some("win.tx-timestamps"));
+        edit_menu.append(Some("Horodatage RX"), Some("win.rx-timestamps"));
+        edit_menu.append(Some("Lecture seule"), Some("win.read-only"));
+        edit_menu.append(Some("Confirmer avant envoi"), Some("win.confirm-send"));
+        edit_menu.append(Some("Mode AT"), Some("win.at-mode"));
+        edit_menu.append(
+            Some("Clavier direct dans le terminal"),
+            Some("win.direct-keyboard"),
+        );
+        let backspace_menu = gio::Menu::new();
+        for mode in BackspaceMode::all() {
+            backspace_menu.append(
+                Some(mode.display_name()),
+                Some(&format!("win.backspace-key::{}", mode.id())),
+            );
+        }
+        edit_menu.append_submenu(Some("Touche Retour arrière"), &backspace_menu);
+        edit_menu.append(
+            Some("Détection automatique de la fin de ligne"),
+            Some("win.detect-line-ending"),
+        );
+        edit_menu.append(
+            Some("Proposer la reconnexion après modification série"),
+            Some("win.serial-reconfigure-prompt"),
+        );
+        let echo_menu = gio::Menu::new();
+        echo_menu.append(Some("Série"), Some("win.local-echo-serial"));
+        echo_menu.append(Some("SSH"), Some("win.local-echo-ssh"));
+        edit_menu.append_submenu(Some("Écho local des envois"), &echo_menu);
+        let reconnect_menu = gio::Menu::new();
+        reconnect_menu.append(Some("Série"), Some("win.auto-reconnect-serial"));
+        reconnect_menu.append(Some("SSH"), Some("win.auto-reconnect-ssh"));
+        reconnect_menu.append(
+            Some("Retrouver l'adaptateur USB rebranché"),
+            Some("win.reconnect-by-identity"),
+        );
+        let strategy_section = gio::Menu::new();
+        strategy_section.append(
+            Some("Intervalle fixe"),
+            Some("win.reconnect-strategy::fixed"),
+        );
+        strategy_section.append(
+            Some("Délai exponentiel"),
+            Some("win.reconnect-strategy::exponential"),
+        );
+        reconnect_menu.append_section(Some("Stratégie"), &strategy_section);
+        edit_menu.append_submenu(Some("Reconnexion automatique"), &reconnect_menu);
         menubar_model.append_submenu(Some("Édition"), &edit_menu);
 
+        let view_menu = gio::Menu::new();
+        view_menu.append(Some("Mode compact"), Some("win.compact-mode"));
+        view_menu.append(
+            Some("Afficher les caractères de contrôle"),
+            Some("win.show-control-chars"),
+        );
+        let delimiter_menu = gio::Menu::new();
+        for (label, id) in [
+            ("LF (\\n)", "LF"),
+            ("CR (\\r)", "CR"),
+            ("CRLF (\\r\\n)", "CRLF"),
+            ("Tous (\\r, \\n, \\r\\n)", "ANY"),
+        ] {
+            delimiter_menu.append(Some(label), Some(&format!("win.line-delimiter::{id}")));
+        }
+        view_menu.append_submenu(Some("Fin de ligne reçue"), &delimiter_menu);
+        view_menu.append(Some("Convertir les tabulations"), Some("win.expand-tabs"));
+        view_menu.append(
+            Some("Figer les statistiques en consultant l'historique"),
+            Some("win.freeze-stats"),
+        );
+        view_menu.append(
+            Some("Mesurer la latence requête/réponse"),
+            Some("win.measure-latency"),
+        );
+        view_menu.append(Some("Affichage hexadécimal"), Some("win.hex-view"));
+        let framing_menu = gio::Menu::new();
+        for mode in FramingMode::all() {
+            framing_menu.append(
+                Some(mode.display_name()),
+                Some(&format!("win.frame-decoding::{}", mode.id())),
+            );
+        }
+        let delimiters_section = gio::Menu::new();
+        delimiters_section.append(
+            Some("Définir les délimiteurs…"),
+            Some("win.frame-delimiters"),
+        );
+        framing_menu.append_section(None, &delimiters_section);
+        view_menu.append_submenu(Some("Décodage de trame"), &framing_menu);
+        let nul_menu = gio::Menu::new();
+        for handling in NulHandling::all() {
+            nul_menu.append(
+                Some(handling.display_name()),
+                Some(&format!("win.nul-handling::{}", handling.id())),
+            );
+        }
+        view_menu.append_submenu(Some("Octets NUL"), &nul_menu);
+        let form_feed_menu = gio::Menu::new();
+        for handling in FormFeedHandling::all() {
+            form_feed_menu.append(
+                Some(handling.display_name()),
+                Some(&format!("win.form-feed::{}", handling.id())),
+            );
+        }
+        view_menu.append_submenu(Some("Saut de page (^L)"), &form_feed_menu);
+        let cursor_menu = gio::Menu::new();
+        let cursor_styles = gio::Menu::new();
+        for style in CursorStyle::all() {
+            cursor_styles.append(
+                Some(style.display_name()),
+                Some(&format!("win.cursor-style::{}", style.id())),
+            );
+        }
+        cursor_menu.append_section(None, &cursor_styles);
+        cursor_menu.append(Some("Clignotant"), Some("win.cursor-blink"));
+        view_menu.append_submenu(Some("Curseur"), &cursor_menu);
+        let highlight_section = gio::Menu::new();
+        highlight_section.append(Some("Coloration des lignes"), Some("win.highlight-lines"));
+        highlight_section.append(
+            Some("Règles de coloration…"),
+            Some("win.edit-highlight-rules"),
+        );
+        highlight_section.append(Some("Afficher la mire de couleurs"), Some("win.color-test"));
+        view_menu.append_section(None, &highlight_section);
+        let notify_section = gio::Menu::new();
+        notify_section.append(Some("Notifier à la connexion"), Some("win.notify-connect"));
+        notify_section.append(
+            Some("Notifier à la déconnexion"),
+            Some("win.notify-disconnect"),
+        );
+        view_menu.append_section(None, &notify_section);
+        menubar_model.append_submenu(Some("Affichage"), &view_menu);
+
         let tools_menu = gio::Menu::new();
         tools_menu.append(Some("Calculatrice & Convertisseur"), Some("win.open-tools"));
+        tools_menu.append(Some("Détails de la connexion"), Some("win.connection-info"));
+        tools_menu.append(Some("Hôtes SSH connus…"), Some("win.known-hosts"));
+        tools_menu.append(
+            Some("Oublier le mot de passe SSH"),
+            Some("win.forget-ssh-password"),
+        );
+        tools_menu.append(Some("Fichiers distants (SFTP)…"), Some("win.sftp-browser"));
+        tools_menu.append(Some("Suspendre la lecture"), Some("win.pause-reading"));
+        tools_menu.append(Some("Envoyer un fichier…"), Some("win.send-file"));
+        tools_menu.append(
+            Some("Envoyer un fichier Intel HEX…"),
+            Some("win.send-intel-hex"),
+        );
         menubar_model.append_submenu(Some("Outils"), &tools_menu);
 
+        // Macros : entrées générées depuis les paramètres (`refresh_macros`)
+        let macros_menu = gio::Menu::new();
+        menubar_model.append_submenu(Some("Macros"), &macros_menu);
+
         let help_menu = gio::Menu::new();
         help_menu.append(Some("À propos"), Some("win.about"));
         menubar_model.append_submenu(Some("Aide"), &help_menu);
@@ -97,6 +429,7 @@ impl MainWindow {
         let separator = gtk4::Separator::new(Orientation::Horizontal);
         main_box.append(&separator);
 
+        main_box.append(&terminal.find_bar.search_bar);
         main_box.append(&terminal.container);
 
         let separator2 = gtk4::Separator::new(Orientation::Horizontal);
@@ -113,10 +446,6 @@ impl MainWindow {
         toolbar_view.set_content(Some(&toast_overlay));
         window.set_content(Some(&toolbar_view));
 
-        // Appliquer le thème initial
-        let theme = Theme::from_str_name(&settings.borrow().settings().ui.theme);
-        ThemeManager::apply(theme);
-
         let main_win = Rc::new(Self {
             window,
             header,
@@ -125,47 +454,39 @@ impl MainWindow {
             input,
             settings,
             connection_tx: RefCell::new(None),
+            active_connection: Cell::new(None),
+            connected_since: Cell::new(None),
+            connection_description: RefCell::new(String::new()),
+            connection_generation: Cell::new(0),
+            reconnect_attempt: Cell::new(0),
+            reconnect_timer: RefCell::new(None),
+            boot_capture_timer: RefCell::new(None),
+            password_clear_timer: RefCell::new(None),
+            session_secret: RefCell::new(None),
+            line_ending_detector: RefCell::new(None),
+            serial_identity: RefCell::new(None),
+            pending_secret: RefCell::new(None),
+            passphrase_to_remember: RefCell::new(None),
+            skip_on_connect_commands: Cell::new(false),
+            close_confirmed: Cell::new(false),
+            single_command_session: Cell::new(false),
+            send_queue: RefCell::new(std::collections::VecDeque::new()),
+            send_queue_draining: Cell::new(false),
+            pending_echoes: RefCell::new(std::collections::VecDeque::new()),
+            reconfigure_prompt_open: Cell::new(false),
+            reconnect_when_closed: Cell::new(false),
+            prompt_queue: RefCell::new(std::collections::VecDeque::new()),
+            active_prompt: RefCell::new(None),
+            toggle_actions: RefCell::new(Vec::new()),
+            profiles_menu,
+            macros_menu,
+            macro_count: Cell::new(0),
             runtime,
             toast_overlay,
         });
 
-        // Restaurer les paramètres persistés dans les widgets UI
-        {
-            let settings = main_win.settings.borrow();
-            let serial = &settings.settings().serial;
-            main_win.connection_panel.serial_panel.apply_settings(
-                serial.baudrate,
-                serial.data_bits,
-                &serial.parity,
-                serial.stop_bits,
-                &serial.flow_control,
-            );
-
-            // Rafraîchir puis restaurer le port précédemment sélectionné
-            main_win.connection_panel.serial_panel.refresh_ports();
-            main_win
-                .connection_panel
-                .serial_panel
-                .select_port_by_device(&settings.settings().serial.port);
-
-            let ssh = &settings.settings().ssh;
-            main_win.connection_panel.ssh_panel.apply_settings(
-                &ssh.host,
-                ssh.port,
-                &ssh.username,
-                &ssh.key_path,
-            );
-            main_win
-                .connection_panel
-                .ssh_panel
-                .set_remember_secrets(ssh.remember_secrets);
-            main_win
-                .connection_panel
-                .ssh_panel
-                .set_favorites(&settings.settings().ssh_favorites);
-        }
-
-        main_win.load_saved_ssh_secrets();
+        main_win.restore_settings();
+        main_win.refresh_profiles_menu();
 
         // Message de bienvenue
         main_win
@@ -174,17 +495,8 @@ impl MainWindow {
         main_win.terminal.append_system(
             "Sélectionnez un mode de connexion (Série ou SSH) et cliquez sur Connecter.",
         );
-
-        // Initialiser le dropdown de fin de ligne depuis les paramètres
-        {
-            let le = main_win.settings.borrow().settings().ui.line_ending.clone();
-            let idx = match le.as_str() {
-                "CR" => 1,
-                "CRLF" => 2,
-                "None" => 3,
-                _ => 0, // LF par défaut
-            };
-            main_win.input.line_ending_dropdown.set_selected(idx);
+        if !main_win.connection_panel.serial_panel.has_ports() {
+            main_win.show_no_ports_help();
         }
 
         // Connecter les signaux
@@ -195,16 +507,399 @@ impl MainWindow {
         main_win
     }
 
+    /// Applique les paramètres persistés du profil actif aux widgets UI.
+    ///
+    /// Appelé à la construction puis à chaque changement de profil.
+    fn restore_settings(self: &Rc<Self>) {
+        let profile = self.settings.borrow().profile().to_string();
+        if profile == DEFAULT_PROFILE {
+            self.window.set_title(Some("SerialSSHTerm"));
+        } else {
+            self.window
+                .set_title(Some(&format!("SerialSSHTerm — {profile}")));
+        }
+
+        // Thème
+        let theme = Theme::from_str_name(&self.settings.borrow().settings().ui.theme);
+        ThemeManager::apply(theme);
+        if let Some(action) = self
+            .window
+            .lookup_action("set-theme")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            action.set_state(&theme.id().to_variant());
+        }
+
+        // Restaurer les paramètres persistés dans les widgets UI.
+        // Copie préalable : les signaux des widgets peuvent réemprunter les paramètres.
+        let (serial, ssh, favorites) = {
+            let settings = self.settings.borrow();
+            (
+                settings.settings().serial.clone(),
+                settings.settings().ssh.clone(),
+                settings.settings().ssh_favorites.clone(),
+            )
+        };
+        self.connection_panel.serial_panel.apply_settings(
+            serial.baudrate,
+            serial.data_bits,
+            &serial.parity,
+            serial.stop_bits,
+            &serial.flow_control,
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        self.connection_panel
+            .serial_panel
+            .break_spin
+            .set_value(serial.break_ms as f64);
+
+        // Rafraîchir puis restaurer le port précédemment sélectionné
+        self.connection_panel.serial_panel.refresh_ports();
+        self.connection_panel
+            .serial_panel
+            .select_port_by_device(&serial.port);
+        self.connection_panel.refresh_connect_availability();
+
+        self.connection_panel.ssh_panel.apply_settings(
+            &ssh.host,
+            ssh.port,
+            &ssh.username,
+            &ssh.key_path,
+        );
+        self.connection_panel
+            .ssh_panel
+            .set_remember_secrets(ssh.remember_secrets);
+        self.connection_panel
+            .ssh_panel
+            .set_connect_timeout_secs(ssh.connect_timeout_secs);
+        self.connection_panel
+            .ssh_panel
+            .set_use_agent(ssh.auth_method == "agent");
+        self.connection_panel
+            .ssh_panel
+            .set_use_interactive(ssh.auth_method == "interactive");
+        self.connection_panel
+            .ssh_panel
+            .set_port_forwards(&ssh.port_forwards);
+        self.connection_panel.ssh_panel.set_favorites(&favorites);
+        self.connection_panel.ssh_panel.set_host_aliases(
+            ssh_config::load_user_hosts()
+                .into_iter()
+                .map(|host| host.alias)
+                .collect(),
+        );
+
+        self.load_saved_ssh_secrets();
+
+        self.input
+            .set_at_mode(self.settings.borrow().settings().ui.at_mode);
+        self.refresh_send_defaults();
+
+        self.set_compact_mode(self.settings.borrow().settings().ui.compact_mode);
+        self.terminal
+            .set_tx_timestamps_enabled(self.settings.borrow().settings().ui.show_tx_timestamps);
+        self.terminal
+            .set_rx_timestamps_enabled(self.settings.borrow().settings().ui.show_rx_timestamps);
+        self.terminal
+            .set_control_chars_visible(self.settings.borrow().settings().ui.show_control_chars);
+        self.apply_highlight_rules();
+        self.terminal
+            .set_line_delimiter(LineDelimiter::from_str_name(
+                &self.settings.borrow().settings().ui.line_delimiter,
+            ));
+        self.terminal.set_cursor_style(CursorStyle::from_str_name(
+            &self.settings.borrow().settings().ui.cursor_style,
+        ));
+        self.terminal
+            .set_cursor_blink(self.settings.borrow().settings().ui.cursor_blink);
+        self.terminal
+            .set_max_lines(self.settings.borrow().settings().ui.max_scrollback_lines);
+        {
+            let settings = self.settings.borrow();
+            let ui = &settings.settings().ui;
+            self.terminal
+                .set_tab_expansion(ui.expand_tabs.then_some(ui.tab_width));
+        }
+        self.terminal.set_framing_mode(FramingMode::from_str_name(
+            &self.settings.borrow().settings().ui.frame_decoding,
+        ));
+        self.apply_frame_delimiters();
+        self.terminal.set_nul_handling(NulHandling::from_str_name(
+            &self.settings.borrow().settings().ui.nul_handling,
+        ));
+        self.terminal
+            .set_form_feed_handling(FormFeedHandling::from_str_name(
+                &self.settings.borrow().settings().ui.form_feed,
+            ));
+
+        // Resynchroniser les cases à cocher et choix du menu
+        for (action, get, apply) in self.toggle_actions.borrow().iter() {
+            let enabled = get(self.settings.borrow().settings());
+            action.set_state(&enabled.to_variant());
+            apply(self, enabled);
+        }
+        if let Some(action) = self
+            .window
+            .lookup_action("reconnect-strategy")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let strategy = BackoffStrategy::from_str_name(
+                &self.settings.borrow().settings().reconnect.strategy,
+            );
+            action.set_state(&strategy.id().to_variant());
+        }
+        if let Some(action) = self
+            .window
+            .lookup_action("line-delimiter")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let delimiter =
+                LineDelimiter::from_str_name(&self.settings.borrow().settings().ui.line_delimiter);
+            action.set_state(&delimiter.id().to_variant());
+        }
+        if let Some(action) = self
+            .window
+            .lookup_action("cursor-style")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let style =
+                CursorStyle::from_str_name(&self.settings.borrow().settings().ui.cursor_style);
+            action.set_state(&style.id().to_variant());
+        }
+        if let Some(action) = self
+            .window
+            .lookup_action("form-feed")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let handling =
+                FormFeedHandling::from_str_name(&self.settings.borrow().settings().ui.form_feed);
+            action.set_state(&handling.id().to_variant());
+        }
+        if let Some(action) = self
+            .window
+            .lookup_action("nul-handling")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let handling =
+                NulHandling::from_str_name(&self.settings.borrow().settings().ui.nul_handling);
+            action.set_state(&handling.id().to_variant());
+        }
+        if let Some(action) = self
+            .window
+            .lookup_action("backspace-key")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let mode =
+                BackspaceMode::from_str_name(&self.settings.borrow().settings().ui.backspace_key);
+            action.set_state(&mode.id().to_variant());
+        }
+        if let Some(action) = self
+            .window
+            .lookup_action("frame-decoding")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let mode =
+                FramingMode::from_str_name(&self.settings.borrow().settings().ui.frame_decoding);
+            action.set_state(&mode.id().to_variant());
+        }
+        self.refresh_macros();
+        self.refresh_read_only();
+    }
+
+    /// Reconstruit la liste des profils du menu Fichier → Profil.
+    fn refresh_profiles_menu(&self) {
+        self.profiles_menu.remove_all();
+        for name in SettingsManager::list_profiles() {
+            let label = if name == DEFAULT_PROFILE {
+                "Par défaut".to_string()
+            } else {
+                name.clone()
+            };
+            self.profiles_menu
+                .append(Some(&label), Some(&format!("win.switch-profile::{name}")));
+        }
+    }
+
+    /// Active le profil `name` et réapplique ses paramètres à l'interface.
+    ///
+    /// `create` : crée le profil à partir des paramètres courants.
+    fn switch_profile(self: &Rc<Self>, name: &str, create: bool) {
+        if self.connection_tx.borrow().is_some() {
+            self.terminal
+                .append_error("Déconnectez-vous avant de changer de profil.");
+            return;
+        }
+        if !create && self.settings.borrow().profile() == name {
+            return;
+        }
+
+        let result = {
+            let mut sm = self.settings.borrow_mut();
+            if create {
+                sm.create_profile(name)
+            } else {
+                sm.switch_profile(name)
+            }
+        };
+        if let Err(e) = result {
+            self.terminal.append_error(&format!("Profil : {e}"));
+            return;
+        }
+
+        if let Some(action) = self
+            .window
+            .lookup_action("switch-profile")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            action.set_state(&name.to_variant());
+        }
+        self.restore_settings();
+        if create {
+            self.refresh_profiles_menu();
+        }
+        self.terminal
+            .append_system(&format!("Profil de configuration actif : {name}"));
+    }
+
+    /// Demande le nom d'un nouveau profil (copie des paramètres courants).
+    /// Transmet au terminal les délimiteurs de trame enregistrés.
+    fn apply_frame_delimiters(&self) {
+        let (start, end) = {
+            let settings = self.settings.borrow();
+            let ui = &settings.settings().ui;
+            (
+                framing::unescape_bytes(&ui.frame_start),
+                framing::unescape_bytes(&ui.frame_end),
+            )
+        };
+        match (start, end) {
+            (Ok(start), Ok(end)) => self.terminal.set_frame_delimiters(start, end),
+            (Err(e), _) | (_, Err(e)) => {
+                log::warn!("Délimiteurs de trame invalides : {e}");
+            }
+        }
+    }
+
+    /// Saisie des marqueurs de début et de fin du décodage par délimiteurs.
+    fn prompt_frame_delimiters(self: &Rc<Self>) {
+        let (start, end) = {
+            let settings = self.settings.borrow();
+            let ui = &settings.settings().ui;
+            (ui.frame_start.clone(), ui.frame_end.clone())
+        };
+        let start_entry = gtk4::Entry::builder()
+            .text(start.as_str())
+            .placeholder_text("Début (vide = aucun)")
+            .build();
+        let end_entry = gtk4::Entry::builder()
+            .text(end.as_str())
+            .placeholder_text("Fin (ex: \\x03)")
+            .activates_default(true)
+            .build();
+        let fields = GtkBox::new(Orientation::Vertical, 6);
+        fields.append(&start_entry);
+        fields.append(&end_entry);
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Délimiteurs de trame"),
+            Some(
+                "Notation \\xNN, \\r, \\n, \\t. Chaque trame complète est affichée en \
+                 bloc ; une trame partielle attend son délimiteur de fin.",
+            ),
+        );
+        dialog.set_extra_child(Some(&fields));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("apply", "Appliquer");
+        dialog.set_default_response(Some("apply"));
+        dialog.set_response_appearance("apply", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "apply" {
+                return;
+            }
+            let start = start_entry.text().to_string();
+            let end = end_entry.text().to_string();
+            let parsed = framing::unescape_bytes(&start).and_then(|start_bytes| {
+                let end_bytes = framing::unescape_bytes(&end)?;
+                if end_bytes.is_empty() {
+                    return Err("Le délimiteur de fin est obligatoire".to_string());
+                }
+                Ok((start_bytes, end_bytes))
+            });
+            let (start_bytes, end_bytes) = match parsed {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    this.terminal
+                        .append_error(&format!("Délimiteurs refusés : {e}"));
+                    return;
+                }
+            };
+            {
+                let mut sm = this.settings.borrow_mut();
+                let ui = &mut sm.settings_mut().ui;
+                ui.frame_start = start;
+                ui.frame_end = end;
+                if let Err(e) = sm.save() {
+                    log::warn!("Impossible de sauvegarder les délimiteurs de trame : {e}");
+                }
+            }
+            this.terminal.append_system(&format!(
+                "Délimiteurs de trame : début « {} », fin « {} ».",
+                framing::escape_bytes(&start_bytes),
+                framing::escape_bytes(&end_bytes)
+            ));
+            this.terminal.set_frame_delimiters(start_bytes, end_bytes);
+            if let Some(action) = this
+                .window
+                .lookup_action("frame-decoding")
+                .and_downcast::<gio::SimpleAction>()
+            {
+                action.activate(Some(&FramingMode::Delimited.id().to_variant()));
+            }
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    fn prompt_new_profile(self: &Rc<Self>) {
+        let name_entry = gtk4::Entry::builder()
+            .placeholder_text("Nom du profil (ex: travail)")
+            .activates_default(true)
+            .build();
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Nouveau profil"),
+            Some("Le profil reprend les paramètres actuels. Lettres, chiffres, - et _ uniquement."),
+        );
+        dialog.set_extra_child(Some(&name_entry));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("create", "Créer");
+        dialog.set_default_response(Some("create"));
+        dialog.set_response_appearance("create", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "create" {
+                this.switch_profile(name_entry.text().trim(), true);
+            }
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
     // =========================================================================
     // Actions GIO (menu, raccourcis)
     // =========================================================================
 
     fn setup_actions(win: &Rc<Self>) {
         // Action : changer de thème
+        let current_theme = Theme::from_str_name(&win.settings.borrow().settings().ui.theme);
         let theme_action = gio::SimpleAction::new_stateful(
             "set-theme",
             Some(&String::static_variant_type()),
-            &"dark".to_variant(),
+            &current_theme.id().to_variant(),
         );
         {
             let w = win.clone();
@@ -221,6 +916,33 @@ impl MainWindow {
         }
         win.window.add_action(&theme_action);
 
+        // Action : changer de profil de configuration
+        let current_profile = win.settings.borrow().profile().to_string();
+        let profile_action = gio::SimpleAction::new_stateful(
+            "switch-profile",
+            Some(&String::static_variant_type()),
+            &current_profile.to_variant(),
+        );
+        {
+            let w = win.clone();
+            profile_action.connect_activate(move |_, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    w.switch_profile(&name, false);
+                }
+            });
+        }
+        win.window.add_action(&profile_action);
+
+        // Action : créer un profil
+        let new_profile_action = gio::SimpleAction::new("new-profile", None);
+        {
+            let w = win.clone();
+            new_profile_action.connect_activate(move |_, _| {
+                w.prompt_new_profile();
+            });
+        }
+        win.window.add_action(&new_profile_action);
+
         // Action : sauvegarder les logs
         let save_action = gio::SimpleAction::new("save-logs", None);
         {
@@ -231,693 +953,4561 @@ impl MainWindow {
         }
         win.window.add_action(&save_action);
 
+        // Action : importer des favoris SSH (~/.ssh/config ou CSV)
+        let import_action = gio::SimpleAction::new("import-ssh-favorites", None);
+        {
+            let w = win.clone();
+            import_action.connect_activate(move |_, _| {
+                w.import_ssh_favorites();
+            });
+        }
+        win.window.add_action(&import_action);
+
         // Action : ouvrir le menu Outils
         let tools_action = gio::SimpleAction::new("open-tools", None);
         {
             let w = win.clone();
             tools_action.connect_activate(move |_, _| {
-                open_tools_dialog(&w.window);
+                let state = w.clone();
+                let can_send =
+                    move || state.connection_tx.borrow().is_some() && !state.is_read_only();
+                let sender = w.clone();
+                open_tools_dialog(&w.window, can_send, move |bytes| {
+                    let hex = framing::hex_bytes(&bytes);
+                    let sent = sender.dispatch_send(bytes);
+                    if sent {
+                        sender.echo_sent(&format!("→ [HEX] {hex}\n"));
+                    }
+                    sent
+                });
             });
         }
         win.window.add_action(&tools_action);
 
-        // Action : effacer le terminal
-        let clear_action = gio::SimpleAction::new("clear-terminal", None);
+        // Action : reconnecter au dernier favori SSH utilisé
+        let reconnect_action = gio::SimpleAction::new("reconnect-last-favorite", None);
         {
             let w = win.clone();
-            clear_action.connect_activate(move |_, _| {
-                w.terminal.clear();
-                w.terminal.append_system("Terminal effacé.");
+            reconnect_action.connect_activate(move |_, _| {
+                w.reconnect_last_favorite();
             });
         }
-        win.window.add_action(&clear_action);
+        win.window.add_action(&reconnect_action);
 
-        // Action : à propos
-        let about_action = gio::SimpleAction::new("about", None);
+        // Action : explorateur SFTP sur la session SSH courante
+        let sftp_action = gio::SimpleAction::new("sftp-browser", None);
         {
             let w = win.clone();
-            about_action.connect_activate(move |_, _| {
-                let about = libadwaita::AboutDialog::builder()
-                    .application_name("SerialSSHTerm")
-                    .version("1.0.0")
-                    .developer_name("M@nu")
-                    .comments(
-                        "Terminal série et SSH professionnel\nÉcrit en Rust + GTK4/Libadwaita",
-                    )
-                    .license_type(gtk4::License::MitX11)
-                    .website("https://github.com/weedmanu/SerialSSHTerm")
-                    .application_icon("utilities-terminal")
-                    .build();
-                about.present(Some(&w.window.clone().upcast::<gtk4::Widget>()));
+            sftp_action.connect_activate(move |_, _| {
+                w.open_sftp_browser();
             });
         }
-        win.window.add_action(&about_action);
+        win.window.add_action(&sftp_action);
 
-        // Action : quitter
-        let close_action = gio::SimpleAction::new("close", None);
+        // Action : envoyer un fichier brut, par morceaux
+        let send_file_action = gio::SimpleAction::new("send-file", None);
         {
             let w = win.clone();
-            close_action.connect_activate(move |_, _| {
-                w.window.close();
+            send_file_action.connect_activate(move |_, _| {
+                w.choose_file_to_send();
             });
         }
-        win.window.add_action(&close_action);
-
-        // Raccourcis clavier
-        let app = win
-            .window
-            .application()
-            .expect("Window doit avoir une application");
-        app.set_accels_for_action("win.save-logs", &["<Ctrl>s"]);
-        app.set_accels_for_action("win.clear-terminal", &["<Ctrl>l"]);
-        app.set_accels_for_action("win.open-tools", &["<Ctrl>t"]);
-    }
-
-    // =========================================================================
-    // Signaux (boutons, entrées, etc.)
-    // =========================================================================
+        win.window.add_action(&send_file_action);
 
-    #[allow(clippy::too_many_lines)]
-    fn setup_signals(win: &Rc<Self>) {
-        // Bouton Connecter / Déconnecter
+        // Action : envoyer un fichier Intel HEX (bootloader série)
+        let intel_hex_action = gio::SimpleAction::new("send-intel-hex", None);
         {
             let w = win.clone();
-            win.connection_panel
-                .connect_button
-                .connect_clicked(move |_| {
-                    w.toggle_connection();
-                });
+            intel_hex_action.connect_activate(move |_, _| {
+                w.choose_intel_hex_file();
+            });
         }
+        win.window.add_action(&intel_hex_action);
 
-        // Bouton Effacer
+        // Action : lien partageable de la connexion configurée
+        let export_action = gio::SimpleAction::new("export-connection", None);
         {
             let w = win.clone();
-            win.connection_panel.clear_button.connect_clicked(move |_| {
-                w.terminal.clear();
-                w.terminal.append_system("Terminal effacé.");
+            export_action.connect_activate(move |_, _| {
+                w.export_connection();
             });
         }
+        win.window.add_action(&export_action);
 
-        // Bouton Rafraîchir les ports série
+        // Action cachée (Ctrl+Alt+Maj+F12) : banc d'essai du rendu ANSI
+        let render_test_action = gio::SimpleAction::new("render-test-stream", None);
         {
             let w = win.clone();
-            win.connection_panel
-                .serial_panel
-                .refresh_button
-                .connect_clicked(move |_| {
-                    w.connection_panel.serial_panel.refresh_ports();
-                    w.terminal.append_system("Ports série rafraîchis.");
-                });
+            render_test_action.connect_activate(move |_, _| {
+                w.run_render_test();
+            });
         }
+        win.window.add_action(&render_test_action);
 
-        // Bouton Envoyer
+        // Action : mire de couleurs ANSI (vérifier le thème et le rendu)
+        let color_test_action = gio::SimpleAction::new("color-test", None);
         {
             let w = win.clone();
-            win.input.send_button.connect_clicked(move |_| {
-                w.send_data();
+            color_test_action.connect_activate(move |_, _| {
+                w.terminal.append_ansi(&color_test_pattern());
             });
         }
+        win.window.add_action(&color_test_action);
 
-        // Entrée : Envoi sur Enter
+        // Action : ouvrir une seconde connexion vers la même cible
+        let duplicate_action = gio::SimpleAction::new("duplicate-connection", None);
         {
             let w = win.clone();
-            win.input.entry.connect_activate(move |_| {
-                w.send_data();
+            duplicate_action.connect_activate(move |_, _| {
+                w.duplicate_connection();
             });
         }
+        win.window.add_action(&duplicate_action);
 
-        // Bouton Sauvegarder logs (header bar)
+        // Action : couper la connexion de cette fenêtre (active seulement si
+        // connectée ou en attente de reconnexion), déclenchée par « Tout déconnecter »
+        let panic_action = gio::SimpleAction::new("panic-disconnect", None);
+        panic_action.set_enabled(false);
         {
             let w = win.clone();
-            win.header.save_log_button.connect_clicked(move |_| {
-                w.save_logs();
+            panic_action.connect_activate(move |_, _| {
+                w.cancel_reconnect();
+                if w.connection_tx.borrow().is_some() {
+                    w.disconnect();
+                }
             });
         }
+        win.window.add_action(&panic_action);
 
-        // Synchroniser le dropdown de fin de ligne avec les paramètres
+        // Action : déconnecter toutes les fenêtres de l'application
+        let disconnect_all_action = gio::SimpleAction::new("disconnect-all", None);
         {
             let w = win.clone();
-            win.input
-                .line_ending_dropdown
-                .connect_selected_notify(move |dropdown| {
-                    let le_str = match dropdown.selected() {
-                        1 => "CR",
-                        2 => "CRLF",
-                        3 => "None",
-                        _ => "LF",
-                    };
-                    w.settings.borrow_mut().set_line_ending(le_str);
-                });
+            disconnect_all_action.connect_activate(move |_, _| {
+                w.disconnect_all();
+            });
         }
+        win.window.add_action(&disconnect_all_action);
 
+        // Action : capture brute des octets échangés (état = capture en cours)
+        let raw_capture_action =
+            gio::SimpleAction::new_stateful("raw-capture", None, &false.to_variant());
         {
             let w = win.clone();
-            win.connection_panel
-                .ssh_panel
-                .remember_secrets_check
-                .connect_toggled(move |checkbox| {
-                    let enabled = checkbox.is_active();
+            raw_capture_action.connect_activate(move |action, _| {
+                let capturing = action
+                    .state()
+                    .and_then(|v| v.get::<bool>())
+                    .unwrap_or(false);
+                if capturing {
+                    w.send_control_command(ConnectionCommand::StopCapture);
+                } else {
+                    w.start_raw_capture();
+                }
+            });
+        }
+        win.window.add_action(&raw_capture_action);
+
+        // Actions : ouvrir les dossiers de configuration et de journaux
+        for (name, logs) in [("open-config-dir", false), ("open-log-dir", true)] {
+            let action = gio::SimpleAction::new(name, None);
+            let w = win.clone();
+            action.connect_activate(move |_, _| {
+                let dir = {
+                    let settings = w.settings.borrow();
+                    if logs {
+                        settings.log_directory()
+                    } else {
+                        settings.config_directory()
+                    }
+                };
+                w.open_directory(&dir);
+            });
+            win.window.add_action(&action);
+        }
 
+        // Action : éditer les règles de coloration des lignes
+        let highlight_rules_action = gio::SimpleAction::new("edit-highlight-rules", None);
+        {
+            let w = win.clone();
+            highlight_rules_action.connect_activate(move |_, _| {
+                let rules = w.settings.borrow().settings().ui.highlight_rules.clone();
+                let this = w.clone();
+                open_highlight_rules_dialog(&w.window, &rules, move |updated| {
                     {
-                        let mut sm = w.settings.borrow_mut();
-                        sm.settings_mut().ssh.remember_secrets = enabled;
+                        let mut sm = this.settings.borrow_mut();
+                        sm.settings_mut().ui.highlight_rules = updated.to_vec();
                         if let Err(e) = sm.save() {
-                            log::warn!("Impossible de sauvegarder remember_secrets : {e}");
+                            log::warn!("Impossible de sauvegarder les règles de coloration : {e}");
                         }
                     }
+                    this.apply_highlight_rules();
+                });
+            });
+        }
+        win.window.add_action(&highlight_rules_action);
 
-                    let sp = &w.connection_panel.ssh_panel;
-                    let host = sp.host();
-                    let port = sp.port();
-                    let username = sp.username();
-                    let key_path = sp.key_path();
+        // Action : détails de la connexion active
+        let info_action = gio::SimpleAction::new("connection-info", None);
+        {
+            let w = win.clone();
+            info_action.connect_activate(move |_, _| {
+                w.show_connection_info();
+            });
+        }
+        win.window.add_action(&info_action);
 
-                    if !enabled {
-                        if !host.is_empty() && !username.is_empty() {
-                            if let Err(e) = secrets::delete_ssh_password(&host, port, &username) {
-                                log::warn!("Suppression password keyring impossible : {e}");
-                            }
-                            if let Err(e) = secrets::delete_ssh_key_passphrase(
-                                &host, port, &username, &key_path,
-                            ) {
-                                log::warn!("Suppression passphrase keyring impossible : {e}");
-                            }
-                        }
-                        sp.clear_password();
-                        sp.clear_passphrase();
-                    } else {
-                        w.load_saved_ssh_secrets();
-                    }
-                });
+        // Action : hôtes SSH connus (~/.ssh/known_hosts)
+        let known_hosts_action = gio::SimpleAction::new("known-hosts", None);
+        {
+            let w = win.clone();
+            known_hosts_action.connect_activate(move |_, _| {
+                open_known_hosts_dialog(&w.window);
+            });
         }
+        win.window.add_action(&known_hosts_action);
 
-        // Case à cocher : arrêt du défilement automatique
+        // Action : supprimer du trousseau le mot de passe de la cible SSH
+        let forget_password_action = gio::SimpleAction::new("forget-ssh-password", None);
         {
-            let terminal = win.terminal.text_view.clone();
             let w = win.clone();
-            win.input
-                .stop_scroll_checkbox
-                .connect_toggled(move |checkbox| {
-                    let auto_scroll = !checkbox.is_active();
-                    w.terminal.set_auto_scroll_enabled(auto_scroll);
-                    if auto_scroll {
-                        let end_mark = w.terminal.buffer.create_mark(
-                            None,
-                            &w.terminal.buffer.end_iter(),
-                            false,
-                        );
-                        terminal.scroll_to_mark(&end_mark, 0.0, false, 0.0, 1.0);
-                        w.terminal.buffer.delete_mark(&end_mark);
-                    }
-                });
+            forget_password_action.connect_activate(move |_, _| w.forget_ssh_password());
         }
+        win.window.add_action(&forget_password_action);
 
-        // Parcourir clé SSH
+        // Action : coller le presse-papiers et l'envoyer
+        let paste_send_action = gio::SimpleAction::new("paste-send", None);
         {
             let w = win.clone();
-            win.connection_panel
-                .ssh_panel
-                .key_browse_button
-                .connect_clicked(move |_| {
-                    let dialog = FileDialog::builder()
-                        .title("Sélectionner la clé SSH")
-                        .build();
+            paste_send_action.connect_activate(move |_, _| {
+                w.paste_and_send();
+            });
+        }
+        win.window.add_action(&paste_send_action);
 
-                    let key_entry = w.connection_panel.ssh_panel.key_path_entry.clone();
-                    dialog.open(Some(&w.window), gio::Cancellable::NONE, move |result| {
-                        if let Ok(file) = result {
-                            if let Some(path) = file.path() {
-                                key_entry.set_text(&path.to_string_lossy());
-                            }
-                        }
-                    });
+        // Action : renvoyer le texte sélectionné dans le terminal
+        let resend_selection_action = gio::SimpleAction::new("resend-selection", None);
+        resend_selection_action.set_enabled(false);
+        {
+            let w = win.clone();
+            resend_selection_action.connect_activate(move |_, _| {
+                w.resend_selection();
+            });
+        }
+        {
+            let action = resend_selection_action.clone();
+            win.terminal
+                .buffer
+                .connect_has_selection_notify(move |buffer| {
+                    action.set_enabled(buffer.has_selection());
                 });
         }
+        win.window.add_action(&resend_selection_action);
 
-        // Ajouter aux favoris SSH
+        // Action : recherche dans le terminal
+        let find_action = gio::SimpleAction::new("find", None);
         {
             let w = win.clone();
-            win.connection_panel
-                .ssh_panel
-                .add_favorite_button
-                .connect_clicked(move |_| {
-                    w.add_current_ssh_favorite();
+            find_action.connect_activate(move |_, _| w.terminal.show_find_bar());
+        }
+        win.window.add_action(&find_action);
+        {
+            // La barre coupe le défilement puis le rétablit : la case suit.
+            let w = win.clone();
+            win.terminal
+                .find_bar
+                .connect_auto_scroll_changed(move |enabled| {
+                    w.input.stop_scroll_checkbox.set_active(!enabled);
                 });
         }
 
-        // Appliquer un favori SSH sélectionné
+        // Actions : marque-pages dans l'historique
+        let toggle_bookmark_action = gio::SimpleAction::new("toggle-bookmark", None);
         {
             let w = win.clone();
-            win.connection_panel
-                .ssh_panel
-                .favorite_dropdown
-                .connect_selected_notify(move |_| {
-                    w.apply_selected_ssh_favorite();
+            toggle_bookmark_action.connect_activate(move |_, _| {
+                let added = w.terminal.toggle_bookmark();
+                w.show_toast(if added {
+                    "Marque-page ajouté"
+                } else {
+                    "Marque-page retiré"
                 });
+            });
         }
-
-        // Sauvegarder la taille de fenêtre à la fermeture
+        win.window.add_action(&toggle_bookmark_action);
+        for (name, forward) in [("next-bookmark", true), ("previous-bookmark", false)] {
+            let action = gio::SimpleAction::new(name, None);
+            let w = win.clone();
+            action.connect_activate(move |_, _| w.goto_bookmark(forward));
+            win.window.add_action(&action);
+        }
+        let show_bookmarks_action = gio::SimpleAction::new("show-bookmarks", None);
         {
             let w = win.clone();
-            win.window.connect_close_request(move |window| {
-                let (width, height) = (window.width(), window.height());
-                w.settings.borrow_mut().set_window_size(width, height);
-                let _ = w.settings.borrow().save();
-
-                // Déconnecter proprement
-                if let Some(tx) = w.connection_tx.borrow_mut().take() {
-                    let _ = tx.try_send(ConnectionCommand::Disconnect);
-                }
+            show_bookmarks_action.connect_activate(move |_, _| w.show_bookmarks());
+        }
+        win.window.add_action(&show_bookmarks_action);
+        let terminal_menu = gio::Menu::new();
+        terminal_menu.append(Some("Renvoyer la sélection"), Some("win.resend-selection"));
+        win.terminal.text_view.set_extra_menu(Some(&terminal_menu));
 
-                log::info!("Application fermée proprement.");
-                glib::Propagation::Proceed
+        // Action : effacer le terminal
+        let clear_action = gio::SimpleAction::new("clear-terminal", None);
+        {
+            let w = win.clone();
+            clear_action.connect_activate(move |_, _| {
+                w.terminal.clear();
+                w.terminal.append_system("Terminal effacé.");
             });
         }
-    }
-
-    // =========================================================================
-    // Logique métier
-    // =========================================================================
+        win.window.add_action(&clear_action);
 
-    /// Bascule connexion / déconnexion.
-    fn toggle_connection(self: &Rc<Self>) {
-        let is_connected = self.connection_tx.borrow().is_some();
+        // Action : effacer l'historique en conservant l'écran visible
+        let clear_scrollback_action = gio::SimpleAction::new("clear-scrollback", None);
+        {
+            let w = win.clone();
+            clear_scrollback_action.connect_activate(move |_, _| {
+                w.terminal.clear_scrollback();
+            });
+        }
+        win.window.add_action(&clear_scrollback_action);
 
-        if is_connected {
-            self.disconnect();
-        } else {
-            self.connect();
+        // Action : régler la limite de scrollback
+        let scrollback_action = gio::SimpleAction::new("scrollback-limit", None);
+        {
+            let w = win.clone();
+            scrollback_action.connect_activate(move |_, _| {
+                w.edit_scrollback_limit();
+            });
         }
-    }
+        win.window.add_action(&scrollback_action);
 
-    /// Établit la connexion (série ou SSH) selon l'onglet actif.
-    ///
-    /// Architecture :
-    ///  - Le manager est construit (validation) sur le thread GTK.
-    ///  - La connexion effective a lieu dans une tâche tokio (via `spawn_connection_actor`).
-    ///  - Le timer `GLib` (20 ms) pompe les événements : `HostKeyUnknown`, Connected, Data...
-    ///  - Cela libère le thread GTK pendant la connexion SSH (`check_server_key`, auth).
-    fn connect(self: &Rc<Self>) {
-        // Validation + construction du manager (sans connexion).
-        let manager: Box<dyn Connection> = match if self.connection_panel.is_serial_selected() {
-            self.build_serial_manager()
-        } else {
-            self.build_ssh_manager()
-        } {
-            Ok(m) => m,
-            Err(e) => {
-                self.header.set_status("Erreur de configuration", false);
-                self.terminal.append_error(&e);
-                self.show_toast(&format!("⚠ {e}"));
-                log::error!("Erreur de configuration : {e}");
-                return;
-            }
-        };
+        // Action : régler le seuil de confirmation des envois multilignes
+        let paste_threshold_action = gio::SimpleAction::new("paste-confirm-threshold", None);
+        {
+            let w = win.clone();
+            paste_threshold_action.connect_activate(move |_, _| {
+                w.edit_paste_confirm_lines();
+            });
+        }
+        win.window.add_action(&paste_threshold_action);
 
-        if !self.connection_panel.is_serial_selected() {
-            self.connection_panel.ssh_panel.clear_password();
-            self.connection_panel.ssh_panel.clear_passphrase();
+        // Action : vider les tampons série ("input" | "output" | "all")
+        let flush_action =
+            gio::SimpleAction::new("flush-serial", Some(&String::static_variant_type()));
+        {
+            let w = win.clone();
+            flush_action.connect_activate(move |_, param| {
+                if let Some(target) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let (input, output) = match target.as_str() {
+                        "input" => (true, false),
+                        "output" => (false, true),
+                        _ => (true, true),
+                    };
+                    w.flush_serial_buffers(input, output);
+                }
+            });
         }
+        win.window.add_action(&flush_action);
 
-        // Indiquer à l'UI que la connexion est en cours.
-        self.header.set_status("Connexion en cours...", false);
-        self.terminal.append_system("Connexion en cours...");
+        // Action : suspendre la lecture côté acteur (l'état suit la confirmation)
+        let pause_reading_action =
+            gio::SimpleAction::new_stateful("pause-reading", None, &false.to_variant());
+        {
+            let w = win.clone();
+            pause_reading_action.connect_activate(move |action, _| {
+                let paused = action
+                    .state()
+                    .and_then(|v| v.get::<bool>())
+                    .unwrap_or(false);
+                // XOFF ou RTS : la suspension agit sur le périphérique.
+                if w.is_read_only() {
+                    w.terminal
+                        .append_error("Mode lecture seule — suspension de la lecture bloquée.");
+                    return;
+                }
+                let Some(tx) = w.connection_tx.borrow().clone() else {
+                    w.terminal
+                        .append_error("Non connecté — aucune lecture à suspendre.");
+                    return;
+                };
+                if let Err(e) = tx.try_send(ConnectionCommand::PauseReading(!paused)) {
+                    w.terminal
+                        .append_error(&format!("Suspension de la lecture impossible : {e}"));
+                }
+            });
+        }
+        win.window.add_action(&pause_reading_action);
 
-        // Lancer l'acteur de connexion dans le runtime tokio.
-        // `runtime.enter()` établit le contexte tokio pour `tokio::spawn`
-        //  sans bloquer le thread GTK (contrairement à `block_on`).
-        let guard = self.runtime.enter();
-        let (cmd_tx, event_rx) = spawn_connection_actor(manager);
-        drop(guard);
+        // Action : envoyer `stty rows/cols` (pas de canal PTY en série)
+        let size_action = gio::SimpleAction::new("notify-terminal-size", None);
+        {
+            let w = win.clone();
+            size_action.connect_activate(move |_, _| {
+                w.notify_terminal_size();
+            });
+        }
+        win.window.add_action(&size_action);
 
-        *self.connection_tx.borrow_mut() = Some(cmd_tx);
+        // Action : mode lecture seule (mémorisé par type de connexion)
+        let read_only_action =
+            gio::SimpleAction::new_stateful("read-only", None, &false.to_variant());
+        {
+            let w = win.clone();
+            read_only_action.connect_activate(move |_, _| {
+                let enabled = !w.is_read_only();
+                {
+                    let mut sm = w.settings.borrow_mut();
+                    match w.current_connection_type() {
+                        ConnectionType::Serial => sm.settings_mut().serial.read_only = enabled,
+                        ConnectionType::Ssh => sm.settings_mut().ssh.read_only = enabled,
+                    }
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder le mode lecture seule : {e}");
+                    }
+                }
+                w.refresh_read_only();
+                w.terminal.append_system(if enabled {
+                    "Mode lecture seule activé : aucun envoi possible."
+                } else {
+                    "Mode lecture seule désactivé."
+                });
+            });
+        }
+        win.window.add_action(&read_only_action);
+        win.refresh_read_only();
 
-        // Pont async_channel → GTK main loop via GLib timer (20 ms)
-        // SOLID : aucune dépendance GTK dans le core.
-        let this = self.clone();
-        glib::timeout_add_local(std::time::Duration::from_millis(20), move || {
-            loop {
-                match event_rx.try_recv() {
-                    Ok(ConnectionEvent::Connected {
-                        conn_type,
-                        description,
-                    }) => {
-                        let type_label = match conn_type {
-                            ConnectionType::Serial => "Série",
-                            ConnectionType::Ssh => "SSH",
-                        };
-                        this.connection_panel.set_connected(true);
-                        this.header
-                            .set_status(&format!("Connecté {type_label} — {description}"), true);
-                        this.terminal
-                            .append_system(&format!("Connecté [{type_label}] {description}"));
-                        this.input.grab_focus();
+        // Options booléennes (cases à cocher du menu)
+        Self::add_toggle_action(
+            win,
+            "freeze-stats",
+            |s| s.ui.freeze_stats_when_scrolled,
+            |s, v| s.ui.freeze_stats_when_scrolled = v,
+            Self::refresh_stats_freeze_with,
+        );
+        Self::add_toggle_action(
+            win,
+            "hex-view",
+            |s| s.ui.hex_view,
+            |s, v| s.ui.hex_view = v,
+            |w, v| w.terminal.set_hex_view(v),
+        );
+        Self::add_toggle_action(
+            win,
+            "measure-latency",
+            |s| s.ui.measure_latency,
+            |s, v| s.ui.measure_latency = v,
+            Self::set_latency_tracking,
+        );
+        Self::add_toggle_action(
+            win,
+            "detect-line-ending",
+            |s| s.ui.detect_line_ending,
+            |s, v| s.ui.detect_line_ending = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "at-mode",
+            |s| s.ui.at_mode,
+            |s, v| s.ui.at_mode = v,
+            Self::set_at_mode,
+        );
+        // Le clavier direct lit l'option à chaque touche : rien à réappliquer
+        // au changement de profil, le message ne suit que la bascule manuelle.
+        Self::add_toggle_action(
+            win,
+            "direct-keyboard",
+            |s| s.ui.direct_keyboard,
+            |s, v| s.ui.direct_keyboard = v,
+            |_, _| {},
+        );
+        if let Some(action) = win
+            .window
+            .lookup_action("direct-keyboard")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            let w = win.clone();
+            action.connect_activate(move |action, _| {
+                let enabled = action
+                    .state()
+                    .and_then(|v| v.get::<bool>())
+                    .unwrap_or(false);
+                w.set_direct_keyboard(enabled);
+            });
+        }
+        Self::add_toggle_action(
+            win,
+            "tx-timestamps",
+            |s| s.ui.show_tx_timestamps,
+            |s, v| s.ui.show_tx_timestamps = v,
+            |w, v| w.terminal.set_tx_timestamps_enabled(v),
+        );
+        Self::add_toggle_action(
+            win,
+            "rx-timestamps",
+            |s| s.ui.show_rx_timestamps,
+            |s, v| s.ui.show_rx_timestamps = v,
+            |w, v| w.terminal.set_rx_timestamps_enabled(v),
+        );
+        Self::add_toggle_action(
+            win,
+            "show-control-chars",
+            |s| s.ui.show_control_chars,
+            |s, v| s.ui.show_control_chars = v,
+            |w, v| w.terminal.set_control_chars_visible(v),
+        );
+        Self::add_toggle_action(
+            win,
+            "highlight-lines",
+            |s| s.ui.highlight_enabled,
+            |s, v| s.ui.highlight_enabled = v,
+            |w, _| w.apply_highlight_rules(),
+        );
+        Self::add_toggle_action(
+            win,
+            "compact-mode",
+            |s| s.ui.compact_mode,
+            |s, v| s.ui.compact_mode = v,
+            Self::set_compact_mode,
+        );
+        Self::add_toggle_action(
+            win,
+            "confirm-quit-connected",
+            |s| s.ui.confirm_quit_connected,
+            |s, v| s.ui.confirm_quit_connected = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "notify-connect",
+            |s| s.ui.notify_on_connect,
+            |s, v| s.ui.notify_on_connect = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "notify-disconnect",
+            |s| s.ui.notify_on_disconnect,
+            |s, v| s.ui.notify_on_disconnect = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "auto-save-logs",
+            |s| s.log.auto_save_on_disconnect,
+            |s, v| s.log.auto_save_on_disconnect = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "raw-capture-tx",
+            |s| s.log.raw_capture_tx,
+            |s, v| s.log.raw_capture_tx = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "raw-capture-tagged",
+            |s| s.log.raw_capture_tagged,
+            |s, v| s.log.raw_capture_tagged = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "raw-capture-timestamps",
+            |s| s.log.raw_capture_timestamps,
+            |s, v| s.log.raw_capture_timestamps = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "local-echo-serial",
+            |s| s.serial.local_echo,
+            |s, v| s.serial.local_echo = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "local-echo-ssh",
+            |s| s.ssh.local_echo,
+            |s, v| s.ssh.local_echo = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "auto-reconnect-serial",
+            |s| s.serial.auto_reconnect,
+            |s, v| s.serial.auto_reconnect = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "auto-reconnect-ssh",
+            |s| s.ssh.auto_reconnect,
+            |s, v| s.ssh.auto_reconnect = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "reconnect-by-identity",
+            |s| s.serial.reconnect_by_identity,
+            |s, v| s.serial.reconnect_by_identity = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "serial-break-on-connect",
+            |s| s.serial.break_on_connect,
+            |s, v| s.serial.break_on_connect = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "serial-reset-on-connect",
+            |s| s.serial.reset_on_connect,
+            |s, v| s.serial.reset_on_connect = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "expand-tabs",
+            |s| s.ui.expand_tabs,
+            |s, v| s.ui.expand_tabs = v,
+            |w, v| {
+                let width = w.settings.borrow().settings().ui.tab_width;
+                w.terminal.set_tab_expansion(v.then_some(width));
+            },
+        );
+        Self::add_toggle_action(
+            win,
+            "cursor-blink",
+            |s| s.ui.cursor_blink,
+            |s, v| s.ui.cursor_blink = v,
+            |w, v| w.terminal.set_cursor_blink(v),
+        );
+        Self::add_toggle_action(
+            win,
+            "confirm-send",
+            |s| s.ui.confirm_send,
+            |s, v| s.ui.confirm_send = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "serial-boot-capture",
+            |s| s.serial.boot_capture,
+            |s, v| s.serial.boot_capture = v,
+            |_, _| {},
+        );
+        Self::add_toggle_action(
+            win,
+            "serial-reconfigure-prompt",
+            |s| s.serial.reconfigure_on_change,
+            |s, v| s.serial.reconfigure_on_change = v,
+            |_, _| {},
+        );
+
+        // Action : stratégie de reconnexion ("fixed" | "exponential")
+        let strategy =
+            BackoffStrategy::from_str_name(&win.settings.borrow().settings().reconnect.strategy);
+        let strategy_action = gio::SimpleAction::new_stateful(
+            "reconnect-strategy",
+            Some(&String::static_variant_type()),
+            &strategy.id().to_variant(),
+        );
+        {
+            let w = win.clone();
+            strategy_action.connect_activate(move |action, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let strategy = BackoffStrategy::from_str_name(&name);
+                    action.set_state(&strategy.id().to_variant());
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().reconnect.strategy = strategy.id().to_string();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder la stratégie de reconnexion : {e}");
                     }
-                    Ok(ConnectionEvent::HostKeyUnknown {
-                        host,
-                        key_type,
-                        fingerprint,
-                        is_key_changed,
-                        decision_tx,
-                    }) => {
-                        // Afficher le dialogue de vérification de clé SSH.
-                        // Le timer CONTINUE de tourner pendant que l'utilisateur répond.
-                        show_host_key_dialog(
-                            &this.window,
-                            &host,
-                            &key_type,
-                            &fingerprint,
-                            is_key_changed,
-                            decision_tx,
-                        );
+                }
+            });
+        }
+        win.window.add_action(&strategy_action);
+
+        // Action : délimiteur de fin de ligne des données reçues
+        let delimiter =
+            LineDelimiter::from_str_name(&win.settings.borrow().settings().ui.line_delimiter);
+        let delimiter_action = gio::SimpleAction::new_stateful(
+            "line-delimiter",
+            Some(&String::static_variant_type()),
+            &delimiter.id().to_variant(),
+        );
+        {
+            let w = win.clone();
+            delimiter_action.connect_activate(move |action, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let delimiter = LineDelimiter::from_str_name(&name);
+                    action.set_state(&delimiter.id().to_variant());
+                    w.terminal.set_line_delimiter(delimiter);
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().ui.line_delimiter = delimiter.id();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder le délimiteur de ligne : {e}");
                     }
-                    Ok(ConnectionEvent::DataReceived(data)) => {
-                        this.terminal.append_ansi(&data);
+                }
+            });
+        }
+        win.window.add_action(&delimiter_action);
+
+        // Action : forme du curseur du terminal
+        let cursor_style =
+            CursorStyle::from_str_name(&win.settings.borrow().settings().ui.cursor_style);
+        let cursor_action = gio::SimpleAction::new_stateful(
+            "cursor-style",
+            Some(&String::static_variant_type()),
+            &cursor_style.id().to_variant(),
+        );
+        {
+            let w = win.clone();
+            cursor_action.connect_activate(move |action, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let style = CursorStyle::from_str_name(&name);
+                    action.set_state(&style.id().to_variant());
+                    w.terminal.set_cursor_style(style);
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().ui.cursor_style = style.id().to_string();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder le style du curseur : {e}");
                     }
-                    Ok(ConnectionEvent::Error(e)) => {
-                        this.terminal.append_error(&e);
-                        this.handle_disconnect();
-                        return glib::ControlFlow::Break;
+                }
+            });
+        }
+        win.window.add_action(&cursor_action);
+
+        // Action : rendu des octets NUL reçus
+        let nul_handling =
+            NulHandling::from_str_name(&win.settings.borrow().settings().ui.nul_handling);
+        let nul_action = gio::SimpleAction::new_stateful(
+            "nul-handling",
+            Some(&String::static_variant_type()),
+            &nul_handling.id().to_variant(),
+        );
+        {
+            let w = win.clone();
+            nul_action.connect_activate(move |action, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let handling = NulHandling::from_str_name(&name);
+                    action.set_state(&handling.id().to_variant());
+                    w.terminal.set_nul_handling(handling);
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().ui.nul_handling = handling.id().to_string();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder le rendu des octets NUL : {e}");
                     }
-                    Err(async_channel::TryRecvError::Empty) => break,
-                    Ok(ConnectionEvent::Disconnected)
-                    | Err(async_channel::TryRecvError::Closed) => {
-                        this.handle_disconnect();
-                        return glib::ControlFlow::Break;
+                }
+            });
+        }
+        win.window.add_action(&nul_action);
+
+        // Action : rendu du saut de page reçu
+        let form_feed =
+            FormFeedHandling::from_str_name(&win.settings.borrow().settings().ui.form_feed);
+        let form_feed_action = gio::SimpleAction::new_stateful(
+            "form-feed",
+            Some(&String::static_variant_type()),
+            &form_feed.id().to_variant(),
+        );
+        {
+            let w = win.clone();
+            form_feed_action.connect_activate(move |action, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let handling = FormFeedHandling::from_str_name(&name);
+                    action.set_state(&handling.id().to_variant());
+                    w.terminal.set_form_feed_handling(handling);
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().ui.form_feed = handling.id().to_string();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder le rendu du saut de page : {e}");
+                    }
+                }
+            });
+        }
+        win.window.add_action(&form_feed_action);
+
+        // Action : octet envoyé par Retour arrière en clavier direct
+        let backspace =
+            BackspaceMode::from_str_name(&win.settings.borrow().settings().ui.backspace_key);
+        let backspace_action = gio::SimpleAction::new_stateful(
+            "backspace-key",
+            Some(&String::static_variant_type()),
+            &backspace.id().to_variant(),
+        );
+        {
+            let w = win.clone();
+            backspace_action.connect_activate(move |action, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let mode = BackspaceMode::from_str_name(&name);
+                    action.set_state(&mode.id().to_variant());
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().ui.backspace_key = mode.id().to_string();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder la touche Retour arrière : {e}");
+                    }
+                }
+            });
+        }
+        win.window.add_action(&backspace_action);
+
+        // Action : décodage de trame des données reçues (SLIP, COBS…)
+        let framing =
+            FramingMode::from_str_name(&win.settings.borrow().settings().ui.frame_decoding);
+        let framing_action = gio::SimpleAction::new_stateful(
+            "frame-decoding",
+            Some(&String::static_variant_type()),
+            &framing.id().to_variant(),
+        );
+        {
+            let w = win.clone();
+            framing_action.connect_activate(move |action, param| {
+                if let Some(name) = param.and_then(gtk4::glib::Variant::get::<String>) {
+                    let mode = FramingMode::from_str_name(&name);
+                    action.set_state(&mode.id().to_variant());
+                    w.terminal.set_framing_mode(mode);
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().ui.frame_decoding = mode.id().to_string();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder le décodage de trame : {e}");
+                    }
+                }
+            });
+        }
+        win.window.add_action(&framing_action);
+
+        // Action : marqueurs du décodage par délimiteurs (STX/ETX…)
+        let frame_delimiters_action = gio::SimpleAction::new("frame-delimiters", None);
+        {
+            let w = win.clone();
+            frame_delimiters_action.connect_activate(move |_, _| {
+                w.prompt_frame_delimiters();
+            });
+        }
+        win.window.add_action(&frame_delimiters_action);
+
+        // Action : à propos
+        let about_action = gio::SimpleAction::new("about", None);
+        {
+            let w = win.clone();
+            about_action.connect_activate(move |_, _| {
+                let about = libadwaita::AboutDialog::builder()
+                    .application_name("SerialSSHTerm")
+                    .version("1.0.0")
+                    .developer_name("M@nu")
+                    .comments(
+                        "Terminal série et SSH professionnel\nÉcrit en Rust + GTK4/Libadwaita",
+                    )
+                    .license_type(gtk4::License::MitX11)
+                    .website("https://github.com/weedmanu/SerialSSHTerm")
+                    .application_icon("utilities-terminal")
+                    .build();
+                about.present(Some(&w.window.clone().upcast::<gtk4::Widget>()));
+            });
+        }
+        win.window.add_action(&about_action);
+
+        // Action : quitter
+        let close_action = gio::SimpleAction::new("close", None);
+        {
+            let w = win.clone();
+            close_action.connect_activate(move |_, _| {
+                w.window.close();
+            });
+        }
+        win.window.add_action(&close_action);
+
+        // Raccourcis clavier
+        let app = win
+            .window
+            .application()
+            .expect("Window doit avoir une application");
+        for (action, accel) in BUILTIN_ACCELS {
+            app.set_accels_for_action(action, &[*accel]);
+        }
+    }
+
+    /// Reconstruit le menu Macros et les actions `win.macro-<n>` associées.
+    ///
+    /// Un raccourci invalide, déjà pris par l'application ou par une macro
+    /// précédente est ignoré (la macro reste disponible dans le menu).
+    fn refresh_macros(self: &Rc<Self>) {
+        let app = self.window.application();
+        for index in 0..self.macro_count.replace(0) {
+            let name = format!("macro-{index}");
+            self.window.remove_action(&name);
+            if let Some(app) = &app {
+                app.set_accels_for_action(&format!("win.{name}"), &[]);
+            }
+        }
+        self.macros_menu.remove_all();
+
+        let macros = self.settings.borrow().settings().ui.macros.clone();
+        if macros.is_empty() {
+            self.macros_menu
+                .append(Some("Aucune macro (voir settings.json)"), None);
+            return;
+        }
+
+        let macro_total = macros.len();
+        let mut used_accels: Vec<(String, String)> = BUILTIN_ACCELS
+            .iter()
+            .map(|(action, accel)| (normalize_accel(accel), (*action).to_string()))
+            .collect();
+        for (index, send_macro) in macros.into_iter().enumerate() {
+            let name = format!("macro-{index}");
+            let action = gio::SimpleAction::new(&name, None);
+            {
+                let this = self.clone();
+                let send_macro = send_macro.clone();
+                action.connect_activate(move |_, _| this.send_macro(&send_macro));
+            }
+            self.window.add_action(&action);
+            self.macros_menu
+                .append(Some(&send_macro.name), Some(&format!("win.{name}")));
+
+            let accel = send_macro.accel.trim();
+            if accel.is_empty() {
+                continue;
+            }
+            let normalized = normalize_accel(accel);
+            if normalized.is_empty() {
+                self.terminal.append_error(&format!(
+                    "Macro « {} » : raccourci invalide « {accel} »",
+                    send_macro.name
+                ));
+            } else if let Some((_, owner)) = used_accels.iter().find(|(a, _)| *a == normalized) {
+                self.terminal.append_error(&format!(
+                    "Macro « {} » : raccourci « {accel} » déjà utilisé par {owner}",
+                    send_macro.name
+                ));
+            } else if let Some(app) = &app {
+                app.set_accels_for_action(&format!("win.{name}"), &[accel]);
+                used_accels.push((normalized, format!("la macro « {} »", send_macro.name)));
+            }
+        }
+        self.macro_count.set(macro_total);
+    }
+
+    /// Envoie une macro (fin de ligne sélectionnée ajoutée).
+    fn send_macro(self: &Rc<Self>, send_macro: &SendMacro) {
+        let line_ending = self.input.selected_line_ending();
+        if self.dispatch_send(format!("{}{line_ending}", send_macro.payload).into_bytes()) {
+            self.echo_sent(&format!("→ [{}] {}\n", send_macro.name, send_macro.payload));
+        }
+    }
+
+    /// Enregistre une action booléenne persistée (case à cocher dans un menu).
+    ///
+    /// `get`/`set` lisent et écrivent l'option dans `AppSettings` ;
+    /// `apply` répercute la nouvelle valeur sur l'UI.
+    fn add_toggle_action(
+        win: &Rc<Self>,
+        name: &str,
+        get: fn(&AppSettings) -> bool,
+        set: fn(&mut AppSettings, bool),
+        apply: fn(&Self, bool),
+    ) {
+        let initial = get(win.settings.borrow().settings());
+        let action = gio::SimpleAction::new_stateful(name, None, &initial.to_variant());
+        {
+            let w = win.clone();
+            let name = name.to_string();
+            action.connect_activate(move |action, _| {
+                let enabled = !action
+                    .state()
+                    .and_then(|v| v.get::<bool>())
+                    .unwrap_or(false);
+                action.set_state(&enabled.to_variant());
+
+                {
+                    let mut sm = w.settings.borrow_mut();
+                    set(sm.settings_mut(), enabled);
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder l'option {name} : {e}");
                     }
                 }
+                apply(&w, enabled);
+            });
+        }
+        win.window.add_action(&action);
+        win.toggle_actions.borrow_mut().push((action, get, apply));
+    }
+
+    // =========================================================================
+    // Signaux (boutons, entrées, etc.)
+    // =========================================================================
+
+    #[allow(clippy::too_many_lines)]
+    fn setup_signals(win: &Rc<Self>) {
+        // Clavier direct : avant les raccourcis d'édition de la vue texte
+        {
+            let key = gtk4::EventControllerKey::new();
+            key.set_propagation_phase(gtk4::PropagationPhase::Capture);
+            let w = win.clone();
+            key.connect_key_pressed(move |_, keyval, _, state| w.on_terminal_key(keyval, state));
+            win.terminal.text_view.add_controller(key);
+        }
+
+        // Bouton Connecter / Déconnecter
+        {
+            let w = win.clone();
+            win.connection_panel
+                .connect_button
+                .connect_clicked(move |_| {
+                    w.toggle_connection();
+                });
+        }
+
+        // Bouton Connecter / Déconnecter du mode compact
+        {
+            let w = win.clone();
+            win.header.connect_button.connect_clicked(move |_| {
+                w.toggle_connection();
+            });
+        }
+
+        // Lecture seule et fin de ligne suivent l'onglet sélectionné (hors connexion)
+        {
+            let w = win.clone();
+            win.connection_panel.notebook.connect_page_notify(move |_| {
+                w.refresh_read_only();
+                w.refresh_send_defaults();
+                w.connection_panel.refresh_connect_availability();
+            });
+        }
+
+        // Bouton Effacer
+        {
+            let w = win.clone();
+            win.connection_panel.clear_button.connect_clicked(move |_| {
+                w.terminal.clear();
+                w.terminal.append_system("Terminal effacé.");
+            });
+        }
+
+        // Bouton Rafraîchir les ports série
+        {
+            let w = win.clone();
+            win.connection_panel
+                .serial_panel
+                .refresh_button
+                .connect_clicked(move |_| {
+                    w.connection_panel.serial_panel.refresh_ports();
+                    w.connection_panel.refresh_connect_availability();
+                    if w.connection_panel.serial_panel.has_ports() {
+                        w.terminal.append_system("Ports série rafraîchis.");
+                    } else {
+                        w.show_no_ports_help();
+                    }
+                });
+        }
+
+        // Paramètres série modifiés pendant une connexion active
+        {
+            let sp = &win.connection_panel.serial_panel;
+            for dropdown in [
+                &sp.baud_dropdown,
+                &sp.databits_dropdown,
+                &sp.parity_dropdown,
+                &sp.stopbits_dropdown,
+                &sp.flowcontrol_dropdown,
+            ] {
+                let w = win.clone();
+                dropdown.connect_selected_notify(move |_| {
+                    w.on_serial_params_changed();
+                });
+            }
+            let w = win.clone();
+            sp.baud_entry.connect_activate(move |_| {
+                w.on_serial_params_changed();
+            });
+        }
+
+        // Bouton Envoyer
+        {
+            let w = win.clone();
+            win.input.send_button.connect_clicked(move |_| {
+                w.send_data();
+            });
+        }
+
+        // Entrée : Envoi sur Enter
+        {
+            let w = win.clone();
+            win.input.entry.connect_activate(move |_| {
+                w.send_data();
+            });
+        }
+
+        // Bouton Sauvegarder logs (header bar)
+        {
+            let w = win.clone();
+            win.header.save_log_button.connect_clicked(move |_| {
+                w.save_logs();
+            });
+        }
+
+        // Mémoriser le mode hexadécimal pour le type de connexion courant
+        {
+            let w = win.clone();
+            win.input.hex_checkbox.connect_toggled(move |check| {
+                let conn_type = w.current_connection_type();
+                w.settings
+                    .borrow_mut()
+                    .set_send_hex(conn_type, check.is_active());
+            });
+        }
+
+        // Synchroniser le dropdown de fin de ligne avec les paramètres
+        {
+            let w = win.clone();
+            win.input
+                .line_ending_dropdown
+                .connect_selected_notify(move |dropdown| {
+                    let le_str = match dropdown.selected() {
+                        1 => "CR",
+                        2 => "CRLF",
+                        3 => "None",
+                        _ => "LF",
+                    };
+                    // Le CR imposé par le mode AT ne remplace pas la fin de ligne mémorisée.
+                    if w.input.at_mode() {
+                        return;
+                    }
+                    let conn_type = w.current_connection_type();
+                    w.settings.borrow_mut().set_line_ending(conn_type, le_str);
+                });
+        }
+
+        // Lignes de contrôle DTR/RTS
+        for (button, is_dtr) in [
+            &win.connection_panel.serial_panel.dtr_button,
+            &win.connection_panel.serial_panel.rts_button,
+        ]
+        .into_iter()
+        .zip([true, false])
+        {
+            let w = win.clone();
+            button.connect_toggled(move |button| {
+                if w.connection_panel.serial_panel.is_syncing_lines() {
+                    return;
+                }
+                let level = button.is_active();
+                w.send_control_command(if is_dtr {
+                    ConnectionCommand::SetDtr(level)
+                } else {
+                    ConnectionCommand::SetRts(level)
+                });
+            });
+        }
+
+        // Break manuel, durée mémorisée
+        {
+            let w = win.clone();
+            win.connection_panel
+                .serial_panel
+                .break_button
+                .connect_clicked(move |_| {
+                    let duration_ms = w.connection_panel.serial_panel.break_ms();
+                    w.send_control_command(ConnectionCommand::SendBreak { duration_ms });
+                });
+        }
+        {
+            let w = win.clone();
+            win.connection_panel
+                .serial_panel
+                .break_spin
+                .connect_value_changed(move |_| {
+                    let mut sm = w.settings.borrow_mut();
+                    sm.settings_mut().serial.break_ms = w.connection_panel.serial_panel.break_ms();
+                    if let Err(e) = sm.save() {
+                        log::warn!("Impossible de sauvegarder la durée du break : {e}");
+                    }
+                });
+        }
+
+        // Secret saisi ou rechargé (favori, trousseau) pendant la session :
+        // il ne reste pas affiché indéfiniment.
+        for entry in [
+            &win.connection_panel.ssh_panel.password_entry,
+            &win.connection_panel.ssh_panel.passphrase_entry,
+        ] {
+            let w = win.clone();
+            entry.connect_changed(move |_| w.arm_password_clear());
+        }
+
+        {
+            let w = win.clone();
+            win.connection_panel
+                .ssh_panel
+                .remember_secrets_check
+                .connect_toggled(move |checkbox| {
+                    let enabled = checkbox.is_active();
+
+                    {
+                        let mut sm = w.settings.borrow_mut();
+                        sm.settings_mut().ssh.remember_secrets = enabled;
+                        if let Err(e) = sm.save() {
+                            log::warn!("Impossible de sauvegarder remember_secrets : {e}");
+                        }
+                    }
+
+                    let sp = &w.connection_panel.ssh_panel;
+                    let host = sp.host();
+                    let port = sp.port();
+                    let username = sp.username();
+                    let key_path = sp.key_path();
+
+                    if !enabled {
+                        if !host.is_empty() && !username.is_empty() {
+                            if let Err(e) = secrets::delete_ssh_password(&host, port, &username) {
+                                log::warn!("Suppression password keyring impossible : {e}");
+                            }
+                            if let Err(e) = secrets::delete_ssh_key_passphrase(
+                                &host, port, &username, &key_path,
+                            ) {
+                                log::warn!("Suppression passphrase keyring impossible : {e}");
+                            }
+                        }
+                        sp.clear_password();
+                        sp.clear_passphrase();
+                    } else {
+                        w.load_saved_ssh_secrets();
+                    }
+                });
+        }
+
+        // Statistiques figées tant que la vue est remontée dans l'historique
+        {
+            let adjustment = win.terminal.container.vadjustment();
+            let w = win.clone();
+            adjustment.connect_value_changed(move |_| w.refresh_stats_freeze());
+            // Nouvelles lignes sans défilement automatique : la fin s'éloigne.
+            let w = win.clone();
+            adjustment.connect_changed(move |_| w.refresh_stats_freeze());
+        }
+
+        // Redimensionnement du terminal : le PTY distant suit la grille affichée
+        {
+            let w = win.clone();
+            win.terminal.connect_grid_resized(move |cols, rows| {
+                let Some(tx) = w.connection_tx.borrow().clone() else {
+                    return false;
+                };
+                match tx.try_send(ConnectionCommand::Resize { cols, rows }) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        log::warn!("Redimensionnement {cols}x{rows} non transmis : {e}");
+                        false
+                    }
+                }
+            });
+        }
+
+        // Case à cocher : arrêt du défilement automatique
+        {
+            let terminal = win.terminal.text_view.clone();
+            let w = win.clone();
+            win.input
+                .stop_scroll_checkbox
+                .connect_toggled(move |checkbox| {
+                    let auto_scroll = !checkbox.is_active();
+                    w.terminal.set_auto_scroll_enabled(auto_scroll);
+                    if auto_scroll {
+                        let end_mark = w.terminal.buffer.create_mark(
+                            None,
+                            &w.terminal.buffer.end_iter(),
+                            false,
+                        );
+                        terminal.scroll_to_mark(&end_mark, 0.0, false, 0.0, 1.0);
+                        w.terminal.buffer.delete_mark(&end_mark);
+                    }
+                });
+        }
+
+        // Parcourir clé SSH
+        {
+            let w = win.clone();
+            win.connection_panel
+                .ssh_panel
+                .key_browse_button
+                .connect_clicked(move |_| {
+                    let dialog = FileDialog::builder()
+                        .title("Sélectionner la clé SSH")
+                        .build();
+
+                    let key_entry = w.connection_panel.ssh_panel.key_path_entry.clone();
+                    dialog.open(Some(&w.window), gio::Cancellable::NONE, move |result| {
+                        if let Ok(file) = result {
+                            if let Some(path) = file.path() {
+                                key_entry.set_text(&path.to_string_lossy());
+                            }
+                        }
+                    });
+                });
+        }
+
+        // Ajouter aux favoris SSH
+        {
+            let w = win.clone();
+            win.connection_panel
+                .ssh_panel
+                .add_favorite_button
+                .connect_clicked(move |_| {
+                    w.prompt_save_ssh_favorite();
+                });
+        }
+
+        // Supprimer le favori SSH sélectionné
+        {
+            let w = win.clone();
+            win.connection_panel
+                .ssh_panel
+                .delete_favorite_button
+                .connect_clicked(move |_| {
+                    w.confirm_delete_selected_favorite();
+                });
+        }
+
+        // Gérer les favoris SSH
+        {
+            let w = win.clone();
+            win.connection_panel
+                .ssh_panel
+                .manage_favorites_button
+                .connect_clicked(move |_| {
+                    w.open_favorites_manager();
+                });
+        }
+
+        // Appliquer un favori SSH sélectionné
+        {
+            let w = win.clone();
+            win.connection_panel
+                .ssh_panel
+                .favorite_dropdown
+                .connect_selected_notify(move |_| {
+                    w.apply_selected_ssh_favorite();
+                });
+        }
+
+        // Sauvegarder la taille de fenêtre à la fermeture
+        {
+            let w = win.clone();
+            win.window.connect_close_request(move |window| {
+                let must_confirm = w.settings.borrow().settings().ui.confirm_quit_connected
+                    && w.connection_tx.borrow().is_some()
+                    && !w.close_confirmed.get();
+                if must_confirm {
+                    w.confirm_close();
+                    return glib::Propagation::Stop;
+                }
+
+                let (width, height) = (window.width(), window.height());
+                w.settings.borrow_mut().set_window_size(width, height);
+                let _ = w.settings.borrow().save();
+
+                w.session_secret.replace(None);
+                // Déconnecter proprement
+                if let Some(tx) = w.connection_tx.borrow_mut().take() {
+                    let _ = tx.try_send(ConnectionCommand::Disconnect);
+                }
+
+                log::info!("Application fermée proprement.");
+                glib::Propagation::Proceed
+            });
+        }
+    }
+
+    /// Demande confirmation avant de fermer une fenêtre connectée ; la
+    /// fermeture n'est relancée que si l'utilisateur la valide.
+    fn confirm_close(self: &Rc<Self>) {
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Fermer la fenêtre ?"),
+            Some("Une connexion est active : la fermeture y mettra fin."),
+        );
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("close", "Déconnecter et fermer");
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
+        dialog.set_response_appearance("close", libadwaita::ResponseAppearance::Destructive);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "close" {
+                this.close_confirmed.set(true);
+                this.window.close();
+            }
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    // =========================================================================
+    // Logique métier
+    // =========================================================================
+
+    /// Bascule connexion / déconnexion.
+    fn toggle_connection(self: &Rc<Self>) {
+        let is_connected = self.connection_tx.borrow().is_some();
+        // Une action manuelle interrompt le cycle de reconnexion automatique.
+        self.cancel_reconnect();
+
+        if is_connected {
+            self.disconnect();
+        } else {
+            self.connect();
+        }
+    }
+
+    /// Établit la connexion (série ou SSH) selon l'onglet actif.
+    ///
+    /// Architecture :
+    ///  - Le manager est construit (validation) sur le thread GTK.
+    ///  - La connexion effective a lieu dans une tâche tokio (via `spawn_connection_actor`).
+    ///  - Le timer `GLib` (20 ms) pompe les événements : `HostKeyUnknown`, Connected, Data...
+    ///  - Cela libère le thread GTK pendant la connexion SSH (`check_server_key`, auth).
+    fn connect(self: &Rc<Self>) {
+        if let Some(timer) = self.reconnect_timer.borrow_mut().take() {
+            timer.remove();
+        }
+        let shift_held = WidgetExt::display(&self.window)
+            .default_seat()
+            .and_then(|seat| seat.keyboard())
+            .is_some_and(|kb| {
+                kb.modifier_state()
+                    .contains(gtk4::gdk::ModifierType::SHIFT_MASK)
+            });
+        self.skip_on_connect_commands.set(shift_held);
+        self.pending_secret.replace(None);
+        self.passphrase_to_remember.replace(None);
+
+        // Validation + construction du manager (sans connexion).
+        let manager: Box<dyn Connection> = match if self.connection_panel.is_serial_selected() {
+            self.build_serial_manager()
+        } else {
+            self.build_ssh_manager()
+        } {
+            Ok(m) => m,
+            Err(e) => {
+                self.header.set_status("Erreur de configuration", false);
+                self.terminal.append_error(&e);
+                self.show_toast(&format!("⚠ {e}"));
+                log::error!("Erreur de configuration : {e}");
+                return;
+            }
+        };
+
+        if !self.connection_panel.is_serial_selected() {
+            self.connection_panel.ssh_panel.clear_password();
+            self.connection_panel.ssh_panel.clear_passphrase();
+            self.connection_panel.ssh_panel.clear_pasted_key();
+        }
+
+        // Une trame partielle de la session précédente ne doit pas être complétée.
+        self.terminal.reset_framing();
+
+        // Indiquer à l'UI que la connexion est en cours.
+        self.header.set_status("Connexion en cours...", false);
+        if self.connection_panel.is_serial_selected() {
+            self.terminal.append_system("Connexion en cours...");
+        } else {
+            self.terminal.append_system(&format!(
+                "Connexion en cours... (délai max : {}s)",
+                self.connection_panel.ssh_panel.connect_timeout_secs()
+            ));
+        }
+
+        // Lancer l'acteur de connexion dans le runtime tokio.
+        // `runtime.enter()` établit le contexte tokio pour `tokio::spawn`
+        //  sans bloquer le thread GTK (contrairement à `block_on`).
+        let guard = self.runtime.enter();
+        let (command_capacity, event_capacity) = {
+            let settings = self.settings.borrow();
+            let channels = &settings.settings().channels;
+            (channels.command_capacity, channels.event_capacity)
+        };
+        let (cmd_tx, event_rx) = spawn_connection_actor(manager, command_capacity, event_capacity);
+        drop(guard);
+
+        if self.settings.borrow().settings().ui.measure_latency {
+            // Traitée par l'acteur dès la connexion établie.
+            if let Err(e) = cmd_tx.try_send(ConnectionCommand::SetLatencyTracking(true)) {
+                log::warn!("Mesure de latence non transmise : {e}");
+            }
+        }
+        *self.connection_tx.borrow_mut() = Some(cmd_tx);
+        self.refresh_panic_action();
+        let generation = self.connection_generation.get() + 1;
+        self.connection_generation.set(generation);
+        // Nouvelle connexion : une reconnexion en attente est caduque.
+        self.reconnect_when_closed.set(false);
+
+        // Pont async_channel → GTK main loop via GLib timer (20 ms)
+        // SOLID : aucune dépendance GTK dans le core.
+        let this = self.clone();
+        glib::timeout_add_local(std::time::Duration::from_millis(20), move || {
+            loop {
+                match event_rx.try_recv() {
+                    Ok(ConnectionEvent::Connected {
+                        conn_type,
+                        description,
+                    }) => {
+                        let type_label = match conn_type {
+                            ConnectionType::Serial => "Série",
+                            ConnectionType::Ssh => "SSH",
+                        };
+                        let since = chrono::Local::now();
+                        this.connected_since.set(Some(since));
+                        this.connection_description.replace(description.clone());
+                        this.header.status_label.set_tooltip_text(Some(&format!(
+                            "Connecté depuis {}",
+                            since.format("%Y-%m-%d %H:%M:%S")
+                        )));
+                        this.active_connection.set(Some(conn_type));
+                        this.input.set_target(Some(&description));
+                        let secret = this.pending_secret.take();
+                        this.session_secret.replace(secret);
+                        // Un PTY SSH convertit toujours en CRLF (onlcr) : la détection
+                        // basculerait l'envoi en CRLF et doublerait chaque Entrée.
+                        let detect = conn_type == ConnectionType::Serial
+                            && this.settings.borrow().settings().ui.detect_line_ending
+                            && !this.input.at_mode();
+                        this.line_ending_detector
+                            .replace(detect.then(LineEndingDetector::new));
+                        match conn_type {
+                            ConnectionType::Ssh => {
+                                this.remember_last_favorite();
+                                this.run_on_connect_commands(generation);
+                                this.arm_password_clear();
+                            }
+                            ConnectionType::Serial => {
+                                this.remember_serial_identity();
+                                this.start_boot_capture();
+                                this.send_serial_connect_signals();
+                            }
+                        }
+                        this.refresh_read_only();
+                        this.refresh_send_defaults();
+                        this.connection_panel.set_connected(true);
+                        this.header.set_connected(true);
+                        this.header
+                            .set_status(&format!("Connecté {type_label} — {description}"), true);
+                        this.terminal
+                            .append_system(&format!("Connecté [{type_label}] {description}"));
+                        this.input.grab_focus();
+                        if this.settings.borrow().settings().ui.notify_on_connect {
+                            this.notify_desktop(
+                                "Connexion établie",
+                                &format!("{type_label} — {description}"),
+                            );
+                        }
+                    }
+                    Ok(ConnectionEvent::HostKeyUnknown {
+                        host,
+                        key_type,
+                        fingerprint,
+                        is_key_changed,
+                        decision_tx,
+                    }) => {
+                        // Dialogue de vérification de clé SSH, après les éventuelles
+                        // demandes déjà en attente. Le timer CONTINUE de tourner
+                        // pendant que l'utilisateur répond.
+                        this.prompt_queue.borrow_mut().push_back((
+                            generation,
+                            InteractivePrompt::HostKey {
+                                host,
+                                key_type,
+                                fingerprint,
+                                is_key_changed,
+                                decision_tx,
+                            },
+                        ));
+                        this.show_next_prompt();
+                    }
+                    Ok(ConnectionEvent::AuthPrompt {
+                        prompts,
+                        response_tx,
+                    }) => {
+                        this.prompt_queue.borrow_mut().push_back((
+                            generation,
+                            InteractivePrompt::Auth {
+                                prompts,
+                                response_tx,
+                            },
+                        ));
+                        this.show_next_prompt();
+                    }
+                    Ok(ConnectionEvent::PassphraseRequired {
+                        key_path,
+                        response_tx,
+                    }) => {
+                        this.prompt_queue.borrow_mut().push_back((
+                            generation,
+                            InteractivePrompt::Passphrase {
+                                key_path,
+                                response_tx,
+                            },
+                        ));
+                        this.show_next_prompt();
+                    }
+                    Ok(ConnectionEvent::PassphraseAccepted { key_path }) => {
+                        this.remember_key_passphrase(&key_path);
+                    }
+                    Ok(ConnectionEvent::DataReceived(data)) => {
+                        // Un retard sur cette lecture arrive juste après (`ReadStalled`) :
+                        // l'indicateur ne reste affiché que si la lecture a encore attendu.
+                        this.header.set_read_stalled(None);
+                        this.detect_line_ending(&data);
+                        this.terminal.append_ansi(&data);
+                    }
+                    Ok(ConnectionEvent::ReadStalled { count, total_ms }) => {
+                        if count == 1 {
+                            this.terminal.append_system(
+                                "Affichage en retard sur le flux reçu : lecture ralentie \
+                                 (aucune donnée perdue).",
+                            );
+                        }
+                        this.header.set_read_stalled(Some((count, total_ms)));
+                    }
+                    Ok(ConnectionEvent::StderrReceived(data)) => {
+                        this.terminal.append_stderr(&data);
+                    }
+                    Ok(ConnectionEvent::DataSent(written)) => {
+                        log::trace!("{written} octets écrits");
+                        let echoes = this.pending_echoes.borrow_mut().pop_front();
+                        for text in echoes.unwrap_or_default() {
+                            this.terminal.append_sent(&text);
+                        }
+                    }
+                    Ok(ConnectionEvent::Latency { last, stats }) => {
+                        this.header.set_latency(Some((last, stats)));
+                    }
+                    Ok(ConnectionEvent::SessionStats {
+                        bytes_sent,
+                        bytes_received,
+                        latency,
+                    }) => {
+                        if let Some(since) = this.connected_since.get() {
+                            let elapsed = (chrono::Local::now() - since).num_seconds();
+                            this.terminal.append_system(&format!(
+                                "Session de {} terminée — envoyés: {bytes_sent} octets, \
+                                 reçus: {bytes_received} octets",
+                                format_duration(elapsed)
+                            ));
+                        }
+                        if let Some(latency) = latency {
+                            this.terminal
+                                .append_system(&format!("Latence requête/réponse : {latency}"));
+                        }
+                    }
+                    Ok(ConnectionEvent::BuffersFlushed { input, output }) => {
+                        let what = match (input, output) {
+                            (true, false) => "entrée",
+                            (false, true) => "sortie",
+                            _ => "entrée + sortie",
+                        };
+                        this.terminal
+                            .append_system(&format!("Tampons série vidés ({what})."));
+                    }
+                    Ok(ConnectionEvent::ControlSignalSent(label)) => {
+                        this.terminal.append_system(&format!("{label}."));
+                    }
+                    Ok(ConnectionEvent::RawCapture(path)) => {
+                        this.set_raw_capture_state(path.is_some());
+                        match path {
+                            Some(path) => this.terminal.append_system(&format!(
+                                "Capture brute démarrée : {}",
+                                path.display()
+                            )),
+                            None => this.terminal.append_system("Capture brute arrêtée."),
+                        }
+                    }
+                    Ok(ConnectionEvent::ControlLines { dtr, rts }) => {
+                        this.connection_panel
+                            .serial_panel
+                            .set_control_lines(Some((dtr, rts)));
+                    }
+                    Ok(ConnectionEvent::Notice(message)) => {
+                        this.terminal.append_system(&message);
+                    }
+                    Ok(ConnectionEvent::ReadingPaused(paused)) => {
+                        this.set_reading_paused_state(paused);
+                        this.terminal.append_system(if paused {
+                            "Lecture suspendue : les données restent côté périphérique."
+                        } else {
+                            "Lecture reprise."
+                        });
+                    }
+                    Ok(ConnectionEvent::CommandFailed(e)) => {
+                        this.terminal.append_error(&e);
+                    }
+                    Ok(ConnectionEvent::Error(e)) => {
+                        this.terminal.append_error(&e);
+                        this.on_connection_lost(generation);
+                        return glib::ControlFlow::Break;
+                    }
+                    Err(async_channel::TryRecvError::Empty) => break,
+                    Ok(ConnectionEvent::Disconnected)
+                    | Err(async_channel::TryRecvError::Closed) => {
+                        this.on_connection_lost(generation);
+                        return glib::ControlFlow::Break;
+                    }
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Traite la déconnexion — idempotente.
+    ///
+    /// Peut être appelée depuis :
+    ///   - l'UI (bouton déconnecter) via `disconnect()`
+    ///   - le timer `GLib` quand l'acteur signale Disconnected/Error/Closed
+    ///
+    /// Sécurité : le `take()` de `connection_tx` est atomique (thread GTK
+    /// unique) et garantit qu'aucun appel simultané ne met à jour l'UI deux fois.
+    fn handle_disconnect(self: &Rc<Self>) {
+        // `take()` retire le sender : seul le premier appelant obtient Some.
+        let had_connection = self.connection_tx.borrow().is_some();
+        if let Some(tx) = self.connection_tx.borrow_mut().take() {
+            // Informer l'acteur de se terminer (peut échouer si déjà fermé — normal).
+            if tx.try_send(ConnectionCommand::Disconnect).is_err() {
+                log::debug!("Acteur déjà fermé lors de handle_disconnect");
+            }
+        }
+        // Mettre à jour l'UI seulement si la connexion était active.
+        // (Prévient les messages 'Déconnecté' dupliquement en cas d'appels successifs.)
+        let conn_type = self.active_connection.get();
+        self.active_connection.set(None);
+        self.clear_send_queue();
+        // Envois jamais confirmés : pas d'écho.
+        self.pending_echoes.borrow_mut().clear();
+        self.cancel_prompts();
+        self.connected_since.set(None);
+        self.refresh_read_only();
+        self.refresh_send_defaults();
+        self.header.status_label.set_tooltip_text(None);
+        self.header.set_read_stalled(None);
+        self.header.set_latency(None);
+        self.set_raw_capture_state(false);
+        self.input.set_target(None);
+        self.stop_boot_capture();
+        self.cancel_password_clear();
+        self.connection_panel.serial_panel.set_control_lines(None);
+        self.set_reading_paused_state(false);
+        self.line_ending_detector.replace(None);
+        self.refresh_panic_action();
+        if had_connection {
+            self.connection_panel.set_connected(false);
+            self.header.set_connected(false);
+            self.header.set_status("Déconnecté", false);
+            self.terminal.append_system("Déconnecté");
+            self.show_toast("Connexion terminée");
+            if let Some(conn_type) = conn_type {
+                self.auto_save_session_log(conn_type);
+            }
+        }
+    }
+
+    /// Présente la prochaine demande interactive, si aucune n'est affichée.
+    ///
+    /// Les demandes d'une connexion abandonnée ou remplacée sont refusées
+    /// sans être affichées.
+    fn show_next_prompt(self: &Rc<Self>) {
+        if self.active_prompt.borrow().is_some() {
+            return;
+        }
+        let prompt = loop {
+            let next = self.prompt_queue.borrow_mut().pop_front();
+            let Some((generation, prompt)) = next else {
+                return;
+            };
+            if generation == self.connection_generation.get()
+                && self.connection_tx.borrow().is_some()
+            {
+                break prompt;
+            }
+            // Drop : le canal se ferme, l'acteur conclut à un refus.
+            log::debug!("Demande interactive obsolète ignorée (connexion {generation})");
+        };
+
+        let this = self.clone();
+        let on_closed = move || {
+            this.active_prompt.borrow_mut().take();
+            this.show_next_prompt();
+        };
+        let dialog = match prompt {
+            InteractivePrompt::HostKey {
+                host,
+                key_type,
+                fingerprint,
+                is_key_changed,
+                decision_tx,
+            } => show_host_key_dialog(
+                &self.window,
+                &host,
+                &key_type,
+                &fingerprint,
+                is_key_changed,
+                decision_tx,
+                on_closed,
+            ),
+            InteractivePrompt::Auth {
+                prompts,
+                response_tx,
+            } => show_auth_prompt_dialog(
+                &self.window,
+                &self.connection_panel.ssh_panel.target(),
+                &prompts,
+                response_tx,
+                on_closed,
+            ),
+            InteractivePrompt::Passphrase {
+                key_path,
+                response_tx,
+            } => {
+                let on_submit = {
+                    let this = self.clone();
+                    let key_path = key_path.clone();
+                    move |passphrase: &str, remember: bool| {
+                        this.accept_key_passphrase(&key_path, passphrase, remember);
+                    }
+                };
+                show_passphrase_dialog(
+                    &self.window,
+                    &key_path,
+                    self.connection_panel.ssh_panel.remember_secrets(),
+                    response_tx,
+                    on_submit,
+                    on_closed,
+                )
+            }
+        };
+        *self.active_prompt.borrow_mut() = Some(dialog);
+    }
+
+    /// Passphrase saisie à la demande de la connexion : reprise à la
+    /// reconnexion et, si demandé, enregistrée dans le trousseau une fois
+    /// vérifiée (`remember_key_passphrase`).
+    fn accept_key_passphrase(&self, key_path: &str, passphrase: &str, remember: bool) {
+        if let Some(pending) = self.pending_secret.borrow_mut().as_mut() {
+            if pending.key_path == key_path {
+                pending.secret = passphrase.to_string();
+            }
+        }
+        self.passphrase_to_remember
+            .replace(remember.then(|| (key_path.to_string(), passphrase.to_string())));
+    }
+
+    /// La clé s'est chargée : la passphrase saisie est la bonne et peut
+    /// rejoindre le trousseau si l'utilisateur l'a demandé.
+    fn remember_key_passphrase(&self, key_path: &str) {
+        let Some((path, passphrase)) = self.passphrase_to_remember.take() else {
+            return;
+        };
+        if path != key_path {
+            return;
+        }
+        let sp = &self.connection_panel.ssh_panel;
+        let (host, port, username) = (sp.host(), sp.port(), sp.username());
+        match secrets::save_ssh_key_passphrase(&host, port, &username, key_path, &passphrase) {
+            // Réactive la mémorisation pour que la passphrase soit essayée
+            // d'abord à la prochaine connexion.
+            Ok(()) => sp.set_remember_secrets(true),
+            Err(e) => {
+                log::warn!("Impossible de sauvegarder la passphrase dans le keyring : {e}");
+                self.show_toast("Trousseau indisponible : passphrase non mémorisée.");
+            }
+        }
+    }
+
+    /// Refuse toutes les demandes interactives en attente et ferme celle affichée.
+    fn cancel_prompts(&self) {
+        self.prompt_queue.borrow_mut().clear();
+        let dialog = self.active_prompt.borrow_mut().take();
+        if let Some(dialog) = dialog {
+            dialog.force_close();
+        }
+    }
+
+    /// Sauvegarde automatique du terminal à la déconnexion (si activée).
+    ///
+    /// L'écriture du fichier a lieu hors du thread GTK.
+    fn auto_save_session_log(self: &Rc<Self>, conn_type: ConnectionType) {
+        let (path, timestamp) = {
+            let sm = self.settings.borrow();
+            let log = &sm.settings().log;
+            if !log.auto_save_on_disconnect {
+                return;
+            }
+            let type_id = match conn_type {
+                ConnectionType::Serial => "serie",
+                ConnectionType::Ssh => "ssh",
+            };
+            let filename = logger::render_log_filename(
+                &log.auto_save_template,
+                &self.connection_description.borrow(),
+                type_id,
+                chrono::Local::now(),
+            );
+            (
+                sm.auto_save_directory().join(filename),
+                log.timestamp_saved_lines,
+            )
+        };
+
+        let content = self.terminal.get_text();
+        let handle = gio::spawn_blocking(move || -> anyhow::Result<std::path::PathBuf> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let log = logger::format_session_log(&content, timestamp);
+            Ok(logger::write_new_file(&path, log.as_bytes())?)
+        });
+
+        let this = self.clone();
+        glib::spawn_future_local(async move {
+            let message = match handle.await {
+                Ok(Ok(path)) => {
+                    log::info!("Session sauvegardée dans {}", path.display());
+                    format!("Session sauvegardée dans {}", path.display())
+                }
+                Ok(Err(e)) => {
+                    log::error!("Sauvegarde automatique impossible : {e}");
+                    format!("Sauvegarde automatique impossible : {e}")
+                }
+                Err(_) => return,
+            };
+            this.terminal.append_system(&message);
+        });
+    }
+
+    /// Fin de connexion signalée par l'acteur (`Disconnected`, `Error` ou canal fermé).
+    ///
+    /// Ignorée si elle provient d'une connexion remplacée depuis. Une coupure
+    /// inattendue (non demandée par l'utilisateur) déclenche la reconnexion
+    /// automatique si elle est activée pour ce type de connexion.
+    fn on_connection_lost(self: &Rc<Self>, generation: u64) {
+        if generation != self.connection_generation.get() {
+            return;
+        }
+        if self.reconnect_when_closed.take() {
+            self.handle_disconnect();
+            self.reconnect_serial();
+            return;
+        }
+
+        let unexpected = self.connection_tx.borrow().is_some();
+        let conn_type = self.current_connection_type();
+        let was_connected = self.connected_since.get();
+        self.handle_disconnect();
+        // Une déconnexion demandée par l'utilisateur n'est pas une perte.
+        if unexpected && self.settings.borrow().settings().ui.notify_on_disconnect {
+            self.notify_desktop("Connexion perdue", "La connexion a été fermée.");
+        }
+
+        let (enabled, stable_after) = {
+            let settings = self.settings.borrow();
+            let s = settings.settings();
+            let enabled = match conn_type {
+                ConnectionType::Serial => s.serial.auto_reconnect,
+                ConnectionType::Ssh => s.ssh.auto_reconnect && !self.single_command_session.get(),
+            };
+            (enabled, i64::from(s.reconnect.stable_after_secs))
+        };
+        // Un premier échec de connexion manuelle ne déclenche pas de reconnexion.
+        let in_cycle = was_connected.is_some() || self.reconnect_attempt.get() > 0;
+        if !unexpected || !enabled || !in_cycle {
+            self.reconnect_attempt.set(0);
+            return;
+        }
+
+        // Connexion restée stable : le délai repart de la valeur initiale.
+        if was_connected
+            .is_some_and(|since| (chrono::Local::now() - since).num_seconds() >= stable_after)
+        {
+            self.reconnect_attempt.set(0);
+        }
+        self.schedule_reconnect();
+    }
+
+    /// Programme la prochaine tentative de reconnexion selon la stratégie choisie.
+    ///
+    /// Le compte à rebours est affiché dans la barre de statut.
+    fn schedule_reconnect(self: &Rc<Self>) {
+        let attempt = self.reconnect_attempt.get() + 1;
+        self.reconnect_attempt.set(attempt);
+
+        let delay = {
+            let settings = self.settings.borrow();
+            let r = &settings.settings().reconnect;
+            reconnect::backoff_delay(
+                BackoffStrategy::from_str_name(&r.strategy),
+                r.delay_secs,
+                r.max_delay_secs,
+                attempt,
+            )
+        };
+
+        self.terminal.append_system(&format!(
+            "Reconnexion automatique (tentative {attempt}) dans {delay} s…"
+        ));
+        self.header
+            .set_status(&format!("Reconnexion dans {delay} s…"), false);
+
+        let remaining = Cell::new(delay);
+        let this = self.clone();
+        let timer = glib::timeout_add_seconds_local(1, move || {
+            let left = remaining.get().saturating_sub(1);
+            remaining.set(left);
+            if left > 0 {
+                this.header
+                    .set_status(&format!("Reconnexion dans {left} s…"), false);
+                return glib::ControlFlow::Continue;
+            }
+
+            // La source se termine d'elle-même : ne pas la retirer dans `connect()`.
+            this.reconnect_timer.borrow_mut().take();
+            if this.connection_tx.borrow().is_none() {
+                if this.locate_replugged_adapter() {
+                    this.connect();
+                } else {
+                    this.schedule_reconnect();
+                }
+            }
+            glib::ControlFlow::Break
+        });
+        *self.reconnect_timer.borrow_mut() = Some(timer);
+        self.refresh_panic_action();
+    }
+
+    /// Mémorise l'identité USB du port série qui vient d'être ouvert.
+    fn remember_serial_identity(&self) {
+        let device = self.connection_panel.serial_panel.selected_port();
+        let identity = device.and_then(|device| {
+            list_serial_ports()
+                .into_iter()
+                .find(|p| p.device == device)
+                .and_then(|p| p.usb)
+                .map(|usb| (usb, device))
+        });
+        self.serial_identity.replace(identity);
+    }
+
+    /// Avant une reconnexion série, sélectionne le chemin actuel de
+    /// l'adaptateur USB (`ttyUSB0` → `ttyUSB1` après rebranchement).
+    ///
+    /// Retourne `false` si l'adaptateur est absent : l'ancien chemin
+    /// pourrait désigner un autre appareil, la tentative est reportée.
+    fn locate_replugged_adapter(&self) -> bool {
+        if self.current_connection_type() != ConnectionType::Serial
+            || !self
+                .settings
+                .borrow()
+                .settings()
+                .serial
+                .reconnect_by_identity
+        {
+            return true;
+        }
+        let Some((identity, previous)) = self.serial_identity.borrow().clone() else {
+            return true;
+        };
+
+        let Some(device) = find_port_by_identity(&list_serial_ports(), &identity, &previous) else {
+            self.terminal
+                .append_system(&format!("Adaptateur {identity} absent, en attente…"));
+            return false;
+        };
+        let sp = &self.connection_panel.serial_panel;
+        if sp.selected_port().as_deref() != Some(device.as_str()) {
+            sp.refresh_ports();
+            sp.select_port_by_device(&device);
+            self.connection_panel.refresh_connect_availability();
+            self.terminal.append_system(&format!(
+                "Adaptateur {identity} retrouvé sur {device} (auparavant {previous})"
+            ));
+        }
+        true
+    }
+
+    /// Annule la reconnexion programmée et remet le compteur de tentatives à zéro.
+    fn cancel_reconnect(&self) {
+        if let Some(timer) = self.reconnect_timer.borrow_mut().take() {
+            timer.remove();
+            self.header.set_status("Déconnecté", false);
+            self.terminal
+                .append_system("Reconnexion automatique annulée.");
+        }
+        self.reconnect_attempt.set(0);
+        self.refresh_panic_action();
+    }
+
+    /// « Tout déconnecter » ne vise que les fenêtres connectées ou en
+    /// attente de reconnexion.
+    fn refresh_panic_action(&self) {
+        let active =
+            self.connection_tx.borrow().is_some() || self.reconnect_timer.borrow().is_some();
+        if let Some(action) = self
+            .window
+            .lookup_action("panic-disconnect")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            action.set_enabled(active);
+        }
+    }
+
+    /// Coupe immédiatement toutes les connexions de l'application (et les
+    /// reconnexions programmées). Confirmation demandée au-delà d'une connexion.
+    fn disconnect_all(self: &Rc<Self>) {
+        let targets: Vec<gtk4::Window> = self
+            .window
+            .application()
+            .map(|app| app.windows())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|window| {
+                window
+                    .dynamic_cast_ref::<gio::ActionMap>()
+                    .and_then(|map| map.lookup_action("panic-disconnect"))
+                    .is_some_and(|action| action.is_enabled())
+            })
+            .collect();
+
+        let disconnect = move |targets: &[gtk4::Window]| {
+            for window in targets {
+                let _ = window.activate_action("win.panic-disconnect", None);
+            }
+        };
+
+        match targets.len() {
+            0 => self.show_toast("Aucune connexion active"),
+            1 => disconnect(&targets),
+            count => {
+                let dialog = libadwaita::AlertDialog::new(
+                    Some("Tout déconnecter ?"),
+                    Some(&format!(
+                        "{count} connexions vont être fermées immédiatement."
+                    )),
+                );
+                dialog.add_response("cancel", "Annuler");
+                dialog.add_response("disconnect", "Tout déconnecter");
+                dialog.set_default_response(Some("disconnect"));
+                dialog.set_response_appearance(
+                    "disconnect",
+                    libadwaita::ResponseAppearance::Destructive,
+                );
+                dialog.connect_response(None, move |_, response| {
+                    if response == "disconnect" {
+                        disconnect(&targets);
+                    }
+                });
+                dialog.present(Some(&self.window));
+            }
+        }
+    }
+
+    /// Applique au terminal les règles de coloration (aucune si désactivées).
+    fn apply_highlight_rules(&self) {
+        let settings = self.settings.borrow();
+        let ui = &settings.settings().ui;
+        if ui.highlight_enabled {
+            self.terminal.set_highlight_rules(&ui.highlight_rules);
+        } else {
+            self.terminal.set_highlight_rules(&[]);
+        }
+    }
+
+    /// Mode compact : masque le panneau de connexion au profit du terminal.
+    ///
+    /// Un bouton Connecter/Déconnecter reste accessible dans la barre d'en-tête.
+    fn set_compact_mode(&self, compact: bool) {
+        self.connection_panel.container.set_visible(!compact);
+        self.header.connect_button.set_visible(compact);
+    }
+
+    /// Propose de rouvrir le port quand un paramètre série change en cours de connexion.
+    fn on_serial_params_changed(self: &Rc<Self>) {
+        if !self
+            .settings
+            .borrow()
+            .settings()
+            .serial
+            .reconfigure_on_change
+            || self.active_connection.get() != Some(ConnectionType::Serial)
+            // « Autre… » choisi mais vitesse pas encore saisie : attendre Entrée.
+            || self.connection_panel.serial_panel.checked_baudrate().is_err()
+            || self.reconfigure_prompt_open.replace(true)
+        {
+            return;
+        }
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Paramètres série modifiés"),
+            Some("Appliquer maintenant ? (reconnexion)"),
+        );
+        dialog.add_response("later", "Plus tard");
+        dialog.add_response("apply", "Appliquer");
+        dialog.set_default_response(Some("apply"));
+        dialog.set_response_appearance("apply", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            this.reconfigure_prompt_open.set(false);
+            if response == "apply" {
+                this.reconfigure_serial();
+            }
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Rouvre le port série avec la configuration courante de l'UI.
+    ///
+    /// Le contenu du terminal est conservé ; la reconnexion attend que
+    /// l'acteur précédent ait libéré le port (`Disconnected`, voir
+    /// `on_connection_lost`).
+    fn reconfigure_serial(self: &Rc<Self>) {
+        self.terminal
+            .append_system("Reconnexion avec les nouveaux paramètres série...");
+        if self.connection_tx.borrow().is_none() {
+            self.reconnect_serial();
+            return;
+        }
+        self.reconnect_when_closed.set(true);
+        self.handle_disconnect();
+    }
+
+    fn reconnect_serial(self: &Rc<Self>) {
+        self.connection_panel.notebook.set_current_page(Some(0));
+        self.connect();
+    }
+
+    /// Affiche une notification toast Adwaita non-bloquante (3 s par défaut).
+    ///
+    /// À utiliser pour les confirmations et erreurs transientes.
+    /// Les erreurs critiques persistantes doivent utiliser `terminal.append_error()`.
+    pub fn show_toast(&self, message: &str) {
+        let toast = libadwaita::Toast::new(message);
+        toast.set_timeout(3);
+        self.toast_overlay.add_toast(toast);
+    }
+
+    /// Signal sonore + notification bureau, uniquement si la fenêtre n'a pas le focus.
+    fn notify_desktop(&self, title: &str, body: &str) {
+        if self.window.is_active() {
+            return;
+        }
+
+        self.window.error_bell();
+        if let Some(app) = self.window.application() {
+            let notification = gio::Notification::new(title);
+            notification.set_body(Some(body));
+            app.send_notification(Some("connection-state"), &notification);
+        }
+    }
+
+    /// Charge les secrets SSH sauvegardés dans le trousseau système.
+    fn load_saved_ssh_secrets(&self) {
+        let sp = &self.connection_panel.ssh_panel;
+        if !sp.remember_secrets() {
+            sp.clear_password();
+            sp.clear_passphrase();
+            return;
+        }
+
+        let host = sp.host();
+        let port = sp.port();
+        let username = sp.username();
+        let key_path = sp.key_path();
+
+        if host.is_empty() || username.is_empty() {
+            sp.clear_password();
+            sp.clear_passphrase();
+            return;
+        }
+
+        if let Some(password) = secrets::load_ssh_password(&host, port, &username) {
+            sp.set_password(&password);
+        } else {
+            sp.clear_password();
+        }
+
+        if key_path.trim().is_empty() {
+            sp.clear_passphrase();
+            return;
+        }
+
+        if let Some(passphrase) =
+            secrets::load_ssh_key_passphrase(&host, port, &username, &key_path)
+        {
+            sp.set_passphrase(&passphrase);
+        } else {
+            sp.clear_passphrase();
+        }
+    }
+
+    /// Construit le manager série à partir de l'UI.
+    /// La connexion effective est établie par `spawn_connection_actor`.
+    fn build_serial_manager(&self) -> Result<Box<dyn Connection>, String> {
+        let sp = &self.connection_panel.serial_panel;
+        let port = sp
+            .selected_port()
+            .ok_or_else(|| "Aucun port sélectionné".to_string())?;
+
+        let baudrate = sp.checked_baudrate()?;
+        let mut config = SerialConfig::from_params(
+            &port,
+            baudrate,
+            sp.selected_data_bits(),
+            &sp.selected_parity(),
+            sp.selected_stop_bits(),
+            &sp.selected_flow_control(),
+            self.settings.borrow().settings().serial.timeout_ms,
+        );
+        {
+            let settings = self.settings.borrow();
+            let serial = &settings.settings().serial;
+            config.open_retries = serial.open_retries;
+            config.open_retry_delay = std::time::Duration::from_millis(serial.open_retry_delay_ms);
+        }
+
+        // Sauvegarder les paramètres série
+        {
+            let mut sm = self.settings.borrow_mut();
+            let serial = &mut sm.settings_mut().serial;
+            serial.port = port;
+            serial.baudrate = baudrate;
+            serial.data_bits = sp.selected_data_bits();
+            serial.parity = sp.selected_parity();
+            serial.stop_bits = sp.selected_stop_bits();
+            serial.flow_control = sp.selected_flow_control();
+            if let Err(e) = sm.save() {
+                log::warn!("Impossible de sauvegarder les paramètres série : {e}");
+            }
+        }
+
+        Ok(Box::new(SerialManager::new(config)))
+    }
+
+    /// Construit le manager SSH à partir de l'UI.
+    /// La connexion effective (TCP + handshake + auth + `known_hosts`) est
+    /// établie par `spawn_connection_actor` dans une tâche tokio.
+    fn build_ssh_manager(&self) -> Result<Box<dyn Connection>, String> {
+        let sp = &self.connection_panel.ssh_panel;
+        let host = sp.host();
+        let port = sp.port();
+        let mut username = sp.username();
+        let mut password = sp.password();
+        let mut passphrase = sp.passphrase();
+        let mut key_path = sp.key_path();
+
+        // Alias de ~/.ssh/config : le champ garde l'alias, résolu à chaque
+        // connexion ; l'utilisateur et la clé saisis restent prioritaires.
+        let ssh_hosts = ssh_config::load_user_hosts();
+        let alias = ssh_config::find_host(&ssh_hosts, &host);
+        if let Some(entry) = alias {
+            if username.is_empty() {
+                username = entry.user.clone().unwrap_or_default();
+            }
+            if key_path.trim().is_empty() && sp.pasted_key().trim().is_empty() {
+                key_path = entry.resolved_identity_file().unwrap_or_default();
+            }
+        }
+        let target_host = alias.map_or_else(|| host.clone(), |e| e.resolved_host().to_string());
+        let target_port = alias.and_then(|e| e.port).unwrap_or(port);
+        let proxy_jump = alias
+            .and_then(|e| e.proxy_jump.clone())
+            .filter(|jump| ssh_config::parse_jump_host(jump).is_some());
+
+        if host.is_empty() || username.is_empty() {
+            return Err("L'hôte et l'utilisateur sont requis.".to_string());
+        }
+
+        let command = sp.single_command();
+        if command.as_deref().is_some_and(str::is_empty) {
+            return Err("Saisissez la commande à exécuter (paramètres avancés).".to_string());
+        }
+
+        let remember_secrets = sp.remember_secrets();
+        let pasted_key = sp.pasted_key();
+        let use_agent = sp.use_agent();
+        let use_interactive = sp.use_interactive();
+        // Agent et 2FA : aucun secret saisi dans le panneau.
+        let uses_secrets = !use_agent && !use_interactive;
+
+        // Reconnexion : le champ a été vidé, reprendre le secret de la session.
+        let cached = self
+            .session_secret
+            .borrow()
+            .as_ref()
+            .filter(|s| s.matches(&host, port, &username, &key_path))
+            .map(|s| s.secret.clone());
+        if let Some(secret) = cached.filter(|_| pasted_key.trim().is_empty()) {
+            if key_path.trim().is_empty() && password.trim().is_empty() {
+                password = secret;
+            } else if !key_path.trim().is_empty() && passphrase.trim().is_empty() {
+                passphrase = secret;
+            }
+        }
+
+        if remember_secrets && uses_secrets {
+            if key_path.trim().is_empty() {
+                if password.trim().is_empty() {
+                    password =
+                        secrets::load_ssh_password(&host, port, &username).unwrap_or_default();
+                }
+            } else if passphrase.trim().is_empty() {
+                passphrase = secrets::load_ssh_key_passphrase(&host, port, &username, &key_path)
+                    .unwrap_or_default();
+            }
+        }
+
+        self.pending_secret
+            .replace(
+                (pasted_key.trim().is_empty() && uses_secrets).then(|| SessionSecret {
+                    host: host.clone(),
+                    port,
+                    username: username.clone(),
+                    key_path: key_path.clone(),
+                    secret: if key_path.trim().is_empty() {
+                        password.clone()
+                    } else {
+                        passphrase.clone()
+                    },
+                }),
+            );
+
+        let auth_method = if use_agent {
+            SshAuthMethod::Agent
+        } else if use_interactive {
+            SshAuthMethod::KeyboardInteractive
+        } else if !pasted_key.trim().is_empty() {
+            SshAuthMethod::KeyData {
+                private_key: pasted_key,
+                passphrase: if passphrase.trim().is_empty() {
+                    None
+                } else {
+                    Some(passphrase.clone())
+                },
+            }
+        } else if key_path.is_empty() {
+            SshAuthMethod::Password(password.clone())
+        } else {
+            SshAuthMethod::KeyFile {
+                private_key_path: key_path.clone(),
+                passphrase: if passphrase.trim().is_empty() {
+                    None
+                } else {
+                    Some(passphrase.clone())
+                },
+            }
+        };
+
+        if alias.is_some() {
+            let via = proxy_jump
+                .as_deref()
+                .map(|jump| format!(" via {jump}"))
+                .unwrap_or_default();
+            self.terminal.append_system(&format!(
+                "Alias ~/.ssh/config « {host} » → {username}@{target_host}:{target_port}{via}"
+            ));
+        }
+
+        let config = SshConfig {
+            host: target_host,
+            port: target_port,
+            username: username.clone(),
+            auth_method,
+            connect_timeout_secs: sp.connect_timeout_secs(),
+            port_forwards: sp.port_forwards(),
+            terminal_size: self
+                .terminal
+                .grid_size()
+                .unwrap_or(SshConfig::default().terminal_size),
+            command,
+            proxy_jump,
+        };
+        self.single_command_session.set(config.command.is_some());
+
+        if !uses_secrets {
+            // Clés dans l'agent ou réponses saisies à la demande : rien à mémoriser.
+        } else if remember_secrets {
+            if key_path.trim().is_empty() {
+                if let Err(e) = secrets::save_ssh_password(&host, port, &username, &password) {
+                    log::warn!("Impossible de sauvegarder le mot de passe dans le keyring : {e}");
+                    self.show_toast("Trousseau indisponible : mot de passe non mémorisé.");
+                }
+            } else if let Err(e) =
+                secrets::save_ssh_key_passphrase(&host, port, &username, &key_path, &passphrase)
+            {
+                log::warn!("Impossible de sauvegarder la passphrase dans le keyring : {e}");
+                self.show_toast("Trousseau indisponible : passphrase non mémorisée.");
+            }
+        } else if key_path.trim().is_empty() {
+            if let Err(e) = secrets::delete_ssh_password(&host, port, &username) {
+                log::warn!("Suppression password keyring impossible : {e}");
+            }
+        } else if let Err(e) = secrets::delete_ssh_key_passphrase(&host, port, &username, &key_path)
+        {
+            log::warn!("Suppression passphrase keyring impossible : {e}");
+        }
+
+        // Sauvegarder les paramètres SSH
+        {
+            let mut sm = self.settings.borrow_mut();
+            let ssh = &mut sm.settings_mut().ssh;
+            ssh.host = host;
+            ssh.port = port;
+            ssh.username = username;
+            ssh.auth_method = if use_agent {
+                "agent".to_string()
+            } else if use_interactive {
+                "interactive".to_string()
+            } else if key_path.is_empty() {
+                "password".to_string()
+            } else {
+                "key".to_string()
+            };
+            ssh.key_path = key_path;
+            ssh.remember_secrets = remember_secrets;
+            ssh.connect_timeout_secs = config.connect_timeout_secs;
+            ssh.port_forwards.clone_from(&config.port_forwards);
+            if let Err(e) = sm.save() {
+                log::warn!("Impossible de sauvegarder les paramètres SSH : {e}");
+            }
+        }
+
+        Ok(Box::new(SshManager::new(config)))
+    }
+
+    /// Supprime du trousseau le mot de passe de la cible SSH saisie et vide le
+    /// champ ; la reconnexion automatique ne le réutilise plus.
+    fn forget_ssh_password(&self) {
+        let sp = &self.connection_panel.ssh_panel;
+        let (host, port, username) = (sp.host(), sp.port(), sp.username());
+        if host.is_empty() || username.is_empty() {
+            self.show_toast("Saisissez l'hôte et l'utilisateur SSH.");
+            return;
+        }
+
+        match secrets::delete_ssh_password(&host, port, &username) {
+            Ok(()) => {
+                sp.clear_password();
+                let forget_session = self
+                    .session_secret
+                    .borrow()
+                    .as_ref()
+                    .is_some_and(|s| s.matches(&host, port, &username, ""));
+                if forget_session {
+                    self.session_secret.replace(None);
+                }
+                self.show_toast(&format!("Mot de passe oublié pour {}", sp.target()));
+            }
+            Err(e) => {
+                log::warn!("Suppression password keyring impossible : {e}");
+                self.show_toast("Trousseau indisponible : mot de passe non supprimé.");
+            }
+        }
+    }
+
+    /// Ouvre le dialogue d'enregistrement du favori SSH courant (nom + notes).
+    fn prompt_save_ssh_favorite(self: &Rc<Self>) {
+        let sp = &self.connection_panel.ssh_panel;
+        let (host, port, username) = (sp.host(), sp.port(), sp.username());
+        if host.is_empty() || username.is_empty() {
+            self.terminal
+                .append_error("Favori SSH: hôte et utilisateur requis.");
+            return;
+        }
+
+        let existing = self
+            .settings
+            .borrow_mut()
+            .find_ssh_favorite_mut(&host, port, &username)
+            .map(|f| {
+                (
+                    f.name.clone(),
+                    f.notes.clone(),
+                    f.on_connect_commands.join("\n"),
+                )
+            });
+        let (name, notes, commands) = existing.unwrap_or_else(|| {
+            (
+                format!("{username}@{host}:{port}"),
+                String::new(),
+                String::new(),
+            )
+        });
+
+        let name_entry = gtk4::Entry::builder()
+            .text(name)
+            .placeholder_text("Nom du favori")
+            .build();
+        let notes_entry = gtk4::Entry::builder()
+            .text(notes)
+            .placeholder_text("Notes (optionnel)")
+            .build();
+        let fields = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        let commands_view = gtk4::TextView::builder()
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::Char)
+            .build();
+        commands_view.buffer().set_text(&commands);
+        let commands_scroll = gtk4::ScrolledWindow::builder()
+            .min_content_height(90)
+            .child(&commands_view)
+            .build();
+        let commands_label = gtk4::Label::builder()
+            .label("Commandes à la connexion (une par ligne, Maj+Connecter pour ignorer) :")
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        fields.append(&name_entry);
+        fields.append(&notes_entry);
+        fields.append(&commands_label);
+        fields.append(&commands_scroll);
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Enregistrer le favori SSH"),
+            Some(&format!("{username}@{host}:{port}")),
+        );
+        dialog.set_extra_child(Some(&fields));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("save", "Enregistrer");
+        dialog.set_default_response(Some("save"));
+        dialog.set_response_appearance("save", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "save" {
+                let buffer = commands_view.buffer();
+                let commands: Vec<String> = buffer
+                    .text(&buffer.start_iter(), &buffer.end_iter(), false)
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                this.add_current_ssh_favorite(&name_entry.text(), &notes_entry.text(), commands);
+            }
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Ajoute ou met à jour le profil SSH courant dans les favoris persistés.
+    fn add_current_ssh_favorite(&self, name: &str, notes: &str, on_connect_commands: Vec<String>) {
+        let sp = &self.connection_panel.ssh_panel;
+        let host = sp.host();
+        let port = sp.port();
+        let username = sp.username();
+        let key_path = sp.key_path();
+
+        if host.is_empty() || username.is_empty() {
+            self.terminal
+                .append_error("Favori SSH: hôte et utilisateur requis.");
+            return;
+        }
+
+        let auth_method = if sp.use_agent() {
+            "agent".to_string()
+        } else if sp.use_interactive() {
+            "interactive".to_string()
+        } else if key_path.is_empty() {
+            "password".to_string()
+        } else {
+            "key".to_string()
+        };
+
+        let name = if name.trim().is_empty() {
+            format!("{username}@{host}:{port}")
+        } else {
+            name.trim().to_string()
+        };
+
+        let favorite = SshFavorite {
+            name,
+            notes: notes.trim().to_string(),
+            on_connect_commands,
+            host,
+            port,
+            username,
+            auth_method,
+            key_path,
+            ..SshFavorite::default()
+        };
+
+        let mut settings = self.settings.borrow_mut();
+
+        if let Some(existing) =
+            settings.find_ssh_favorite_mut(&favorite.host, favorite.port, &favorite.username)
+        {
+            // Conserver les dossiers mémorisés pour ce favori.
+            let previous = std::mem::replace(existing, favorite.clone());
+            existing.default_remote_dir = previous.default_remote_dir;
+            existing.default_local_dir = previous.default_local_dir;
+            self.show_toast(&format!("✓ Favori mis à jour : {}", favorite.name));
+            self.terminal
+                .append_system(&format!("Favori SSH mis à jour : {}", favorite.name));
+        } else {
+            settings.settings_mut().ssh_favorites.push(favorite.clone());
+            self.show_toast(&format!("✓ Favori ajouté : {}", favorite.name));
+            self.terminal
+                .append_system(&format!("Favori SSH ajouté : {}", favorite.name));
+        }
+
+        if let Err(e) = settings.save() {
+            self.terminal
+                .append_error(&format!("Impossible de sauvegarder les favoris SSH : {e}"));
+            return;
+        }
+
+        let refreshed = settings.settings().ssh_favorites.clone();
+        drop(settings);
+        self.connection_panel.ssh_panel.set_favorites(&refreshed);
+    }
+
+    /// Ouvre la fenêtre de gestion des favoris ; chaque modification est persistée.
+    fn open_favorites_manager(self: &Rc<Self>) {
+        let favorites = self.settings.borrow().settings().ssh_favorites.clone();
+        let this = self.clone();
+        open_favorites_dialog(&self.window, favorites, move |updated| {
+            this.replace_ssh_favorites(updated.to_vec());
+        });
+    }
+
+    /// Demande confirmation puis supprime le favori sélectionné.
+    fn confirm_delete_selected_favorite(self: &Rc<Self>) {
+        let Some(favorite) = self.connection_panel.ssh_panel.selected_favorite() else {
+            self.show_toast("Aucun favori sélectionné");
+            return;
+        };
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Supprimer le favori ?"),
+            Some(&format!(
+                "« {} » ({}@{}:{}) sera supprimé, ainsi que ses secrets \
+                 enregistrés dans le trousseau.",
+                favorite.name, favorite.username, favorite.host, favorite.port
+            )),
+        );
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("delete", "Supprimer");
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_response_appearance("delete", libadwaita::ResponseAppearance::Destructive);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "delete" {
+                return;
+            }
+            let mut favorites = this.settings.borrow().settings().ssh_favorites.clone();
+            // Retirer l'entrée exacte (des doublons peuvent partager hôte/port/utilisateur).
+            if let Some(idx) = favorites
+                .iter()
+                .position(|f| f.name == favorite.name && f.host == favorite.host)
+            {
+                favorites.remove(idx);
+            }
+            this.replace_ssh_favorites(favorites);
+            this.show_toast(&format!("Favori supprimé : {}", favorite.name));
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Remplace la liste des favoris, la persiste et rafraîchit le dropdown.
+    ///
+    /// Les secrets du trousseau d'un hôte qui n'a plus aucun favori sont supprimés.
+    fn replace_ssh_favorites(&self, updated: Vec<SshFavorite>) {
+        let mut settings = self.settings.borrow_mut();
+        let previous = std::mem::replace(&mut settings.settings_mut().ssh_favorites, updated);
+        if let Err(e) = settings.save() {
+            self.terminal
+                .append_error(&format!("Impossible de sauvegarder les favoris SSH : {e}"));
+        }
+        let current = settings.settings().ssh_favorites.clone();
+        drop(settings);
+
+        for removed in previous.iter().filter(|p| {
+            !current
+                .iter()
+                .any(|c| c.host == p.host && c.port == p.port && c.username == p.username)
+        }) {
+            if let Err(e) =
+                secrets::delete_ssh_password(&removed.host, removed.port, &removed.username)
+            {
+                log::warn!("Suppression password keyring impossible : {e}");
+            }
+            if let Err(e) = secrets::delete_ssh_key_passphrase(
+                &removed.host,
+                removed.port,
+                &removed.username,
+                &removed.key_path,
+            ) {
+                log::warn!("Suppression passphrase keyring impossible : {e}");
+            }
+        }
+
+        self.connection_panel.ssh_panel.set_favorites(&current);
+    }
+
+    /// Importe des favoris depuis un fichier OpenSSH config ou CSV choisi par l'utilisateur.
+    fn import_ssh_favorites(self: &Rc<Self>) {
+        let dialog = FileDialog::builder()
+            .title("Importer des favoris SSH (~/.ssh/config ou CSV)")
+            .build();
+        if let Some(path) = ssh_config::user_ssh_config_path().filter(|p| p.exists()) {
+            dialog.set_initial_file(Some(&gio::File::for_path(path)));
+        }
+
+        let this = self.clone();
+        dialog.open(Some(&self.window), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            match ssh_config::load_favorites_from_file(&path) {
+                Ok(candidates) => this.show_import_selection(candidates),
+                Err(e) => this
+                    .terminal
+                    .append_error(&format!("Import impossible : {e}")),
+            }
+        });
+    }
+
+    /// Choisit un fichier à transmettre tel quel, puis règle le découpage.
+    fn choose_file_to_send(self: &Rc<Self>) {
+        if self.connection_tx.borrow().is_none() {
+            self.terminal
+                .append_error("Non connecté — impossible d'envoyer.");
+            return;
+        }
+
+        let dialog = FileDialog::builder().title("Envoyer un fichier").build();
+        let this = self.clone();
+        dialog.open(Some(&self.window), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            match std::fs::read(&path) {
+                Ok(data) if data.is_empty() => this
+                    .terminal
+                    .append_error(&format!("{} est vide, rien à envoyer.", path.display())),
+                Ok(data) => this.confirm_file_send(path, data),
+                Err(e) => this
+                    .terminal
+                    .append_error(&format!("Lecture de {} impossible : {e}", path.display())),
+            }
+        });
+    }
+
+    /// Propose la taille des morceaux et la pause entre eux avant l'envoi.
+    fn confirm_file_send(self: &Rc<Self>, path: std::path::PathBuf, data: Vec<u8>) {
+        let (chunk_size, chunk_delay) = {
+            let settings = self.settings.borrow();
+            let ui = &settings.settings().ui;
+            (ui.file_chunk_size, ui.file_chunk_delay_ms)
+        };
+        let chunk_spin = gtk4::SpinButton::with_range(16.0, 65536.0, 256.0);
+        chunk_spin.set_value(f64::from(chunk_size));
+        let delay_spin = gtk4::SpinButton::with_range(0.0, 10_000.0, 10.0);
+        delay_spin.set_value(f64::from(chunk_delay));
+
+        let fields = gtk4::Grid::builder()
+            .row_spacing(6)
+            .column_spacing(12)
+            .build();
+        for (row, title, spin) in [
+            (0, "Taille des morceaux (octets)", &chunk_spin),
+            (1, "Pause entre morceaux (ms)", &delay_spin),
+        ] {
+            fields.attach(
+                &gtk4::Label::builder().label(title).xalign(0.0).build(),
+                0,
+                row,
+                1,
+                1,
+            );
+            fields.attach(spin, 1, row, 1, 1);
+        }
+
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into(),
+        );
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Envoyer un fichier"),
+            Some(&format!("{name} — {} octets", data.len())),
+        );
+        dialog.set_extra_child(Some(&fields));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("send", "Envoyer");
+        dialog.set_default_response(Some("send"));
+        dialog.set_close_response("cancel");
+        dialog.set_response_appearance("send", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "send" {
+                return;
+            }
+            // Bornes du SpinButton : conversion sans perte.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let (chunk_size, chunk_delay) = (chunk_spin.value() as u32, delay_spin.value() as u32);
+            {
+                let mut sm = this.settings.borrow_mut();
+                let ui = &mut sm.settings_mut().ui;
+                ui.file_chunk_size = chunk_size;
+                ui.file_chunk_delay_ms = chunk_delay;
+                if let Err(e) = sm.save() {
+                    log::warn!("Impossible de sauvegarder les réglages d'envoi de fichier : {e}");
+                }
+            }
+            this.send_file(&name, data.clone(), chunk_size, chunk_delay);
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Transmet `data` par morceaux de `chunk_size` octets espacés de
+    /// `chunk_delay` ms, via le chemin d'envoi commun (série comme SSH).
+    ///
+    /// La progression s'affiche dans un toast mis à jour ; l'envoi s'arrête si
+    /// la connexion est fermée ou remplacée. Un morceau n'est émis que lorsque
+    /// la file d'envoi est vide, pour ne pas charger tout le fichier en attente.
+    fn send_file(self: &Rc<Self>, name: &str, data: Vec<u8>, chunk_size: u32, chunk_delay: u32) {
+        let total = data.len();
+        self.terminal
+            .append_system(&format!("Envoi de {name} ({total} octets)…"));
+
+        let toast = libadwaita::Toast::new(&format!("Envoi de {name} : 0 / {total} octets"));
+        toast.set_timeout(0);
+        self.toast_overlay.add_toast(toast.clone());
+
+        let chunk_size = usize::try_from(chunk_size.max(1)).unwrap_or(usize::MAX);
+        let generation = self.connection_generation.get();
+        let name = name.to_string();
+        let mut sent = 0;
+        let this = self.clone();
+        glib::timeout_add_local(
+            std::time::Duration::from_millis(u64::from(chunk_delay.max(1))),
+            move || {
+                if this.connection_generation.get() != generation
+                    || this.connection_tx.borrow().is_none()
+                {
+                    toast.dismiss();
+                    this.terminal.append_error(&format!(
+                        "Envoi de {name} interrompu : connexion fermée ({sent} / {total} octets)."
+                    ));
+                    return glib::ControlFlow::Break;
+                }
+                if !this.send_queue.borrow().is_empty() {
+                    return glib::ControlFlow::Continue;
+                }
+
+                let end = (sent + chunk_size).min(total);
+                if !this.dispatch_send(data[sent..end].to_vec()) {
+                    toast.dismiss();
+                    this.terminal.append_error(&format!(
+                        "Envoi de {name} interrompu à {sent} / {total} octets."
+                    ));
+                    return glib::ControlFlow::Break;
+                }
+                sent = end;
+                #[allow(clippy::cast_precision_loss)]
+                let percent = sent as f64 * 100.0 / total as f64;
+                toast.set_title(&format!(
+                    "Envoi de {name} : {sent} / {total} octets ({percent:.0} %)"
+                ));
+
+                if sent < total {
+                    return glib::ControlFlow::Continue;
+                }
+                toast.dismiss();
+                this.show_toast(&format!("✓ {name} envoyé"));
+                this.terminal
+                    .append_system(&format!("Fichier {name} envoyé ({total} octets)."));
+                glib::ControlFlow::Break
+            },
+        );
+    }
+
+    /// Choisit un fichier Intel HEX, le valide puis l'envoie ligne par ligne.
+    fn choose_intel_hex_file(self: &Rc<Self>) {
+        if self.connection_tx.borrow().is_none() {
+            self.terminal
+                .append_error("Non connecté — impossible d'envoyer.");
+            return;
+        }
+
+        let filter = gtk4::FileFilter::new();
+        filter.set_name(Some("Intel HEX (*.hex, *.ihx)"));
+        filter.add_pattern("*.hex");
+        filter.add_pattern("*.ihx");
+        let filters = gio::ListStore::new::<gtk4::FileFilter>();
+        filters.append(&filter);
+        let dialog = FileDialog::builder()
+            .title("Envoyer un fichier Intel HEX")
+            .filters(&filters)
+            .build();
+
+        let this = self.clone();
+        dialog.open(Some(&self.window), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            match intelhex::load_intel_hex(&path) {
+                Ok(Ok(file)) => this.send_intel_hex(&path, file),
+                Ok(Err(errors)) => {
+                    this.terminal.append_error(&format!(
+                        "{} : {} enregistrement(s) invalide(s), rien n'a été envoyé",
+                        path.display(),
+                        errors.len()
+                    ));
+                    for error in &errors {
+                        this.terminal
+                            .append_error(&format!("ligne {} : {}", error.line, error.message));
+                    }
+                }
+                Err(e) => this.terminal.append_error(&format!("{e}")),
+            }
+        });
+    }
+
+    /// Envoie les enregistrements validés, un par ligne, espacés de
+    /// `serial.hex_line_delay_ms` (fin de ligne sélectionnée ajoutée).
+    ///
+    /// L'envoi s'arrête si la connexion est fermée ou remplacée.
+    fn send_intel_hex(self: &Rc<Self>, path: &std::path::Path, file: intelhex::IntelHexFile) {
+        let range = file.address_range.map_or_else(
+            || "aucune donnée".to_string(),
+            |(start, end)| format!("0x{start:08X}–0x{end:08X}"),
+        );
+        self.terminal.append_system(&format!(
+            "Envoi de {} : {} enregistrements, {} octets, adresses {range}",
+            path.display(),
+            file.records.len(),
+            file.data_len
+        ));
+
+        let delay = self.settings.borrow().settings().serial.hex_line_delay_ms;
+        let generation = self.connection_generation.get();
+        let total = file.records.len();
+        let mut records = file.records.into_iter();
+        let this = self.clone();
+        glib::timeout_add_local(
+            std::time::Duration::from_millis(u64::from(delay.max(1))),
+            move || {
+                if this.connection_generation.get() != generation
+                    || this.connection_tx.borrow().is_none()
+                {
+                    this.terminal
+                        .append_error("Envoi Intel HEX interrompu : connexion fermée.");
+                    return glib::ControlFlow::Break;
+                }
+                let Some(record) = records.next() else {
+                    this.terminal
+                        .append_system(&format!("Fichier Intel HEX envoyé ({total} lignes)"));
+                    return glib::ControlFlow::Break;
+                };
+                let line_ending = this.input.selected_line_ending();
+                if this.dispatch_send(format!("{}{line_ending}", record.text).into_bytes()) {
+                    glib::ControlFlow::Continue
+                } else {
+                    this.terminal.append_error(&format!(
+                        "Envoi Intel HEX interrompu à la ligne {}",
+                        record.line
+                    ));
+                    glib::ControlFlow::Break
+                }
+            },
+        );
+    }
+
+    /// Laisse l'utilisateur choisir les hôtes à importer parmi les candidats.
+    ///
+    /// Les hôtes déjà présents (même hôte/port/utilisateur) sont écartés.
+    fn show_import_selection(self: &Rc<Self>, candidates: Vec<SshFavorite>) {
+        let candidates: Vec<SshFavorite> = {
+            let settings = self.settings.borrow();
+            let existing = &settings.settings().ssh_favorites;
+            candidates
+                .into_iter()
+                .filter(|c| {
+                    !existing
+                        .iter()
+                        .any(|f| f.host == c.host && f.port == c.port && f.username == c.username)
+                })
+                .collect()
+        };
+
+        if candidates.is_empty() {
+            self.show_toast("Aucun nouvel hôte à importer");
+            return;
+        }
+
+        let list = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .build();
+        let checks: Vec<gtk4::CheckButton> = candidates
+            .iter()
+            .map(|c| {
+                let check = gtk4::CheckButton::builder()
+                    .label(format!("{} — {}@{}:{}", c.name, c.username, c.host, c.port))
+                    .active(true)
+                    .build();
+                list.append(&check);
+                check
+            })
+            .collect();
+        let scroll = gtk4::ScrolledWindow::builder()
+            .min_content_height(200)
+            .max_content_height(400)
+            .propagate_natural_height(true)
+            .child(&list)
+            .build();
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Importer des favoris SSH"),
+            Some(&format!("{} hôte(s) trouvé(s)", candidates.len())),
+        );
+        dialog.set_extra_child(Some(&scroll));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("import", "Importer");
+        dialog.set_default_response(Some("import"));
+        dialog.set_response_appearance("import", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "import" {
+                return;
+            }
+
+            let selected: Vec<SshFavorite> = candidates
+                .iter()
+                .zip(&checks)
+                .filter(|(_, check)| check.is_active())
+                .map(|(c, _)| c.clone())
+                .collect();
+            let count = selected.len();
+
+            let mut settings = this.settings.borrow_mut();
+            settings.settings_mut().ssh_favorites.extend(selected);
+            if let Err(e) = settings.save() {
+                this.terminal
+                    .append_error(&format!("Impossible de sauvegarder les favoris SSH : {e}"));
+                return;
+            }
+            let refreshed = settings.settings().ssh_favorites.clone();
+            drop(settings);
+
+            this.connection_panel.ssh_panel.set_favorites(&refreshed);
+            this.show_toast(&format!("✓ {count} favori(s) importé(s)"));
+            this.terminal
+                .append_system(&format!("{count} favori(s) SSH importé(s)."));
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Applique les champs SSH depuis le favori sélectionné.
+    fn apply_selected_ssh_favorite(&self) {
+        self.connection_panel.ssh_panel.update_favorite_tooltip();
+        let Some(favorite) = self.connection_panel.ssh_panel.selected_favorite() else {
+            return;
+        };
+
+        self.connection_panel.ssh_panel.apply_settings(
+            &favorite.host,
+            favorite.port,
+            &favorite.username,
+            &favorite.key_path,
+        );
+        self.connection_panel
+            .ssh_panel
+            .set_use_agent(favorite.auth_method == "agent");
+        self.connection_panel
+            .ssh_panel
+            .set_use_interactive(favorite.auth_method == "interactive");
+        self.load_saved_ssh_secrets();
+
+        if favorite.notes.is_empty() {
+            self.terminal
+                .append_system(&format!("Favori SSH chargé : {}", favorite.name));
+        } else {
+            self.terminal.append_system(&format!(
+                "Favori SSH chargé : {} — {}",
+                favorite.name, favorite.notes
+            ));
+        }
+    }
+
+    /// Envoie les commandes de connexion du favori courant, une toutes les 500 ms.
+    ///
+    /// Ignoré si Maj était enfoncée au lancement de la connexion.
+    fn run_on_connect_commands(self: &Rc<Self>, generation: u64) {
+        let Some(favorite) = self.current_ssh_favorite() else {
+            return;
+        };
+        if favorite.on_connect_commands.is_empty() {
+            return;
+        }
+        if self.skip_on_connect_commands.get() {
+            self.terminal
+                .append_system("Commandes de connexion ignorées (Maj).");
+            return;
+        }
+
+        self.terminal.append_system(&format!(
+            "Envoi de {} commande(s) de connexion…",
+            favorite.on_connect_commands.len()
+        ));
+        for (i, command) in favorite.on_connect_commands.into_iter().enumerate() {
+            let this = self.clone();
+            let delay = std::time::Duration::from_millis(500 * (i as u64 + 1));
+            glib::timeout_add_local_once(delay, move || {
+                // Connexion fermée ou remplacée entre-temps.
+                if this.connection_generation.get() != generation
+                    || this.connection_tx.borrow().is_none()
+                {
+                    return;
+                }
+                let line_ending = this.input.selected_line_ending();
+                if this.dispatch_send(format!("{command}{line_ending}").into_bytes()) {
+                    this.echo_sent(&format!("→ {command}\n"));
+                }
+            });
+        }
+    }
+
+    /// Mémorise le favori SSH de la connexion établie.
+    fn remember_last_favorite(&self) {
+        let Some(favorite) = self.current_ssh_favorite() else {
+            return;
+        };
+        let mut sm = self.settings.borrow_mut();
+        if sm.settings().ssh.last_favorite == favorite.name {
+            return;
+        }
+        sm.settings_mut().ssh.last_favorite = favorite.name;
+        if let Err(e) = sm.save() {
+            log::warn!("Impossible de mémoriser le dernier favori : {e}");
+        }
+    }
+
+    /// Recharge le dernier favori SSH utilisé (secrets du trousseau inclus) et s'y connecte.
+    fn reconnect_last_favorite(self: &Rc<Self>) {
+        if self.connection_tx.borrow().is_some() {
+            self.show_toast("Déjà connecté — déconnectez-vous d'abord.");
+            return;
+        }
+
+        let name = self.settings.borrow().settings().ssh.last_favorite.clone();
+        if name.is_empty() {
+            self.show_toast("Aucun favori SSH utilisé récemment.");
+            return;
+        }
+
+        self.connection_panel.notebook.set_current_page(Some(1));
+        if !self.connection_panel.ssh_panel.select_favorite(&name) {
+            self.terminal
+                .append_error(&format!("Favori SSH introuvable : {name}"));
+            return;
+        }
+        self.connect();
+    }
+
+    /// Ouvre une nouvelle fenêtre (même profil) pré-remplie avec la
+    /// configuration de la connexion active.
+    ///
+    /// SSH : une nouvelle session est ouverte (la session existante n'est pas
+    /// partagée) ; connexion immédiate si une clé ou le trousseau fournit
+    /// l'authentification, sinon le mot de passe est demandé. Série : le port
+    /// est déjà occupé ici, seuls les paramètres sont recopiés.
+    fn duplicate_connection(self: &Rc<Self>) {
+        let Some(conn_type) = self.active_connection.get() else {
+            self.show_toast("Aucune connexion active à dupliquer.");
+            return;
+        };
+        let Some(app) = self
+            .window
+            .application()
+            .and_downcast::<libadwaita::Application>()
+        else {
+            return;
+        };
+
+        let profile = self.settings.borrow().profile().to_string();
+        let copy = Self::new(&app, Some(&profile));
+        match conn_type {
+            ConnectionType::Serial => {
+                let source = &self.connection_panel.serial_panel;
+                copy.connection_panel.serial_panel.apply_settings(
+                    source.selected_baudrate(),
+                    source.selected_data_bits(),
+                    &source.selected_parity(),
+                    source.selected_stop_bits(),
+                    &source.selected_flow_control(),
+                );
+                copy.connection_panel.notebook.set_current_page(Some(0));
+                copy.terminal.append_system(
+                    "Paramètres série recopiés — choisissez un autre port puis connectez-vous.",
+                );
+            }
+            ConnectionType::Ssh => {
+                let source = &self.connection_panel.ssh_panel;
+                let target = &copy.connection_panel.ssh_panel;
+                copy.connection_panel.notebook.set_current_page(Some(1));
+                target.apply_settings(
+                    &source.host(),
+                    source.port(),
+                    &source.username(),
+                    &source.key_path(),
+                );
+                target.set_connect_timeout_secs(source.connect_timeout_secs());
+                target.set_remember_secrets(source.remember_secrets());
+                target.set_use_agent(source.use_agent());
+                target.set_use_interactive(source.use_interactive());
+                if source.use_agent()
+                    || source.use_interactive()
+                    || !source.key_path().trim().is_empty()
+                    || source.remember_secrets()
+                {
+                    copy.connect();
+                } else {
+                    copy.terminal
+                        .append_system("Saisissez le mot de passe pour ouvrir la seconde session.");
+                    target.password_entry.grab_focus();
+                }
+            }
+        }
+    }
+
+    /// Affiche le lien `sshterm://` de l'onglet de connexion sélectionné
+    /// (aucun secret inclus), avec la commande équivalente.
+    fn export_connection(&self) {
+        let uri = if self.connection_panel.is_serial_selected() {
+            let sp = &self.connection_panel.serial_panel;
+            let Some(port) = sp.selected_port() else {
+                self.show_toast("Aucun port série sélectionné.");
+                return;
+            };
+            ConnectionUri::Serial {
+                port,
+                baudrate: sp.selected_baudrate(),
+                data_bits: sp.selected_data_bits(),
+                parity: sp.selected_parity(),
+                stop_bits: sp.selected_stop_bits(),
+                flow_control: sp.selected_flow_control(),
+            }
+        } else {
+            let sp = &self.connection_panel.ssh_panel;
+            if sp.host().is_empty() || sp.username().is_empty() {
+                self.show_toast("L'hôte et l'utilisateur sont requis.");
+                return;
+            }
+            ConnectionUri::Ssh {
+                host: sp.host(),
+                port: sp.port(),
+                username: sp.username(),
+                key_path: sp.key_path(),
+            }
+        }
+        .to_uri();
+        let command = format!("serial-ssh-term '{uri}'");
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Exporter cette connexion"),
+            Some("Ce lien ouvre SerialSSHTerm pré-configuré. Aucun mot de passe n'y figure."),
+        );
+        let details = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        for text in [&uri, &command] {
+            let label = gtk4::Label::builder()
+                .label(text.as_str())
+                .xalign(0.0)
+                .wrap(true)
+                .wrap_mode(gtk4::pango::WrapMode::Char)
+                .selectable(true)
+                .build();
+            label.add_css_class("monospace");
+            details.append(&label);
+        }
+        dialog.set_extra_child(Some(&details));
+        dialog.add_response("close", "Fermer");
+        dialog.add_response("copy-command", "Copier la commande");
+        dialog.add_response("copy", "Copier le lien");
+        dialog.set_default_response(Some("copy"));
+        dialog.set_response_appearance("copy", libadwaita::ResponseAppearance::Suggested);
+
+        let clipboard = self.window.clipboard();
+        dialog.connect_response(None, move |_, response| match response {
+            "copy" => clipboard.set_text(&uri),
+            "copy-command" => clipboard.set_text(&command),
+            _ => {}
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    /// Injecte un flux synthétique (couleurs, retours arrière, UTF-8 coupé
+    /// entre deux morceaux) dans le rendu et affiche le temps mesuré.
+    ///
+    /// Diagnostic : permet de reproduire un problème d'affichage sans
+    /// périphérique et de comparer les performances.
+    fn run_render_test(&self) {
+        let stream = synthetic_ansi_stream(RENDER_TEST_LINES);
+        let inserts = Rc::new(Cell::new(0u64));
+        let handler = {
+            let inserts = inserts.clone();
+            self.terminal
+                .buffer
+                .connect_insert_text(move |_, _, _| inserts.set(inserts.get() + 1))
+        };
+
+        let started = std::time::Instant::now();
+        let mut chunks = 0;
+        // Morceaux de taille irrégulière : séquences et caractères coupés.
+        for chunk in stream.chunks(RENDER_TEST_CHUNK) {
+            self.terminal.append_ansi(chunk);
+            chunks += 1;
+        }
+        let elapsed = started.elapsed();
+        self.terminal.buffer.disconnect(handler);
+
+        #[allow(clippy::cast_precision_loss)]
+        let throughput = stream.len() as f64 / 1024.0 / elapsed.as_secs_f64().max(f64::EPSILON);
+        self.terminal.append_system(&format!(
+            "Test de rendu : {} octets en {chunks} morceaux, {RENDER_TEST_LINES} lignes, \
+             {} insertions, {} ms ({throughput:.0} Kio/s)",
+            stream.len(),
+            inserts.get(),
+            elapsed.as_millis()
+        ));
+    }
+
+    /// Pré-remplit l'onglet de connexion depuis un lien `sshterm://`.
+    ///
+    /// La connexion n'est pas lancée : l'utilisateur vérifie puis connecte.
+    pub fn apply_connection_uri(&self, uri: &ConnectionUri) {
+        match uri {
+            ConnectionUri::Serial {
+                port,
+                baudrate,
+                data_bits,
+                parity,
+                stop_bits,
+                flow_control,
+            } => {
+                let sp = &self.connection_panel.serial_panel;
+                self.connection_panel.notebook.set_current_page(Some(0));
+                sp.select_port_by_device(port);
+                sp.apply_settings(*baudrate, *data_bits, parity, *stop_bits, flow_control);
+                if sp.selected_port().as_deref() != Some(port.as_str()) {
+                    self.terminal
+                        .append_error(&format!("Port {port} introuvable sur cette machine."));
+                }
+            }
+            ConnectionUri::Ssh {
+                host,
+                port,
+                username,
+                key_path,
+            } => {
+                self.connection_panel.notebook.set_current_page(Some(1));
+                self.connection_panel
+                    .ssh_panel
+                    .apply_settings(host, *port, username, key_path);
+            }
+        }
+        self.terminal.append_system(
+            "Connexion pré-configurée depuis un lien — vérifiez puis connectez-vous.",
+        );
+    }
+
+    /// Retourne le favori SSH correspondant aux champs de l'onglet SSH actif.
+    fn current_ssh_favorite(&self) -> Option<SshFavorite> {
+        if self.connection_panel.is_serial_selected() {
+            return None;
+        }
+
+        let sp = &self.connection_panel.ssh_panel;
+        let (host, port, username) = (sp.host(), sp.port(), sp.username());
+        self.settings
+            .borrow()
+            .settings()
+            .ssh_favorites
+            .iter()
+            .find(|f| f.host == host && f.port == port && f.username == username)
+            .cloned()
+    }
+
+    /// Déconnexion propre initiée par l'utilisateur.
+    /// Délègue à `handle_disconnect()` qui envoie la commande et met à jour l'UI.
+    fn disconnect(self: &Rc<Self>) {
+        // Déconnexion volontaire : plus de reconnexion à authentifier.
+        self.session_secret.replace(None);
+        self.handle_disconnect();
+    }
+
+    /// Ouvre l'explorateur SFTP sur un second canal de la session SSH active.
+    ///
+    /// Le dossier distant visité est mémorisé dans le favori correspondant.
+    fn open_sftp_browser(self: &Rc<Self>) {
+        let Some(tx) = self.connection_tx.borrow().clone() else {
+            self.show_toast("Non connecté");
+            return;
+        };
+        if self.active_connection.get() != Some(ConnectionType::Ssh) {
+            self.terminal
+                .append_error("SFTP : disponible uniquement pour une connexion SSH.");
+            return;
+        }
+
+        let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+        if let Err(e) = tx.try_send(ConnectionCommand::OpenSftp(reply_tx)) {
+            self.terminal
+                .append_error(&format!("SFTP indisponible : {e}"));
+            return;
+        }
+
+        let favorite = self.current_ssh_favorite();
+        let sp = &self.connection_panel.ssh_panel;
+        let (host, port, username) = (sp.host(), sp.port(), sp.username());
+        let this = self.clone();
+        glib::spawn_future_local(async move {
+            // Acteur arrêté avant de répondre : connexion déjà fermée.
+            let Ok(result) = reply_rx.await else {
+                return;
+            };
+            let handle = match result {
+                Ok(handle) => handle,
+                Err(e) => {
+                    this.terminal.append_error(&format!("SFTP : {e:#}"));
+                    return;
+                }
+            };
+            let start_dir = favorite.map(|f| f.default_remote_dir);
+            let settings = this.settings.clone();
+            let title = format!("{username}@{host}");
+            open_sftp_dialog(&this.window, &title, handle, start_dir, move |dir| {
+                settings
+                    .borrow_mut()
+                    .set_favorite_remote_dir(&host, port, &username, dir);
+            });
+        });
+    }
+
+    /// Interroge l'acteur et affiche l'état courant de la connexion.
+    fn show_connection_info(self: &Rc<Self>) {
+        let Some(tx) = self.connection_tx.borrow().clone() else {
+            self.show_toast("Non connecté");
+            return;
+        };
+
+        let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+        if let Err(e) = tx.try_send(ConnectionCommand::QueryInfo(reply_tx)) {
+            self.terminal
+                .append_error(&format!("Détails de connexion indisponibles : {e}"));
+            return;
+        }
+
+        let this = self.clone();
+        glib::spawn_future_local(async move {
+            // Acteur arrêté avant de répondre : rien à afficher.
+            let Ok(info) = reply_rx.await else {
+                return;
+            };
+
+            let mut lines = vec![
+                format!("Type : {}", info.conn_type),
+                format!("État : {}", info.state),
+                format!("Connexion : {}", info.description),
+            ];
+            if let Some(since) = this.connected_since.get() {
+                let elapsed = (chrono::Local::now() - since).num_seconds();
+                lines.push(format!(
+                    "Connecté depuis : {} ({})",
+                    since.format("%H:%M:%S"),
+                    format_duration(elapsed)
+                ));
+            }
+            lines.push(format!("Octets envoyés : {}", info.bytes_sent));
+            lines.push(format!("Octets reçus : {}", info.bytes_received));
+            lines.extend(
+                info.details
+                    .iter()
+                    .map(|(label, value)| format!("{label} : {value}")),
+            );
+
+            let dialog = libadwaita::AlertDialog::new(
+                Some("Détails de la connexion"),
+                Some(&lines.join("\n")),
+            );
+            dialog.add_response("close", "Fermer");
+            dialog.present(Some(&this.window));
+        });
+    }
+
+    /// Règle la limite de scrollback, avec l'occupation actuelle et la
+    /// mémoire estimée pour la limite choisie.
+    fn edit_scrollback_limit(self: &Rc<Self>) {
+        let (lines, chars) = self.terminal.scrollback_usage();
+        let chars_per_line = f64::from(chars) / f64::from(lines.max(1));
+
+        let spin = gtk4::SpinButton::with_range(100.0, 1_000_000.0, 1000.0);
+        spin.set_value(f64::from(self.terminal.max_lines()));
+        let usage_label = gtk4::Label::builder()
+            .label(format!(
+                "Actuellement : {lines} lignes, ≈ {}",
+                format_bytes(estimated_buffer_bytes(f64::from(chars)))
+            ))
+            .xalign(0.0)
+            .build();
+        let preview_label = gtk4::Label::builder().xalign(0.0).wrap(true).build();
+        let fields = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        fields.append(&spin);
+        fields.append(&usage_label);
+        fields.append(&preview_label);
+
+        let refresh_preview = move |spin: &gtk4::SpinButton| {
+            let limit = spin.value();
+            let mut text = format!(
+                "Limite pleine : ≈ {} (au débit actuel de {chars_per_line:.0} car./ligne)",
+                format_bytes(estimated_buffer_bytes(limit * chars_per_line))
+            );
+            if limit > f64::from(SCROLLBACK_WARNING_LINES) {
+                text.push_str("\n⚠ Historique très long : défilement et recherche ralentis.");
+                preview_label.add_css_class("warning");
+            } else {
+                preview_label.remove_css_class("warning");
+            }
+            preview_label.set_label(&text);
+        };
+        refresh_preview(&spin);
+        spin.connect_value_changed(refresh_preview);
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Taille de l'historique"),
+            Some("Nombre maximal de lignes conservées dans le terminal."),
+        );
+        dialog.set_extra_child(Some(&fields));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("apply", "Appliquer");
+        dialog.set_default_response(Some("apply"));
+        dialog.set_response_appearance("apply", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "apply" {
+                return;
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let limit = spin.value() as u32;
+            this.terminal.set_max_lines(limit);
+            let mut sm = this.settings.borrow_mut();
+            sm.settings_mut().ui.max_scrollback_lines = limit;
+            if let Err(e) = sm.save() {
+                log::warn!("Impossible de sauvegarder la taille de l'historique : {e}");
+            }
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Ouvre le réglage du nombre de lignes au-delà duquel un collage ou une
+    /// saisie multiligne demande confirmation.
+    fn edit_paste_confirm_lines(self: &Rc<Self>) {
+        let spin = gtk4::SpinButton::with_range(1.0, 10_000.0, 1.0);
+        spin.set_value(f64::from(
+            self.settings.borrow().settings().ui.paste_confirm_lines,
+        ));
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Seuil de confirmation d'envoi"),
+            Some("Au-delà de ce nombre de lignes, un collage ou une saisie multiligne demande confirmation avant l'envoi."),
+        );
+        dialog.set_extra_child(Some(&spin));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("apply", "Appliquer");
+        dialog.set_default_response(Some("apply"));
+        dialog.set_response_appearance("apply", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "apply" {
+                return;
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let lines = spin.value() as u32;
+            let mut sm = this.settings.borrow_mut();
+            sm.settings_mut().ui.paste_confirm_lines = lines;
+            if let Err(e) = sm.save() {
+                log::warn!("Impossible de sauvegarder le seuil de confirmation : {e}");
+            }
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Fige les statistiques de l'en-tête si l'option est active et la vue
+    /// remontée dans l'historique.
+    fn refresh_stats_freeze(&self) {
+        let enabled = self
+            .settings
+            .borrow()
+            .settings()
+            .ui
+            .freeze_stats_when_scrolled;
+        self.refresh_stats_freeze_with(enabled);
+    }
+
+    fn refresh_stats_freeze_with(&self, enabled: bool) {
+        self.header
+            .set_stats_frozen(enabled && self.terminal.is_scrolled_up());
+    }
+
+    /// Conseils affichés quand aucun port série n'est détecté.
+    fn show_no_ports_help(&self) {
+        self.terminal
+            .append_system("Aucun port série détecté. Vérifiez :");
+        self.terminal.append_system(
+            "  • le câble ou l'adaptateur USB-série est branché (essayez un autre port USB) ;",
+        );
+        self.terminal.append_system(
+            "  • les droits d'accès : sudo usermod -aG dialout $USER, puis reconnectez votre session ;",
+        );
+        self.terminal.append_system(
+            "  • puis cliquez sur le bouton Rafraîchir à côté de la liste des ports.",
+        );
+        self.terminal
+            .append_system("Pour une connexion SSH, choisissez l'onglet SSH.");
+    }
+
+    /// Reflète dans le menu l'état de suspension de la lecture.
+    fn set_reading_paused_state(&self, paused: bool) {
+        if let Some(action) = self
+            .window
+            .lookup_action("pause-reading")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            action.set_state(&paused.to_variant());
+        }
+    }
+
+    /// Demande à l'acteur de vider les tampons série.
+    fn flush_serial_buffers(&self, input: bool, output: bool) {
+        if let Some(tx) = self.connection_tx.borrow().as_ref() {
+            if let Err(e) = tx.try_send(ConnectionCommand::FlushBuffers { input, output }) {
+                self.terminal
+                    .append_error(&format!("Vidage des tampons impossible : {e}"));
             }
-            glib::ControlFlow::Continue
-        });
+        } else {
+            self.terminal
+                .append_error("Non connecté — aucun tampon à vider.");
+        }
     }
 
-    /// Traite la déconnexion — idempotente.
-    ///
-    /// Peut être appelée depuis :
-    ///   - l'UI (bouton déconnecter) via `disconnect()`
-    ///   - le timer `GLib` quand l'acteur signale Disconnected/Error/Closed
-    ///
-    /// Sécurité : le `take()` de `connection_tx` est atomique (thread GTK
-    /// unique) et garantit qu'aucun appel simultané ne met à jour l'UI deux fois.
-    fn handle_disconnect(&self) {
-        // `take()` retire le sender : seul le premier appelant obtient Some.
-        let had_connection = self.connection_tx.borrow().is_some();
-        if let Some(tx) = self.connection_tx.borrow_mut().take() {
-            // Informer l'acteur de se terminer (peut échouer si déjà fermé — normal).
-            if tx.try_send(ConnectionCommand::Disconnect).is_err() {
-                log::debug!("Acteur déjà fermé lors de handle_disconnect");
+    /// Active ou coupe la mesure de latence de la connexion en cours.
+    fn set_latency_tracking(&self, enabled: bool) {
+        if !enabled {
+            self.header.set_latency(None);
+        }
+        if let Some(tx) = self.connection_tx.borrow().as_ref() {
+            if let Err(e) = tx.try_send(ConnectionCommand::SetLatencyTracking(enabled)) {
+                log::warn!("Mesure de latence non transmise : {e}");
             }
         }
-        // Mettre à jour l'UI seulement si la connexion était active.
-        // (Prévient les messages 'Déconnecté' dupliquement en cas d'appels successifs.)
-        if had_connection {
-            self.connection_panel.set_connected(false);
-            self.header.set_status("Déconnecté", false);
-            self.terminal.append_system("Déconnecté");
-            self.show_toast("Connexion terminée");
+    }
+
+    /// Choisit le fichier de capture brute puis la démarre dans l'acteur.
+    fn start_raw_capture(self: &Rc<Self>) {
+        if self.connection_tx.borrow().is_none() {
+            self.show_toast("Non connecté");
+            return;
+        }
+        let (options, folder) = {
+            let settings = self.settings.borrow();
+            let log = &settings.settings().log;
+            let options = CaptureOptions {
+                record_tx: log.raw_capture_tx,
+                tag_direction: log.raw_capture_tagged,
+                timestamps: log.raw_capture_timestamps,
+            };
+            (options, settings.log_directory())
+        };
+        let dialog = FileDialog::builder()
+            .title("Capture brute des octets")
+            .initial_name(format!(
+                "capture_{}.bin",
+                chrono::Local::now().format("%Y%m%d_%H%M%S")
+            ))
+            .build();
+        if folder.is_dir() {
+            dialog.set_initial_folder(Some(&gio::File::for_path(&folder)));
         }
+        let this = self.clone();
+        dialog.save(Some(&self.window), gio::Cancellable::NONE, move |result| {
+            if let Some(path) = result.ok().and_then(|file| file.path()) {
+                this.send_control_command(ConnectionCommand::StartCapture { path, options });
+            }
+        });
     }
 
-    /// Affiche une notification toast Adwaita non-bloquante (3 s par défaut).
+    /// Reflète l'état de la capture brute dans la case du menu.
+    fn set_raw_capture_state(&self, capturing: bool) {
+        if let Some(action) = self
+            .window
+            .lookup_action("raw-capture")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            action.set_state(&capturing.to_variant());
+        }
+    }
+
+    /// Ouvre un dossier dans le gestionnaire de fichiers, en le créant au besoin.
+    fn open_directory(&self, dir: &std::path::Path) {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            self.terminal.append_error(&format!(
+                "Impossible de créer le dossier {} : {e}",
+                dir.display()
+            ));
+            return;
+        }
+        let uri = gio::File::for_path(dir).uri();
+        let shown = dir.display().to_string();
+        gtk4::UriLauncher::new(&uri).launch(
+            Some(&self.window),
+            gio::Cancellable::NONE,
+            move |result| {
+                if let Err(e) = result {
+                    log::warn!("Impossible d'ouvrir le dossier {shown} : {e}");
+                }
+            },
+        );
+    }
+
+    /// Envoie une commande de contrôle ponctuelle (lignes série, break…).
+    fn send_control_command(&self, command: ConnectionCommand) {
+        if drives_device(&command) && self.is_read_only() {
+            self.terminal
+                .append_error("Mode lecture seule — signal non envoyé.");
+            return;
+        }
+        let Some(tx) = self.connection_tx.borrow().clone() else {
+            self.show_toast("Non connecté");
+            return;
+        };
+        if let Err(e) = tx.try_send(command) {
+            self.terminal
+                .append_error(&format!("Commande non envoyée : {e}"));
+        }
+    }
+
+    /// Fige l'affichage pendant la durée de capture du démarrage, si activée.
     ///
-    /// À utiliser pour les confirmations et erreurs transientes.
-    /// Les erreurs critiques persistantes doivent utiliser `terminal.append_error()`.
-    pub fn show_toast(&self, message: &str) {
-        let toast = libadwaita::Toast::new(message);
-        toast.set_timeout(3);
-        self.toast_overlay.add_toast(toast);
+    /// Le flux du démarrage reste lisible depuis le message de connexion ;
+    /// l'affichage reprend normalement à la fin du délai.
+    fn start_boot_capture(self: &Rc<Self>) {
+        let (enabled, secs) = {
+            let settings = self.settings.borrow();
+            let serial = &settings.settings().serial;
+            (serial.boot_capture, serial.boot_capture_secs)
+        };
+        if !enabled || secs == 0 {
+            return;
+        }
+
+        self.stop_boot_capture();
+        self.terminal.set_capture_hold(true);
+        self.terminal.append_system(&format!(
+            "Capture du démarrage pendant {secs} s (défilement suspendu)"
+        ));
+        let this = self.clone();
+        let timer = glib::timeout_add_seconds_local_once(secs, move || {
+            this.boot_capture_timer.borrow_mut().take();
+            this.terminal.set_capture_hold(false);
+            this.terminal.append_system("Capture du démarrage terminée");
+        });
+        *self.boot_capture_timer.borrow_mut() = Some(timer);
     }
 
-    /// Charge les secrets SSH sauvegardés dans le trousseau système.
-    fn load_saved_ssh_secrets(&self) {
+    /// Programme l'effacement des champs de secrets SSH, `ssh.password_clear_secs`
+    /// après la connexion ou la dernière saisie.
+    ///
+    /// `connect()` vide déjà ces champs ; ce délai couvre ceux remplis
+    /// pendant la session (favori chargé, trousseau, saisie anticipée).
+    fn arm_password_clear(self: &Rc<Self>) {
+        self.cancel_password_clear();
+        if self.active_connection.get() != Some(ConnectionType::Ssh) {
+            return;
+        }
+        let secs = self.settings.borrow().settings().ssh.password_clear_secs;
         let sp = &self.connection_panel.ssh_panel;
-        if !sp.remember_secrets() {
-            sp.clear_password();
-            sp.clear_passphrase();
+        if secs == 0 || (sp.password().is_empty() && sp.passphrase().is_empty()) {
             return;
         }
 
-        let host = sp.host();
-        let port = sp.port();
-        let username = sp.username();
-        let key_path = sp.key_path();
-
-        if host.is_empty() || username.is_empty() {
+        let this = self.clone();
+        let timer = glib::timeout_add_seconds_local_once(secs, move || {
+            this.password_clear_timer.borrow_mut().take();
+            let sp = &this.connection_panel.ssh_panel;
             sp.clear_password();
             sp.clear_passphrase();
-            return;
+        });
+        *self.password_clear_timer.borrow_mut() = Some(timer);
+    }
+
+    /// Annule l'effacement programmé des secrets (déconnexion).
+    fn cancel_password_clear(&self) {
+        if let Some(timer) = self.password_clear_timer.borrow_mut().take() {
+            timer.remove();
         }
+    }
 
-        if let Some(password) = secrets::load_ssh_password(&host, port, &username) {
-            sp.set_password(&password);
-        } else {
-            sp.clear_password();
+    /// Interrompt la capture du démarrage en cours (déconnexion).
+    fn stop_boot_capture(&self) {
+        if let Some(timer) = self.boot_capture_timer.borrow_mut().take() {
+            timer.remove();
         }
+        self.terminal.set_capture_hold(false);
+    }
 
-        if key_path.trim().is_empty() {
-            sp.clear_passphrase();
+    /// Envoie le reset DTR/RTS puis le break configurés pour l'ouverture du port.
+    ///
+    /// Les commandes sont traitées dans l'ordre par l'acteur : le break part
+    /// une fois la carte sortie du reset.
+    fn send_serial_connect_signals(&self) {
+        let (reset, send_break, break_ms) = {
+            let settings = self.settings.borrow();
+            let serial = &settings.settings().serial;
+            (
+                serial.reset_on_connect,
+                serial.break_on_connect,
+                serial.break_ms,
+            )
+        };
+        let Some(tx) = self.connection_tx.borrow().clone() else {
+            return;
+        };
+        if (reset || send_break) && self.is_read_only() {
+            self.terminal
+                .append_system("Lecture seule : signaux de connexion non envoyés.");
             return;
         }
+        let mut commands = Vec::new();
+        if reset {
+            commands.push(ConnectionCommand::PulseReset {
+                duration_ms: SERIAL_RESET_PULSE_MS,
+            });
+        }
+        if send_break {
+            commands.push(ConnectionCommand::SendBreak {
+                duration_ms: break_ms,
+            });
+        }
+        for command in commands {
+            if let Err(e) = tx.try_send(command) {
+                self.terminal
+                    .append_error(&format!("Signal de connexion non envoyé : {e}"));
+            }
+        }
+    }
 
-        if let Some(passphrase) =
-            secrets::load_ssh_key_passphrase(&host, port, &username, &key_path)
-        {
-            sp.set_passphrase(&passphrase);
-        } else {
-            sp.clear_passphrase();
+    /// Envoie `stty rows N cols M` au périphérique d'après la grille affichée.
+    fn notify_terminal_size(self: &Rc<Self>) {
+        let Some((cols, rows)) = self.terminal.grid_size() else {
+            self.terminal
+                .append_error("Taille du terminal indisponible.");
+            return;
+        };
+        let command = format!("stty rows {rows} cols {cols}");
+        let line_ending = self.input.selected_line_ending();
+        if self.dispatch_send(format!("{command}{line_ending}").into_bytes()) {
+            self.echo_sent(&format!("→ {command}\n"));
         }
     }
 
-    /// Construit le manager série à partir de l'UI.
-    /// La connexion effective est établie par `spawn_connection_actor`.
-    fn build_serial_manager(&self) -> Result<Box<dyn Connection>, String> {
-        let sp = &self.connection_panel.serial_panel;
-        let port = sp
-            .selected_port()
-            .ok_or_else(|| "Aucun port sélectionné".to_string())?;
+    /// Type de la connexion active, sinon celui de l'onglet sélectionné.
+    fn current_connection_type(&self) -> ConnectionType {
+        self.active_connection.get().unwrap_or_else(|| {
+            if self.connection_panel.is_serial_selected() {
+                ConnectionType::Serial
+            } else {
+                ConnectionType::Ssh
+            }
+        })
+    }
 
-        let config = SerialConfig::from_params(
-            &port,
-            sp.selected_baudrate(),
-            sp.selected_data_bits(),
-            &sp.selected_parity(),
-            sp.selected_stop_bits(),
-            &sp.selected_flow_control(),
-            self.settings.borrow().settings().serial.timeout_ms,
-        );
+    /// Indique si le mode lecture seule s'applique actuellement.
+    fn is_read_only(&self) -> bool {
+        let settings = self.settings.borrow();
+        match self.current_connection_type() {
+            ConnectionType::Serial => settings.settings().serial.read_only,
+            ConnectionType::Ssh => settings.settings().ssh.read_only,
+        }
+    }
 
-        // Sauvegarder les paramètres série
+    /// Répercute le mode lecture seule sur le menu, l'en-tête et la saisie.
+    fn refresh_read_only(&self) {
+        let read_only = self.is_read_only();
+        if let Some(action) = self
+            .window
+            .lookup_action("read-only")
+            .and_downcast::<gio::SimpleAction>()
         {
-            let mut sm = self.settings.borrow_mut();
-            let serial = &mut sm.settings_mut().serial;
-            serial.port = port;
-            serial.baudrate = sp.selected_baudrate();
-            serial.data_bits = sp.selected_data_bits();
-            serial.parity = sp.selected_parity();
-            serial.stop_bits = sp.selected_stop_bits();
-            serial.flow_control = sp.selected_flow_control();
-            if let Err(e) = sm.save() {
-                log::warn!("Impossible de sauvegarder les paramètres série : {e}");
-            }
+            action.set_state(&read_only.to_variant());
         }
-
-        Ok(Box::new(SerialManager::new(config)))
+        if let Some(action) = self
+            .window
+            .lookup_action("pause-reading")
+            .and_downcast::<gio::SimpleAction>()
+        {
+            action.set_enabled(!read_only);
+        }
+        self.header.set_read_only(read_only);
+        self.input.set_read_only(read_only);
+        self.connection_panel.serial_panel.set_read_only(read_only);
     }
 
-    /// Construit le manager SSH à partir de l'UI.
-    /// La connexion effective (TCP + handshake + auth + `known_hosts`) est
-    /// établie par `spawn_connection_actor` dans une tâche tokio.
-    fn build_ssh_manager(&self) -> Result<Box<dyn Connection>, String> {
-        let sp = &self.connection_panel.ssh_panel;
-        let host = sp.host();
-        let port = sp.port();
-        let username = sp.username();
-        let mut password = sp.password();
-        let mut passphrase = sp.passphrase();
-        let key_path = sp.key_path();
+    /// Active le mode AT : saisie en majuscules, complétion et fin de ligne CR.
+    fn set_at_mode(&self, enabled: bool) {
+        self.input.set_at_mode(enabled);
+        self.refresh_send_defaults();
+    }
 
-        if host.is_empty() || username.is_empty() {
-            return Err("L'hôte et l'utilisateur sont requis.".to_string());
+    /// Bascule manuelle du clavier direct : focus sur le terminal et message.
+    fn set_direct_keyboard(&self, enabled: bool) {
+        if enabled {
+            self.terminal.text_view.grab_focus();
+            self.terminal.append_system(
+                "Clavier direct activé : les touches frappées dans le terminal sont \
+                 envoyées immédiatement (Ctrl+Maj+C / Ctrl+Maj+V pour copier / coller).",
+            );
+        } else {
+            self.terminal.append_system("Clavier direct désactivé.");
         }
+    }
 
-        let remember_secrets = sp.remember_secrets();
+    /// Clavier direct : envoie la séquence de la touche pressée dans le
+    /// terminal. Les combinaisons Ctrl+Maj restent aux raccourcis de l'UI.
+    fn on_terminal_key(
+        self: &Rc<Self>,
+        keyval: gdk::Key,
+        state: gdk::ModifierType,
+    ) -> glib::Propagation {
+        let (enabled, backspace) = {
+            let settings = self.settings.borrow();
+            let ui = &settings.settings().ui;
+            (
+                ui.direct_keyboard,
+                BackspaceMode::from_str_name(&ui.backspace_key),
+            )
+        };
+        let mods = KeyModifiers {
+            shift: state.contains(gdk::ModifierType::SHIFT_MASK),
+            alt: state.contains(gdk::ModifierType::ALT_MASK),
+            ctrl: state.contains(gdk::ModifierType::CONTROL_MASK),
+        };
+        if !enabled || self.connection_tx.borrow().is_none() || (mods.ctrl && mods.shift) {
+            return glib::Propagation::Proceed;
+        }
+        let Some(bytes) =
+            terminal_key(keyval).and_then(|key| keymap::key_to_bytes(key, mods, backspace))
+        else {
+            return glib::Propagation::Proceed;
+        };
+        self.dispatch_send(bytes);
+        glib::Propagation::Stop
+    }
 
-        if remember_secrets {
-            if key_path.trim().is_empty() {
-                if password.trim().is_empty() {
-                    password =
-                        secrets::load_ssh_password(&host, port, &username).unwrap_or_default();
-                }
-            } else if passphrase.trim().is_empty() {
-                passphrase = secrets::load_ssh_key_passphrase(&host, port, &username, &key_path)
-                    .unwrap_or_default();
+    /// Alimente la détection de fin de ligne ; adopte le résultat pour les
+    /// envois (mémorisé pour le type de connexion) et le signale.
+    fn detect_line_ending(&self, data: &[u8]) {
+        let detected = {
+            let mut detector = self.line_ending_detector.borrow_mut();
+            let Some(d) = detector.as_mut() else {
+                return;
+            };
+            let detected = d.push(data);
+            if detected.is_some() || d.is_exhausted() {
+                *detector = None;
             }
+            detected
+        };
+        let Some(delimiter) = detected else {
+            return;
+        };
+
+        let idx = match delimiter {
+            LineDelimiter::Cr => 1,
+            LineDelimiter::CrLf => 2,
+            _ => 0,
+        };
+        if self.input.line_ending_dropdown.selected() != idx {
+            self.input.line_ending_dropdown.set_selected(idx);
+            self.show_toast(&format!(
+                "Fin de ligne détectée : {} (utilisée pour les envois)",
+                delimiter.id()
+            ));
         }
+    }
 
-        let auth_method = if key_path.is_empty() {
-            SshAuthMethod::Password(password.clone())
-        } else {
-            SshAuthMethod::KeyFile {
-                private_key_path: key_path.clone(),
-                passphrase: if passphrase.trim().is_empty() {
-                    None
-                } else {
-                    Some(passphrase.clone())
-                },
+    /// Applique les réglages d'envoi mémorisés pour le type de connexion
+    /// courant : fin de ligne (CR en mode AT) et mode hexadécimal.
+    fn refresh_send_defaults(&self) {
+        let hex = self
+            .settings
+            .borrow()
+            .send_hex_for(self.current_connection_type());
+        self.input.hex_checkbox.set_active(hex);
+        let idx = {
+            let settings = self.settings.borrow();
+            if self.input.at_mode() {
+                1
+            } else {
+                match settings.line_ending_for(self.current_connection_type()) {
+                    "CR" => 1,
+                    "CRLF" => 2,
+                    "None" => 3,
+                    _ => 0, // LF par défaut
+                }
             }
         };
+        // Hors de l'emprunt : le changement de sélection sauvegarde les paramètres.
+        self.input.line_ending_dropdown.set_selected(idx);
+    }
 
-        let config = SshConfig {
-            host: host.clone(),
-            port,
-            username: username.clone(),
-            auth_method,
-            connect_timeout_secs: 10,
+    /// Point de passage unique de tout envoi vers la connexion active.
+    ///
+    /// Refuse l'envoi en mode lecture seule ou hors connexion (message
+    /// d'erreur affiché) ; retourne `true` si les données ont été transmises
+    /// ou mises en file d'attente.
+    fn dispatch_send(self: &Rc<Self>, data: Vec<u8>) -> bool {
+        if self.is_read_only() {
+            self.terminal
+                .append_error("Mode lecture seule — envoi bloqué.");
+            return false;
+        }
+
+        let Some(tx) = self.connection_tx.borrow().clone() else {
+            self.terminal
+                .append_error("Non connecté — impossible d'envoyer.");
+            return false;
         };
 
-        if remember_secrets {
-            if key_path.trim().is_empty() {
-                if let Err(e) = secrets::save_ssh_password(&host, port, &username, &password) {
-                    log::warn!("Impossible de sauvegarder le mot de passe dans le keyring : {e}");
-                }
-            } else if let Err(e) =
-                secrets::save_ssh_key_passphrase(&host, port, &username, &key_path, &passphrase)
-            {
-                log::warn!("Impossible de sauvegarder la passphrase dans le keyring : {e}");
+        // Des envois attendent déjà : passer derrière pour préserver l'ordre.
+        if !self.send_queue.borrow().is_empty() {
+            self.enqueue_send(data);
+            self.pending_echoes.borrow_mut().push_back(Vec::new());
+            return true;
+        }
+
+        let sent = match tx.try_send(ConnectionCommand::SendData(data)) {
+            Ok(()) => true,
+            Err(tokio::sync::mpsc::error::TrySendError::Full(ConnectionCommand::SendData(
+                data,
+            ))) => {
+                self.enqueue_send(data);
+                true
             }
-        } else if key_path.trim().is_empty() {
-            if let Err(e) = secrets::delete_ssh_password(&host, port, &username) {
-                log::warn!("Suppression password keyring impossible : {e}");
+            Err(e) => {
+                self.terminal.append_error(&format!("Erreur d'envoi : {e}"));
+                false
             }
-        } else if let Err(e) = secrets::delete_ssh_key_passphrase(&host, port, &username, &key_path)
+        };
+        if sent {
+            self.pending_echoes.borrow_mut().push_back(Vec::new());
+        }
+        sent
+    }
+
+    /// Écho local d'un envoi, rattaché au dernier `dispatch_send`.
+    ///
+    /// Affiché quand l'acteur confirme l'écriture (`DataSent`) : les données
+    /// reçues entre-temps restent avant l'écho, même si l'affichage a du retard.
+    fn echo_sent(&self, text: &str) {
+        if !self
+            .settings
+            .borrow()
+            .local_echo_for(self.current_connection_type())
         {
-            log::warn!("Suppression passphrase keyring impossible : {e}");
+            return;
+        }
+        let mut pending = self.pending_echoes.borrow_mut();
+        if let Some(echoes) = pending.back_mut() {
+            echoes.push(text.to_string());
+        } else {
+            drop(pending);
+            self.terminal.append_sent(text);
+        }
+    }
+
+    /// Met un envoi en file d'attente et démarre la vidange si nécessaire.
+    fn enqueue_send(self: &Rc<Self>, data: Vec<u8>) {
+        let depth = {
+            let mut queue = self.send_queue.borrow_mut();
+            queue.push_back(data);
+            queue.len()
+        };
+        self.input.set_queue_depth(depth);
+
+        if self.send_queue_draining.replace(true) {
+            return;
         }
 
-        // Sauvegarder les paramètres SSH
-        {
-            let mut sm = self.settings.borrow_mut();
-            let ssh = &mut sm.settings_mut().ssh;
-            ssh.host = host;
-            ssh.port = port;
-            ssh.username = username;
-            ssh.auth_method = if key_path.is_empty() {
-                "password".to_string()
-            } else {
-                "key".to_string()
+        // Vidange dans l'ordre dès que le canal de commandes a de la place.
+        let this = self.clone();
+        glib::timeout_add_local(std::time::Duration::from_millis(10), move || {
+            let Some(tx) = this.connection_tx.borrow().clone() else {
+                this.clear_send_queue();
+                return glib::ControlFlow::Break;
             };
-            ssh.key_path = key_path;
-            ssh.remember_secrets = remember_secrets;
-            if let Err(e) = sm.save() {
-                log::warn!("Impossible de sauvegarder les paramètres SSH : {e}");
+
+            loop {
+                let next = this.send_queue.borrow_mut().pop_front();
+                let Some(data) = next else {
+                    this.clear_send_queue();
+                    return glib::ControlFlow::Break;
+                };
+                match tx.try_send(ConnectionCommand::SendData(data)) {
+                    Ok(()) => {}
+                    Err(tokio::sync::mpsc::error::TrySendError::Full(
+                        ConnectionCommand::SendData(data),
+                    )) => {
+                        this.send_queue.borrow_mut().push_front(data);
+                        break;
+                    }
+                    Err(e) => {
+                        this.terminal.append_error(&format!("Erreur d'envoi : {e}"));
+                        this.clear_send_queue();
+                        return glib::ControlFlow::Break;
+                    }
+                }
             }
-        }
 
-        Ok(Box::new(SshManager::new(config)))
+            this.input.set_queue_depth(this.send_queue.borrow().len());
+            glib::ControlFlow::Continue
+        });
     }
 
-    /// Ajoute ou met à jour le profil SSH courant dans les favoris persistés.
-    fn add_current_ssh_favorite(&self) {
-        let sp = &self.connection_panel.ssh_panel;
-        let host = sp.host();
-        let port = sp.port();
-        let username = sp.username();
-        let key_path = sp.key_path();
+    /// Vide la file d'envoi (déconnexion ou vidange terminée).
+    fn clear_send_queue(&self) {
+        let dropped = std::mem::take(&mut *self.send_queue.borrow_mut()).len();
+        if dropped > 0 {
+            self.terminal.append_error(&format!(
+                "{dropped} envoi(s) en attente abandonné(s) : connexion fermée."
+            ));
+        }
+        self.send_queue_draining.set(false);
+        self.input.set_queue_depth(0);
+    }
 
-        if host.is_empty() || username.is_empty() {
-            self.terminal
-                .append_error("Favori SSH: hôte et utilisateur requis.");
+    /// Envoie les données saisies à la connexion active.
+    ///
+    /// Syntaxe de répétition : `@5 commande` envoie `commande` cinq fois.
+    fn send_data(self: &Rc<Self>) {
+        let input = self.input.get_text();
+        if input.is_empty() {
             return;
         }
 
-        let auth_method = if key_path.is_empty() {
-            "password".to_string()
+        let (count, text) = parse_repeat_prefix(&input).unwrap_or((1, input.as_str()));
+        let repeat = if count > 1 {
+            format!("[×{count}] ")
         } else {
-            "key".to_string()
+            String::new()
         };
-
-        let favorite = SshFavorite {
-            name: format!("{username}@{host}:{port}"),
-            host,
-            port,
-            username,
-            auth_method,
-            key_path,
+        let (data, echo) = if self.input.hex_mode() {
+            // Octets exacts : pas de fin de ligne ajoutée.
+            let bytes = match framing::parse_hex_bytes(text) {
+                Ok(bytes) if !bytes.is_empty() => bytes,
+                Ok(_) => return,
+                Err(e) => {
+                    self.terminal
+                        .append_error(&format!("Saisie hexadécimale invalide : {e}"));
+                    return;
+                }
+            };
+            let echo = format!("→ {repeat}[HEX] {}\n", framing::hex_bytes(&bytes));
+            (bytes.repeat(count), echo)
+        } else {
+            let line_ending = self.input.selected_line_ending();
+            (
+                format!("{text}{line_ending}").repeat(count).into_bytes(),
+                format!("→ {repeat}{text}\n"),
+            )
         };
 
-        let mut settings = self.settings.borrow_mut();
-        let favorites = &mut settings.settings_mut().ssh_favorites;
+        // Texte collé dans le champ : même garde-fou que « Coller et envoyer ».
+        let line_count = if self.input.hex_mode() {
+            1
+        } else {
+            text.lines().count() * count
+        };
+        let threshold = self.settings.borrow().settings().ui.paste_confirm_lines;
+        if line_count > 1 && line_count > threshold as usize {
+            let lines: Vec<String> = text.lines().map(str::to_string).collect();
+            let this = self.clone();
+            self.confirm_line_count(&lines, line_count, "La saisie", move || {
+                this.submit_input(data.clone(), echo.clone());
+            });
+        } else {
+            self.submit_input(data, echo);
+        }
+    }
 
-        if let Some(existing) = favorites.iter_mut().find(|f| {
-            f.host == favorite.host && f.port == favorite.port && f.username == favorite.username
-        }) {
-            *existing = favorite.clone();
-            self.show_toast(&format!("✓ Favori mis à jour : {}", favorite.name));
-            self.terminal
-                .append_system(&format!("Favori SSH mis à jour : {}", favorite.name));
+    /// Envoie la saisie, après confirmation des octets si l'option est active.
+    fn submit_input(self: &Rc<Self>, data: Vec<u8>, echo: String) {
+        if self.settings.borrow().settings().ui.confirm_send {
+            self.confirm_send(data, echo);
         } else {
-            favorites.push(favorite.clone());
-            self.show_toast(&format!("✓ Favori ajouté : {}", favorite.name));
-            self.terminal
-                .append_system(&format!("Favori SSH ajouté : {}", favorite.name));
+            self.send_input(data, &echo);
         }
+    }
 
-        if let Err(e) = settings.save() {
-            self.terminal
-                .append_error(&format!("Impossible de sauvegarder les favoris SSH : {e}"));
-            return;
+    /// Transmet la saisie et vide le champ si l'envoi est accepté.
+    fn send_input(self: &Rc<Self>, data: Vec<u8>, echo: &str) {
+        if self.dispatch_send(data) {
+            self.echo_sent(echo);
+            self.input.clear();
+            self.input.grab_focus();
         }
+    }
 
-        let refreshed = settings.settings().ssh_favorites.clone();
-        drop(settings);
-        self.connection_panel.ssh_panel.set_favorites(&refreshed);
+    /// Montre les octets exacts à envoyer (texte échappé, hex, taille)
+    /// et n'envoie qu'après validation.
+    fn confirm_send(self: &Rc<Self>, data: Vec<u8>, echo: String) {
+        let dialog =
+            libadwaita::AlertDialog::new(Some(&format!("Envoyer {} octet(s) ?", data.len())), None);
+        let details = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        for (title, value) in [
+            ("Texte", framing::escape_bytes(&data)),
+            ("Hex", framing::hex_bytes(&data)),
+        ] {
+            let heading = gtk4::Label::builder().label(title).xalign(0.0).build();
+            heading.add_css_class("heading");
+            let label = gtk4::Label::builder()
+                .label(value)
+                .xalign(0.0)
+                .wrap(true)
+                .wrap_mode(gtk4::pango::WrapMode::Char)
+                .selectable(true)
+                .build();
+            label.add_css_class("monospace");
+            details.append(&heading);
+            details.append(&label);
+        }
+        dialog.set_extra_child(Some(&details));
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("send", "Envoyer");
+        dialog.set_default_response(Some("send"));
+        dialog.set_close_response("cancel");
+        dialog.set_response_appearance("send", libadwaita::ResponseAppearance::Suggested);
+
+        let this = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "send" {
+                this.send_input(data.clone(), &echo);
+            } else {
+                this.input.grab_focus();
+            }
+        });
+
+        dialog.present(Some(&self.window));
     }
 
-    /// Applique les champs SSH depuis le favori sélectionné.
-    fn apply_selected_ssh_favorite(&self) {
-        let Some(favorite) = self.connection_panel.ssh_panel.selected_favorite() else {
+    /// Lit le presse-papiers et l'envoie ligne par ligne.
+    ///
+    /// Au-delà de `paste_confirm_lines` lignes, une confirmation est demandée.
+    fn paste_and_send(self: &Rc<Self>) {
+        let this = self.clone();
+        self.window
+            .clipboard()
+            .read_text_async(gio::Cancellable::NONE, move |result| {
+                let text = match result {
+                    Ok(Some(text)) => text.to_string(),
+                    Ok(None) => return,
+                    Err(e) => {
+                        this.terminal
+                            .append_error(&format!("Lecture du presse-papiers impossible : {e}"));
+                        return;
+                    }
+                };
+
+                let lines: Vec<String> = text.lines().map(str::to_string).collect();
+                if lines.is_empty() {
+                    return;
+                }
+
+                let threshold = this.settings.borrow().settings().ui.paste_confirm_lines;
+                if lines.len() > threshold as usize {
+                    this.confirm_paste_send(lines, "Le presse-papiers");
+                } else {
+                    this.send_lines(&lines);
+                }
+            });
+    }
+
+    /// Va au marque-page suivant ou précédent.
+    fn goto_bookmark(&self, forward: bool) {
+        // Le défilement automatique ramènerait aussitôt la vue en bas.
+        self.input.stop_scroll_checkbox.set_active(true);
+        if self.terminal.goto_bookmark(forward).is_none() {
+            self.show_toast("Aucun marque-page (Ctrl+B pour marquer une ligne)");
+        }
+    }
+
+    /// Liste des marque-pages ; un clic sur une ligne y amène la vue.
+    fn show_bookmarks(self: &Rc<Self>) {
+        let bookmarks = self.terminal.bookmarks();
+        if bookmarks.is_empty() {
+            self.show_toast("Aucun marque-page (Ctrl+B pour marquer une ligne)");
             return;
-        };
+        }
 
-        self.connection_panel.ssh_panel.apply_settings(
-            &favorite.host,
-            favorite.port,
-            &favorite.username,
-            &favorite.key_path,
+        let list = gtk4::ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .build();
+        list.add_css_class("boxed-list");
+        for (line, snippet) in &bookmarks {
+            let label = gtk4::Label::builder()
+                .label(format!("{:>6}  {snippet}", line + 1))
+                .xalign(0.0)
+                .ellipsize(gtk4::pango::EllipsizeMode::End)
+                .build();
+            label.add_css_class("monospace");
+            let row = gtk4::ListBoxRow::builder()
+                .child(&label)
+                .activatable(true)
+                .build();
+            list.append(&row);
+        }
+        let scroll = gtk4::ScrolledWindow::builder()
+            .min_content_height(200)
+            .max_content_height(400)
+            .min_content_width(480)
+            .propagate_natural_height(true)
+            .child(&list)
+            .build();
+
+        let dialog = libadwaita::AlertDialog::new(
+            Some("Marque-pages"),
+            Some(&format!("{} ligne(s) marquée(s)", bookmarks.len())),
         );
-        self.load_saved_ssh_secrets();
+        dialog.set_extra_child(Some(&scroll));
+        dialog.add_response("close", "Fermer");
+        dialog.set_default_response(Some("close"));
 
-        self.terminal
-            .append_system(&format!("Favori SSH chargé : {}", favorite.name));
-    }
+        let this = self.clone();
+        let weak_dialog = dialog.downgrade();
+        list.connect_row_activated(move |_, row| {
+            let Some((line, _)) = usize::try_from(row.index())
+                .ok()
+                .and_then(|i| bookmarks.get(i))
+            else {
+                return;
+            };
+            this.input.stop_scroll_checkbox.set_active(true);
+            this.terminal.scroll_to_line(*line);
+            if let Some(dialog) = weak_dialog.upgrade() {
+                dialog.close();
+            }
+        });
 
-    /// Déconnexion propre initiée par l'utilisateur.
-    /// Délègue à `handle_disconnect()` qui envoie la commande et met à jour l'UI.
-    fn disconnect(&self) {
-        self.handle_disconnect();
+        dialog.present(Some(&self.window));
     }
 
-    /// Envoie les données saisies à la connexion active.
-    fn send_data(&self) {
-        let text = self.input.get_text();
-        if text.is_empty() {
+    /// Renvoie la sélection du terminal (commande relue dans l'historique).
+    ///
+    /// Même garde-fou que le collage, plus une limite en octets pour les
+    /// longues lignes sélectionnées par mégarde.
+    fn resend_selection(self: &Rc<Self>) {
+        let Some(text) = self.terminal.selected_text() else {
+            return;
+        };
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
             return;
         }
 
+        let threshold = self.settings.borrow().settings().ui.paste_confirm_lines;
+        if lines.len() > threshold as usize || text.len() > RESEND_CONFIRM_BYTES {
+            self.confirm_paste_send(lines, "La sélection");
+        } else {
+            self.send_lines(&lines);
+        }
+    }
+
+    /// Demande confirmation avant d'envoyer un collage volumineux.
+    fn confirm_paste_send(self: &Rc<Self>, lines: Vec<String>, source: &str) {
+        let this = self.clone();
+        let total = lines.len();
+        let to_send = lines.clone();
+        self.confirm_line_count(&lines, total, source, move || this.send_lines(&to_send));
+    }
+
+    /// Demande confirmation avant d'envoyer `total` lignes (aperçu des
+    /// premières) ; `on_send` n'est appelé qu'après validation.
+    fn confirm_line_count(
+        self: &Rc<Self>,
+        lines: &[String],
+        total: usize,
+        source: &str,
+        on_send: impl Fn() + 'static,
+    ) {
+        let preview: String = lines.iter().take(5).map(|l| format!("{l}\n")).collect();
+        let dialog = libadwaita::AlertDialog::new(
+            Some(&format!("Envoyer {total} lignes ?")),
+            Some(&format!(
+                "{source} contient {total} lignes :\n\n{preview}{}",
+                if total > 5 { "…" } else { "" }
+            )),
+        );
+        dialog.add_response("cancel", "Annuler");
+        dialog.add_response("send", "Envoyer");
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_response_appearance("send", libadwaita::ResponseAppearance::Suggested);
+
+        dialog.connect_response(None, move |_, response| {
+            if response == "send" {
+                on_send();
+            }
+        });
+
+        dialog.present(Some(&self.window));
+    }
+
+    /// Envoie plusieurs lignes en une seule commande (fin de ligne sélectionnée ajoutée).
+    fn send_lines(self: &Rc<Self>, lines: &[String]) {
         let line_ending = self.input.selected_line_ending();
-        let data = format!("{text}{line_ending}");
+        let data: String = lines
+            .iter()
+            .map(|line| format!("{line}{line_ending}"))
+            .collect();
 
-        if let Some(tx) = self.connection_tx.borrow().as_ref() {
-            if let Err(e) = tx.try_send(ConnectionCommand::SendData(data.into_bytes())) {
-                self.terminal.append_error(&format!("Erreur d'envoi : {e}"));
-            } else {
-                self.terminal.append_sent(&format!("→ {text}\n"));
-                self.input.clear();
-                self.input.grab_focus();
+        if self.dispatch_send(data.into_bytes()) {
+            for line in lines {
+                self.echo_sent(&format!("→ {line}\n"));
             }
-        } else {
-            self.terminal
-                .append_error("Non connecté — impossible d'envoyer.");
         }
     }
 
@@ -939,6 +5529,15 @@ impl MainWindow {
             ))
             .build();
 
+        // Dossier local mémorisé pour le favori SSH courant
+        let favorite = self.current_ssh_favorite();
+        if let Some(fav) = &favorite {
+            if !fav.default_local_dir.is_empty() {
+                dialog.set_initial_folder(Some(&gio::File::for_path(&fav.default_local_dir)));
+            }
+        }
+        let settings = self.settings.clone();
+
         let terminal_buffer = self.terminal.buffer.clone();
         let term_text_view = self.terminal.text_view.clone();
         let sys_tag = terminal_buffer.tag_table().lookup("system");
@@ -954,25 +5553,19 @@ impl MainWindow {
                             false,
                         )
                         .to_string();
-                    let output = if timestamp_saved_lines {
-                        content
-                            .lines()
-                            .map(|line| {
-                                format!(
-                                    "[{}] {}",
-                                    chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
-                                    line
-                                )
-                            })
-                            .collect::<Vec<_>>()
-                            .join("\n")
-                    } else {
-                        content
-                    };
+                    let output = logger::format_session_log(&content, timestamp_saved_lines);
 
                     match std::fs::write(&path, &output) {
                         Ok(()) => {
                             log::info!("Logs sauvegardés dans {}", path.display());
+                            if let (Some(fav), Some(dir)) = (&favorite, path.parent()) {
+                                settings.borrow_mut().set_favorite_local_dir(
+                                    &fav.host,
+                                    fav.port,
+                                    &fav.username,
+                                    &dir.to_string_lossy(),
+                                );
+                            }
                             // Toast de confirmation non-bloquant
                             let toast = libadwaita::Toast::new(&format!(
                                 "✓ Logs sauvegardés : {}",
@@ -1008,12 +5601,237 @@ impl MainWindow {
         });
     }
 }
+
+/// Raccourcis intégrés (action, accélérateur) : les macros ne peuvent pas les reprendre.
+const BUILTIN_ACCELS: &[(&str, &str)] = &[
+    ("win.save-logs", "<Ctrl>s"),
+    ("win.clear-terminal", "<Ctrl>l"),
+    ("win.clear-scrollback", "<Ctrl><Shift>k"),
+    ("win.open-tools", "<Ctrl>t"),
+    ("win.compact-mode", "<Ctrl><Shift>m"),
+    ("win.paste-send", "<Ctrl><Shift>v"),
+    ("win.connection-info", "<Ctrl>i"),
+    ("win.reconnect-last-favorite", "<Ctrl><Shift>r"),
+    ("win.duplicate-connection", "<Ctrl><Shift>d"),
+    ("win.render-test-stream", "<Ctrl><Alt><Shift>F12"),
+    ("win.disconnect-all", "<Ctrl><Alt>d"),
+    ("win.find", "<Ctrl>f"),
+    ("win.toggle-bookmark", "<Ctrl>b"),
+    ("win.next-bookmark", "F2"),
+    ("win.previous-bookmark", "<Shift>F2"),
+];
+
+/// Mot de passe (ou passphrase de clé) d'une connexion SSH, gardé en
+/// mémoire le temps de la session ; jamais écrit sur disque.
+struct SessionSecret {
+    host: String,
+    port: u16,
+    username: String,
+    key_path: String,
+    secret: String,
+}
+
+impl SessionSecret {
+    fn matches(&self, host: &str, port: u16, username: &str, key_path: &str) -> bool {
+        self.host == host
+            && self.port == port
+            && self.username == username
+            && self.key_path == key_path
+    }
+}
+
+/// Taille de sélection au-delà de laquelle le renvoi demande confirmation.
+const RESEND_CONFIRM_BYTES: usize = 4096;
+
+/// Au-delà, la taille de l'historique est signalée comme excessive.
+const SCROLLBACK_WARNING_LINES: u32 = 200_000;
+
+/// Estimation grossière de la mémoire d'un `TextBuffer` : texte UTF-8 plus
+/// segments et balises de couleur, comptés ensemble à 4 octets par caractère.
+fn estimated_buffer_bytes(chars: f64) -> f64 {
+    chars * 4.0
+}
+
+/// Taille lisible (`12,3 Mio`).
+fn format_bytes(bytes: f64) -> String {
+    if bytes < 1024.0 * 1024.0 {
+        format!("{:.0} Kio", bytes / 1024.0)
+    } else {
+        format!("{:.1} Mio", bytes / 1024.0 / 1024.0)
+    }
+}
+
+/// Nombre de lignes du flux de test de rendu.
+const RENDER_TEST_LINES: usize = 2000;
+
+/// Taille des morceaux injectés (impaire : coupe séquences et caractères UTF-8).
+const RENDER_TEST_CHUNK: usize = 61;
+
+/// Flux de test : couleurs SGR, gras/souligné, réécriture par `\r` et
+/// retour arrière, caractères accentués, CJK et emoji.
+fn synthetic_ansi_stream(lines: usize) -> Vec<u8> {
+    let mut out = String::new();
+    for i in 0..lines {
+        let color = 31 + i % 7;
+        out.push_str(&format!(
+            "\x1b[{color}m{i:05}\x1b[0m \x1b[1mgras\x1b[22m \x1b[4msouligné\x1b[24m \
+             température 23,5 °C — 温度 🌡 "
+        ));
+        match i % 3 {
+            0 => out.push_str("progression 10%\rprogression 100%"),
+            1 => out.push_str("erreur\x08\x08\x08\x08\x08\x08succès"),
+            _ => out.push_str("\x1b[38;5;208morange\x1b[39m"),
+        }
+        out.push_str("\r\n");
+    }
+    out.into_bytes()
+}
+
+/// Mire de couleurs : 16 couleurs (texte et fond, normales et vives),
+/// grille 256 couleurs (système, cube 6×6×6, gris) et attributs SGR.
+fn color_test_pattern() -> Vec<u8> {
+    let mut out = String::from("\r\n\x1b[1m── Mire de couleurs ANSI ──\x1b[0m\r\n\r\n");
+
+    out.push_str("16 couleurs (texte) :\r\n");
+    for base in [30, 90] {
+        for code in base..base + 8 {
+            out.push_str(&format!("\x1b[{code}m {code:>3} \x1b[0m"));
+        }
+        out.push_str("\r\n");
+    }
+    out.push_str("16 couleurs (fond) :\r\n");
+    for base in [40, 100] {
+        for code in base..base + 8 {
+            out.push_str(&format!("\x1b[{code}m {code:>3} \x1b[0m"));
+        }
+        out.push_str("\r\n");
+    }
+
+    out.push_str("\r\n256 couleurs :\r\n");
+    for row in [0..8, 8..16] {
+        for index in row {
+            out.push_str(&format!("\x1b[48;5;{index}m {index:>3} \x1b[0m"));
+        }
+        out.push_str("\r\n");
+    }
+    for row in 0..12 {
+        let start = 16 + row * 18;
+        for index in start..start + 18 {
+            out.push_str(&format!("\x1b[48;5;{index}m{index:>4}\x1b[0m"));
+        }
+        out.push_str("\r\n");
+    }
+    for index in 232..256 {
+        out.push_str(&format!("\x1b[48;5;{index}m  \x1b[0m"));
+    }
+    out.push_str("\r\n");
+
+    out.push_str(
+        "\r\nAttributs : \x1b[1mgras\x1b[0m \x1b[2matténué\x1b[0m \x1b[3mitalique\x1b[0m \
+         \x1b[4msouligné\x1b[0m \x1b[7minversé\x1b[0m \x1b[9mbarré\x1b[0m \
+         \x1b[1;4;31mgras+souligné+rouge\x1b[0m\r\n\r\n",
+    );
+    out.into_bytes()
+}
+
+/// Forme canonique d'un accélérateur (`<Ctrl>` ≡ `<Control>`…), vide si invalide.
+fn normalize_accel(accel: &str) -> String {
+    gtk4::accelerator_parse(accel)
+        .map(|(key, mods)| gtk4::accelerator_name(key, mods).to_string())
+        .unwrap_or_default()
+}
+
+/// Commande qui agit sur le périphérique (lignes de contrôle, break,
+/// contrôle de flux) : refusée en lecture seule comme les envois.
+const fn drives_device(command: &ConnectionCommand) -> bool {
+    matches!(
+        command,
+        ConnectionCommand::PulseReset { .. }
+            | ConnectionCommand::SendBreak { .. }
+            | ConnectionCommand::SetDtr(_)
+            | ConnectionCommand::SetRts(_)
+            | ConnectionCommand::PauseReading(_)
+    )
+}
+
+/// Durée du pulse DTR/RTS de reset à l'ouverture du port série.
+const SERIAL_RESET_PULSE_MS: u64 = 100;
+
+/// Nombre maximal de répétitions accepté par la syntaxe `@N commande`.
+const MAX_REPEAT: usize = 1000;
+
+/// Décode le préfixe de répétition `@N ` (N entre 1 et `MAX_REPEAT`).
+///
+/// Retourne `None` si le texte ne commence pas par ce préfixe : il est
+/// alors envoyé tel quel.
+fn parse_repeat_prefix(text: &str) -> Option<(usize, &str)> {
+    let rest = text.strip_prefix('@')?;
+    let (count, command) = rest.split_once(' ')?;
+    // `parse` accepterait un signe (`@+3`).
+    if !count.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let count: usize = count.parse().ok()?;
+    if !(1..=MAX_REPEAT).contains(&count) || command.is_empty() {
+        return None;
+    }
+    Some((count, command))
+}
+
+/// Touche GDK → touche de terminal (`None` pour les modificateurs seuls…).
+fn terminal_key(keyval: gdk::Key) -> Option<TerminalKey> {
+    let key = match keyval {
+        gdk::Key::Return | gdk::Key::KP_Enter => TerminalKey::Enter,
+        gdk::Key::Tab | gdk::Key::ISO_Left_Tab | gdk::Key::KP_Tab => TerminalKey::Tab,
+        gdk::Key::BackSpace => TerminalKey::Backspace,
+        gdk::Key::Escape => TerminalKey::Escape,
+        gdk::Key::Up | gdk::Key::KP_Up => TerminalKey::Up,
+        gdk::Key::Down | gdk::Key::KP_Down => TerminalKey::Down,
+        gdk::Key::Right | gdk::Key::KP_Right => TerminalKey::Right,
+        gdk::Key::Left | gdk::Key::KP_Left => TerminalKey::Left,
+        gdk::Key::Home | gdk::Key::KP_Home => TerminalKey::Home,
+        gdk::Key::End | gdk::Key::KP_End => TerminalKey::End,
+        gdk::Key::Insert | gdk::Key::KP_Insert => TerminalKey::Insert,
+        gdk::Key::Delete | gdk::Key::KP_Delete => TerminalKey::Delete,
+        gdk::Key::Page_Up | gdk::Key::KP_Page_Up => TerminalKey::PageUp,
+        gdk::Key::Page_Down | gdk::Key::KP_Page_Down => TerminalKey::PageDown,
+        gdk::Key::F1 => TerminalKey::Function(1),
+        gdk::Key::F2 => TerminalKey::Function(2),
+        gdk::Key::F3 => TerminalKey::Function(3),
+        gdk::Key::F4 => TerminalKey::Function(4),
+        gdk::Key::F5 => TerminalKey::Function(5),
+        gdk::Key::F6 => TerminalKey::Function(6),
+        gdk::Key::F7 => TerminalKey::Function(7),
+        gdk::Key::F8 => TerminalKey::Function(8),
+        gdk::Key::F9 => TerminalKey::Function(9),
+        gdk::Key::F10 => TerminalKey::Function(10),
+        gdk::Key::F11 => TerminalKey::Function(11),
+        gdk::Key::F12 => TerminalKey::Function(12),
+        _ => TerminalKey::Char(keyval.to_unicode().filter(|c| !c.is_control())?),
+    };
+    Some(key)
+}
+
+/// Formate une durée en secondes au format `HH:MM:SS`.
+fn format_duration(total_secs: i64) -> String {
+    let secs = total_secs.max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
 // =============================================================================
 // Dialogue de vérification de clé SSH (hors impl MainWindow)
 // =============================================================================
 
 /// Affiche un dialogue `adw::AlertDialog` pour la vérification TOFU de la clé SSH.
 ///
+/// La décision est toujours transmise : une fermeture sans réponse vaut refus.
+/// `on_closed` est appelé une fois le dialogue fermé, quelle qu'en soit la raison.
+///
 /// Ce dialogue est non-bloquant : le thread GTK continue, le timer `GLib`
 /// continue de pomper les événements. Quand l'utilisateur répond, `decision_tx`
 /// est renseigné → la tâche tokio SSH continue ou abandonne.
@@ -1026,8 +5844,9 @@ fn show_host_key_dialog(
     key_type: &str,
     fingerprint: &str,
     is_key_changed: bool,
-    decision_tx: tokio::sync::oneshot::Sender<bool>,
-) {
+    decision_tx: tokio::sync::oneshot::Sender<HostKeyDecision>,
+    on_closed: impl Fn() + 'static,
+) -> libadwaita::AlertDialog {
     let (heading, body) = if is_key_changed {
         (
             "⚠ AVERTISSEMENT : Clé SSH modifiée !".to_string(),
@@ -1046,14 +5865,17 @@ fn show_host_key_dialog(
                 "Le serveur {host} n'est pas encore dans vos hôtes connus.\n\n\
                  Type : {key_type}\n\
                  Empreinte SHA256 : {fingerprint}\n\n\
-                 Voulez-vous faire confiance à ce serveur et enregistrer sa clé ?"
+                 Voulez-vous faire confiance à ce serveur ?\n\
+                 « Temporairement » : la clé n'est pas enregistrée et sera \
+                 redemandée au prochain lancement."
             ),
         )
     };
 
     let dialog = libadwaita::AlertDialog::new(Some(&heading), Some(&body));
     dialog.add_response("reject", "Rejeter");
-    dialog.add_response("accept", "Accepter");
+    dialog.add_response("accept-once", "Accepter temporairement");
+    dialog.add_response("accept", "Accepter et enregistrer");
     // Par sécurité : le refus est la réponse par défaut.
     dialog.set_default_response(Some("reject"));
     // Clé changée = action destructive (rouge) ; hôte nouveau = action suggérée (bleu).
@@ -1065,14 +5887,220 @@ fn show_host_key_dialog(
     dialog.set_response_appearance("accept", appearance);
 
     let decision_tx = std::rc::Rc::new(std::cell::RefCell::new(Some(decision_tx)));
-    dialog.connect_response(None, move |_, response| {
-        let accepted = response == "accept";
-        if let Some(tx) = decision_tx.borrow_mut().take() {
-            if let Err(e) = tx.send(accepted) {
-                log::warn!("SSH : impossible d'envoyer la décision host-key : {e:?}");
+    {
+        let decision_tx = decision_tx.clone();
+        dialog.connect_response(None, move |_, response| {
+            let decision = match response {
+                "accept" => HostKeyDecision::AcceptAndSave,
+                "accept-once" => HostKeyDecision::AcceptOnce,
+                _ => HostKeyDecision::Reject,
+            };
+            if let Some(tx) = decision_tx.borrow_mut().take() {
+                if let Err(e) = tx.send(decision) {
+                    log::warn!("SSH : impossible d'envoyer la décision host-key : {e:?}");
+                }
             }
+        });
+    }
+    dialog.connect_closed(move |_| {
+        // Fermé sans réponse (déconnexion, fenêtre fermée) : refus explicite.
+        if let Some(tx) = decision_tx.borrow_mut().take() {
+            let _ = tx.send(HostKeyDecision::Reject);
         }
+        on_closed();
+    });
+
+    dialog.present(Some(parent));
+    dialog
+}
+
+/// Affiche les questions d'une authentification keyboard-interactive (une
+/// entrée par question, masquée si le serveur ne demande pas d'écho).
+///
+/// Les réponses partent dans `response_tx` ; une fermeture sans validation
+/// abandonne l'authentification.
+fn show_auth_prompt_dialog(
+    parent: &libadwaita::ApplicationWindow,
+    target: &str,
+    prompts: &[(String, bool)],
+    response_tx: tokio::sync::oneshot::Sender<Vec<String>>,
+    on_closed: impl Fn() + 'static,
+) -> libadwaita::AlertDialog {
+    let fields = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .build();
+    let mut entries: Vec<gtk4::Editable> = Vec::with_capacity(prompts.len());
+    for (prompt, echo) in prompts {
+        let label = gtk4::Label::builder()
+            .label(prompt.trim())
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        let entry: gtk4::Editable = if *echo {
+            gtk4::Entry::builder()
+                .activates_default(true)
+                .build()
+                .upcast()
+        } else {
+            gtk4::PasswordEntry::builder()
+                .activates_default(true)
+                .show_peek_icon(true)
+                .build()
+                .upcast()
+        };
+        entry.update_relation(&[gtk4::accessible::Relation::LabelledBy(
+            &[label.upcast_ref()],
+        )]);
+        fields.append(&label);
+        fields.append(&entry);
+        entries.push(entry);
+    }
+    let first_entry = entries.first().cloned();
+
+    let dialog = libadwaita::AlertDialog::new(Some("Authentification SSH"), Some(target));
+    dialog.set_extra_child(Some(&fields));
+    dialog.add_response("cancel", "Annuler");
+    dialog.add_response("submit", "Valider");
+    dialog.set_default_response(Some("submit"));
+    dialog.set_close_response("cancel");
+    dialog.set_response_appearance("submit", libadwaita::ResponseAppearance::Suggested);
+
+    let response_tx = std::rc::Rc::new(std::cell::RefCell::new(Some(response_tx)));
+    {
+        let response_tx = response_tx.clone();
+        dialog.connect_response(None, move |_, response| {
+            let Some(tx) = response_tx.borrow_mut().take() else {
+                return;
+            };
+            if response == "submit" {
+                let answers = entries.iter().map(|e| e.text().to_string()).collect();
+                if tx.send(answers).is_err() {
+                    log::warn!("SSH : impossible d'envoyer les réponses d'authentification");
+                }
+            }
+            // Autre réponse : `tx` est abandonné, l'acteur conclut à une annulation.
+        });
+    }
+    dialog.connect_closed(move |_| {
+        response_tx.borrow_mut().take();
+        on_closed();
+    });
+
+    dialog.present(Some(parent));
+    if let Some(entry) = first_entry {
+        dialog.set_focus(Some(&entry));
+    }
+    dialog
+}
+
+/// Demande la passphrase d'une clé privée chiffrée (saisie masquée), avec
+/// l'option de la mémoriser dans le trousseau.
+///
+/// `on_submit` reçoit la passphrase et le choix de mémorisation ; une
+/// fermeture sans validation abandonne la connexion.
+fn show_passphrase_dialog(
+    parent: &libadwaita::ApplicationWindow,
+    key_path: &str,
+    remember_default: bool,
+    response_tx: tokio::sync::oneshot::Sender<String>,
+    on_submit: impl Fn(&str, bool) + 'static,
+    on_closed: impl Fn() + 'static,
+) -> libadwaita::AlertDialog {
+    let entry = gtk4::PasswordEntry::builder()
+        .activates_default(true)
+        .show_peek_icon(true)
+        .placeholder_text("Passphrase")
+        .build();
+    let remember_check = gtk4::CheckButton::builder()
+        .label("Mémoriser dans le trousseau")
+        .active(remember_default)
+        .build();
+    let fields = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .build();
+    fields.append(&entry);
+    fields.append(&remember_check);
+
+    let dialog = libadwaita::AlertDialog::new(
+        Some("Clé SSH chiffrée"),
+        Some(&format!("Passphrase de la clé {key_path}")),
+    );
+    dialog.set_extra_child(Some(&fields));
+    dialog.add_response("cancel", "Annuler");
+    dialog.add_response("submit", "Déverrouiller");
+    dialog.set_default_response(Some("submit"));
+    dialog.set_close_response("cancel");
+    dialog.set_response_appearance("submit", libadwaita::ResponseAppearance::Suggested);
+
+    let response_tx = std::rc::Rc::new(std::cell::RefCell::new(Some(response_tx)));
+    {
+        let response_tx = response_tx.clone();
+        let entry = entry.clone();
+        dialog.connect_response(None, move |_, response| {
+            let Some(tx) = response_tx.borrow_mut().take() else {
+                return;
+            };
+            if response == "submit" {
+                let passphrase = entry.text().to_string();
+                on_submit(&passphrase, remember_check.is_active());
+                if tx.send(passphrase).is_err() {
+                    log::warn!("SSH : impossible d'envoyer la passphrase");
+                }
+            }
+        });
+    }
+    dialog.connect_closed(move |_| {
+        response_tx.borrow_mut().take();
+        on_closed();
     });
 
     dialog.present(Some(parent));
+    dialog.set_focus(Some(&entry));
+    dialog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeat_prefix_gives_count_and_command() {
+        assert_eq!(parse_repeat_prefix("@5 AT+CSQ"), Some((5, "AT+CSQ")));
+        assert_eq!(parse_repeat_prefix("@1 x"), Some((1, "x")));
+        assert_eq!(
+            parse_repeat_prefix(&format!("@{MAX_REPEAT} ping")),
+            Some((MAX_REPEAT, "ping"))
+        );
+        // Seul le premier espace sépare : la commande garde les suivants.
+        assert_eq!(parse_repeat_prefix("@2  echo a b"), Some((2, " echo a b")));
+        assert_eq!(parse_repeat_prefix("@03 ls"), Some((3, "ls")));
+    }
+
+    #[test]
+    fn text_without_valid_prefix_is_sent_as_is() {
+        for text in [
+            "AT+CSQ",
+            "",
+            "@",
+            "@5",
+            "@5 ",
+            "@ 5 ls",
+            "@0 ls",
+            "@+3 ls",
+            "@-1 ls",
+            "@x ls",
+            "@5x ls",
+            " @5 ls",
+            "@5\tls",
+            "@99999999999999999999999 ls",
+        ] {
+            assert_eq!(parse_repeat_prefix(text), None, "{text:?}");
+        }
+        assert_eq!(
+            parse_repeat_prefix(&format!("@{} ping", MAX_REPEAT + 1)),
+            None
+        );
+    }
 }